@@ -0,0 +1,425 @@
+//! Headless command-line launcher.
+//!
+//! Shares the complete backend (`State`, `ProfileManager`, installer, `ProcessManager`) with the
+//! Tauri app but runs without an `AppHandle`. Progress events from `EventState` are rendered as
+//! terminal progress bars instead of being sent to the webview.
+
+use clap::{Parser, Subcommand};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use noriskclient_launcher_v3_lib::commands::profile_command::{self, CreateProfileParams};
+use noriskclient_launcher_v3_lib::error::{AppError, CommandError, Result};
+use noriskclient_launcher_v3_lib::minecraft::installer;
+use noriskclient_launcher_v3_lib::state::event_state::{EventPayload, EventType};
+use noriskclient_launcher_v3_lib::state::profile_state::Profile;
+use noriskclient_launcher_v3_lib::state::state_manager::State;
+use noriskclient_launcher_v3_lib::utils::{profile_utils, repair_utils};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+#[derive(Parser)]
+#[command(name = "geg-cli", version, about = "Headless GEG launcher")]
+struct Cli {
+    #[command(subcommand)]
+    command: CliCommand,
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    /// List all profiles
+    List,
+    /// Create a new profile
+    Create {
+        #[arg(long)]
+        name: String,
+        /// Minecraft version, e.g. 1.21.1
+        #[arg(long = "game-version")]
+        game_version: String,
+        /// vanilla, fabric, quilt, forge or neoforge
+        #[arg(long, default_value = "vanilla")]
+        loader: String,
+        #[arg(long)]
+        loader_version: Option<String>,
+        /// GEG pack to select for the profile
+        #[arg(long)]
+        pack: Option<String>,
+    },
+    /// Download everything a profile needs without starting the game
+    Install {
+        /// Profile ID or name
+        profile: String,
+    },
+    /// Install (if needed) and launch a profile, then wait for the game to exit
+    Launch {
+        /// Profile ID or name
+        profile: String,
+        /// Quick Play into a singleplayer world
+        #[arg(long, conflicts_with = "server")]
+        world: Option<String>,
+        /// Quick Play onto a server
+        #[arg(long)]
+        server: Option<String>,
+        /// Return as soon as the game has started instead of streaming its output
+        #[arg(long)]
+        detach: bool,
+    },
+    /// Repair a profile
+    Repair {
+        /// Profile ID or name
+        profile: String,
+    },
+    /// Print a profile's latest.log
+    Logs {
+        /// Profile ID or name
+        profile: String,
+        /// Keep printing new lines as they are written
+        #[arg(short, long)]
+        follow: bool,
+    },
+    /// Export a profile as .GEGpack
+    Export {
+        /// Profile ID or name
+        profile: String,
+        /// Target file, defaults to the launcher's exports directory
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    let cli = Cli::parse();
+
+    if let Err(e) = State::init(None).await {
+        eprintln!("Failed to initialize launcher state: {}", e);
+        std::process::exit(1);
+    }
+
+    if let Err(e) = run(cli.command).await {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(command: CliCommand) -> Result<()> {
+    let state = State::get().await?;
+
+    match command {
+        CliCommand::List => {
+            let mut profiles = state.profile_manager.list_profiles().await?;
+            profiles.sort_by_key(|p| p.name.to_lowercase());
+            for profile in profiles {
+                println!(
+                    "{}  {:<32} {:<10} {:<9} {:?}",
+                    profile.id,
+                    profile.name,
+                    profile.game_version,
+                    profile.loader.as_str(),
+                    profile.state
+                );
+            }
+        }
+        CliCommand::Create {
+            name,
+            game_version,
+            loader,
+            loader_version,
+            pack,
+        } => {
+            let id = profile_command::create_profile(CreateProfileParams {
+                name,
+                game_version,
+                loader,
+                loader_version,
+                selected_GEG_pack_id: pack,
+                use_shared_minecraft_folder: None,
+            })
+            .await
+            .map_err(command_error)?;
+            println!("{}", id);
+        }
+        CliCommand::Install { profile } => {
+            let profile = resolve_profile(&state, &profile).await?;
+            let credentials = state
+                .minecraft_account_manager_v2
+                .get_active_account()
+                .await?;
+
+            let events = state.event_state.subscribe();
+            let progress = tokio::spawn(render_progress(events, profile.id));
+
+            let result = installer::install_minecraft_version(
+                &profile.game_version,
+                profile.loader.as_str(),
+                &profile,
+                credentials,
+                None,
+                None,
+                None,
+                false,
+            )
+            .await;
+            progress.abort();
+            result?;
+            println!("Profile '{}' installed.", profile.name);
+        }
+        CliCommand::Launch {
+            profile,
+            world,
+            server,
+            detach,
+        } => {
+            let profile = resolve_profile(&state, &profile).await?;
+            launch(&state, &profile, world, server, detach).await?;
+        }
+        CliCommand::Repair { profile } => {
+            let profile = resolve_profile(&state, &profile).await?;
+            let result = repair_utils::repair_profile(profile.id).await?;
+            println!(
+                "Profile '{}' repaired: {} files fixed, {} refetched on next launch, {} failed.",
+                profile.name,
+                result.repaired,
+                result.deferred,
+                result.failed.len()
+            );
+            for failure in &result.failed {
+                println!("  failed: {}", failure);
+            }
+        }
+        CliCommand::Logs { profile, follow } => {
+            let profile = resolve_profile(&state, &profile).await?;
+            let instance_path = state
+                .profile_manager
+                .calculate_instance_path_for_profile(&profile)?;
+            tail_log(instance_path.join("logs").join("latest.log"), follow).await?;
+        }
+        CliCommand::Export { profile, output } => {
+            let profile = resolve_profile(&state, &profile).await?;
+            let path = profile_utils::export_profile_to_GEGpack(profile.id, output, None).await?;
+            println!("{}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves a profile by UUID or, failing that, by case-insensitive name.
+async fn resolve_profile(state: &State, query: &str) -> Result<Profile> {
+    if let Ok(id) = Uuid::parse_str(query) {
+        return state.profile_manager.get_profile(id).await;
+    }
+
+    let matches: Vec<Profile> = state
+        .profile_manager
+        .list_profiles()
+        .await?
+        .into_iter()
+        .filter(|p| p.name.eq_ignore_ascii_case(query))
+        .collect();
+
+    match matches.len() {
+        0 => Err(AppError::NotFound(format!("No profile named '{}'", query))),
+        1 => Ok(matches.into_iter().next().unwrap()),
+        n => Err(AppError::InvalidInput(format!(
+            "{} profiles are named '{}', use the profile ID instead",
+            n, query
+        ))),
+    }
+}
+
+async fn launch(
+    state: &State,
+    profile: &Profile,
+    world: Option<String>,
+    server: Option<String>,
+    detach: bool,
+) -> Result<()> {
+    // Subscribe before launching so no event gets lost
+    let mut events = state.event_state.subscribe();
+    let progress = tokio::spawn(render_progress(state.event_state.subscribe(), profile.id));
+    // Games of this profile that already run, their output is not ours
+    let earlier_processes: HashSet<Uuid> = state
+        .process_manager
+        .get_process_metadata_by_profile(profile.id)
+        .await
+        .into_iter()
+        .map(|process| process.id)
+        .collect();
+
+    profile_command::launch_profile(profile.id, world, server, None, Some(false))
+        .await
+        .map_err(command_error)?;
+
+    // Wait for the install/launch task to either succeed or fail
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => {
+                return Err(AppError::EventError("Event channel closed".to_string()))
+            }
+        };
+        if event.target_id != Some(profile.id) {
+            continue;
+        }
+        match event.event_type {
+            EventType::LaunchSuccessful => break,
+            EventType::Error => {
+                progress.abort();
+                return Err(AppError::ProcessError(
+                    event.error.unwrap_or(event.message),
+                ));
+            }
+            _ => {}
+        }
+    }
+    progress.abort();
+    println!("Minecraft started for profile '{}'.", profile.name);
+
+    if detach {
+        return Ok(());
+    }
+
+    let process_id = state
+        .process_manager
+        .get_process_metadata_by_profile(profile.id)
+        .await
+        .into_iter()
+        .filter(|process| !earlier_processes.contains(&process.id))
+        .max_by_key(|process| process.start_time)
+        .map(|process| process.id);
+    if process_id.is_none() {
+        eprintln!("[geg-cli] game process not found, it may have exited already");
+    }
+
+    // Stream the output of our game process until it exits, other games log to the same channel
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                eprintln!("[geg-cli] skipped {} log lines", skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        match event.event_type {
+            EventType::MinecraftOutput => {
+                if process_id.is_some() && event.target_id == process_id {
+                    println!("{}", event.message);
+                }
+            }
+            EventType::MinecraftProcessExited => {
+                let payload: serde_json::Value =
+                    serde_json::from_str(&event.message).unwrap_or_default();
+                let ours = match process_id {
+                    Some(_) => event.target_id == process_id,
+                    None => payload["profile_id"].as_str() == Some(profile.id.to_string().as_str()),
+                };
+                if !ours {
+                    continue;
+                }
+                let exit_code = payload["exit_code"].as_i64();
+                println!("Minecraft exited with code {:?}.", exit_code);
+                if payload["success"].as_bool() == Some(true) {
+                    return Ok(());
+                }
+                return Err(AppError::ProcessError(format!(
+                    "Minecraft exited with code {:?}",
+                    exit_code
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Renders `EventState` progress for one profile as one bar per event type.
+async fn render_progress(mut events: broadcast::Receiver<EventPayload>, profile_id: Uuid) {
+    let multi = MultiProgress::new();
+    let style = ProgressStyle::with_template("{prefix:>22} [{bar:30}] {percent:>3}% {wide_msg}")
+        .unwrap_or_else(|_| ProgressStyle::default_bar())
+        .progress_chars("=> ");
+    let mut bars: HashMap<String, ProgressBar> = HashMap::new();
+
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        if event.target_id != Some(profile_id) {
+            continue;
+        }
+        let Some(progress) = event.progress else {
+            continue;
+        };
+        if matches!(
+            event.event_type,
+            EventType::LaunchSuccessful | EventType::Error | EventType::MinecraftOutput
+        ) {
+            continue;
+        }
+
+        let key = format!("{:?}", event.event_type);
+        let bar = bars.entry(key.clone()).or_insert_with(|| {
+            let bar = multi.add(ProgressBar::new(100));
+            bar.set_style(style.clone());
+            bar.set_prefix(key);
+            bar
+        });
+        bar.set_position((progress.clamp(0.0, 1.0) * 100.0) as u64);
+        bar.set_message(event.message);
+        if let Some(error) = event.error {
+            bar.abandon_with_message(error);
+        } else if progress >= 1.0 {
+            bar.finish();
+        }
+    }
+}
+
+async fn tail_log(path: PathBuf, follow: bool) -> Result<()> {
+    if !path.exists() && !follow {
+        return Err(AppError::FileNotFound(path));
+    }
+
+    let mut position: u64 = 0;
+    loop {
+        if let Ok(metadata) = tokio::fs::metadata(&path).await {
+            // The game rotated latest.log, start over
+            if metadata.len() < position {
+                position = 0;
+            }
+            if metadata.len() > position {
+                let mut file = tokio::fs::File::open(&path).await?;
+                file.seek(std::io::SeekFrom::Start(position)).await?;
+                let mut reader = BufReader::new(file);
+                let mut line = Vec::new();
+                loop {
+                    line.clear();
+                    let read = reader.read_until(b'\n', &mut line).await?;
+                    if read == 0 {
+                        break;
+                    }
+                    position += read as u64;
+                    print!("{}", String::from_utf8_lossy(&line));
+                }
+            }
+        }
+
+        if !follow {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+fn command_error(error: CommandError) -> AppError {
+    AppError::Other(error.message)
+}
@@ -3,6 +3,8 @@ use crate::integrations::curseforge::{
     get_mods_by_ids, GetModsByIdsRequestBody, CurseForgeModsResponse, CurseForgeMod,
     import_curseforge_pack_as_profile, download_and_install_curseforge_modpack, get_file_changelog
 };
+use crate::state::download_queue_state::{DownloadJobKind, DownloadJobRequest};
+use crate::state::state_manager::State;
 use serde::Serialize;
 use std::path::PathBuf;
 
@@ -70,14 +72,21 @@ pub async fn download_and_install_curseforge_modpack_command(
         project_id, file_id, icon_url
     );
 
-    let profile_id_uuid = download_and_install_curseforge_modpack(
-        project_id,
-        file_id,
-        file_name,
-        download_url,
-        icon_url,
-    )
-    .await
+    let state = State::get().await?;
+    let job = DownloadJobRequest::new(DownloadJobKind::ModpackImport, file_name.clone());
+    let profile_id_uuid = state
+        .download_queue
+        .run_job(
+            job,
+            download_and_install_curseforge_modpack(
+                project_id,
+                file_id,
+                file_name,
+                download_url,
+                icon_url,
+            ),
+        )
+        .await
     .map_err(|e| {
         log::error!("Failed to download and install CurseForge modpack: {}", e);
         CommandError::from(e)
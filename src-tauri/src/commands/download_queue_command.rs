@@ -0,0 +1,52 @@
+use crate::error::CommandError;
+use crate::state::download_queue_state::{DownloadJob, DownloadPriority};
+use crate::state::state_manager::State;
+use log::info;
+use uuid::Uuid;
+
+/// Returns all queued, running and recently finished download jobs.
+/// Changes are also pushed as `download_queue_updated` events.
+#[tauri::command]
+pub async fn get_download_queue() -> Result<Vec<DownloadJob>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.download_queue.list_jobs())
+}
+
+#[tauri::command]
+pub async fn pause_download_job(job_id: Uuid) -> Result<(), CommandError> {
+    info!("Pausing download job {}", job_id);
+    let state = State::get().await?;
+    Ok(state.download_queue.pause_job(job_id)?)
+}
+
+#[tauri::command]
+pub async fn resume_download_job(job_id: Uuid) -> Result<(), CommandError> {
+    info!("Resuming download job {}", job_id);
+    let state = State::get().await?;
+    Ok(state.download_queue.resume_job(job_id)?)
+}
+
+/// Requests cancellation. The job removes its partial files and ends at its next checkpoint.
+#[tauri::command]
+pub async fn cancel_download_job(job_id: Uuid) -> Result<(), CommandError> {
+    info!("Cancelling download job {}", job_id);
+    let state = State::get().await?;
+    Ok(state.download_queue.cancel_job(job_id)?)
+}
+
+#[tauri::command]
+pub async fn set_download_job_priority(
+    job_id: Uuid,
+    priority: DownloadPriority,
+) -> Result<(), CommandError> {
+    info!("Setting priority of download job {} to {:?}", job_id, priority);
+    let state = State::get().await?;
+    Ok(state.download_queue.set_job_priority(job_id, priority)?)
+}
+
+#[tauri::command]
+pub async fn clear_finished_download_jobs() -> Result<(), CommandError> {
+    let state = State::get().await?;
+    state.download_queue.clear_finished_jobs();
+    Ok(())
+}
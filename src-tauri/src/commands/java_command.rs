@@ -3,8 +3,13 @@ use crate::utils::java_detector::{
     detect_java_installations, find_best_java_for_minecraft, get_java_info, invalidate_java_cache,
     JavaInstallation,
 };
+use crate::utils::java_runtime_utils::{
+    self, JavaRuntimeRemovalResult, JavaRuntimeUpdateInfo, JavaRuntimeUpdateResult,
+    ManagedJavaRuntime,
+};
 use log::info;
 use std::path::PathBuf;
+use uuid::Uuid;
 
 /// Detects all Java installations on the system
 #[tauri::command]
@@ -69,3 +74,49 @@ pub async fn validate_java_path_command(path: String) -> Result<bool, CommandErr
         }
     }
 }
+
+/// Lists the Java runtimes the launcher downloaded, with the profiles using them
+#[tauri::command]
+pub async fn list_java_runtimes_command() -> Result<Vec<ManagedJavaRuntime>, CommandError> {
+    info!("Command: Listing managed Java runtimes");
+    Ok(java_runtime_utils::list_managed_runtimes().await?)
+}
+
+/// Pins a profile to a managed Java runtime, `None` restores automatic selection
+#[tauri::command]
+pub async fn pin_profile_java_runtime_command(
+    profile_id: Uuid,
+    runtime_id: Option<String>,
+) -> Result<(), CommandError> {
+    info!(
+        "Command: Pinning profile {} to Java runtime {:?}",
+        profile_id, runtime_id
+    );
+    Ok(java_runtime_utils::pin_profile_runtime(profile_id, runtime_id.as_deref()).await?)
+}
+
+/// Checks the managed Java runtimes for newer patch releases
+#[tauri::command]
+pub async fn check_java_runtime_updates_command(
+) -> Result<Vec<JavaRuntimeUpdateInfo>, CommandError> {
+    info!("Command: Checking managed Java runtimes for updates");
+    Ok(java_runtime_utils::check_runtime_updates().await?)
+}
+
+/// Replaces a managed Java runtime with the latest release of its distribution
+#[tauri::command]
+pub async fn update_java_runtime_command(
+    runtime_id: String,
+) -> Result<JavaRuntimeUpdateResult, CommandError> {
+    info!("Command: Updating Java runtime {}", runtime_id);
+    Ok(java_runtime_utils::update_runtime(&runtime_id).await?)
+}
+
+/// Moves a managed Java runtime into the trash
+#[tauri::command]
+pub async fn remove_java_runtime_command(
+    runtime_id: String,
+) -> Result<JavaRuntimeRemovalResult, CommandError> {
+    info!("Command: Removing Java runtime {}", runtime_id);
+    Ok(java_runtime_utils::remove_runtime(&runtime_id).await?)
+}
@@ -130,6 +130,17 @@ pub async fn set_active_account(account_id: Uuid) -> Result<(), CommandError> {
     Ok(())
 }
 
+/// Add a local offline account (singleplayer and offline-mode servers only)
+#[tauri::command]
+pub async fn add_offline_account(username: String) -> Result<Credentials, CommandError> {
+    let state = State::get().await?;
+    let account = state
+        .minecraft_account_manager_v2
+        .add_offline_account(username.trim())
+        .await?;
+    Ok(account)
+}
+
 /// Get all Minecraft accounts
 #[tauri::command]
 pub async fn get_accounts() -> Result<Vec<Credentials>, CommandError> {
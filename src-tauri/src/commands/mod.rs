@@ -2,6 +2,7 @@ pub mod cape_command;
 pub mod config_commands;
 pub mod content_command;
 pub mod curseforge_commands;
+pub mod download_queue_command;
 pub mod file_command;
 pub mod flagsmith_commands;
 pub mod java_command;
@@ -12,4 +13,7 @@ pub mod nrc_commands;
 pub mod path_commands;
 pub mod process_command;
 pub mod profile_command;
+pub mod server_command;
+pub mod storage_command;
 pub mod vanilla_cape_command;
+pub mod world_command;
@@ -12,7 +12,9 @@ use crate::integrations::unified_mod::{
     UnifiedProjectType, UnifiedSortType, UnifiedUpdateCheckRequest, UnifiedUpdateCheckResponse, UnifiedVersionResponse,
     ModpackSwitchRequest, ModpackSwitchResponse,
 };
+use crate::state::download_queue_state::{DownloadJobKind, DownloadJobRequest};
 use crate::state::profile_state::ModPackSource;
+use crate::state::state_manager::State;
 use serde::Serialize;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -177,13 +179,20 @@ pub async fn download_and_install_modrinth_modpack(
         file_name.clone() // Clone if already correct to ensure ownership for logging later if needed
     };
 
-    let profile_id_uuid = mrpack::download_and_process_mrpack(
-            &download_url,
-            &file_name_mrpack,
-            Some(project_id),
-            Some(version_id),
+    let state = State::get().await?;
+    let job = DownloadJobRequest::new(DownloadJobKind::ModpackImport, file_name_mrpack.clone());
+    let profile_id_uuid = state
+        .download_queue
+        .run_job(
+            job,
+            mrpack::download_and_process_mrpack(
+                &download_url,
+                &file_name_mrpack,
+                Some(project_id),
+                Some(version_id),
+            ),
         )
-    .await
+        .await
     .map_err(|e| {
         log::error!("Failed to download and process modpack: {}", e);
         CommandError::from(e)
@@ -282,7 +291,15 @@ pub async fn check_mod_updates_unified_command(
         request.algorithm
     );
 
-    let updates = check_mod_updates_unified(request)
+    // Update checks only run once launches and installs have finished downloading
+    let state = State::get().await?;
+    let job = DownloadJobRequest::new(
+        DownloadJobKind::UpdateCheck,
+        format!("Checking {} mods for updates", request.hashes.len()),
+    );
+    let updates = state
+        .download_queue
+        .run_job(job, check_mod_updates_unified(request))
         .await
         .map_err(CommandError::from)?;
 
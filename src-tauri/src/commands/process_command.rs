@@ -1,6 +1,10 @@
 use crate::error::CommandError;
+use crate::state::launch_timeline_state::LaunchTimeline;
+use crate::state::play_session_state::ProfilePlayHistory;
 use crate::state::process_state::ProcessMetadata;
 use crate::state::state_manager::State;
+use crate::utils::crash_analyzer::{self, CrashAnalysis};
+use std::collections::HashMap;
 use tauri::Manager;
 use uuid::Uuid;
 
@@ -30,6 +34,52 @@ pub async fn get_processes_by_profile(
     Ok(processes)
 }
 
+/// Recent launch timelines (per-phase durations and download volume) of a profile, newest first
+#[tauri::command]
+pub async fn get_launch_timelines(
+    profile_id: Uuid,
+    limit: Option<usize>,
+) -> Result<Vec<LaunchTimeline>, CommandError> {
+    let state = State::get().await?;
+    let timelines = state
+        .launch_timeline_manager
+        .get_recent_timelines(profile_id, limit.unwrap_or(10))
+        .await;
+    Ok(timelines)
+}
+
+/// Cumulative playtime and the most recent play sessions of a profile, newest first
+#[tauri::command]
+pub async fn get_profile_play_history(
+    profile_id: Uuid,
+    limit: Option<usize>,
+) -> Result<ProfilePlayHistory, CommandError> {
+    let state = State::get().await?;
+    let history = state
+        .play_session_manager
+        .get_play_history(profile_id, limit.unwrap_or(50))
+        .await;
+    Ok(history)
+}
+
+/// Cumulative playtime in seconds per profile
+#[tauri::command]
+pub async fn get_profile_playtimes() -> Result<HashMap<Uuid, u64>, CommandError> {
+    let state = State::get().await?;
+    Ok(state.play_session_manager.get_total_playtimes().await)
+}
+
+/// Analyzes the newest crash report and `latest.log` of a profile for known crash causes
+#[tauri::command]
+pub async fn analyze_profile_crash(profile_id: Uuid) -> Result<CrashAnalysis, CommandError> {
+    let state = State::get().await?;
+    let instance_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?;
+    Ok(crash_analyzer::analyze_instance(&instance_path, None, None, None).await)
+}
+
 #[tauri::command]
 pub async fn stop_process(process_id: Uuid) -> Result<(), CommandError> {
     let state = State::get().await?;
@@ -21,6 +21,7 @@ use crate::utils::profile_utils::{
     LocalContentLoader as ProfileUtilLocalContentLoader, MigrationInfo, ScreenshotInfo,
 };
 use crate::utils::resourcepack_utils::ResourcePackInfo;
+use crate::utils::repair_utils::{ProfileIntegrityReport, ProfileRepairResult};
 use crate::utils::shaderpack_utils::ShaderPackInfo;
 use crate::utils::world_utils;
 use crate::utils::{
@@ -38,15 +39,20 @@ use tauri_plugin_opener::OpenerExt;
 use tokio::fs as TokioFs;
 use uuid::Uuid;
 
+// How long a cancelled launch job may take to reach its next cancellation point
+// before the launch task is aborted
+const LAUNCH_CANCEL_GRACE_MS: u64 = 3000;
+const LAUNCH_CANCEL_POLL_MS: u64 = 100;
+
 // DTOs für Command-Parameter
 #[derive(Deserialize)]
 pub struct CreateProfileParams {
-    name: String,
-    game_version: String,
-    loader: String,
-    loader_version: Option<String>,
-    selected_GEG_pack_id: Option<String>,
-    use_shared_minecraft_folder: Option<bool>,
+    pub name: String,
+    pub game_version: String,
+    pub loader: String,
+    pub loader_version: Option<String>,
+    pub selected_GEG_pack_id: Option<String>,
+    pub use_shared_minecraft_folder: Option<bool>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -157,6 +163,16 @@ pub async fn create_profile(params: CreateProfileParams) -> Result<Uuid, Command
     Ok(id)
 }
 
+/// Splits a `quick_play_path` into (singleplayer world, multiplayer server).
+/// A dot means server address, otherwise it's a world name.
+pub fn split_quick_play_path(quick_play_path: &str) -> (Option<String>, Option<String>) {
+    if quick_play_path.contains('.') {
+        (None, Some(quick_play_path.to_string()))
+    } else {
+        (Some(quick_play_path.to_string()), None)
+    }
+}
+
 #[tauri::command]
 pub async fn launch_profile(
     id: Uuid,
@@ -265,15 +281,8 @@ pub async fn launch_profile(
     let (final_quick_play_sp, final_quick_play_mp) = if quick_play_singleplayer.is_none() && quick_play_multiplayer.is_none() {
         // Check profile's quick_play_path setting
         if let Some(quick_play_path) = &profile.settings.quick_play_path {
-            if quick_play_path.contains('.') {
-                // Contains dot, assume it's a server address (multiplayer)
-                info!("Using profile's quick_play_path as multiplayer server: {}", quick_play_path);
-                (None, Some(quick_play_path.clone()))
-            } else {
-                // No dot, assume it's a world name (singleplayer)
-                info!("Using profile's quick_play_path as singleplayer world: {}", quick_play_path);
-                (Some(quick_play_path.clone()), None)
-            }
+            info!("Using profile's quick_play_path: {}", quick_play_path);
+            split_quick_play_path(quick_play_path)
         } else {
             // No Quick Play configured
             (None, None)
@@ -313,6 +322,7 @@ pub async fn launch_profile(
             quick_play_sp_clone,
             quick_play_mp_clone,
             migration_info_clone,
+            true,
         )
         .await;
 
@@ -343,6 +353,10 @@ pub async fn launch_profile(
                         );
                     }
                 }
+                Err(AppError::DownloadCancelled(_)) => {
+                    // abort_profile_launch already told the frontend
+                    info!("Launch of profile {} was cancelled", profile_id);
+                }
                 Err(e) => {
                     let error_message = e.to_string();
                     info!(
@@ -408,8 +422,30 @@ pub async fn abort_profile_launch(profile_id: Uuid) -> Result<(), CommandError>
         ))));
     }
 
-    // Attempt to abort the process
-    match state.process_manager.abort_launch_process(profile_id) {
+    // Cancel the launch job cooperatively so downloads remove their partial files. Steps
+    // without a cancellation point (e.g. a pre-launch hook) don't see it, so the task is
+    // still aborted if it has not stopped within the grace period
+    let abort_result = if state.download_queue.cancel_profile_jobs(profile_id) > 0 {
+        let mut waited_ms = 0;
+        while state.process_manager.has_launching_process(profile_id)
+            && waited_ms < LAUNCH_CANCEL_GRACE_MS
+        {
+            tokio::time::sleep(std::time::Duration::from_millis(LAUNCH_CANCEL_POLL_MS)).await;
+            waited_ms += LAUNCH_CANCEL_POLL_MS;
+        }
+        if state.process_manager.has_launching_process(profile_id) {
+            warn!(
+                "Launch job of profile {} did not stop after cancellation, aborting its task",
+                profile_id
+            );
+            // The task may have finished in the meantime, which is just as good
+            let _ = state.process_manager.abort_launch_process(profile_id);
+        }
+        Ok(())
+    } else {
+        state.process_manager.abort_launch_process(profile_id)
+    };
+    match abort_result {
         Ok(_) => {
             info!(
                 "Successfully aborted launch process for profile ID: {}",
@@ -693,13 +729,17 @@ pub async fn delete_profile(id: Uuid) -> Result<(), CommandError> {
 }
 
 #[tauri::command]
-pub async fn repair_profile(id: Uuid) -> Result<(), CommandError> {
+pub async fn repair_profile(id: Uuid) -> Result<ProfileRepairResult, CommandError> {
     info!("Executing repair_profile command for profile {}", id);
     
     // Call the actual repair function from repair_utils
-    repair_utils::repair_profile(id).await?;
-    
-    Ok(())
+    Ok(repair_utils::repair_profile(id).await?)
+}
+
+#[tauri::command]
+pub async fn verify_profile_integrity(id: Uuid) -> Result<ProfileIntegrityReport, CommandError> {
+    info!("Executing verify_profile_integrity command for profile {}", id);
+    Ok(repair_utils::verify_profile_integrity(id).await?)
 }
 
 #[tauri::command]
@@ -2228,3 +2268,22 @@ pub async fn purge_trash(max_age_seconds: Option<u64>) -> Result<u64, CommandErr
     let removed = crate::utils::trash_utils::purge_expired(secs).await?;
     Ok(removed)
 }
+
+/// Creates a Linux `.desktop` shortcut that launches the profile via `geg://launch/<id>`.
+/// Without an explicit `quick_play_path` the profile's own Quick Play setting is baked in.
+#[tauri::command]
+pub async fn create_profile_desktop_shortcut(
+    profile_id: Uuid,
+    quick_play_path: Option<String>,
+) -> Result<String, CommandError> {
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    let quick_play_path = quick_play_path.or_else(|| profile.settings.quick_play_path.clone());
+
+    let path = crate::utils::deep_link_utils::create_desktop_shortcut(
+        &profile,
+        quick_play_path.as_deref(),
+    )
+    .await?;
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,188 @@
+use crate::error::CommandError;
+use crate::state::server_status_state::{ServerStatusEntry, ServerStatusSample};
+use crate::state::state_manager::State;
+use crate::utils::server_list_utils::{
+    self, ServerListCopyMode, ServerListEntry, ServerListPushResult,
+};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Lets open server list views of the profile reload.
+async fn emit_profile_update(profile_id: Uuid) {
+    match State::get().await {
+        Ok(state) => {
+            if let Err(e) = state.event_state.trigger_profile_update(profile_id).await {
+                warn!(
+                    "Failed to emit profile update event after editing the server list of {}: {}",
+                    profile_id, e
+                );
+            }
+        }
+        Err(e) => warn!("Could not get state to emit profile update event: {}", e),
+    }
+}
+
+/// Lists all entries of a profile's servers.dat, including hidden "Direct Connect" ones.
+#[tauri::command]
+pub async fn list_profile_servers(profile_id: Uuid) -> Result<Vec<ServerListEntry>, CommandError> {
+    info!("Executing list_profile_servers for profile {}", profile_id);
+    Ok(server_list_utils::list_servers(profile_id).await?)
+}
+
+/// Adds a server at `position`, or at the end of the list.
+#[tauri::command]
+pub async fn add_profile_server(
+    profile_id: Uuid,
+    entry: ServerListEntry,
+    position: Option<usize>,
+) -> Result<Vec<ServerListEntry>, CommandError> {
+    info!("Executing add_profile_server for profile {}", profile_id);
+    let servers = server_list_utils::add_server(profile_id, entry, position).await?;
+    emit_profile_update(profile_id).await;
+    Ok(servers)
+}
+
+/// Renames a server or changes its address, icon or resource pack policy.
+#[tauri::command]
+pub async fn update_profile_server(
+    profile_id: Uuid,
+    index: usize,
+    entry: ServerListEntry,
+) -> Result<Vec<ServerListEntry>, CommandError> {
+    info!(
+        "Executing update_profile_server for profile {}, index {}",
+        profile_id, index
+    );
+    let servers = server_list_utils::update_server(profile_id, index, entry).await?;
+    emit_profile_update(profile_id).await;
+    Ok(servers)
+}
+
+/// Moves a server to another position in the list.
+#[tauri::command]
+pub async fn move_profile_server(
+    profile_id: Uuid,
+    from: usize,
+    to: usize,
+) -> Result<Vec<ServerListEntry>, CommandError> {
+    info!(
+        "Executing move_profile_server for profile {}: {} -> {}",
+        profile_id, from, to
+    );
+    let servers = server_list_utils::move_server(profile_id, from, to).await?;
+    emit_profile_update(profile_id).await;
+    Ok(servers)
+}
+
+/// Removes a server from the list.
+#[tauri::command]
+pub async fn remove_profile_server(
+    profile_id: Uuid,
+    index: usize,
+) -> Result<Vec<ServerListEntry>, CommandError> {
+    info!(
+        "Executing remove_profile_server for profile {}, index {}",
+        profile_id, index
+    );
+    let servers = server_list_utils::remove_server(profile_id, index).await?;
+    emit_profile_update(profile_id).await;
+    Ok(servers)
+}
+
+/// Sets a server's icon from an image file (scaled to 64x64), or removes it without a path.
+#[tauri::command]
+pub async fn set_profile_server_icon(
+    profile_id: Uuid,
+    index: usize,
+    image_path: Option<String>,
+) -> Result<Vec<ServerListEntry>, CommandError> {
+    info!(
+        "Executing set_profile_server_icon for profile {}, index {}",
+        profile_id, index
+    );
+    let image_path = image_path.map(PathBuf::from);
+    let servers =
+        server_list_utils::set_server_icon(profile_id, index, image_path.as_deref()).await?;
+    emit_profile_update(profile_id).await;
+    Ok(servers)
+}
+
+/// Copies the server list of one profile into another, replacing or merging by address.
+#[tauri::command]
+pub async fn copy_profile_server_list(
+    source_profile_id: Uuid,
+    target_profile_id: Uuid,
+    mode: ServerListCopyMode,
+) -> Result<Vec<ServerListEntry>, CommandError> {
+    info!(
+        "Executing copy_profile_server_list from {} to {} ({:?})",
+        source_profile_id, target_profile_id, mode
+    );
+    let servers =
+        server_list_utils::copy_server_list(source_profile_id, target_profile_id, mode).await?;
+    emit_profile_update(target_profile_id).await;
+    Ok(servers)
+}
+
+/// Merges a team-defined server list into every profile of a group.
+#[tauri::command]
+pub async fn push_server_list_to_group(
+    group: String,
+    servers: Vec<ServerListEntry>,
+) -> Result<Vec<ServerListPushResult>, CommandError> {
+    info!(
+        "Executing push_server_list_to_group for group '{}' ({} servers)",
+        group,
+        servers.len()
+    );
+    let results = server_list_utils::push_server_list_to_group(&group, &servers).await?;
+    for result in results.iter().filter(|result| result.error.is_none()) {
+        emit_profile_update(result.profile_id).await;
+    }
+    Ok(results)
+}
+
+/// Pings every server of a profile's list, also pushed as `server_status_updated` event.
+#[tauri::command]
+pub async fn ping_profile_servers(
+    profile_id: Uuid,
+) -> Result<Vec<ServerStatusEntry>, CommandError> {
+    info!("Executing ping_profile_servers for profile {}", profile_id);
+    let state = State::get().await?;
+    Ok(state
+        .server_status_manager
+        .ping_profile_servers(profile_id)
+        .await?)
+}
+
+/// Last known status of a profile's servers from the background refresh, without pinging.
+#[tauri::command]
+pub async fn get_profile_server_statuses(
+    profile_id: Uuid,
+) -> Result<Vec<ServerStatusEntry>, CommandError> {
+    info!(
+        "Executing get_profile_server_statuses for profile {}",
+        profile_id
+    );
+    let state = State::get().await?;
+    Ok(state
+        .server_status_manager
+        .get_profile_statuses(profile_id)
+        .await?)
+}
+
+/// Latency and player count history of a server, optionally only after `since`.
+#[tauri::command]
+pub async fn get_server_status_history(
+    address: String,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<ServerStatusSample>, CommandError> {
+    info!("Executing get_server_status_history for {}", address);
+    let state = State::get().await?;
+    Ok(state
+        .server_status_manager
+        .get_history(&address, since)
+        .await)
+}
@@ -0,0 +1,34 @@
+use crate::error::CommandError;
+use crate::minecraft::downloads::mod_cache::{ModCache, ModCacheGcReport};
+use crate::state::state_manager::State;
+use crate::utils::storage_utils::{self, StorageCategory, StorageCleanupResult, StorageReport};
+use log::info;
+
+/// Removes mod cache entries that no profile or GEG pack references anymore.
+/// With `dry_run` only reports what would be deleted and how many bytes that frees.
+#[tauri::command]
+pub async fn gc_mod_cache(dry_run: bool) -> Result<ModCacheGcReport, CommandError> {
+    info!("Executing gc_mod_cache command (dry run: {})", dry_run);
+    let state = State::get().await?;
+    let profiles = state.profile_manager.list_profiles().await?;
+    let pack_config = state.GEG_pack_manager.get_config().await;
+
+    let references = ModCache::collect_references(&profiles, &pack_config);
+    Ok(ModCache::collect_garbage(&references, dry_run).await?)
+}
+
+/// Reports the disk usage of the meta directory per category and per profile.
+#[tauri::command]
+pub async fn analyze_storage() -> Result<StorageReport, CommandError> {
+    info!("Executing analyze_storage command");
+    Ok(storage_utils::analyze_storage().await?)
+}
+
+/// Moves artifacts of the given categories that no profile needs anymore into the trash.
+#[tauri::command]
+pub async fn cleanup_storage(
+    categories: Vec<StorageCategory>,
+) -> Result<StorageCleanupResult, CommandError> {
+    info!("Executing cleanup_storage command for {:?}", categories);
+    Ok(storage_utils::cleanup_storage(&categories).await?)
+}
@@ -0,0 +1,217 @@
+use crate::error::CommandError;
+use crate::state::state_manager::State;
+use crate::state::world_backup_state::{
+    WorldBackupEntry, WorldBackupPolicy, WorldBackupRunResult, WorldBackupTrigger,
+};
+use crate::utils::level_dat_utils::{self, LevelDatSettings, LevelDatUpdate};
+use crate::utils::world_utils::{self, WorldImportResult};
+use log::{info, warn};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Returns the world backup policy of a profile (defaults if none was saved).
+#[tauri::command]
+pub async fn get_world_backup_policy(profile_id: Uuid) -> Result<WorldBackupPolicy, CommandError> {
+    info!(
+        "Executing get_world_backup_policy for profile {}",
+        profile_id
+    );
+    let state = State::get().await?;
+    Ok(state.world_backup_manager.get_policy(profile_id).await)
+}
+
+/// Saves the world backup policy (format, triggers, schedule and retention) of a profile.
+#[tauri::command]
+pub async fn set_world_backup_policy(
+    profile_id: Uuid,
+    policy: WorldBackupPolicy,
+) -> Result<(), CommandError> {
+    info!(
+        "Executing set_world_backup_policy for profile {}",
+        profile_id
+    );
+    let state = State::get().await?;
+    Ok(state
+        .world_backup_manager
+        .set_policy(profile_id, policy)
+        .await?)
+}
+
+/// Backs up a single world. Fails while the world is open in Minecraft.
+#[tauri::command]
+pub async fn create_world_backup(
+    profile_id: Uuid,
+    world_folder: String,
+) -> Result<WorldBackupEntry, CommandError> {
+    info!(
+        "Executing create_world_backup for profile {}, world '{}'",
+        profile_id, world_folder
+    );
+    let state = State::get().await?;
+    Ok(state
+        .world_backup_manager
+        .backup_world(profile_id, &world_folder, WorldBackupTrigger::Manual)
+        .await?)
+}
+
+/// Backs up all worlds of a profile, open worlds are skipped and reported.
+#[tauri::command]
+pub async fn backup_profile_worlds(profile_id: Uuid) -> Result<WorldBackupRunResult, CommandError> {
+    info!("Executing backup_profile_worlds for profile {}", profile_id);
+    let state = State::get().await?;
+    Ok(state
+        .world_backup_manager
+        .backup_profile_worlds(profile_id, None, WorldBackupTrigger::Manual)
+        .await?)
+}
+
+/// Lists the backups of a profile, or of one of its worlds, newest first.
+#[tauri::command]
+pub async fn list_world_backups(
+    profile_id: Uuid,
+    world_folder: Option<String>,
+) -> Result<Vec<WorldBackupEntry>, CommandError> {
+    info!(
+        "Executing list_world_backups for profile {}, world {:?}",
+        profile_id, world_folder
+    );
+    let state = State::get().await?;
+    Ok(state
+        .world_backup_manager
+        .list_backups(profile_id, world_folder.as_deref())
+        .await?)
+}
+
+/// Restores a backup over its world, or into a new world if `new_world_name` is given.
+/// Returns the folder name of the restored world.
+#[tauri::command]
+pub async fn restore_world_backup(
+    profile_id: Uuid,
+    world_folder: String,
+    file_name: String,
+    new_world_name: Option<String>,
+) -> Result<String, CommandError> {
+    info!(
+        "Executing restore_world_backup for profile {}, world '{}', backup '{}'",
+        profile_id, world_folder, file_name
+    );
+    let state = State::get().await?;
+    let restored_folder = state
+        .world_backup_manager
+        .restore_backup(
+            profile_id,
+            &world_folder,
+            &file_name,
+            new_world_name.as_deref(),
+        )
+        .await?;
+
+    if let Err(e) = state.event_state.trigger_profile_update(profile_id).await {
+        warn!(
+            "Failed to emit profile update event after restoring world '{}': {}",
+            restored_folder, e
+        );
+    }
+    Ok(restored_folder)
+}
+
+/// Deletes a single world backup.
+#[tauri::command]
+pub async fn delete_world_backup(
+    profile_id: Uuid,
+    world_folder: String,
+    file_name: String,
+) -> Result<(), CommandError> {
+    info!(
+        "Executing delete_world_backup for profile {}, world '{}', backup '{}'",
+        profile_id, world_folder, file_name
+    );
+    let state = State::get().await?;
+    Ok(state
+        .world_backup_manager
+        .delete_backup(profile_id, &world_folder, &file_name)
+        .await?)
+}
+
+/// Imports a world from a zip or tar.zst archive. The result reports when the world
+/// was played in a newer Minecraft version than the profile uses.
+#[tauri::command]
+pub async fn import_world(
+    profile_id: Uuid,
+    archive_path: String,
+    world_name: Option<String>,
+) -> Result<WorldImportResult, CommandError> {
+    info!(
+        "Executing import_world for profile {} from '{}'",
+        profile_id, archive_path
+    );
+    let result = world_utils::import_world_archive(
+        profile_id,
+        &PathBuf::from(&archive_path),
+        world_name.as_deref(),
+    )
+    .await?;
+
+    if let Ok(state) = State::get().await {
+        if let Err(e) = state.event_state.trigger_profile_update(profile_id).await {
+            warn!(
+                "Failed to emit profile update event after importing world '{}': {}",
+                result.folder_name, e
+            );
+        }
+    }
+    Ok(result)
+}
+
+/// Exports a world as a zip to `export_path`, returns the size of the zip.
+#[tauri::command]
+pub async fn export_world(
+    profile_id: Uuid,
+    world_folder: String,
+    export_path: String,
+) -> Result<u64, CommandError> {
+    info!(
+        "Executing export_world for profile {}, world '{}' to '{}'",
+        profile_id, world_folder, export_path
+    );
+    Ok(
+        world_utils::export_world_archive(profile_id, &world_folder, &PathBuf::from(export_path))
+            .await?,
+    )
+}
+
+/// Reads seed, spawn, game rules, time, weather and other settings from a world's level.dat.
+#[tauri::command]
+pub async fn get_level_dat(
+    profile_id: Uuid,
+    world_folder: String,
+) -> Result<LevelDatSettings, CommandError> {
+    info!(
+        "Executing get_level_dat for profile {}, world '{}'",
+        profile_id, world_folder
+    );
+    Ok(level_dat_utils::read_level_dat(profile_id, &world_folder).await?)
+}
+
+/// Edits a world's level.dat after backing the world up. Fails while the world is open.
+#[tauri::command]
+pub async fn update_level_dat(
+    profile_id: Uuid,
+    world_folder: String,
+    update: LevelDatUpdate,
+) -> Result<LevelDatSettings, CommandError> {
+    info!(
+        "Executing update_level_dat for profile {}, world '{}'",
+        profile_id, world_folder
+    );
+    let settings = level_dat_utils::update_level_dat(profile_id, &world_folder, &update).await?;
+
+    let state = State::get().await?;
+    if let Err(e) = state.event_state.trigger_profile_update(profile_id).await {
+        warn!(
+            "Failed to emit profile update event after editing world '{}': {}",
+            world_folder, e
+        );
+    }
+    Ok(settings)
+}
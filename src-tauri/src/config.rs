@@ -1,5 +1,6 @@
 use directories::ProjectDirs;
 use once_cell::sync::Lazy;
+use crate::utils::mirror_utils::MirroredClient;
 use reqwest::Client;
 use std::path::PathBuf;
 use std::sync::RwLock;
@@ -26,6 +27,16 @@ pub static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
     client
 });
 
+/// HTTP Client for game files, loader metadata, mod downloads and the Modrinth and
+/// CurseForge APIs. Its GET and HEAD requests go through the configured download mirrors,
+/// credentials are only sent to the original host.
+///
+/// Not mirrored on purpose: POST lookups (e.g. CurseForge fingerprints and batch queries)
+/// and everything on `HTTP_CLIENT`: the Microsoft/Xbox login, Mojang session and profile
+/// lookups, skins and capes, log uploads and the launcher's own backend.
+pub static DOWNLOAD_CLIENT: Lazy<MirroredClient> =
+    Lazy::new(|| MirroredClient::new(HTTP_CLIENT.clone()));
+
 // Extension trait for ProjectDirs to add meta_dir functionality
 pub trait ProjectDirsExt {
     fn meta_dir(&self) -> PathBuf;
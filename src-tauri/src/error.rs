@@ -19,6 +19,9 @@ pub enum AppError {
     #[error("Download error: {0}")]
     Download(String),
 
+    #[error("Download job {0} was cancelled")]
+    DownloadCancelled(Uuid),
+
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
@@ -112,9 +115,15 @@ pub enum AppError {
     #[error("Failed to resolve mod: {0}")]
     ModResolutionFailed(String),
 
+    #[error("The mods of profile '{profile_name}' have problems that would crash the game:\n{details}\nFix them or enable 'Skip mod validation' in the profile settings to launch anyway.")]
+    ModValidationFailed { profile_name: String, details: String },
+
     #[error("Process spawn failed: {0}")]
     ProcessSpawnFailed(String),
 
+    #[error("Invalid wrapper command '{command}': {reason}")]
+    InvalidWrapperCommand { command: String, reason: String },
+
     #[error("Process not found: {0}")]
     ProcessNotFound(Uuid),
 
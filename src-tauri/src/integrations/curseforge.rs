@@ -1,4 +1,4 @@
-use crate::config::HTTP_CLIENT;
+use crate::config::{DOWNLOAD_CLIENT, HTTP_CLIENT};
 use crate::error::{AppError, Result};
 use crate::state::profile_state::{Mod, ModLoader, ModPackInfo, ModPackSource, ModSource, Profile, ProfileSettings, ProfileState};
 use log::{debug, error, info, warn};
@@ -335,7 +335,7 @@ pub async fn search_mods(
 
     log::info!("Searching CurseForge: {}", final_url);
 
-    let response = HTTP_CLIENT
+    let response = DOWNLOAD_CLIENT
         .get(final_url)
         .header("x-api-key", CURSEFORGE_API_KEY)
         .send()
@@ -626,7 +626,7 @@ pub async fn get_mod_files(
 
     log::info!("Getting CurseForge files: {}", final_url);
 
-    let response = HTTP_CLIENT
+    let response = DOWNLOAD_CLIENT
         .get(final_url)
         .header("x-api-key", CURSEFORGE_API_KEY)
         .send()
@@ -705,7 +705,7 @@ pub async fn get_file_details(mod_id: u32, file_id: u32) -> Result<CurseForgeFil
 
     log::info!("Getting CurseForge file details: mod_id={}, file_id={}", mod_id, file_id);
 
-    let response = HTTP_CLIENT
+    let response = DOWNLOAD_CLIENT
         .get(&url)
         .header("x-api-key", CURSEFORGE_API_KEY)
         .header("Accept", "application/json")
@@ -736,7 +736,7 @@ pub async fn get_mod_info(mod_id: u32) -> Result<CurseForgeMod> {
 
     log::info!("Getting CurseForge mod info: mod_id={}", mod_id);
 
-    let response = HTTP_CLIENT
+    let response = DOWNLOAD_CLIENT
         .get(&url)
         .header("x-api-key", CURSEFORGE_API_KEY)
         .header("Accept", "application/json")
@@ -774,7 +774,7 @@ pub async fn get_file_changelog(mod_id: u32, file_id: u32) -> Result<String> {
 
     log::info!("Getting CurseForge file changelog: mod_id={}, file_id={}", mod_id, file_id);
 
-    let response = HTTP_CLIENT
+    let response = DOWNLOAD_CLIENT
         .get(&url)
         .header("x-api-key", CURSEFORGE_API_KEY)
         .header("Accept", "application/json")
@@ -1855,7 +1855,7 @@ pub async fn download_and_install_curseforge_modpack(
     info!("Downloading to temporary file: {:?}", temp_file_path);
 
     // Download the file
-    let client = HTTP_CLIENT.clone();
+    let client = DOWNLOAD_CLIENT.clone();
     let response = client
         .get(&download_url)
         .header(
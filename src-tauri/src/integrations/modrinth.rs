@@ -1,3 +1,4 @@
+use crate::config::DOWNLOAD_CLIENT;
 use crate::error::{AppError, Result};
 use futures::future::join_all;
 use log::{self, error, info};
@@ -295,7 +296,7 @@ pub async fn search_projects(
     client_side_filter: Option<String>,
     server_side_filter: Option<String>,
 ) -> Result<ModrinthSearchResponse> {
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
     let base_url = format!("{}/search", MODRINTH_API_BASE_URL);
 
     let mut query_params: Vec<(String, String)> = Vec::new();
@@ -463,7 +464,7 @@ pub async fn get_mod_versions(
     loaders: Option<Vec<String>>,
     game_versions: Option<Vec<String>>,
 ) -> Result<Vec<ModrinthVersion>> {
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
     let url = format!(
         "{}/project/{}/version",
         MODRINTH_API_BASE_URL, project_id_or_slug
@@ -545,7 +546,7 @@ pub async fn get_mod_versions(
 // Function to get details for a specific Modrinth version ID
 // Based on https://docs.modrinth.com/api-spec/#tag/versions/operation/getVersion
 pub async fn get_version_details(version_id: String) -> Result<ModrinthVersion> {
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
     let url = format!("{}/version/{}", MODRINTH_API_BASE_URL, version_id);
 
     log::info!("Getting Modrinth version details: {}", url);
@@ -613,7 +614,7 @@ pub async fn get_all_versions_for_projects(
         contexts.len()
     );
 
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
 
     // Create a list of futures, one for each context
     let futures = contexts.into_iter().map(|context| {
@@ -678,7 +679,7 @@ pub async fn get_version_by_hash(file_hash: String) -> Result<ModrinthVersion> {
         }
     };
 
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
     let url = format!(
         "{}/version_file/{}?algorithm={}", // Correct endpoint path
         MODRINTH_API_BASE_URL, file_hash, algorithm
@@ -759,7 +760,7 @@ pub async fn get_versions_by_hashes(
         )));
     }
 
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
     let url = format!("{}/version_files", MODRINTH_API_BASE_URL); // POST endpoint
 
     let request_body = HashesRequestBody {
@@ -884,7 +885,7 @@ impl ModrinthBulkUpdateRequestBody {
 pub async fn check_bulk_updates(
     request: ModrinthBulkUpdateRequestBody,
 ) -> Result<HashMap<String, ModrinthVersion>> {
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
     let url = format!("{}/version_files/update", MODRINTH_API_BASE_URL); // Update check endpoint
 
     log::info!(
@@ -962,7 +963,7 @@ pub async fn get_multiple_projects(ids: Vec<String>) -> Result<Vec<ModrinthProje
     // Modrinth expects the IDs as a JSON array string in the query parameter
     let ids_json = serde_json::to_string(&ids).map_err(|e| AppError::Json(e))?; // Use appropriate error type
 
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
     // Note: No trailing slash needed for the base URL when using parse_with_params
     let base_url = format!("{}/projects", MODRINTH_API_BASE_URL);
 
@@ -1064,7 +1065,7 @@ pub async fn get_multiple_projects(ids: Vec<String>) -> Result<Vec<ModrinthProje
 /// Fetches a list of all categories from Modrinth.
 /// https://docs.modrinth.com/api/operations/categorylist/
 pub async fn get_modrinth_categories() -> Result<Vec<ModrinthCategory>> {
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
     let url = format!("{}/tag/category", MODRINTH_API_BASE_URL);
 
     log::info!("Fetching Modrinth categories from: {}", url);
@@ -1121,7 +1122,7 @@ pub async fn get_modrinth_categories() -> Result<Vec<ModrinthCategory>> {
 /// Fetches a list of all loaders from Modrinth.
 /// https://docs.modrinth.com/api/operations/loaderlist/
 pub async fn get_modrinth_loaders() -> Result<Vec<ModrinthLoader>> {
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
     let url = format!("{}/tag/loader", MODRINTH_API_BASE_URL);
 
     log::info!("Fetching Modrinth loaders from: {}", url);
@@ -1172,7 +1173,7 @@ pub async fn get_modrinth_loaders() -> Result<Vec<ModrinthLoader>> {
 /// Fetches a list of all game versions from Modrinth.
 /// https://docs.modrinth.com/api/operations/versionlist/
 pub async fn get_modrinth_game_versions() -> Result<Vec<ModrinthGameVersion>> {
-    let client = reqwest::Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
     let url = format!("{}/tag/game_version", MODRINTH_API_BASE_URL);
 
     log::info!("Fetching Modrinth game versions from: {}", url);
@@ -1,3 +1,4 @@
+use crate::config::DOWNLOAD_CLIENT;
 use crate::error::{AppError, Result};
 use crate::integrations::modrinth;
 use crate::state::profile_state::{
@@ -8,7 +9,6 @@ use async_zip::tokio::read::seek::ZipFileReader;
 use chrono::Utc;
 use futures::future::try_join_all;
 use log::{debug, error, info, warn};
-use reqwest::Client;
 use sanitize_filename::sanitize;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -889,7 +889,7 @@ pub async fn download_and_process_mrpack(
     );
 
     // Create HTTP client
-    let client = Client::new();
+    let client = DOWNLOAD_CLIENT.clone();
 
     // Download the file
     let response = client
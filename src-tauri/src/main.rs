@@ -21,14 +21,17 @@ use std::sync::Arc;
 use tauri::Listener;
 use tauri::Manager;
 use utils::debug_utils;
+use utils::deep_link_utils;
 use utils::updater_utils;
 
 use crate::commands::process_command::{
-    get_full_log, get_process, get_processes, get_processes_by_profile, open_log_window,
+    analyze_profile_crash, get_full_log, get_launch_timelines, get_process, get_processes,
+    get_processes_by_profile, get_profile_play_history, get_profile_playtimes, open_log_window,
     set_discord_state, stop_process,
 };
 use commands::minecraft_auth_command::{
-    begin_login, get_accounts, get_active_account, remove_account, set_active_account,
+    add_offline_account, begin_login, get_accounts, get_active_account, remove_account,
+    set_active_account,
 };
 use commands::minecraft_command::{
     add_skin,
@@ -54,7 +57,7 @@ use commands::minecraft_command::{
 use commands::profile_command::{
     abort_profile_launch, add_modrinth_content_to_profile, add_modrinth_mod_to_profile,
     batch_check_content_installed, check_for_group_migration_command, check_world_lock_status, copy_profile, copy_world,
-    create_profile, delete_custom_mod, delete_mod_from_profile, delete_profile, delete_world,
+    create_profile, create_profile_desktop_shortcut, delete_custom_mod, delete_mod_from_profile, delete_profile, delete_world,
     export_profile, get_all_profiles_and_last_played, get_custom_mods, get_local_content,
     get_local_datapacks, get_local_resourcepacks, get_local_shaderpacks, get_log_file_content,
     get_GEG_packs, get_GEG_packs_resolved, get_profile, get_profile_directory_structure,
@@ -66,6 +69,7 @@ use commands::profile_command::{
     resolve_loader_version, search_profiles, set_custom_mod_enabled, set_GEG_mod_status,
     set_profile_mod_enabled, update_datapack_from_modrinth, update_modrinth_mod_version,
     update_profile, update_resourcepack_from_modrinth, update_shaderpack_from_modrinth,
+    verify_profile_integrity,
 };
 
 // Use statements for registered commands only
@@ -95,6 +99,30 @@ use tauri::{
 // Import path commands
 use commands::path_commands::{get_launcher_directory, resolve_image_path};
 
+// Import storage commands
+use commands::storage_command::{analyze_storage, cleanup_storage, gc_mod_cache};
+
+// Import server list commands
+use commands::server_command::{
+    add_profile_server, copy_profile_server_list, get_profile_server_statuses,
+    get_server_status_history, list_profile_servers, move_profile_server, ping_profile_servers,
+    push_server_list_to_group, remove_profile_server, set_profile_server_icon,
+    update_profile_server,
+};
+
+// Import world commands
+use commands::world_command::{
+    backup_profile_worlds, create_world_backup, delete_world_backup, export_world, get_level_dat,
+    get_world_backup_policy, import_world, list_world_backups, restore_world_backup,
+    set_world_backup_policy, update_level_dat,
+};
+
+// Import download queue commands
+use commands::download_queue_command::{
+    cancel_download_job, clear_finished_download_jobs, get_download_queue, pause_download_job,
+    resume_download_job, set_download_job_priority,
+};
+
 // Import cape commands
 use commands::cape_command::{
     browse_capes, delete_cape, download_template_and_open_explorer, equip_cape, get_player_capes,
@@ -120,7 +148,9 @@ use commands::content_command::{
 // Import Java commands
 use commands::java_command::{
     detect_java_installations_command, find_best_java_for_minecraft_command, get_java_info_command,
-    invalidate_java_cache_command, validate_java_path_command,
+    invalidate_java_cache_command, validate_java_path_command, list_java_runtimes_command,
+    pin_profile_java_runtime_command, check_java_runtime_updates_command,
+    update_java_runtime_command, remove_java_runtime_command,
 };
 
 #[tokio::main]
@@ -137,6 +167,11 @@ async fn main() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
             info!("SingleInstance plugin: Second instance triggered with args: {:?}", argv);
+            // geg://launch/<profile-id> starts the profile without showing the launcher
+            if deep_link_utils::find_launch_deep_link(&argv).is_some() {
+                tauri::async_runtime::spawn(deep_link_utils::handle_deep_link_args(argv));
+                return;
+            }
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.show();
                 let _ = window.unminimize();
@@ -240,12 +275,15 @@ async fn main() {
             tauri::async_runtime::spawn(async move {
                 // --- State Initialization --- 
                 info!("Initiating state initialization...");
-                if let Err(e) = state::state_manager::State::init(Arc::new(state_init_app_handle.clone())).await {
+                if let Err(e) = state::state_manager::State::init(Some(Arc::new(state_init_app_handle.clone()))).await {
                     error!("CRITICAL: Failed to initialize state: {}", e);
                     return;
                 }
                 info!("State initialization finished successfully.");
 
+                // First start via desktop shortcut / deep link (later ones arrive via single-instance)
+                deep_link_utils::handle_deep_link_args(std::env::args().collect()).await;
+
                 info!("Attempting to retrieve launcher configuration for update check...");
                 match state::state_manager::State::get().await {
                     Ok(state_manager_instance) => {
@@ -334,16 +372,53 @@ async fn main() {
             update_profile,
             delete_profile,
             repair_profile,
+            verify_profile_integrity,
             resolve_loader_version,
             list_profiles,
             search_profiles,
             get_minecraft_versions,
             launch_profile,
+            create_profile_desktop_shortcut,
             abort_profile_launch,
             is_profile_launching,
             get_processes,
             get_process,
             get_processes_by_profile,
+            get_launch_timelines,
+            get_profile_play_history,
+            get_profile_playtimes,
+            analyze_profile_crash,
+            gc_mod_cache,
+            analyze_storage,
+            cleanup_storage,
+            get_world_backup_policy,
+            set_world_backup_policy,
+            create_world_backup,
+            backup_profile_worlds,
+            list_world_backups,
+            restore_world_backup,
+            delete_world_backup,
+            import_world,
+            export_world,
+            get_level_dat,
+            update_level_dat,
+            list_profile_servers,
+            add_profile_server,
+            update_profile_server,
+            move_profile_server,
+            remove_profile_server,
+            set_profile_server_icon,
+            copy_profile_server_list,
+            push_server_list_to_group,
+            ping_profile_servers,
+            get_profile_server_statuses,
+            get_server_status_history,
+            get_download_queue,
+            pause_download_job,
+            resume_download_job,
+            cancel_download_job,
+            set_download_job_priority,
+            clear_finished_download_jobs,
             stop_process,
             open_log_window,
             begin_login,
@@ -351,6 +426,7 @@ async fn main() {
             get_active_account,
             set_active_account,
             get_accounts,
+            add_offline_account,
             search_modrinth_mods,
             search_modrinth_projects,
             search_mods_unified_command,
@@ -438,6 +514,11 @@ async fn main() {
             find_best_java_for_minecraft_command,
             invalidate_java_cache_command,
             validate_java_path_command,
+            list_java_runtimes_command,
+            pin_profile_java_runtime_command,
+            check_java_runtime_updates_command,
+            update_java_runtime_command,
+            remove_java_runtime_command,
             get_worlds_for_profile,
             get_servers_for_profile,
             copy_world,
@@ -1,4 +1,4 @@
-use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
+use crate::config::{ProjectDirsExt, DOWNLOAD_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::dto::forge_maven_meta::ForgeMavenMetadata;
 use log::{debug, error, info};
@@ -31,7 +31,7 @@ impl ForgeApi {
     async fn fetch_and_cache_metadata(base_url: &str, cache_path: &PathBuf) -> Result<ForgeMavenMetadata> {
         debug!("Fetching Forge metadata from: {}", base_url);
 
-        let response = HTTP_CLIENT.get(base_url)
+        let response = DOWNLOAD_CLIENT.get(base_url)
             .send()
             .await
             .map_err(|e| AppError::ForgeError(format!("Failed to fetch Forge versions: {}", e)))?;
@@ -1,5 +1,6 @@
-use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
+use crate::config::{ProjectDirsExt, DOWNLOAD_CLIENT, HTTP_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
+use crate::minecraft::dto::java_runtime_manifest::{JavaRuntimeIndex, JavaRuntimeManifest};
 use crate::minecraft::dto::minecraft_profile::MinecraftProfile;
 use crate::minecraft::dto::piston_meta::PistonMeta;
 use crate::minecraft::dto::version_manifest::VersionManifest;
@@ -17,6 +18,7 @@ use tokio;
 use tokio::fs as tokio_fs;
 
 const VERSION_MANIFEST_URL: &str = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
+const JAVA_RUNTIME_INDEX_URL: &str = "https://launchermeta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
 const MOJANG_API_URL: &str = "https://api.mojang.com";
 const MOJANG_SESSION_URL: &str = "https://sessionserver.mojang.com";
 
@@ -46,7 +48,7 @@ impl MinecraftApiService {
     async fn fetch_and_cache_manifest(cache_path: &PathBuf) -> Result<VersionManifest> {
         debug!("Fetching Minecraft version manifest from: {}", VERSION_MANIFEST_URL);
         
-        let response = HTTP_CLIENT.get(VERSION_MANIFEST_URL)
+        let response = DOWNLOAD_CLIENT.get(VERSION_MANIFEST_URL)
             .send()
             .await
             .map_err(AppError::MinecraftApi)?;
@@ -116,7 +118,7 @@ impl MinecraftApiService {
     async fn fetch_and_cache_piston_meta(cache_path: &PathBuf, url: &str) -> Result<PistonMeta> {
         debug!("Fetching Piston Meta from: {}", url);
         
-        let response = HTTP_CLIENT.get(url)
+        let response = DOWNLOAD_CLIENT.get(url)
             .send()
             .await
             .map_err(AppError::MinecraftApi)?;
@@ -188,6 +190,101 @@ impl MinecraftApiService {
         Self::fetch_and_cache_piston_meta(&cache_path, url).await
     }
 
+    async fn fetch_and_cache_java_runtime_index(cache_path: &PathBuf) -> Result<JavaRuntimeIndex> {
+        debug!("Fetching Java runtime index from: {}", JAVA_RUNTIME_INDEX_URL);
+
+        let response = DOWNLOAD_CLIENT.get(JAVA_RUNTIME_INDEX_URL)
+            .send()
+            .await
+            .map_err(AppError::MinecraftApi)?;
+
+        let index = response
+            .json::<JavaRuntimeIndex>()
+            .await
+            .map_err(AppError::MinecraftApi)?;
+
+        let json_data = serde_json::to_string_pretty(&index).map_err(|e| {
+            AppError::Other(format!("Failed to serialize Java runtime index: {}", e))
+        })?;
+
+        if let Err(e) = tokio_fs::write(cache_path, json_data).await {
+            error!("Failed to write Java runtime index cache: {}", e);
+        } else {
+            debug!("Cached Java runtime index: {:?}", cache_path);
+        }
+
+        Ok(index)
+    }
+
+    /// Mojang's index of the Java runtimes the official launcher ships, per
+    /// platform and component (e.g. `java-runtime-delta`).
+    pub async fn get_java_runtime_index(&self) -> Result<JavaRuntimeIndex> {
+        let cache_path = self.cache_dir.join("java_runtime_index.json");
+
+        if cache_path.exists() {
+            debug!("Cache hit for Java runtime index: {:?}", cache_path);
+
+            match tokio_fs::read_to_string(&cache_path).await {
+                Ok(cached_data) => match serde_json::from_str::<JavaRuntimeIndex>(&cached_data) {
+                    Ok(cached_index) => {
+                        let cache_path_clone = cache_path.clone();
+                        tokio::spawn(async move {
+                            debug!("[BG] Updating Java runtime index");
+                            if let Err(e) =
+                                Self::fetch_and_cache_java_runtime_index(&cache_path_clone).await
+                            {
+                                error!("[BG] Failed to update Java runtime index cache: {}", e);
+                            }
+                        });
+                        return Ok(cached_index);
+                    }
+                    Err(e) => {
+                        error!("Failed to parse cached Java runtime index: {}", e);
+                    }
+                },
+                Err(e) => {
+                    error!("Failed to read Java runtime index cache: {}", e);
+                }
+            }
+        }
+
+        debug!("Cache miss for Java runtime index, fetching...");
+        Self::fetch_and_cache_java_runtime_index(&cache_path).await
+    }
+
+    /// Fetches the Java runtime index in the foreground, bypassing the cache.
+    /// Used by update checks, which must not act on a stale index.
+    pub async fn refresh_java_runtime_index(&self) -> Result<JavaRuntimeIndex> {
+        let cache_path = self.cache_dir.join("java_runtime_index.json");
+        Self::fetch_and_cache_java_runtime_index(&cache_path).await
+    }
+
+    /// Downloads the file manifest of one runtime build and checks it against the
+    /// sha1 listed in the index. Not cached: the installed runtime keeps its own copy.
+    pub async fn get_java_runtime_manifest(&self, url: &str, sha1: &str) -> Result<JavaRuntimeManifest> {
+        debug!("Fetching Java runtime manifest from: {}", url);
+
+        let bytes = DOWNLOAD_CLIENT.get(url)
+            .send()
+            .await
+            .map_err(AppError::MinecraftApi)?
+            .bytes()
+            .await
+            .map_err(AppError::MinecraftApi)?;
+
+        let mut hasher = Sha1::new();
+        hasher.update(&bytes);
+        let actual = format!("{:x}", hasher.finalize());
+        if !actual.eq_ignore_ascii_case(sha1) {
+            return Err(AppError::JavaDownload(format!(
+                "Java runtime manifest hash mismatch: expected {}, got {}",
+                sha1, actual
+            )));
+        }
+
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
     // Get user profile including skin information
     pub async fn get_user_profile(&self, uuid: &str) -> Result<MinecraftProfile> {
         debug!("API call: get_user_profile for UUID: {}", uuid);
@@ -1,4 +1,4 @@
-use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
+use crate::config::{ProjectDirsExt, DOWNLOAD_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::dto::neo_forge_maven_meta::NeoForgeMavenMetadata;
 use log::{debug, error, info};
@@ -31,7 +31,7 @@ impl NeoForgeApi {
     async fn fetch_and_cache_metadata(base_url: &str, cache_path: &PathBuf) -> Result<NeoForgeMavenMetadata> {
         debug!("Fetching NeoForge metadata from: {}", base_url);
 
-        let response = HTTP_CLIENT.get(base_url)
+        let response = DOWNLOAD_CLIENT.get(base_url)
             .send()
             .await
             .map_err(|e| AppError::ForgeError(format!("Failed to fetch NeoForge versions: {}", e)))?;
@@ -1,4 +1,4 @@
-use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
+use crate::config::{ProjectDirsExt, DOWNLOAD_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::Result;
 use crate::minecraft::dto::quilt_meta::QuiltVersionInfo;
 use log::{debug, error};
@@ -33,7 +33,7 @@ impl QuiltApi {
         let url = format!("{}/versions/loader/{}", base_url, minecraft_version);
         debug!("Fetching Quilt versions from: {}", url);
 
-        let response = HTTP_CLIENT.get(&url).send().await.map_err(|e| {
+        let response = DOWNLOAD_CLIENT.get(&url).send().await.map_err(|e| {
             crate::error::AppError::QuiltError(format!("Failed to fetch Quilt versions: {}", e))
         })?;
 
@@ -45,6 +45,56 @@ pub struct Credentials {
     pub expires: DateTime<Utc>,
     pub GEG_credentials: GEGCredentials,
     pub active: bool,
+    #[serde(default)]
+    pub account_type: AccountType,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountType {
+    #[default]
+    Microsoft,
+    /// Local account for singleplayer and offline-mode servers, never authenticated
+    Offline,
+}
+
+impl Credentials {
+    /// Creates a local offline account. The UUID is derived from the name exactly like vanilla
+    /// does (`UUID.nameUUIDFromBytes("OfflinePlayer:" + name)`), so worlds keep their player data.
+    pub fn offline(username: &str) -> Result<Self> {
+        let valid = (3..=16).contains(&username.len())
+            && username
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(AppError::InvalidInput(format!(
+                "'{}' is not a valid Minecraft name (3-16 characters, letters, digits and _)",
+                username
+            )));
+        }
+
+        let digest = md5::Md5::digest(format!("OfflinePlayer:{}", username).as_bytes());
+        let id = uuid::Builder::from_md5_bytes(digest.into()).into_uuid();
+
+        Ok(Self {
+            id,
+            username: username.to_string(),
+            // Vanilla accepts any token for offline sessions
+            access_token: "0".to_string(),
+            refresh_token: String::new(),
+            expires: DateTime::<Utc>::MAX_UTC,
+            GEG_credentials: GEGCredentials {
+                production: None,
+                experimental: None,
+            },
+            active: false,
+            account_type: AccountType::Offline,
+        })
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.account_type == AccountType::Offline
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -495,6 +545,7 @@ impl MinecraftAuthStore {
                     experimental: None,
                 },
             },
+            account_type: AccountType::Microsoft,
         };
 
         info!(
@@ -678,6 +729,7 @@ impl MinecraftAuthStore {
             expires: oauth_token.date + Duration::seconds(oauth_token.value.expires_in as i64),
             GEG_credentials: creds.clone().GEG_credentials,
             active: creds.clone().active,
+            account_type: AccountType::Microsoft,
         };
 
         info!("[Token Refresh] Updating account in storage");
@@ -729,6 +781,10 @@ impl MinecraftAuthStore {
             "[Token Check] Starting token validation check for user: {}",
             creds.username
         );
+        if creds.is_offline() {
+            info!("[Token Check] Offline account, nothing to refresh");
+            return Ok(Some(creds.clone()));
+        }
         info!(
             "[Token Check] Microsoft token expires at: {}",
             creds.expires
@@ -762,11 +818,18 @@ impl MinecraftAuthStore {
                         MinecraftAuthenticationError::Request { ref source, .. },
                     ) = err
                     {
-                        if source.is_connect() || source.is_timeout() {
+                        // The request never got an answer (offline, DNS, timeout...)
+                        if source.is_connect() || source.is_timeout() || source.is_request() {
                             info!("[Token Check] Connection error during refresh, using old credentials");
                             return Ok(Some(old_credentials));
                         }
                     }
+                    if let AppError::MinecraftApi(ref source) = err {
+                        if source.is_connect() || source.is_timeout() || source.is_request() {
+                            info!("[Token Check] Network error during refresh, using old credentials");
+                            return Ok(Some(old_credentials));
+                        }
+                    }
                     info!("[Token Check] Error during token refresh: {:?}", err);
                     Err(err)
                 }
@@ -806,6 +869,15 @@ impl MinecraftAuthStore {
         };
 
         if let Some(account) = active_account {
+            // Offline mode: launch with the last-known credentials, don't touch the network
+            if state.config_manager.is_offline_mode().await {
+                info!(
+                    "[Account Manager] Offline mode enabled, using cached credentials for: {}",
+                    account.username
+                );
+                return Ok(Some(account));
+            }
+
             info!(
                 "[Account Manager] Refreshing credentials for active account: {}",
                 account.username
@@ -893,6 +965,34 @@ impl MinecraftAuthStore {
         Ok(())
     }
 
+    /// Adds (or reactivates) a local offline account and makes it the active one.
+    pub async fn add_offline_account(&self, username: &str) -> Result<Credentials> {
+        let mut credentials = Credentials::offline(username)?;
+        info!(
+            "[Account Manager] Adding offline account {} ({})",
+            credentials.username, credentials.id
+        );
+
+        if let Some(existing) = self.get_account_by_id(credentials.id).await? {
+            if !existing.is_offline() {
+                return Err(AppError::AccountError(format!(
+                    "A Microsoft account with the UUID {} already exists",
+                    credentials.id
+                )));
+            }
+        }
+
+        credentials.active = true;
+        {
+            let mut accounts = self.accounts.write().await;
+            for account in accounts.iter_mut() {
+                account.active = false;
+            }
+        }
+        self.update_or_insert(credentials.clone()).await?;
+        Ok(credentials)
+    }
+
     pub async fn get_all_accounts(&self) -> Result<Vec<Credentials>> {
         info!("[Account Manager] Starting get_all_accounts operation");
 
@@ -900,7 +1000,11 @@ impl MinecraftAuthStore {
         let accounts = self.accounts.read().await;
         info!("[Account Manager] Successfully acquired read lock");
 
-        info!("[Account Manager] Found {} accounts", accounts.len());
+        info!(
+            "[Account Manager] Found {} accounts ({} offline)",
+            accounts.len(),
+            accounts.iter().filter(|acc| acc.is_offline()).count()
+        );
         let accounts_clone = accounts.clone();
 
         info!("[Account Manager] Returning all accounts");
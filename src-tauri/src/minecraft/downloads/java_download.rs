@@ -1,5 +1,6 @@
-use crate::config::{ProjectDirsExt, HTTP_CLIENT, LAUNCHER_DIRECTORY};
+use crate::config::{ProjectDirsExt, DOWNLOAD_CLIENT, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
+use crate::minecraft::downloads::mojang_java_runtime_download::MojangJavaRuntimeService;
 use crate::minecraft::dto::{JavaDistribution, ZuluApiResponse};
 use crate::state::State;
 use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
@@ -7,7 +8,7 @@ use crate::utils::system_info::{Architecture, OperatingSystem, ARCHITECTURE, OS}
 use async_zip::tokio::read::seek::ZipFileReader;
 use flate2::read::GzDecoder;
 use futures::future::try_join_all;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use reqwest;
 use std::fs::File;
 use std::io::Cursor;
@@ -67,6 +68,25 @@ impl JavaDownloadService {
         // Handle architecture override for legacy Java component on ARM64 Mac
         let force_x86_64 = self.needs_x86_64_java(java_component);
 
+        // Mojang runtimes are picked by component, fall back to Zulu by major version
+        let distribution = if *distribution == JavaDistribution::Mojang {
+            if let Some(component) = java_component {
+                match MojangJavaRuntimeService::new()
+                    .get_or_install(component, force_x86_64)
+                    .await
+                {
+                    Ok(java_binary) => return Ok(java_binary),
+                    Err(e) => warn!(
+                        "Mojang Java runtime '{}' unavailable: {}. Falling back to Zulu {}.",
+                        component, e, version
+                    ),
+                }
+            }
+            &JavaDistribution::Zulu
+        } else {
+            distribution
+        };
+
         // Check if Java is already downloaded
         if let Ok(java_binary) = self
             .find_java_binary(distribution, &version, force_x86_64)
@@ -107,7 +127,7 @@ impl JavaDownloadService {
         // For Zulu, we need to make an extra API call to get the actual download URL
         let download_url = if distribution.requires_api_response() {
             info!("Fetching actual download URL from Zulu API...");
-            let client = reqwest::Client::new();
+            let client = DOWNLOAD_CLIENT.clone();
             let response = client
                 .get(&initial_url)
                 .header("Accept", "application/json")
@@ -150,6 +170,7 @@ impl JavaDownloadService {
         let config = DownloadConfig::new()
             .with_streaming(true)  // Java archives are large files
             .with_retries(3)  // Built-in retry logic for network issues
+            .with_resume(true)  // Continue interrupted archive downloads
             .with_force_overwrite(true);  // Always download fresh Java
 
         DownloadUtils::download_file(&download_url, &archive_path, config)
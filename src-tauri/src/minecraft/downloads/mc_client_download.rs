@@ -34,7 +34,8 @@ impl MinecraftClientDownloadService {
         let config = DownloadConfig::new()
             .with_size(client_info.size as u64)  // Size verification prevents corruption
             .with_streaming(true)  // Client JARs are large files
-            .with_retries(3);  // Built-in retry logic for network issues
+            .with_retries(3)  // Built-in retry logic for network issues
+            .with_resume(true);  // Continue interrupted downloads
 
         DownloadUtils::download_file(&client_info.url, &target_path, config).await?;
 
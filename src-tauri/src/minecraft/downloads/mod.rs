@@ -7,8 +7,10 @@ pub mod mc_assets_download;
 pub mod mc_client_download;
 pub mod mc_libraries_download;
 pub mod mc_natives_download;
+pub mod mod_cache;
 pub mod mod_downloader;
 pub mod mod_resolver;
+pub mod mojang_java_runtime_download;
 pub mod neo_forge_installer_download;
 pub mod neo_forge_libraries_download;
 pub mod norisk_assets_download;
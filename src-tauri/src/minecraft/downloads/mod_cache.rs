@@ -0,0 +1,775 @@
+use crate::error::{AppError, Result};
+use crate::integrations::norisk_packs::{self, GEGModSourceDefinition, GEGModpacksConfig};
+use crate::minecraft::downloads::mod_resolver;
+use crate::state::profile_state::{self, Profile};
+use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
+use chrono::{DateTime, Duration, Utc};
+use log::{debug, error, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use sha2::Sha512;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use tokio::fs;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const MOD_CACHE_DIR_NAME: &str = "mod_cache";
+const OBJECTS_DIR_NAME: &str = "objects";
+const TMP_DIR_NAME: &str = "tmp";
+const INDEX_FILENAME: &str = "index.json";
+/// Partial downloads younger than this are assumed to belong to a running download.
+const TMP_FILE_GRACE_HOURS: i64 = 24;
+/// Index entries younger than this survive GC unreferenced, e.g. a mod that was downloaded
+/// but whose profile isn't saved yet.
+const NEW_ENTRY_GRACE_MINUTES: i64 = 60;
+
+/// In-memory copy of `mod_cache/index.json`, loaded on first access.
+static MOD_CACHE_INDEX: Lazy<RwLock<ModCacheIndex>> =
+    Lazy::new(|| RwLock::new(ModCache::load_index()));
+static INDEX_SAVE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+/// Held while an object is written and indexed and during GC, so GC never sees an object
+/// whose index entry isn't inserted yet.
+static OBJECT_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// A single file stored in the cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModCacheEntry {
+    pub sha1: String,
+    pub sha512: String,
+    pub size: u64,
+    /// Filename the mod is deployed under (also used for the object's extension).
+    pub filename: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Maps cache keys (source identity + filename) to the content hash of the stored file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ModCacheIndex {
+    #[serde(default)]
+    entries: HashMap<String, ModCacheEntry>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModCacheGcItemKind {
+    /// Content-addressed object that no profile or pack references anymore.
+    Object,
+    /// Flat file from the old filename-keyed cache layout.
+    Legacy,
+    /// Leftover from an interrupted download.
+    Partial,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModCacheGcItem {
+    pub kind: ModCacheGcItemKind,
+    pub path: PathBuf,
+    pub sha1: Option<String>,
+    pub filenames: Vec<String>,
+    pub size_bytes: u64,
+}
+
+/// Result of a garbage collection run. With `dry_run` nothing is deleted and
+/// `reclaimable_bytes` tells how much space a real run would free.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModCacheGcReport {
+    pub dry_run: bool,
+    pub total_objects: usize,
+    pub total_bytes: u64,
+    pub referenced_objects: usize,
+    /// Number of references (profiles and packs) keeping objects alive.
+    pub total_references: usize,
+    pub stale_index_entries: usize,
+    pub items: Vec<ModCacheGcItem>,
+    pub reclaimable_bytes: u64,
+}
+
+/// Content-addressed store for mod jars below `meta/mod_cache`.
+///
+/// Files live in `objects/<sha1[..2]>/<sha1>.<ext>`; `index.json` maps a cache
+/// key (see [`ModCache::cache_key`]) to the stored file. Identical files used by
+/// several profiles or packs are therefore stored once.
+pub struct ModCache;
+
+impl ModCache {
+    #[cfg(not(test))]
+    pub fn cache_dir() -> PathBuf {
+        use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+        LAUNCHER_DIRECTORY.meta_dir().join(MOD_CACHE_DIR_NAME)
+    }
+
+    #[cfg(test)]
+    pub fn cache_dir() -> PathBuf {
+        tests::TEST_CACHE_DIR.clone()
+    }
+
+    fn objects_dir() -> PathBuf {
+        Self::cache_dir().join(OBJECTS_DIR_NAME)
+    }
+
+    fn index_path() -> PathBuf {
+        Self::cache_dir().join(INDEX_FILENAME)
+    }
+
+    /// Builds the cache key for a mod from its canonical source key
+    /// (e.g. `modrinth:AANobbMI`) and the filename of the requested version.
+    pub fn cache_key(canonical_key: &str, filename: &str) -> String {
+        format!("{}#{}", canonical_key, filename)
+    }
+
+    /// Cache key of a mod from a profile's mod list, if the source is cacheable.
+    pub fn profile_mod_key(source: &profile_state::ModSource) -> Option<String> {
+        let canonical_key = mod_resolver::profile_mod_canonical_key(source)?;
+        let filename = profile_state::get_profile_mod_filename(source).ok()?;
+        Some(Self::cache_key(&canonical_key, &filename))
+    }
+
+    /// Cache key of a GEG pack mod for the given (effective) source and filename.
+    pub fn pack_mod_key(source: &GEGModSourceDefinition, mod_id: &str, filename: &str) -> String {
+        let canonical_key = mod_resolver::pack_mod_canonical_key(source, mod_id)
+            .unwrap_or_else(|| format!("pack:{}", mod_id));
+        Self::cache_key(&canonical_key, filename)
+    }
+
+    /// Moves the index entry of `old_key` to `new_key` unless `new_key` already has one.
+    /// Pack mods with a source override in their compatibility target were keyed by the
+    /// mod's base source before keys followed the effective source; their objects were
+    /// downloaded from the override either way, so the content stays valid.
+    pub fn migrate_key(old_key: &str, new_key: &str) -> bool {
+        if old_key == new_key {
+            return false;
+        }
+        let Ok(mut index) = MOD_CACHE_INDEX.write() else {
+            return false;
+        };
+        if index.entries.contains_key(new_key) {
+            return false;
+        }
+        match index.entries.remove(old_key) {
+            Some(entry) => {
+                info!("Migrated mod cache entry '{}' to '{}'", old_key, new_key);
+                index.entries.insert(new_key.to_string(), entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn object_path(sha1: &str, filename: &str) -> PathBuf {
+        let extension = Path::new(filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("jar");
+        Self::objects_dir()
+            .join(&sha1[..2.min(sha1.len())])
+            .join(format!("{}.{}", sha1, extension))
+    }
+
+    /// Returns where the file for `key` lives. Falls back to the old flat
+    /// `mod_cache/<filename>` location for mods that were cached before the
+    /// content-addressed layout existed. The returned path may not exist.
+    pub fn path_for(key: &str, filename: &str) -> PathBuf {
+        if let Some(entry) = Self::get_entry(key) {
+            let object = Self::object_path(&entry.sha1, &entry.filename);
+            if object.exists() {
+                return object;
+            }
+        }
+        Self::cache_dir().join(filename)
+    }
+
+    pub fn get_entry(key: &str) -> Option<ModCacheEntry> {
+        MOD_CACHE_INDEX
+            .read()
+            .ok()
+            .and_then(|index| index.entries.get(key).cloned())
+    }
+
+    /// Makes sure the file for `key` is in the cache, downloading it from `url`
+    /// if needed, and returns the object path.
+    pub async fn ensure_cached(
+        key: &str,
+        filename: &str,
+        url: &str,
+        expected_sha1: Option<&str>,
+    ) -> Result<PathBuf> {
+        let expected_sha1 = expected_sha1.map(|h| h.to_lowercase());
+
+        // 1. Already indexed under this key
+        if let Some(entry) = Self::get_entry(key) {
+            let hash_matches = expected_sha1.as_deref().is_none_or(|h| h == entry.sha1);
+            let object = Self::object_path(&entry.sha1, &entry.filename);
+            if hash_matches && Self::has_size(&object, entry.size).await {
+                debug!("Mod cache hit for '{}' ({})", key, entry.sha1);
+                return Ok(object);
+            }
+            debug!(
+                "Mod cache entry for '{}' is outdated or missing, refetching",
+                key
+            );
+        }
+
+        // 2. Same content already stored for another key (e.g. pack and profile both use it)
+        if let Some(sha1) = expected_sha1.as_deref() {
+            let object = Self::object_path(sha1, filename);
+            if object.exists() {
+                match Self::ingest_file(key, filename, &object, Some(sha1)).await {
+                    Ok(entry) => return Ok(Self::object_path(&entry.sha1, &entry.filename)),
+                    Err(e) => {
+                        warn!("Stored object {:?} is corrupt, removing it: {}", object, e);
+                        let _ = fs::remove_file(&object).await;
+                    }
+                }
+            }
+        }
+
+        // 3. File from the old filename-keyed layout
+        let legacy_path = Self::cache_dir().join(filename);
+        if legacy_path.is_file() {
+            match Self::ingest_file(key, filename, &legacy_path, expected_sha1.as_deref()).await {
+                Ok(entry) => {
+                    info!(
+                        "Migrated legacy mod cache file '{}' ({})",
+                        filename, entry.sha1
+                    );
+                    return Ok(Self::object_path(&entry.sha1, &entry.filename));
+                }
+                Err(e) => debug!("Ignoring legacy mod cache file '{}': {}", filename, e),
+            }
+        }
+
+        // 4. Download
+        let tmp_dir = Self::cache_dir().join(TMP_DIR_NAME);
+        fs::create_dir_all(&tmp_dir).await?;
+        let tmp_path = tmp_dir.join(format!("{}-{}", Uuid::new_v4(), filename));
+
+        let mut config = DownloadConfig::new()
+            .with_streaming(true) // Mods can be large files
+            .with_retries(3); // Built-in retry logic for network issues
+        if let Some(sha1) = expected_sha1.as_deref() {
+            config = config.with_sha1(sha1);
+        }
+
+        let result = match DownloadUtils::download_file(url, &tmp_path, config).await {
+            Ok(()) => Self::ingest_file(key, filename, &tmp_path, expected_sha1.as_deref()).await,
+            Err(e) => Err(e),
+        };
+        if tmp_path.exists() {
+            let _ = fs::remove_file(&tmp_path).await;
+        }
+
+        let entry = result?;
+        Ok(Self::object_path(&entry.sha1, &entry.filename))
+    }
+
+    /// Hashes `source`, stores it as an object (copying, so the source stays
+    /// untouched) and registers it under `key`.
+    async fn ingest_file(
+        key: &str,
+        filename: &str,
+        source: &Path,
+        expected_sha1: Option<&str>,
+    ) -> Result<ModCacheEntry> {
+        let (sha1, sha512, size) = Self::hash_file(source).await?;
+
+        if let Some(expected) = expected_sha1 {
+            if !expected.eq_ignore_ascii_case(&sha1) {
+                return Err(AppError::Download(format!(
+                    "SHA1 mismatch for '{}': expected {}, got {}",
+                    filename, expected, sha1
+                )));
+            }
+        }
+
+        let _guard = OBJECT_LOCK.lock().await;
+        let object = Self::object_path(&sha1, filename);
+        if object != source && !Self::has_size(&object, size).await {
+            if let Some(parent) = object.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            // Write to a sibling first so a crash never leaves a truncated object behind
+            let staging = object.with_extension(format!("{}.tmp", Uuid::new_v4()));
+            fs::copy(source, &staging).await?;
+            fs::rename(&staging, &object).await?;
+        }
+
+        let entry = ModCacheEntry {
+            sha1,
+            sha512,
+            size,
+            filename: filename.to_string(),
+            added_at: Utc::now(),
+        };
+        if let Ok(mut index) = MOD_CACHE_INDEX.write() {
+            index.entries.insert(key.to_string(), entry.clone());
+        }
+        Ok(entry)
+    }
+
+    /// Hashes the stored object of `key` again. An intact object is kept, other profiles
+    /// and packs may link or reference it. A corrupt one is removed, so the next
+    /// `ensure_cached` for the key fetches it again. Returns whether the object is intact.
+    pub async fn reverify(key: &str) -> Result<bool> {
+        let Some(entry) = Self::get_entry(key) else {
+            return Ok(false);
+        };
+        let object = Self::object_path(&entry.sha1, &entry.filename);
+        if !object.is_file() {
+            return Ok(false);
+        }
+        let (sha1, _, _) = Self::hash_file(&object).await?;
+        if sha1 == entry.sha1 {
+            return Ok(true);
+        }
+
+        let _guard = OBJECT_LOCK.lock().await;
+        warn!(
+            "Mod cache object {:?} of '{}' is corrupt (sha1 {}), removing it",
+            object, key, sha1
+        );
+        if let Err(e) = fs::remove_file(&object).await {
+            warn!("Failed to remove mod cache file {:?}: {}", object, e);
+        }
+        Ok(false)
+    }
+
+    pub async fn save_index() -> Result<()> {
+        let _guard = INDEX_SAVE_LOCK.lock().await;
+        let json = {
+            let index = MOD_CACHE_INDEX
+                .read()
+                .map_err(|_| AppError::Other("Mod cache index lock poisoned".to_string()))?;
+            serde_json::to_string_pretty(&*index)?
+        };
+
+        let path = Self::index_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).await?;
+        fs::rename(&tmp_path, &path).await?;
+        debug!("Saved mod cache index to {:?}", path);
+        Ok(())
+    }
+
+    fn load_index() -> ModCacheIndex {
+        let path = Self::index_path();
+        if !path.exists() {
+            return ModCacheIndex::default();
+        }
+        match std::fs::read_to_string(&path)
+            .map_err(AppError::Io)
+            .and_then(|content| serde_json::from_str(&content).map_err(AppError::Json))
+        {
+            Ok(index) => index,
+            Err(e) => {
+                error!(
+                    "Failed to load mod cache index {:?}, starting empty: {}",
+                    path, e
+                );
+                ModCacheIndex::default()
+            }
+        }
+    }
+
+    async fn has_size(path: &Path, size: u64) -> bool {
+        matches!(fs::metadata(path).await, Ok(meta) if meta.len() == size)
+    }
+
+    /// Computes SHA1 and SHA512 in a single pass.
+    async fn hash_file(path: &Path) -> Result<(String, String, u64)> {
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || -> Result<(String, String, u64)> {
+            let mut file = std::fs::File::open(&path)?;
+            let mut sha1 = Sha1::new();
+            let mut sha512 = Sha512::new();
+            let mut buffer = vec![0u8; 64 * 1024];
+            let mut size = 0u64;
+            loop {
+                let n = file.read(&mut buffer)?;
+                if n == 0 {
+                    break;
+                }
+                sha1.update(&buffer[..n]);
+                sha512.update(&buffer[..n]);
+                size += n as u64;
+            }
+            Ok((
+                format!("{:x}", sha1.finalize()),
+                format!("{:x}", sha512.finalize()),
+                size,
+            ))
+        })
+        .await?
+    }
+
+    /// Collects all cache keys referenced by profiles and GEG packs. The value
+    /// lists the owners (`profile:<id>` / `pack:<id>`) holding the reference.
+    pub fn collect_references(
+        profiles: &[Profile],
+        pack_config: &GEGModpacksConfig,
+    ) -> HashMap<String, HashSet<String>> {
+        let mut references: HashMap<String, HashSet<String>> = HashMap::new();
+
+        for profile in profiles {
+            let owner = format!("profile:{}", profile.id);
+            for mod_info in &profile.mods {
+                if let Some(key) = Self::profile_mod_key(&mod_info.source) {
+                    references.entry(key).or_default().insert(owner.clone());
+                }
+            }
+        }
+
+        // Every version of every pack mod counts, packs can be used with any game version
+        for (pack_id, pack) in &pack_config.packs {
+            let owner = format!("pack:{}", pack_id);
+            for mod_entry in &pack.mods {
+                for target in mod_entry.compatibility.values().flat_map(|l| l.values()) {
+                    let source = target.source.as_ref().unwrap_or(&mod_entry.source);
+                    if let Ok(filename) =
+                        norisk_packs::get_GEG_pack_mod_filename(source, target, &mod_entry.id)
+                    {
+                        references
+                            .entry(Self::pack_mod_key(source, &mod_entry.id, &filename))
+                            .or_default()
+                            .insert(owner.clone());
+                    }
+                }
+            }
+        }
+
+        references
+    }
+
+    /// Removes every cache file that is not reachable from `references`.
+    /// An object stays alive as long as at least one referenced key points to it.
+    pub async fn collect_garbage(
+        references: &HashMap<String, HashSet<String>>,
+        dry_run: bool,
+    ) -> Result<ModCacheGcReport> {
+        info!(
+            "Running mod cache garbage collection ({} referenced keys, dry run: {})",
+            references.len(),
+            dry_run
+        );
+
+        let mut report = ModCacheGcReport {
+            dry_run,
+            ..Default::default()
+        };
+        let _guard = OBJECT_LOCK.lock().await;
+        let grace_cutoff = Utc::now() - Duration::minutes(NEW_ENTRY_GRACE_MINUTES);
+
+        // Reference count per object and the index entries nobody uses anymore
+        let mut refcounts: HashMap<String, usize> = HashMap::new();
+        let mut stale_keys: Vec<String> = Vec::new();
+        let mut filenames_by_hash: HashMap<String, Vec<String>> = HashMap::new();
+        {
+            let index = MOD_CACHE_INDEX
+                .read()
+                .map_err(|_| AppError::Other("Mod cache index lock poisoned".to_string()))?;
+            for (key, entry) in &index.entries {
+                filenames_by_hash
+                    .entry(entry.sha1.clone())
+                    .or_default()
+                    .push(entry.filename.clone());
+                match references.get(key) {
+                    Some(owners) => {
+                        *refcounts.entry(entry.sha1.clone()).or_default() += owners.len()
+                    }
+                    // Keeps the object alive without counting as a reference
+                    None if entry.added_at > grace_cutoff => {
+                        refcounts.entry(entry.sha1.clone()).or_default();
+                    }
+                    None => stale_keys.push(key.clone()),
+                }
+            }
+        }
+        report.stale_index_entries = stale_keys.len();
+        report.total_references = refcounts.values().sum();
+
+        // Objects
+        let objects_dir = Self::objects_dir();
+        if objects_dir.exists() {
+            let mut shards = fs::read_dir(&objects_dir).await?;
+            while let Some(shard) = shards.next_entry().await? {
+                if !shard.file_type().await?.is_dir() {
+                    continue;
+                }
+                let mut objects = fs::read_dir(shard.path()).await?;
+                while let Some(object) = objects.next_entry().await? {
+                    let path = object.path();
+                    let size = object.metadata().await.map(|m| m.len()).unwrap_or(0);
+                    let sha1 = path
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    report.total_objects += 1;
+                    report.total_bytes += size;
+
+                    if refcounts.contains_key(&sha1) {
+                        report.referenced_objects += 1;
+                        continue;
+                    }
+                    let mut filenames = filenames_by_hash.remove(&sha1).unwrap_or_default();
+                    filenames.dedup();
+                    report.items.push(ModCacheGcItem {
+                        kind: ModCacheGcItemKind::Object,
+                        path,
+                        sha1: Some(sha1),
+                        filenames,
+                        size_bytes: size,
+                    });
+                }
+            }
+        }
+
+        // Legacy flat files are still served for referenced keys that were never migrated
+        let legacy_in_use: HashSet<String> = {
+            let index = MOD_CACHE_INDEX
+                .read()
+                .map_err(|_| AppError::Other("Mod cache index lock poisoned".to_string()))?;
+            references
+                .keys()
+                .filter(|key| !index.entries.contains_key(*key))
+                .filter_map(|key| key.rsplit_once('#').map(|(_, f)| f.to_string()))
+                .collect()
+        };
+        let cache_dir = Self::cache_dir();
+        if cache_dir.exists() {
+            let mut entries = fs::read_dir(&cache_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                let Some(name) = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .map(str::to_string)
+                else {
+                    continue;
+                };
+                if !entry.file_type().await?.is_file() || name == INDEX_FILENAME {
+                    continue;
+                }
+                let size = entry.metadata().await.map(|m| m.len()).unwrap_or(0);
+                report.total_bytes += size;
+                if legacy_in_use.contains(&name) {
+                    continue;
+                }
+                report.items.push(ModCacheGcItem {
+                    kind: ModCacheGcItemKind::Legacy,
+                    path,
+                    sha1: None,
+                    filenames: vec![name],
+                    size_bytes: size,
+                });
+            }
+        }
+
+        // Partial downloads
+        let tmp_dir = cache_dir.join(TMP_DIR_NAME);
+        if tmp_dir.exists() {
+            let cutoff = Utc::now() - Duration::hours(TMP_FILE_GRACE_HOURS);
+            let mut entries = fs::read_dir(&tmp_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let meta = entry.metadata().await?;
+                let modified: DateTime<Utc> = meta
+                    .modified()
+                    .map(Into::into)
+                    .unwrap_or_else(|_| Utc::now());
+                if modified > cutoff {
+                    continue;
+                }
+                report.total_bytes += meta.len();
+                report.items.push(ModCacheGcItem {
+                    kind: ModCacheGcItemKind::Partial,
+                    path: entry.path(),
+                    sha1: None,
+                    filenames: Vec::new(),
+                    size_bytes: meta.len(),
+                });
+            }
+        }
+
+        report.reclaimable_bytes = report.items.iter().map(|i| i.size_bytes).sum();
+
+        if dry_run {
+            info!(
+                "Mod cache GC dry run: {} removable files, {} bytes reclaimable",
+                report.items.len(),
+                report.reclaimable_bytes
+            );
+            return Ok(report);
+        }
+
+        let mut removed_items = Vec::with_capacity(report.items.len());
+        for item in std::mem::take(&mut report.items) {
+            match fs::remove_file(&item.path).await {
+                Ok(_) => removed_items.push(item),
+                Err(e) => warn!("Failed to remove mod cache file {:?}: {}", item.path, e),
+            }
+        }
+        let freed: u64 = removed_items.iter().map(|i| i.size_bytes).sum();
+        report.items = removed_items;
+        report.reclaimable_bytes = freed;
+
+        if let Ok(mut index) = MOD_CACHE_INDEX.write() {
+            for key in &stale_keys {
+                index.entries.remove(key);
+            }
+        }
+        Self::save_index().await?;
+
+        info!(
+            "Mod cache GC removed {} files ({} bytes) and {} stale index entries",
+            report.items.len(),
+            freed,
+            stale_keys.len()
+        );
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::minecraft::downloads::mod_downloader::ModDownloadService;
+
+    /// Drops the keys of a test from the process-wide index
+    fn remove_keys(keys: &[&String]) {
+        let mut index = MOD_CACHE_INDEX.write().unwrap();
+        for key in keys {
+            index.entries.remove(*key);
+        }
+    }
+
+    pub(super) static TEST_CACHE_DIR: Lazy<PathBuf> = Lazy::new(|| {
+        std::env::temp_dir().join(format!(
+            "geg-{}-test-{}",
+            MOD_CACHE_DIR_NAME,
+            Uuid::new_v4()
+        ))
+    });
+    // The index is process-wide, GC must not see the entries of other tests
+    static TEST_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+    async fn source_file(name: &str, content: &[u8]) -> PathBuf {
+        let dir = TEST_CACHE_DIR.with_file_name(format!(
+            "{}-sources",
+            TEST_CACHE_DIR.file_name().unwrap().to_string_lossy()
+        ));
+        fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join(name);
+        fs::write(&path, content).await.unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn stores_and_looks_up_objects() {
+        let _lock = TEST_LOCK.lock().await;
+        let source = source_file("sodium.jar", b"sodium 0.5.3").await;
+        let key = ModCache::cache_key("modrinth:AANobbMI", "sodium.jar");
+
+        let entry = ModCache::ingest_file(&key, "sodium.jar", &source, None)
+            .await
+            .unwrap();
+        let object = ModCache::object_path(&entry.sha1, "sodium.jar");
+        assert_eq!(fs::read(&object).await.unwrap(), b"sodium 0.5.3");
+        assert_eq!(ModCache::path_for(&key, "sodium.jar"), object);
+
+        // Indexed keys and known content are served without downloading
+        let cached = ModCache::ensure_cached(&key, "sodium.jar", "invalid://", None)
+            .await
+            .unwrap();
+        assert_eq!(cached, object);
+        let other_key = ModCache::cache_key("pack:sodium", "sodium.jar");
+        let shared =
+            ModCache::ensure_cached(&other_key, "sodium.jar", "invalid://", Some(&entry.sha1))
+                .await
+                .unwrap();
+        assert_eq!(shared, object);
+
+        assert!(ModCache::reverify(&key).await.unwrap());
+        fs::write(&object, b"sodium 0.5.4").await.unwrap();
+        assert!(!ModCache::reverify(&other_key).await.unwrap());
+        assert!(!object.exists());
+
+        remove_keys(&[&key, &other_key]);
+    }
+
+    #[tokio::test]
+    async fn gc_removes_unreferenced_objects_only() {
+        let _lock = TEST_LOCK.lock().await;
+        let kept_key = ModCache::cache_key("modrinth:kept", "kept.jar");
+        let stale_key = ModCache::cache_key("modrinth:stale", "stale.jar");
+        let fresh_key = ModCache::cache_key("modrinth:fresh", "fresh.jar");
+        let mut objects = Vec::new();
+        for (key, filename) in [
+            (&kept_key, "kept.jar"),
+            (&stale_key, "stale.jar"),
+            (&fresh_key, "fresh.jar"),
+        ] {
+            let source = source_file(filename, filename.as_bytes()).await;
+            let entry = ModCache::ingest_file(key, filename, &source, None)
+                .await
+                .unwrap();
+            objects.push(ModCache::object_path(&entry.sha1, filename));
+        }
+        if let Ok(mut index) = MOD_CACHE_INDEX.write() {
+            index.entries.get_mut(&stale_key).unwrap().added_at = Utc::now() - Duration::days(2);
+        }
+        let references = HashMap::from([(
+            kept_key.clone(),
+            HashSet::from(["profile:test".to_string()]),
+        )]);
+
+        let dry_run = ModCache::collect_garbage(&references, true).await.unwrap();
+        assert_eq!(dry_run.stale_index_entries, 1);
+        assert!(dry_run.items.iter().any(|item| item.path == objects[1]));
+        assert!(objects[1].exists());
+
+        let report = ModCache::collect_garbage(&references, false).await.unwrap();
+        assert_eq!(report.total_references, 1);
+        assert!(objects[0].exists());
+        assert!(!objects[1].exists());
+        // Unreferenced but added within the grace period
+        assert!(objects[2].exists());
+        assert!(ModCache::get_entry(&stale_key).is_none());
+        assert!(ModCache::get_entry(&fresh_key).is_some());
+
+        remove_keys(&[&kept_key, &fresh_key]);
+    }
+
+    #[tokio::test]
+    async fn deployed_files_do_not_share_fate_with_the_object() {
+        let _lock = TEST_LOCK.lock().await;
+        let source = source_file("lithium.jar", b"lithium").await;
+        let key = ModCache::cache_key("modrinth:lithium", "lithium.jar");
+        let entry = ModCache::ingest_file(&key, "lithium.jar", &source, None)
+            .await
+            .unwrap();
+        let object = ModCache::object_path(&entry.sha1, "lithium.jar");
+
+        // Reflink, hard link or copy, whatever the filesystem supports
+        let target = TEST_CACHE_DIR
+            .join("profile")
+            .join("mods")
+            .join("lithium.jar");
+        fs::create_dir_all(target.parent().unwrap()).await.unwrap();
+        fs::write(&target, b"corrupt").await.unwrap();
+        ModDownloadService::deploy_file(&object, &target)
+            .await
+            .unwrap();
+        assert_eq!(fs::read(&target).await.unwrap(), b"lithium");
+
+        fs::remove_file(&target).await.unwrap();
+        assert_eq!(fs::read(&object).await.unwrap(), b"lithium");
+
+        remove_keys(&[&key]);
+    }
+}
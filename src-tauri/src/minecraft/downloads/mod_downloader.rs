@@ -1,8 +1,8 @@
-use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
+use crate::minecraft::downloads::mod_cache::ModCache;
 use crate::minecraft::downloads::mod_resolver::TargetMod;
 use crate::state::profile_state::{self, ModSource, Profile};
-use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
+use crate::utils::download_utils::DownloadUtils;
 use futures::stream::{iter, StreamExt};
 use log::{debug, error, info, warn};
 use std::collections::{HashMap, HashSet};
@@ -11,7 +11,6 @@ use tokio::fs::{self, read_dir};
 use tokio::io::AsyncWriteExt;
 
 const DEFAULT_CONCURRENT_MOD_DOWNLOADS: usize = 4;
-const MOD_CACHE_DIR_NAME: &str = "mod_cache";
 
 pub struct ModDownloadService {
     concurrent_downloads: usize,
@@ -39,7 +38,7 @@ impl ModDownloadService {
             profile.name, self.concurrent_downloads
         );
 
-        let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join(MOD_CACHE_DIR_NAME);
+        let mod_cache_dir = ModCache::cache_dir();
         if !mod_cache_dir.exists() {
             info!("Creating mod cache directory: {:?}", mod_cache_dir);
             fs::create_dir_all(&mod_cache_dir).await?;
@@ -54,7 +53,7 @@ impl ModDownloadService {
             }
 
             let display_name_opt = mod_info.display_name.clone();
-            let cache_dir_clone = mod_cache_dir.clone();
+            let cache_key = ModCache::profile_mod_key(&mod_info.source);
             let source_clone = mod_info.source.clone();
 
             let filename_result = profile_state::get_profile_mod_filename(&mod_info.source);
@@ -72,7 +71,7 @@ impl ModDownloadService {
                     }
                 };
                 let display_name = display_name_opt.as_deref().unwrap_or(&filename);
-                let target_path = cache_dir_clone.join(&filename);
+                let cache_key = cache_key.unwrap_or_else(|| filename.clone());
 
                 match source_clone {
                     ModSource::Modrinth {
@@ -84,12 +83,14 @@ impl ModDownloadService {
                             "Preparing Modrinth mod for cache: {} ({})",
                             display_name, filename
                         );
-                        Self::download_and_verify_file(
+                        ModCache::ensure_cached(
+                            &cache_key,
+                            &filename,
                             &download_url,
-                            &target_path,
                             file_hash_sha1.as_deref(),
                         )
                         .await
+                        .map(|_| ())
                         .map_err(|e| {
                             error!("Failed cache mod {}: {}", display_name, e);
                             e
@@ -104,12 +105,14 @@ impl ModDownloadService {
                             "Preparing CurseForge mod for cache: {} ({})",
                             display_name, filename
                         );
-                        Self::download_and_verify_file(
+                        ModCache::ensure_cached(
+                            &cache_key,
+                            &filename,
                             &download_url,
-                            &target_path,
                             file_hash_sha1.as_deref(),
                         )
                         .await
+                        .map(|_| ())
                         .map_err(|e| {
                             error!("Failed cache mod {}: {}", display_name, e);
                             e
@@ -163,6 +166,8 @@ impl ModDownloadService {
             }
         }
 
+        ModCache::save_index().await?;
+
         if errors.is_empty() {
             info!(
                 "Mod cache check/download process completed successfully for profile: '{}'",
@@ -253,10 +258,10 @@ impl ModDownloadService {
         for filename in &mods_to_add {
             if let Some(cache_path) = required_mods.get(filename) {
                 let target_path = profile_mods_dir.join(filename);
-                info!("Copying mod to '{}': {}", profile_name, filename);
-                Self::robust_copy_file(cache_path, &target_path).await.map_err(|e| {
+                info!("Deploying mod to '{}': {}", profile_name, filename);
+                Self::deploy_file(cache_path, &target_path).await.map_err(|e| {
                     error!(
-                        "Failed to copy {:?} to {:?}: {}",
+                        "Failed to deploy {:?} to {:?}: {}",
                         cache_path, target_path, e
                     );
                     e
@@ -280,23 +285,40 @@ impl ModDownloadService {
         Ok(())
     }
 
-    /// Downloads a file from a URL to a target path, optionally verifying its SHA1 hash.
-    async fn download_and_verify_file(
-        url: &str,
-        target_path: &PathBuf,
-        expected_sha1: Option<&str>,
-    ) -> Result<()> {
-        // Use the new centralized download utility with SHA1 verification
-        let mut config = DownloadConfig::new()
-            .with_streaming(true)  // Mods can be large files
-            .with_retries(3);      // Built-in retry logic for network issues
-
-        // Add SHA1 verification if provided
-        if let Some(sha1) = expected_sha1 {
-            config = config.with_sha1(sha1);
+    /// Places a cached mod into a profile without duplicating its content where possible:
+    /// reflink (copy-on-write clone) first, then a hard link, then a regular copy.
+    pub(crate) async fn deploy_file(source_path: &PathBuf, target_path: &PathBuf) -> Result<()> {
+        // Replaced files (e.g. corrupt ones) must be gone before linking
+        if fs::symlink_metadata(target_path).await.is_ok() {
+            fs::remove_file(target_path).await?;
+        }
+        if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent).await?;
         }
 
-        DownloadUtils::download_file(url, target_path, config).await
+        let source = source_path.clone();
+        let target = target_path.clone();
+        let linked = tokio::task::spawn_blocking(move || {
+            if reflink_copy::reflink(&source, &target).is_ok() {
+                return Some("reflink");
+            }
+            if std::fs::hard_link(&source, &target).is_ok() {
+                return Some("hard link");
+            }
+            None
+        })
+        .await?;
+
+        match linked {
+            Some(method) => {
+                debug!("Deployed {:?} via {}", target_path, method);
+                Ok(())
+            }
+            None => {
+                debug!("Linking not supported for {:?}, falling back to copy", target_path);
+                Self::robust_copy_file(source_path, target_path).await
+            }
+        }
     }
 
     /// fix for https://github.com/GEG/issues/issues/1487
@@ -1,4 +1,5 @@
 use crate::error::Result;
+use crate::minecraft::downloads::mod_cache::ModCache;
 use crate::integrations::norisk_packs::{self, GEGModSourceDefinition, GEGModpacksConfig};
 use crate::state::profile_state::{
     self, CustomModInfo, ModLoader, ModSource, GEGModIdentifier, Profile,
@@ -20,6 +21,33 @@ pub struct TargetMod {
     pub cache_path: PathBuf,
 }
 
+// --- Canonical keys identifying a mod independent of its version ---
+pub fn pack_mod_canonical_key(source: &GEGModSourceDefinition, mod_id: &str) -> Option<String> {
+    match source {
+        GEGModSourceDefinition::Modrinth { project_id, .. } => {
+            Some(format!("modrinth:{}", project_id))
+        }
+        GEGModSourceDefinition::Url { .. } => Some(format!("url:{}", mod_id)),
+        GEGModSourceDefinition::Maven {
+            group_id,
+            artifact_id,
+            ..
+        } => Some(format!("maven:{}:{}", group_id, artifact_id)),
+        // Add other types if needed
+        _ => None,
+    }
+}
+
+pub fn profile_mod_canonical_key(source: &ModSource) -> Option<String> {
+    match source {
+        ModSource::Modrinth { project_id, .. } => Some(format!("modrinth:{}", project_id)),
+        ModSource::CurseForge { project_id, .. } => Some(format!("curseforge:{}", project_id)),
+        ModSource::Url { url, .. } => Some(format!("url:{}", url)),
+        ModSource::Maven { coordinates, .. } => Some(format!("maven:{}", coordinates)),
+        _ => None, // Ignore other types
+    }
+}
+
 // --- Helper function to check if a filename is blocked by Flagsmith config ---
 async fn is_filename_blocked_by_config(filename: &str, enable_flagsmith_blocking: bool) -> bool {
     if !enable_flagsmith_blocking {
@@ -64,7 +92,6 @@ async fn is_modrinth_project_id_blocked_by_config(project_id: &str, enable_flags
 async fn try_add_mod_to_final_list(
     canonical_key: String,
     filename: String,
-    mod_cache_dir: &PathBuf,
     final_mods: &mut HashMap<String, TargetMod>,
     mod_type_str: &str,
     mod_name: &str,
@@ -92,7 +119,7 @@ async fn try_add_mod_to_final_list(
     }
     
     // 3. Check if file exists in cache
-    let cache_path = mod_cache_dir.join(&filename);
+    let cache_path = ModCache::path_for(&ModCache::cache_key(&canonical_key, &filename), &filename);
     if !cache_path.exists() {
         warn!(
             "{} mod '{}' not found in cache at: {:?}. Skipping.",
@@ -134,7 +161,6 @@ pub async fn resolve_target_mods(
     custom_mod_infos: Option<&[CustomModInfo]>,
     minecraft_version: &str,
     loader_str: &str,
-    mod_cache_dir: &PathBuf,
 ) -> Result<Vec<TargetMod>> {
     let mut final_mods: HashMap<String, TargetMod> = HashMap::new(); // Key: Canonical Mod Identifier
     
@@ -147,32 +173,6 @@ pub async fn resolve_target_mods(
         debug!("Flagsmith mod blocking is disabled (no GEG pack selected)");
     }
 
-    // --- Helper: Get Canonical Key ---
-    fn get_canonical_key(source: &GEGModSourceDefinition, mod_id: &str) -> Option<String> {
-        match source {
-            GEGModSourceDefinition::Modrinth { project_id, .. } => {
-                Some(format!("modrinth:{}", project_id))
-            }
-            GEGModSourceDefinition::Url { .. } => Some(format!("url:{}", mod_id)),
-            GEGModSourceDefinition::Maven {
-                group_id,
-                artifact_id,
-                ..
-            } => Some(format!("maven:{}:{}", group_id, artifact_id)),
-            // Add other types if needed
-            _ => None,
-        }
-    }
-    fn get_canonical_key_profile(source: &ModSource) -> Option<String> {
-        match source {
-            ModSource::Modrinth { project_id, .. } => Some(format!("modrinth:{}", project_id)),
-            ModSource::CurseForge { project_id, .. } => Some(format!("curseforge:{}", project_id)),
-            ModSource::Url { url, .. } => Some(format!("url:{}", url)),
-            ModSource::Maven { coordinates, .. } => Some(format!("maven:{}", coordinates)),
-            _ => None, // Ignore other types
-        }
-    }
-
     // 1. Process Pack Mods (Only Modrinth)
     if let (Some(ref pack_id), Some(config)) = (&profile.selected_GEG_pack_id, GEG_config) {
         info!("Resolving mods from selected GEG Pack: '{}'", pack_id);
@@ -222,7 +222,7 @@ pub async fn resolve_target_mods(
                             let effective_source = target.source.as_ref().unwrap_or(&mod_entry.source);
 
                             if let Some(canonical_key) =
-                                get_canonical_key(effective_source, &mod_entry.id)
+                                pack_mod_canonical_key(effective_source, &mod_entry.id)
                             {
                                 match norisk_packs::get_GEG_pack_mod_filename(
                                     effective_source,
@@ -234,7 +234,6 @@ pub async fn resolve_target_mods(
                                         try_add_mod_to_final_list(
                                             canonical_key,
                                             filename,
-                                            mod_cache_dir,
                                             &mut final_mods,
                                             "pack Modrinth",
                                             mod_name,
@@ -260,11 +259,14 @@ pub async fn resolve_target_mods(
                             .and_then(|l| l.get(loader_str))
                         {
                             // Disabled check is handled above
+                            // Same key/filename derivation as the pack downloader and the mod cache
+                            let effective_source = target.source.as_ref().unwrap_or(&mod_entry.source);
+
                             if let Some(canonical_key) =
-                                get_canonical_key(&mod_entry.source, &mod_entry.id)
+                                pack_mod_canonical_key(effective_source, &mod_entry.id)
                             {
                                 match norisk_packs::get_GEG_pack_mod_filename(
-                                    &mod_entry.source,
+                                    effective_source,
                                     target,
                                     &mod_entry.id,
                                 ) {
@@ -273,7 +275,6 @@ pub async fn resolve_target_mods(
                                         try_add_mod_to_final_list(
                                             canonical_key,
                                             filename,
-                                            mod_cache_dir,
                                             &mut final_mods,
                                             "pack URL",
                                             mod_name,
@@ -309,7 +310,7 @@ pub async fn resolve_target_mods(
                             let effective_source = target.source.as_ref().unwrap_or(&mod_entry.source);
 
                             if let Some(canonical_key) =
-                                get_canonical_key(effective_source, &mod_entry.id)
+                                pack_mod_canonical_key(effective_source, &mod_entry.id)
                             {
                                 // Filename can be derived for Maven, or explicitly provided
                                 match norisk_packs::get_GEG_pack_mod_filename(
@@ -322,7 +323,6 @@ pub async fn resolve_target_mods(
                                         try_add_mod_to_final_list(
                                             canonical_key,
                                             filename,
-                                            mod_cache_dir,
                                             &mut final_mods,
                                             "pack Maven",
                                             mod_name,
@@ -418,7 +418,7 @@ pub async fn resolve_target_mods(
         match &mod_info.source {
             ModSource::Modrinth { project_id, .. } => {
                 // Common logic for sources that can override pack mods
-                if let Some(canonical_key) = get_canonical_key_profile(&mod_info.source) {
+                if let Some(canonical_key) = profile_mod_canonical_key(&mod_info.source) {
                     match profile_state::get_profile_mod_filename(&mod_info.source) {
                         Ok(filename) => {
                             let mod_id_string = mod_info.id.to_string();
@@ -426,7 +426,6 @@ pub async fn resolve_target_mods(
                             try_add_mod_to_final_list(
                                 canonical_key,
                                 filename,
-                                mod_cache_dir,
                                 &mut final_mods,
                                 "profile Modrinth",
                                 mod_name,
@@ -456,7 +455,7 @@ pub async fn resolve_target_mods(
             }
             ModSource::CurseForge { project_id, .. } => {
                 // Common logic for sources that can override pack mods
-                if let Some(canonical_key) = get_canonical_key_profile(&mod_info.source) {
+                if let Some(canonical_key) = profile_mod_canonical_key(&mod_info.source) {
                     match profile_state::get_profile_mod_filename(&mod_info.source) {
                         Ok(filename) => {
                             let mod_id_string = mod_info.id.to_string();
@@ -464,7 +463,6 @@ pub async fn resolve_target_mods(
                             try_add_mod_to_final_list(
                                 canonical_key,
                                 filename,
-                                mod_cache_dir,
                                 &mut final_mods,
                                 "profile CurseForge",
                                 mod_name,
@@ -494,7 +492,7 @@ pub async fn resolve_target_mods(
             }
             ModSource::Url { .. } | ModSource::Maven { .. } => {
                 // Common logic for sources that can override pack mods
-                if let Some(canonical_key) = get_canonical_key_profile(&mod_info.source) {
+                if let Some(canonical_key) = profile_mod_canonical_key(&mod_info.source) {
                     match profile_state::get_profile_mod_filename(&mod_info.source) {
                         Ok(filename) => {
                             let mod_type_str = match &mod_info.source {
@@ -507,7 +505,6 @@ pub async fn resolve_target_mods(
                             try_add_mod_to_final_list(
                                 canonical_key,
                                 filename,
-                                mod_cache_dir,
                                 &mut final_mods,
                                 mod_type_str,
                                 mod_name,
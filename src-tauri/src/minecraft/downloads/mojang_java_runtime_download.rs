@@ -0,0 +1,468 @@
+use crate::error::{AppError, Result};
+use crate::minecraft::api::mc_api::MinecraftApiService;
+use crate::minecraft::dto::java_runtime_manifest::{
+    JavaRuntimeBuild, JavaRuntimeDownload, JavaRuntimeFile, JavaRuntimeFileDownloads,
+    JavaRuntimeManifest,
+};
+use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
+use crate::utils::hash_utils;
+use crate::utils::system_info::{Architecture, OperatingSystem, ARCHITECTURE, OS};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use tokio::fs;
+
+const JAVA_DIR: &str = "java";
+/// Runtime folders are named `mojang_<component>[_x86_64]`.
+pub const MOJANG_RUNTIME_DIR_PREFIX: &str = "mojang_";
+const RUNTIME_INFO_FILE: &str = ".runtime.json";
+const DEFAULT_CONCURRENT_DOWNLOADS: usize = 16;
+
+/// Written next to an installed runtime so updates can be detected without
+/// re-hashing every file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledMojangRuntime {
+    pub component: String,
+    pub platform: String,
+    pub version_name: String,
+    pub manifest_sha1: String,
+    pub installed_at: DateTime<Utc>,
+}
+
+/// Installs the Java runtimes of the official launcher from Mojang's
+/// java-runtime manifests, one file at a time.
+pub struct MojangJavaRuntimeService {
+    base_path: PathBuf,
+    concurrent_downloads: usize,
+}
+
+impl MojangJavaRuntimeService {
+    pub fn new() -> Self {
+        Self {
+            base_path: crate::config::standard_meta_dir().join(JAVA_DIR),
+            concurrent_downloads: DEFAULT_CONCURRENT_DOWNLOADS,
+        }
+    }
+
+    pub fn runtime_dir(&self, component: &str, force_x86_64: bool) -> PathBuf {
+        self.base_path.join(format!(
+            "{}{}{}",
+            MOJANG_RUNTIME_DIR_PREFIX,
+            component,
+            if force_x86_64 { "_x86_64" } else { "" }
+        ))
+    }
+
+    pub async fn read_installed(runtime_dir: &Path) -> Option<InstalledMojangRuntime> {
+        let content = fs::read_to_string(runtime_dir.join(RUNTIME_INFO_FILE))
+            .await
+            .ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Returns the Java binary of `component`, installing it or updating it in
+    /// place when Mojang published a newer build.
+    pub async fn get_or_install(&self, component: &str, force_x86_64: bool) -> Result<PathBuf> {
+        let arch = if force_x86_64 {
+            Architecture::X64
+        } else {
+            ARCHITECTURE
+        };
+        let platform = OS.get_mojang_runtime_platform(&arch)?;
+        let runtime_dir = self.runtime_dir(component, force_x86_64);
+        let installed = Self::read_installed(&runtime_dir).await;
+
+        let build = match Self::find_build(platform, component).await {
+            Ok(build) => build,
+            Err(e) => {
+                // Offline or index unavailable: an installed runtime is still good to use
+                if installed.is_some() {
+                    if let Ok(binary) = Self::find_java_binary(&runtime_dir).await {
+                        warn!(
+                            "Could not check for updates of Java runtime '{}' ({}), using the installed one",
+                            component, e
+                        );
+                        return Ok(binary);
+                    }
+                }
+                return Err(e);
+            }
+        };
+
+        if let Some(installed) = &installed {
+            if installed.manifest_sha1 == build.manifest.sha1 {
+                if let Ok(binary) = Self::find_java_binary(&runtime_dir).await {
+                    debug!(
+                        "Java runtime '{}' {} is up to date",
+                        component, installed.version_name
+                    );
+                    return Ok(binary);
+                }
+            }
+            info!(
+                "Updating Java runtime '{}' from {} to {}",
+                component, installed.version_name, build.version.name
+            );
+        }
+
+        self.install(component, platform, &build, &runtime_dir)
+            .await?;
+        Self::find_java_binary(&runtime_dir).await
+    }
+
+    async fn find_build(platform: &str, component: &str) -> Result<JavaRuntimeBuild> {
+        let index = MinecraftApiService::new().get_java_runtime_index().await?;
+        index
+            .get(platform)
+            .and_then(|components| components.get(component))
+            .and_then(|builds| builds.first())
+            .cloned()
+            .ok_or_else(|| {
+                AppError::JavaDownload(format!(
+                    "Mojang provides no '{}' runtime for {}",
+                    component, platform
+                ))
+            })
+    }
+
+    /// Installs `build` into `runtime_dir`. Files that already match the manifest
+    /// are kept, so updating an existing runtime only downloads what changed.
+    pub async fn install(
+        &self,
+        component: &str,
+        platform: &str,
+        build: &JavaRuntimeBuild,
+        runtime_dir: &Path,
+    ) -> Result<()> {
+        info!(
+            "Installing Java runtime '{}' {} for {} into {:?}",
+            component, build.version.name, platform, runtime_dir
+        );
+        let manifest = MinecraftApiService::new()
+            .get_java_runtime_manifest(&build.manifest.url, &build.manifest.sha1)
+            .await?;
+        fs::create_dir_all(runtime_dir).await?;
+
+        let mut directories = Vec::new();
+        let mut files = Vec::new();
+        let mut links = Vec::new();
+        for (name, entry) in &manifest.files {
+            let Some(path) = entry_path(runtime_dir, name) else {
+                warn!("Skipping runtime entry with unsafe path: {}", name);
+                continue;
+            };
+            match entry {
+                JavaRuntimeFile::Directory => directories.push(path),
+                JavaRuntimeFile::File {
+                    downloads,
+                    executable,
+                } => files.push((path, downloads, *executable)),
+                JavaRuntimeFile::Link { target } => links.push((path, target)),
+            }
+        }
+
+        directories.sort();
+        for directory in &directories {
+            fs::create_dir_all(directory).await?;
+        }
+
+        info!(
+            "Verifying {} runtime files with {} concurrent downloads",
+            files.len(),
+            self.concurrent_downloads
+        );
+        let results: Vec<Result<()>> = stream::iter(files)
+            .map(|(path, downloads, executable)| async move {
+                Self::install_file(&path, downloads, executable).await
+            })
+            .buffer_unordered(self.concurrent_downloads)
+            .collect()
+            .await;
+        let errors: Vec<AppError> = results.into_iter().filter_map(|r| r.err()).collect();
+        if let Some(first) = errors.into_iter().next() {
+            return Err(AppError::JavaDownload(format!(
+                "Failed to install Java runtime '{}': {}",
+                component, first
+            )));
+        }
+
+        // Links last, their targets have to exist first
+        for (path, target) in links {
+            Self::install_link(&path, target).await?;
+        }
+
+        Self::remove_stale_entries(runtime_dir, &manifest).await?;
+
+        let info = InstalledMojangRuntime {
+            component: component.to_string(),
+            platform: platform.to_string(),
+            version_name: build.version.name.clone(),
+            manifest_sha1: build.manifest.sha1.clone(),
+            installed_at: Utc::now(),
+        };
+        fs::write(
+            runtime_dir.join(RUNTIME_INFO_FILE),
+            serde_json::to_string_pretty(&info)?,
+        )
+        .await?;
+
+        info!(
+            "Java runtime '{}' {} installed",
+            component, build.version.name
+        );
+        Ok(())
+    }
+
+    async fn install_file(
+        path: &Path,
+        downloads: &JavaRuntimeFileDownloads,
+        executable: bool,
+    ) -> Result<()> {
+        if !Self::matches(path, &downloads.raw).await {
+            if fs::symlink_metadata(path).await.is_ok() {
+                fs::remove_file(path).await?;
+            }
+            let lzma_result = match &downloads.lzma {
+                Some(lzma) => Some(Self::download_lzma(path, lzma, &downloads.raw).await),
+                None => None,
+            };
+            match lzma_result {
+                Some(Ok(())) => {}
+                Some(Err(e)) => {
+                    warn!(
+                        "Compressed download of {:?} failed ({}), fetching it uncompressed",
+                        path, e
+                    );
+                    Self::download_raw(path, &downloads.raw).await?;
+                }
+                None => Self::download_raw(path, &downloads.raw).await?,
+            }
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if executable {
+                let mut permissions = fs::metadata(path).await?.permissions();
+                if permissions.mode() & 0o111 != 0o111 {
+                    permissions.set_mode(permissions.mode() | 0o755);
+                    fs::set_permissions(path, permissions).await?;
+                }
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = executable;
+
+        Ok(())
+    }
+
+    async fn matches(path: &Path, download: &JavaRuntimeDownload) -> bool {
+        let size_matches = fs::symlink_metadata(path)
+            .await
+            .is_ok_and(|m| m.is_file() && m.len() == download.size);
+        size_matches
+            && hash_utils::calculate_sha1_from_file(path)
+                .await
+                .is_ok_and(|sha1| sha1.eq_ignore_ascii_case(&download.sha1))
+    }
+
+    async fn download_raw(path: &Path, raw: &JavaRuntimeDownload) -> Result<()> {
+        let config = DownloadConfig::new()
+            .with_sha1(raw.sha1.clone())
+            .with_size(raw.size)
+            .with_retries(3)
+            .with_force_overwrite(true);
+        DownloadUtils::download_file(&raw.url, path, config).await
+    }
+
+    /// Downloads the lzma variant next to `path`, unpacks it and checks the result
+    /// against the hash of the uncompressed file.
+    async fn download_lzma(
+        path: &Path,
+        lzma: &JavaRuntimeDownload,
+        raw: &JavaRuntimeDownload,
+    ) -> Result<()> {
+        let mut compressed_name = path.as_os_str().to_owned();
+        compressed_name.push(".lzma");
+        let compressed = PathBuf::from(compressed_name);
+
+        let config = DownloadConfig::new()
+            .with_sha1(lzma.sha1.clone())
+            .with_size(lzma.size)
+            .with_retries(3)
+            .with_force_overwrite(true);
+        DownloadUtils::download_file(&lzma.url, &compressed, config).await?;
+
+        let source = compressed.clone();
+        let target = path.to_path_buf();
+        let unpacked = tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut reader = std::io::BufReader::new(std::fs::File::open(&source)?);
+            let mut writer = std::io::BufWriter::new(std::fs::File::create(&target)?);
+            lzma_rs::lzma_decompress(&mut reader, &mut writer).map_err(|e| {
+                AppError::JavaDownload(format!("Failed to unpack {:?}: {}", source, e))
+            })?;
+            writer.flush()?;
+            Ok(())
+        })
+        .await;
+        let _ = fs::remove_file(&compressed).await;
+        unpacked??;
+
+        if !Self::matches(path, raw).await {
+            let _ = fs::remove_file(path).await;
+            return Err(AppError::JavaDownload(format!(
+                "Unpacked {:?} does not match its expected hash",
+                path
+            )));
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    async fn install_link(path: &Path, target: &str) -> Result<()> {
+        if let Ok(current) = fs::read_link(path).await {
+            if current == Path::new(target) {
+                return Ok(());
+            }
+        }
+        if fs::symlink_metadata(path).await.is_ok() {
+            fs::remove_file(path).await?;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::symlink(target, path).await?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    async fn install_link(path: &Path, target: &str) -> Result<()> {
+        debug!(
+            "Skipping runtime link {:?} -> {} on this platform",
+            path, target
+        );
+        Ok(())
+    }
+
+    /// Deletes files left over from a previous build that the manifest no longer lists.
+    async fn remove_stale_entries(
+        runtime_dir: &Path,
+        manifest: &JavaRuntimeManifest,
+    ) -> Result<()> {
+        let mut removed = 0;
+        let mut pending = vec![runtime_dir.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let mut entries = fs::read_dir(&dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_type().await?.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let Ok(relative) = path.strip_prefix(runtime_dir) else {
+                    continue;
+                };
+                let name = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if name == RUNTIME_INFO_FILE || manifest.files.contains_key(&name) {
+                    continue;
+                }
+                debug!("Removing stale runtime file {:?}", path);
+                fs::remove_file(&path).await?;
+                removed += 1;
+            }
+        }
+        if removed > 0 {
+            info!("Removed {} files of the previous runtime build", removed);
+        }
+        Ok(())
+    }
+
+    pub async fn find_java_binary(runtime_dir: &Path) -> Result<PathBuf> {
+        let candidates = match OS {
+            OperatingSystem::WINDOWS => vec![runtime_dir.join("bin").join("javaw.exe")],
+            OperatingSystem::OSX => vec![
+                runtime_dir
+                    .join("jre.bundle")
+                    .join("Contents")
+                    .join("Home")
+                    .join("bin")
+                    .join("java"),
+                runtime_dir.join("bin").join("java"),
+            ],
+            _ => vec![runtime_dir.join("bin").join("java")],
+        };
+        for candidate in candidates {
+            if fs::metadata(&candidate).await.is_ok_and(|m| m.is_file()) {
+                return Ok(candidate);
+            }
+        }
+        Err(AppError::JavaDownload(format!(
+            "No Java binary found in runtime {:?}",
+            runtime_dir
+        )))
+    }
+}
+
+/// Joins a manifest path onto the runtime root, rejecting anything that would escape it.
+fn entry_path(runtime_dir: &Path, name: &str) -> Option<PathBuf> {
+    let relative = Path::new(name);
+    relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        .then(|| runtime_dir.join(relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_paths_stay_inside_the_runtime() {
+        let root = Path::new("/meta/java/mojang_java-runtime-delta");
+        assert_eq!(
+            entry_path(root, "bin/java"),
+            Some(root.join("bin").join("java"))
+        );
+        assert_eq!(entry_path(root, "../zulu_17/bin/java"), None);
+        assert_eq!(entry_path(root, "/etc/passwd"), None);
+    }
+
+    #[test]
+    fn manifest_entries_deserialize_by_type() {
+        let manifest: JavaRuntimeManifest = serde_json::from_str(
+            r#"{"files": {
+                "bin": {"type": "directory"},
+                "bin/java": {"type": "file", "executable": true, "downloads": {
+                    "raw": {"sha1": "aa", "size": 10, "url": "https://example.com/raw"},
+                    "lzma": {"sha1": "bb", "size": 5, "url": "https://example.com/lzma"}
+                }},
+                "legal/LICENSE": {"type": "link", "target": "../LICENSE"}
+            }}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(manifest.files["bin"], JavaRuntimeFile::Directory));
+        match &manifest.files["bin/java"] {
+            JavaRuntimeFile::File {
+                downloads,
+                executable,
+            } => {
+                assert!(*executable);
+                assert_eq!(downloads.raw.size, 10);
+                assert_eq!(downloads.lzma.as_ref().unwrap().sha1, "bb");
+            }
+            other => panic!("unexpected entry {:?}", other),
+        }
+        assert!(matches!(
+            &manifest.files["legal/LICENSE"],
+            JavaRuntimeFile::Link { target } if target == "../LICENSE"
+        ));
+    }
+}
@@ -1,14 +1,11 @@
-use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::integrations::norisk_packs::{self, GEGModSourceDefinition, GEGModpacksConfig};
-use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
+use crate::minecraft::downloads::mod_cache::ModCache;
 use futures::stream::{iter, StreamExt};
 use log::{error, info, warn};
-use std::path::PathBuf;
 use tokio::fs;
 
 const DEFAULT_CONCURRENT_MOD_DOWNLOADS: usize = 4;
-const MOD_CACHE_DIR_NAME: &str = "mod_cache"; // Reuse the same cache directory
 const MODRINTH_MAVEN_URL: &str = "https://api.modrinth.com/maven"; // Modrinth Maven repo
 
 #[derive(Clone)]
@@ -43,7 +40,7 @@ impl NoriskPackDownloadService {
             pack_id, minecraft_version, loader, self.concurrent_downloads
         );
 
-        let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join(MOD_CACHE_DIR_NAME);
+        let mod_cache_dir = ModCache::cache_dir();
         if !mod_cache_dir.exists() {
             info!("Creating mod cache directory: {:?}", mod_cache_dir);
             fs::create_dir_all(&mod_cache_dir).await?;
@@ -72,7 +69,6 @@ impl NoriskPackDownloadService {
                 }
             };
 
-            let cache_dir_clone = mod_cache_dir.clone();
             let source = mod_entry.source.clone();
             let mod_id = mod_entry.id.clone();
             let display_name_opt = mod_entry.display_name.clone();
@@ -102,7 +98,13 @@ impl NoriskPackDownloadService {
                     }
                 };
 
-                let target_path = cache_dir_clone.join(&filename);
+                let cache_key = ModCache::pack_mod_key(effective_source, &mod_id, &filename);
+                if target_clone.source.is_some() {
+                    ModCache::migrate_key(
+                        &ModCache::pack_mod_key(&source, &mod_id, &filename),
+                        &cache_key,
+                    );
+                }
 
                 match effective_source {
                     GEGModSourceDefinition::Modrinth {
@@ -119,8 +121,7 @@ impl NoriskPackDownloadService {
                             artifact_id.clone(),
                             version,
                             filename,
-                            target_path,
-                            None,
+                            cache_key,
                         )
                         .await
                         .map_err(|e| {
@@ -154,8 +155,7 @@ impl NoriskPackDownloadService {
                             artifact_id.clone(),
                             effective_identifier,
                             filename,
-                            target_path,
-                            None,
+                            cache_key,
                         )
                         .await
                         .map_err(|e| {
@@ -172,7 +172,7 @@ impl NoriskPackDownloadService {
                             display_name, filename, effective_identifier
                         );
 
-                        Self::download_and_verify_file(&effective_identifier, &target_path, None).await
+                        ModCache::ensure_cached(&cache_key, &filename, &effective_identifier, None).await
                             .map_err(|e| {
                                 error!("Failed to download URL mod '{}': {}", display_name, e);
                                 e
@@ -201,6 +201,8 @@ impl NoriskPackDownloadService {
             }
         }
 
+        ModCache::save_index().await?;
+
         if errors.is_empty() {
             info!(
                 "GEG pack mod cache check/download process completed successfully for pack: '{}'",
@@ -223,8 +225,7 @@ impl NoriskPackDownloadService {
         artifact_id: String,
         version: String,
         filename: String,
-        target_path: PathBuf,
-        expected_sha1: Option<&str>,
+        cache_key: String,
     ) -> Result<()> {
         let group_path = group_id.replace('.', "/");
         let artifact_path = format!("{}/{}/{}/{}", group_path, artifact_id, version, filename);
@@ -235,29 +236,11 @@ impl NoriskPackDownloadService {
             filename, group_id, artifact_id, version, repo_url
         );
 
-        Self::download_and_verify_file(&download_url, &target_path, expected_sha1).await
-    }
-
-    /// Downloads a file from a URL to a target path, optionally verifying its SHA1 hash.
-    async fn download_and_verify_file(
-        url: &str,
-        target_path: &PathBuf,
-        expected_sha1: Option<&str>,
-    ) -> Result<()> {
-        // Use the new centralized download utility with optional SHA1 verification
-        let mut config = DownloadConfig::new()
-            .with_streaming(true)  // Use streaming for potentially large mod files
-            .with_retries(3);
-
-        // Only add SHA1 verification if hash is provided
-        if let Some(hash) = expected_sha1 {
-            config = config.with_sha1(hash.to_string());
-        }
-
-        DownloadUtils::download_file(url, target_path, config).await
+        ModCache::ensure_cached(&cache_key, &filename, &download_url, None)
+            .await
+            .map(|_| ())
     }
 
-
 }
 
 // Note: Syncing logic (like `sync_mods_to_profile` from ModDownloadService)
@@ -19,7 +19,7 @@ impl Default for DistributionSelection {
     }
 }
 
-#[derive(Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub enum JavaDistribution {
     #[serde(rename = "temurin")]
     Temurin,
@@ -27,6 +27,10 @@ pub enum JavaDistribution {
     GraalVM,
     #[serde(rename = "zulu")]
     Zulu,
+    /// The runtimes of the official launcher, selected by the piston meta's
+    /// `javaVersion.component` instead of the major version.
+    #[serde(rename = "mojang")]
+    Mojang,
 }
 
 impl Default for JavaDistribution {
@@ -40,6 +44,9 @@ impl Default for JavaDistribution {
 #[derive(Deserialize)]
 pub struct ZuluApiResponse {
     pub url: String,
+    /// e.g. `[21, 0, 4, 7]`, used to check installed runtimes for updates
+    #[serde(default)]
+    pub jdk_version: Vec<u32>,
 }
 
 impl JavaDistribution {
@@ -116,6 +123,11 @@ impl JavaDistribution {
                     zulu_os
                 )
             }
+            JavaDistribution::Mojang => {
+                return Err(AppError::JavaDownload(
+                    "Mojang runtimes are installed file by file from their manifest".to_string(),
+                ))
+            }
         })
     }
 
@@ -131,6 +143,18 @@ impl JavaDistribution {
             JavaDistribution::Temurin => "temurin",
             JavaDistribution::GraalVM => "graalvm",
             JavaDistribution::Zulu => "zulu",
+            JavaDistribution::Mojang => "mojang",
+        }
+    }
+
+    /// Inverse of `get_name`, used to read runtime folder names.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "temurin" => Some(JavaDistribution::Temurin),
+            "graalvm" => Some(JavaDistribution::GraalVM),
+            "zulu" => Some(JavaDistribution::Zulu),
+            "mojang" => Some(JavaDistribution::Mojang),
+            _ => None,
         }
     }
 
@@ -139,6 +163,7 @@ impl JavaDistribution {
             JavaDistribution::Temurin => true, // Supports 8, 11, 17, 21
             JavaDistribution::GraalVM => version >= 17, // Only supports 17+
             JavaDistribution::Zulu => true,    // Supports 7, 8, 11, 17, 21
+            JavaDistribution::Mojang => true,  // Whatever the component of the version needs
         }
     }
 }
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Mojang's `all.json`: platform (e.g. `windows-x64`) -> component
+/// (e.g. `java-runtime-delta`) -> available builds, newest first.
+pub type JavaRuntimeIndex = HashMap<String, HashMap<String, Vec<JavaRuntimeBuild>>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaRuntimeBuild {
+    pub availability: JavaRuntimeAvailability,
+    pub manifest: JavaRuntimeDownload,
+    pub version: JavaRuntimeVersion,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaRuntimeAvailability {
+    pub group: u32,
+    pub progress: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaRuntimeVersion {
+    pub name: String,
+    pub released: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaRuntimeDownload {
+    pub sha1: String,
+    pub size: u64,
+    pub url: String,
+}
+
+/// Per-file listing of one runtime build, keyed by the path relative to the runtime root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaRuntimeManifest {
+    pub files: HashMap<String, JavaRuntimeFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum JavaRuntimeFile {
+    File {
+        downloads: JavaRuntimeFileDownloads,
+        #[serde(default)]
+        executable: bool,
+    },
+    Directory,
+    Link {
+        target: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaRuntimeFileDownloads {
+    pub raw: JavaRuntimeDownload,
+    pub lzma: Option<JavaRuntimeDownload>,
+}
@@ -3,6 +3,7 @@ pub mod forge_install_profile;
 pub mod forge_maven_meta;
 pub mod forge_meta;
 pub mod java_distribution;
+pub mod java_runtime_manifest;
 pub mod minecraft_profile;
 pub mod neo_forge_install_profile;
 pub mod neo_forge_maven_meta;
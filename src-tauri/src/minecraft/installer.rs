@@ -1,4 +1,3 @@
-use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::integrations::norisk_packs::GEGModpacksConfig;
 use crate::minecraft::api::mc_api::MinecraftApiService;
@@ -11,6 +10,7 @@ use crate::minecraft::downloads::NoriskPackDownloadService;
 use crate::minecraft::downloads::{ModDownloadService, NoriskAssetsDownloadService};
 use crate::minecraft::dto::JavaDistribution;
 use crate::minecraft::{MinecraftLaunchParameters, MinecraftLauncher};
+use crate::state::download_queue_state::{self, DownloadJobKind, DownloadJobRequest};
 use crate::state::event_state::{EventPayload, EventType};
 use crate::state::profile_state::{ModLoader, Profile};
 use crate::state::state_manager::State;
@@ -21,6 +21,9 @@ use uuid::Uuid;
 use super::minecraft_auth::Credentials;
 use super::modloader::ModloaderFactory;
 use crate::minecraft::downloads::MinecraftLoggingDownloadService;
+use crate::minecraft::mod_validation::{self, ModEnvironment};
+use crate::state::launch_timeline_state::{LaunchOutcome, LaunchTimelineRecorder};
+use crate::utils::hook_utils::HookContext;
 use crate::utils::mc_utils;
 use tokio::fs as async_fs;
 
@@ -32,6 +35,13 @@ async fn emit_progress_event(
     progress: f64,
     error: Option<String>,
 ) -> Result<Uuid> {
+    // Starting a phase doubles as a point where a paused or cancelled launch job stops;
+    // completions and errors are always reported, e.g. after the game process was already
+    // spawned or when the error is what ends the job
+    if progress < 1.0 && error.is_none() {
+        download_queue_state::checkpoint().await?;
+    }
+
     let event_id = Uuid::new_v4();
     state
         .emit_event(EventPayload {
@@ -54,6 +64,65 @@ pub async fn install_minecraft_version(
     quick_play_singleplayer: Option<String>,
     quick_play_multiplayer: Option<String>,
     migration_info: Option<crate::utils::profile_utils::MigrationInfo>,
+    launch_after_install: bool,
+) -> Result<()> {
+    let state = State::get().await?;
+
+    // Record how long each phase of this launch takes
+    let recorder = LaunchTimelineRecorder::start(
+        &state.event_state,
+        profile.id,
+        version_id,
+        modloader_str,
+    );
+
+    let job_kind = if launch_after_install {
+        DownloadJobKind::Launch
+    } else {
+        DownloadJobKind::Install
+    };
+    let job = DownloadJobRequest::new(job_kind, format!("{} ({})", profile.name, version_id))
+        .for_profile(profile.id);
+    let result = state
+        .download_queue
+        .run_job(
+            job,
+            install_and_launch(
+                version_id,
+                modloader_str,
+                profile,
+                credentials,
+                quick_play_singleplayer,
+                quick_play_multiplayer,
+                migration_info,
+                launch_after_install,
+            ),
+        )
+        .await;
+
+    let (outcome, error) = match &result {
+        Ok(()) if launch_after_install => (LaunchOutcome::Launched, None),
+        Ok(()) => (LaunchOutcome::Installed, None),
+        Err(e) => (LaunchOutcome::Failed, Some(e.to_string())),
+    };
+    if let Some(timeline) = recorder.finish(outcome, error).await {
+        if let Err(e) = state.launch_timeline_manager.add_timeline(timeline).await {
+            warn!("Failed to save launch timeline for profile {}: {}", profile.id, e);
+        }
+    }
+
+    result
+}
+
+async fn install_and_launch(
+    version_id: &str,
+    modloader_str: &str,
+    profile: &Profile,
+    credentials: Option<Credentials>,
+    quick_play_singleplayer: Option<String>,
+    quick_play_multiplayer: Option<String>,
+    migration_info: Option<crate::utils::profile_utils::MigrationInfo>,
+    launch_after_install: bool,
 ) -> Result<()> {
     // Convert string modloader to ModLoader enum
     let modloader_enum = match modloader_str {
@@ -259,7 +328,7 @@ pub async fn install_minecraft_version(
         let downloaded_path = java_service
             .get_or_download_java(
                 java_version,
-                &JavaDistribution::Zulu,
+                &JavaDistribution::Mojang,
                 Some(&piston_meta.java_version.component),
             )
             .await?;
@@ -280,6 +349,18 @@ pub async fn install_minecraft_version(
         downloaded_path
     };
 
+    // Detect the runtime actually used so the JVM preset can pick matching GC flags
+    let java_installation = match crate::utils::java_detector::get_java_info(&java_path).await {
+        Ok(installation) => Some(installation),
+        Err(e) => {
+            warn!(
+                "Could not detect Java runtime at {:?}: {}. Assuming Java {}.",
+                java_path, e, java_version
+            );
+            None
+        }
+    };
+
     // Create game directory
     let game_directory = state
         .profile_manager
@@ -446,6 +527,12 @@ pub async fn install_minecraft_version(
         .with_resolution(profile.settings.resolution.clone())
         .with_experimental_mode(is_experimental_mode);
 
+    launch_params = match &java_installation {
+        Some(installation) => launch_params
+            .with_java_runtime(installation.major_version, Some(installation.vendor.clone())),
+        None => launch_params.with_java_runtime(java_version, None),
+    };
+
     // Add Quick Play parameters if provided
     if let Some(world_name) = quick_play_singleplayer {
         launch_params = launch_params.with_quick_play_singleplayer(world_name);
@@ -454,6 +541,7 @@ pub async fn install_minecraft_version(
     }
 
     // Install modloader using the factory
+    let mut resolved_loader_version = profile.loader_version.clone();
     if modloader_enum != ModLoader::Vanilla {
         // Resolve loader version using the new modloader factory method
         let mut install_profile = profile.clone();
@@ -479,7 +567,8 @@ pub async fn install_minecraft_version(
                 version_id,
                 modloader_enum
             );
-            install_profile.loader_version = Some(version);
+            install_profile.loader_version = Some(version.clone());
+            resolved_loader_version = Some(version);
         }
 
         let modloader_installer = ModloaderFactory::create_installer_with_config(
@@ -552,7 +641,12 @@ pub async fn install_minecraft_version(
             "Fetching GEG config because pack '{}' is selected. Attempting to refresh first.",
             pack_id
         );
-        if let Some(creds) = credentials.as_ref() {
+        if credentials.as_ref().is_some_and(|c| c.is_offline()) {
+            info!(
+                "Offline account selected, using the cached configuration of GEG pack '{}'.",
+                pack_id
+            );
+        } else if let Some(creds) = credentials.as_ref() {
             match creds
                 .GEG_credentials
                 .get_token_for_mode(is_experimental_mode)
@@ -724,8 +818,6 @@ pub async fn install_minecraft_version(
     )
     .await?;
 
-    let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join("mod_cache");
-
     // ---> NEW: Get custom mods for this profile <---
     info!("Listing custom mods for profile '{}'...", profile.name);
     let custom_mod_infos = state.profile_manager.list_custom_mods(&profile).await?;
@@ -743,7 +835,6 @@ pub async fn install_minecraft_version(
         Some(&custom_mod_infos),       // ---> NEW: Pass custom mods <---
         version_id,
         modloader_enum.as_str(),
-        &mod_cache_dir,
     )
     .await?;
 
@@ -757,6 +848,25 @@ pub async fn install_minecraft_version(
     )
     .await?;
 
+    // --- Validate dependencies and conflicts of the resolved mod set ---
+    if modloader_enum != ModLoader::Vanilla && !target_mods.is_empty() {
+        if profile.settings.skip_mod_validation {
+            info!(
+                "Skipping mod validation for profile '{}' (disabled in profile settings)",
+                profile.name
+            );
+        } else {
+            let environment = ModEnvironment::new(modloader_enum, version_id)
+                .with_loader_version(resolved_loader_version.clone());
+            let mod_files = target_mods.iter().map(|m| m.cache_path.clone()).collect();
+            let report = mod_validation::validate_mod_files(mod_files, environment).await?;
+            for issue in &report.issues {
+                warn!("[Mod Validation] {}", issue.message);
+            }
+            report.into_result(&profile.name)?;
+        }
+    }
+
     // --- Prototype: Provide managed mods via Fabric addMods meta file (Fabric only) ---
     if modloader_enum == ModLoader::Fabric {
         let add_mods_arg = crate::minecraft::downloads::mod_resolver::build_fabric_add_mods_arg(
@@ -835,9 +945,22 @@ pub async fn install_minecraft_version(
         launch_params = launch_params.with_additional_jvm_args(jvm_args);
     }
 
+    // Install-only mode (e.g. `geg-cli install`): everything is in place, don't start the game
+    if !launch_after_install {
+        info!(
+            "Installation of profile '{}' complete. Skipping launch as requested.",
+            profile.name
+        );
+        return Ok(());
+    }
+
     // --- Execute pre-launch hooks ---
     let launcher_config = state.config_manager.get_config().await;
-    if let Some(hook) = &launcher_config.hooks.pre_launch {
+    let hooks = launcher_config.hooks.merged_with(&profile.settings.hooks);
+    let hook_context = HookContext::new(game_directory.clone(), profile.id, profile.name.clone())
+        .with_java_path(java_path.clone())
+        .with_minecraft_version(version_id.to_string());
+    if let Some(hook) = hooks.pre_launch.as_deref() {
         info!("Executing pre-launch hook: {}", hook);
         let hook_event_id = emit_progress_event(
             &state,
@@ -849,11 +972,13 @@ pub async fn install_minecraft_version(
         )
         .await?;
 
-        let mut cmd = hook.split(' ');
-        if let Some(command) = cmd.next() {
+        let hook_argv = hook_context.expand_command(hook)?;
+        if let Some((command, args)) = hook_argv.split_first() {
             let result = std::process::Command::new(command)
-                .args(cmd.collect::<Vec<&str>>())
+                .args(args)
                 .current_dir(&game_directory)
+                .envs(hook_context.resolve_env(&profile.settings.env_vars))
+                .envs(hook_context.variables())
                 .spawn()
                 .map_err(|e| AppError::Io(e))?
                 .wait()
@@ -111,7 +111,13 @@ impl GameArguments {
         )
         .replace("${clientid}", "c4502edb-87c6-40cb-b595-64a280cf8906")
         .replace("${auth_xuid}", "0")
-        .replace("${user_type}", "msa")
+        .replace(
+            "${user_type}",
+            match &self.credentials {
+                Some(c) if c.is_offline() => "legacy",
+                _ => "msa",
+            },
+        )
         .replace("${version_type}", &self.version_type)
         .replace("${user_properties}", "{}")
     }
@@ -0,0 +1,204 @@
+use crate::state::profile_state::JvmPreset;
+use log::{info, warn};
+
+/// Flags the launcher always used before presets existed (vanilla-launcher-like G1 tuning).
+const G1_DEFAULT_FLAGS: &[&str] = &[
+    "-XX:+UnlockExperimentalVMOptions",
+    "-XX:+UseG1GC",
+    "-XX:G1NewSizePercent=20",
+    "-XX:G1ReservePercent=20",
+    "-XX:MaxGCPauseMillis=50",
+    "-XX:G1HeapRegionSize=32M",
+];
+
+const AIKAR_FLAGS: &[&str] = &[
+    "-XX:+UseG1GC",
+    "-XX:+ParallelRefProcEnabled",
+    "-XX:MaxGCPauseMillis=200",
+    "-XX:+UnlockExperimentalVMOptions",
+    "-XX:+DisableExplicitGC",
+    "-XX:+AlwaysPreTouch",
+    "-XX:G1NewSizePercent=30",
+    "-XX:G1MaxNewSizePercent=40",
+    "-XX:G1HeapRegionSize=8M",
+    "-XX:G1ReservePercent=20",
+    "-XX:G1HeapWastePercent=5",
+    "-XX:G1MixedGCCountTarget=4",
+    "-XX:InitiatingHeapOccupancyPercent=15",
+    "-XX:G1MixedGCLiveThresholdPercent=90",
+    "-XX:SurvivorRatio=32",
+    "-XX:+PerfDisableSharedMem",
+    "-XX:MaxTenuringThreshold=1",
+];
+
+/// Returns the flags of a preset for the given Java runtime.
+/// Presets the runtime can't handle fall back to `G1Default`.
+pub fn preset_flags(preset: JvmPreset, java_major: u32, java_vendor: Option<&str>) -> Vec<String> {
+    let flags: Vec<&str> = match preset {
+        JvmPreset::None | JvmPreset::Custom => Vec::new(),
+        JvmPreset::G1Default => G1_DEFAULT_FLAGS.to_vec(),
+        JvmPreset::Aikar => {
+            let mut flags = AIKAR_FLAGS.to_vec();
+            // Removed in JDK 20, older runtimes still benefit from it
+            if java_major < 20 {
+                flags.push("-XX:G1RSetUpdatingPauseTimePercent=5");
+            }
+            flags
+        }
+        JvmPreset::GenerationalZgc => match java_major {
+            // Generational mode is the only ZGC mode since JDK 23
+            23.. => vec!["-XX:+UseZGC"],
+            21..=22 => vec!["-XX:+UseZGC", "-XX:+ZGenerational"],
+            15..=20 => {
+                info!(
+                    "Java {} has no generational ZGC, using non-generational ZGC instead",
+                    java_major
+                );
+                vec!["-XX:+UseZGC"]
+            }
+            _ => {
+                warn!(
+                    "ZGC is not production-ready on Java {}, falling back to the default G1 preset",
+                    java_major
+                );
+                G1_DEFAULT_FLAGS.to_vec()
+            }
+        },
+        JvmPreset::Shenandoah => {
+            // Oracle's own builds ship without Shenandoah
+            let is_oracle_build = java_vendor
+                .map(|vendor| vendor.to_lowercase().contains("oracle"))
+                .unwrap_or(false);
+            if java_major < 12 || is_oracle_build {
+                warn!(
+                    "Shenandoah is not available on Java {} ({}), falling back to the default G1 preset",
+                    java_major,
+                    java_vendor.unwrap_or("unknown vendor")
+                );
+                G1_DEFAULT_FLAGS.to_vec()
+            } else {
+                vec!["-XX:+UseShenandoahGC"]
+            }
+        }
+    };
+
+    flags.into_iter().map(String::from).collect()
+}
+
+/// Resolves the GC flags for a launch, de-duplicated against the user's own JVM arguments.
+///
+/// If the user already selects a collector (e.g. `-XX:+UseZGC` in `custom_jvm_args`) the preset is
+/// dropped completely so the JVM never gets two GCs. Otherwise single preset options the user
+/// overrides are left out, the user's value wins.
+pub fn resolve_gc_flags(
+    preset: JvmPreset,
+    java_major: u32,
+    java_vendor: Option<&str>,
+    user_args: &[String],
+) -> Vec<String> {
+    if let Some(user_gc) = user_args.iter().find(|arg| selects_gc(arg)) {
+        if preset != JvmPreset::None && preset != JvmPreset::Custom {
+            info!(
+                "User JVM arguments select a GC ({}), ignoring JVM preset {:?}",
+                user_gc, preset
+            );
+        }
+        return Vec::new();
+    }
+
+    if preset == JvmPreset::Custom {
+        warn!("JVM preset is Custom but the custom JVM arguments select no GC, the JVM default is used");
+    }
+
+    let user_keys: Vec<&str> = user_args.iter().filter_map(|arg| option_key(arg)).collect();
+    preset_flags(preset, java_major, java_vendor)
+        .into_iter()
+        .filter(|flag| match option_key(flag) {
+            Some(key) => !user_keys.contains(&key),
+            None => !user_args.contains(flag),
+        })
+        .collect()
+}
+
+/// Name of a `-XX:` option, e.g. `G1HeapRegionSize` for `-XX:G1HeapRegionSize=32M`
+/// and `UseG1GC` for `-XX:+UseG1GC`.
+fn option_key(arg: &str) -> Option<&str> {
+    let option = arg.strip_prefix("-XX:")?;
+    let option = option
+        .strip_prefix('+')
+        .or_else(|| option.strip_prefix('-'))
+        .unwrap_or(option);
+    Some(option.split('=').next().unwrap_or(option))
+}
+
+/// Options that pick the garbage collector. Other `Use...GC` options, e.g.
+/// `UseAdaptiveSizePolicyWithSystemGC`, only tune one.
+const GC_SELECTOR_OPTIONS: &[&str] = &[
+    "UseG1GC",
+    "UseZGC",
+    "UseShenandoahGC",
+    "UseParallelGC",
+    "UseSerialGC",
+    "UseConcMarkSweepGC",
+    "UseEpsilonGC",
+];
+
+fn selects_gc(arg: &str) -> bool {
+    arg.strip_prefix("-XX:+")
+        .is_some_and(|option| GC_SELECTOR_OPTIONS.contains(&option))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_user_gc_disables_preset() {
+        let user = args(&["-XX:+UseShenandoahGC"]);
+        assert!(resolve_gc_flags(JvmPreset::Aikar, 21, None, &user).is_empty());
+    }
+
+    #[test]
+    fn test_gc_tuning_option_keeps_preset() {
+        let user = args(&["-XX:+UseAdaptiveSizePolicyWithSystemGC"]);
+        let flags = resolve_gc_flags(JvmPreset::G1Default, 17, None, &user);
+        assert!(flags.contains(&"-XX:+UseG1GC".to_string()));
+    }
+
+    #[test]
+    fn test_user_option_overrides_preset_option() {
+        let user = args(&["-XX:MaxGCPauseMillis=100"]);
+        let flags = resolve_gc_flags(JvmPreset::G1Default, 17, None, &user);
+        assert!(flags.contains(&"-XX:+UseG1GC".to_string()));
+        assert!(!flags.iter().any(|f| f.starts_with("-XX:MaxGCPauseMillis")));
+    }
+
+    #[test]
+    fn test_zgc_depends_on_java_version() {
+        assert_eq!(
+            preset_flags(JvmPreset::GenerationalZgc, 21, None),
+            args(&["-XX:+UseZGC", "-XX:+ZGenerational"])
+        );
+        assert_eq!(preset_flags(JvmPreset::GenerationalZgc, 23, None), args(&["-XX:+UseZGC"]));
+        assert_eq!(
+            preset_flags(JvmPreset::GenerationalZgc, 8, None),
+            preset_flags(JvmPreset::G1Default, 8, None)
+        );
+    }
+
+    #[test]
+    fn test_shenandoah_falls_back_on_oracle_builds() {
+        assert_eq!(
+            preset_flags(JvmPreset::Shenandoah, 17, Some("Eclipse Adoptium")),
+            args(&["-XX:+UseShenandoahGC"])
+        );
+        assert_eq!(
+            preset_flags(JvmPreset::Shenandoah, 17, Some("Oracle Corporation")),
+            preset_flags(JvmPreset::G1Default, 17, None)
+        );
+    }
+}
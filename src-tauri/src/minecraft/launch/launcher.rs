@@ -2,11 +2,14 @@ use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::Result;
 use crate::minecraft::dto::piston_meta::PistonMeta;
 use crate::minecraft::minecraft_auth::Credentials;
+use crate::minecraft::jvm_presets;
 use crate::minecraft::ClasspathBuilder;
 use crate::minecraft::GameArguments;
 use crate::minecraft::JvmArguments;
+use crate::state::download_queue_state;
 use crate::state::profile_state::{Profile, WindowSize};
 use crate::state::state_manager::State;
+use crate::utils::hook_utils::{join_command, HookContext};
 use log::{debug, error, info, warn};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -30,6 +33,8 @@ pub struct MinecraftLaunchParameters {
     pub resolution: Option<WindowSize>,
     pub quick_play_singleplayer: Option<String>,
     pub quick_play_multiplayer: Option<String>,
+    pub java_major_version: Option<u32>,
+    pub java_vendor: Option<String>,
 }
 
 impl MinecraftLaunchParameters {
@@ -48,6 +53,8 @@ impl MinecraftLaunchParameters {
             resolution: None,
             quick_play_singleplayer: None,
             quick_play_multiplayer: None,
+            java_major_version: None,
+            java_vendor: None,
         }
     }
 
@@ -110,6 +117,12 @@ impl MinecraftLaunchParameters {
         self.quick_play_multiplayer = Some(server_address);
         self
     }
+
+    pub fn with_java_runtime(mut self, major_version: u32, vendor: Option<String>) -> Self {
+        self.java_major_version = Some(major_version);
+        self.java_vendor = vendor;
+        self
+    }
 }
 
 pub struct MinecraftLauncher {
@@ -219,15 +232,31 @@ impl MinecraftLauncher {
 
         // 2. Java-Befehl initialisieren (mit wrapper support)
         let launcher_config = state.config_manager.get_config().await;
-        let mut command = match launcher_config.hooks.wrapper {
+        let (hooks, env_vars, hook_context) = match &profile {
+            Some(p) => (
+                launcher_config.hooks.merged_with(&p.settings.hooks),
+                p.settings.env_vars.clone(),
+                HookContext::new(self.game_directory.clone(), p.id, p.name.clone()),
+            ),
+            None => (
+                launcher_config.hooks.clone(),
+                HashMap::new(),
+                HookContext::new(self.game_directory.clone(), params.profile_id, String::new()),
+            ),
+        };
+        let hook_context = hook_context
+            .with_java_path(self.java_path.clone())
+            .with_minecraft_version(piston_meta.id.clone());
+
+        let mut command = match hooks.wrapper.as_deref() {
             Some(wrapper) => {
-                info!("Using wrapper command: {}", wrapper);
-                // Exactly like Modrinth: use the whole wrapper string as command and add java path as arg
-                {
-                    let mut it = Command::new(wrapper);
-                    it.arg(&self.java_path);
-                    it
-                }
+                // Validated up front so a typo doesn't end up as a generic spawn failure
+                let wrapper_argv = hook_context.wrapper_argv(wrapper)?;
+                info!("Using wrapper command: {}", join_command(&wrapper_argv));
+                let mut it = Command::new(&wrapper_argv[0]);
+                it.args(&wrapper_argv[1..]);
+                it.arg(&self.java_path);
+                it
             }
             None => Command::new(&self.java_path),
         };
@@ -281,14 +310,27 @@ impl MinecraftLauncher {
         info!("Adding RAM JVM argument: -Xmx{}M", params.memory_max_mb);
         command.arg(format!("-Xmx{}M", params.memory_max_mb));
 
-        // Add recommended GC flags
-        command.arg("-XX:+UnlockExperimentalVMOptions");
-        command.arg("-XX:+UseG1GC");
-        // Add additional G1GC optimization flags like vanilla launcher
-        command.arg("-XX:G1NewSizePercent=20");
-        command.arg("-XX:G1ReservePercent=20");
-        command.arg("-XX:MaxGCPauseMillis=50");
-        command.arg("-XX:G1HeapRegionSize=32M");
+        // Add GC flags from the profile's JVM preset, matching the Java runtime in use
+        let jvm_preset = profile
+            .as_ref()
+            .map(|p| p.settings.jvm_preset)
+            .unwrap_or_default();
+        let java_major = params
+            .java_major_version
+            .unwrap_or(piston_meta.java_version.major_version as u32);
+        let gc_flags = jvm_presets::resolve_gc_flags(
+            jvm_preset,
+            java_major,
+            params.java_vendor.as_deref(),
+            &params.additional_jvm_args,
+        );
+        info!(
+            "Adding GC flags for preset {:?} (Java {}): {:?}",
+            jvm_preset, java_major, gc_flags
+        );
+        for flag in gc_flags {
+            command.arg(flag);
+        }
 
         // Add GEG client specific parameters
         // Only add token if we have credentials AND a GEG pack is selected in the profile
@@ -299,7 +341,12 @@ impl MinecraftLauncher {
             command.arg(format!("-DGEG.profile.name={}", p.name));
         }
 
-        if let Some(creds) = &self.credentials {
+        if let Some(creds) = self.credentials.as_ref().filter(|c| c.is_offline()) {
+            info!(
+                "[GEG Launcher] Offline account '{}', skipping GEG token and experimental mode parameters",
+                creds.username
+            );
+        } else if let Some(creds) = &self.credentials {
             if has_GEG_pack {
                 // Get the appropriate GEG token based on experimental mode setting
                 if let Some(GEG_token) = if params.is_experimental_mode {
@@ -464,9 +511,15 @@ impl MinecraftLauncher {
                 None => (None, None, None, None),
             };
 
-        // Get post-exit hook from config at launch time (not at exit time)
-        let launcher_config = state.config_manager.get_config().await;
-        let post_exit_hook = launcher_config.hooks.post_exit.clone();
+        // Post-exit hook and environment are resolved at launch time (not at exit time)
+        let post_exit_hook = match hooks.post_exit.as_deref() {
+            Some(hook) => Some(join_command(&hook_context.expand_command(hook)?)),
+            None => None,
+        };
+        let environment = hook_context.resolve_env(&env_vars);
+
+        // Last point a cancelled launch job can stop, once spawned the game keeps running
+        download_queue_state::checkpoint().await?;
 
         // Start the process using ProcessManager with additional metadata
         process_manager
@@ -481,6 +534,7 @@ impl MinecraftLauncher {
                 profile_GEG_pack,
                 profile_name,
                 post_exit_hook,
+                environment,
             )
             .await?;
 
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result};
 use crate::minecraft::dto::neo_forge_install_profile::{NeoForgeInstallProfile, NeoForgeProcessor};
+use crate::state::download_queue_state;
 use crate::state::event_state::{EventPayload, EventType};
 use crate::state::state_manager::State;
 use async_zip::tokio::read::seek::ZipFileReader;
@@ -399,6 +400,9 @@ impl NeoForgePatcher {
         let total_processors = install_profile.processors.len();
 
         for (i, processor) in install_profile.processors.iter().enumerate() {
+            // Processors run for a while, a cancelled launch job stops before the next one
+            download_queue_state::checkpoint().await?;
+
             // Filter nach Side
             if let Some(sides) = &processor.sides {
                 let is_processor_for_side = if is_client {
@@ -4,6 +4,7 @@ pub mod downloads;
 pub mod dto;
 pub mod installer;
 pub mod launch;
+pub mod mod_validation;
 pub mod modloader;
 
 pub use api::*;
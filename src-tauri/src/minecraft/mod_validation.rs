@@ -0,0 +1,1035 @@
+//! Pre-launch validation of the mod set: reads the metadata of every jar that will be loaded
+//! (`fabric.mod.json`, `quilt.mod.json`, `META-INF/mods.toml`, `META-INF/neoforge.mods.toml`,
+//! including jar-in-jar libraries) and reports missing dependencies, version range violations,
+//! duplicate mod IDs and declared incompatibilities before the game gets a chance to crash.
+
+use crate::error::{AppError, Result};
+use crate::state::download_queue_state;
+use crate::state::profile_state::ModLoader;
+use lazy_static::lazy_static;
+use log::{debug, info, warn};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// Jar-in-jar nesting deeper than this is not followed
+const MAX_NESTING_DEPTH: usize = 3;
+// Directories in which Fabric/Quilt (`META-INF/jars`) and Forge/NeoForge (`META-INF/jarjar`) bundle jars
+const NESTED_JAR_DIRS: [&str; 2] = ["META-INF/jars/", "META-INF/jarjar/"];
+
+lazy_static! {
+    static ref MAVEN_RANGE_REGEX: Regex = Regex::new(r"[\[\(][^\]\)]*[\]\)]").unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModMetadataFormat {
+    Fabric,
+    Quilt,
+    Forge,
+    NeoForge,
+}
+
+/// A version requirement as written in the metadata file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionRequirement {
+    Any,
+    /// Fabric/Quilt predicates; the requirement is met if any of them matches
+    Predicates(Vec<String>),
+    /// Maven version range as used by Forge and NeoForge, e.g. `[47,)`
+    MavenRange(String),
+}
+
+impl VersionRequirement {
+    pub fn matches(&self, version: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Predicates(predicates) => {
+                predicates.is_empty()
+                    || predicates
+                        .iter()
+                        .any(|predicate| predicate_matches(predicate, version))
+            }
+            Self::MavenRange(range) => maven_range_matches(range, version),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Any => "any version".to_string(),
+            Self::Predicates(predicates) => predicates.join(" or "),
+            Self::MavenRange(range) => range.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DependencyKind {
+    Required,
+    /// Not needed, but if present the version has to match
+    Optional,
+    Incompatible,
+}
+
+#[derive(Debug, Clone)]
+struct ModDependency {
+    mod_id: String,
+    requirement: VersionRequirement,
+    kind: DependencyKind,
+}
+
+/// Metadata of one mod, either a jar in the mods list or a library nested inside one.
+#[derive(Debug, Clone)]
+struct ModMetadata {
+    mod_id: String,
+    version: String,
+    name: String,
+    provides: Vec<String>,
+    dependencies: Vec<ModDependency>,
+}
+
+#[derive(Debug, Default)]
+struct JarMetadata {
+    /// Mods declared by the jar itself for the current loader
+    mods: Vec<ModMetadata>,
+    /// Mods declared by jars nested inside it
+    nested: Vec<ModMetadata>,
+    /// Metadata formats present in the jar, used to detect jars built for another loader
+    formats: Vec<ModMetadataFormat>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModValidationIssueKind {
+    MissingDependency,
+    VersionMismatch,
+    DuplicateModId,
+    Incompatible,
+    WrongLoader,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModValidationIssue {
+    pub kind: ModValidationIssueKind,
+    pub mod_id: String,
+    pub file_name: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModValidationReport {
+    pub checked_files: usize,
+    pub issues: Vec<ModValidationIssue>,
+}
+
+impl ModValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Converts a failed report into the error that blocks the launch.
+    pub fn into_result(self, profile_name: &str) -> Result<()> {
+        if self.is_ok() {
+            return Ok(());
+        }
+        let details = self
+            .issues
+            .iter()
+            .map(|issue| format!("- {}", issue.message))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(AppError::ModValidationFailed {
+            profile_name: profile_name.to_string(),
+            details,
+        })
+    }
+}
+
+/// What the game environment itself provides (Minecraft, the loader and Java).
+#[derive(Debug, Clone)]
+pub struct ModEnvironment {
+    pub loader: ModLoader,
+    pub minecraft_version: String,
+    pub loader_version: Option<String>,
+}
+
+impl ModEnvironment {
+    pub fn new(loader: ModLoader, minecraft_version: &str) -> Self {
+        Self {
+            loader,
+            minecraft_version: minecraft_version.to_string(),
+            loader_version: None,
+        }
+    }
+
+    pub fn with_loader_version(mut self, loader_version: Option<String>) -> Self {
+        self.loader_version = loader_version;
+        self
+    }
+
+    /// Loader version as mods compare against it. Forge (and NeoForge for 1.20.1) versions
+    /// are maven versions like `1.20.1-47.2.0` or `1.7.10-10.13.4.1614-1.7.10`, mods require
+    /// the part without Minecraft version, e.g. `[47,)`.
+    fn loader_version_without_minecraft(&self) -> Option<String> {
+        let version = self.loader_version.as_deref()?;
+        let version = version
+            .strip_prefix(&format!("{}-", self.minecraft_version))
+            .unwrap_or(version);
+        let version = version
+            .strip_suffix(&format!("-{}", self.minecraft_version))
+            .unwrap_or(version);
+        Some(version.to_string())
+    }
+
+    /// Mod IDs provided by the environment with their version (`None` = version unknown).
+    fn provided_ids(&self) -> Vec<(&'static str, Option<String>)> {
+        let minecraft = Some(self.minecraft_version.clone());
+        let loader = self.loader_version_without_minecraft();
+        match self.loader {
+            ModLoader::Fabric => {
+                // Fabric Loader bundles MixinExtras since 0.15
+                let bundles_mixinextras = loader
+                    .as_deref()
+                    .is_some_and(|version| compare_versions(version, "0.15") != Ordering::Less);
+                let mut ids = vec![
+                    ("minecraft", minecraft),
+                    ("fabricloader", loader),
+                    ("java", None),
+                ];
+                if bundles_mixinextras {
+                    ids.push(("mixinextras", None));
+                }
+                ids
+            }
+            ModLoader::Quilt => vec![
+                ("minecraft", minecraft),
+                ("quilt_loader", loader),
+                ("fabricloader", None),
+                ("java", None),
+            ],
+            ModLoader::Forge => vec![
+                ("minecraft", minecraft),
+                ("forge", loader),
+                ("javafml", None),
+                ("java", None),
+            ],
+            ModLoader::NeoForge => {
+                // NeoForge for 1.20.1 is a Forge fork and still answers to the `forge` ID
+                let forge = (self.minecraft_version == "1.20.1").then(|| loader.clone());
+                let mut ids = vec![
+                    ("minecraft", minecraft),
+                    ("neoforge", loader),
+                    ("javafml", None),
+                    ("java", None),
+                ];
+                if let Some(forge) = forge {
+                    ids.push(("forge", forge));
+                }
+                ids
+            }
+            ModLoader::Vanilla => vec![("minecraft", minecraft)],
+        }
+    }
+
+    fn reads(&self, format: ModMetadataFormat) -> bool {
+        match self.loader {
+            ModLoader::Fabric => format == ModMetadataFormat::Fabric,
+            // Quilt loads Fabric mods as well
+            ModLoader::Quilt => {
+                matches!(format, ModMetadataFormat::Quilt | ModMetadataFormat::Fabric)
+            }
+            ModLoader::Forge => format == ModMetadataFormat::Forge,
+            // `mods.toml` is still read by NeoForge before 1.20.5
+            ModLoader::NeoForge => {
+                matches!(
+                    format,
+                    ModMetadataFormat::NeoForge | ModMetadataFormat::Forge
+                )
+            }
+            ModLoader::Vanilla => false,
+        }
+    }
+}
+
+/// Validates the given mod jars against each other and the environment. A cancelled
+/// launch job stops between two jars.
+pub async fn validate_mod_files(
+    mod_files: Vec<PathBuf>,
+    environment: ModEnvironment,
+) -> Result<ModValidationReport> {
+    let environment = Arc::new(environment);
+    let mut jars = Vec::new();
+    for path in mod_files {
+        download_queue_state::checkpoint().await?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let jar_environment = Arc::clone(&environment);
+        let read_path = path.clone();
+        let metadata =
+            tokio::task::spawn_blocking(move || read_jar_file(&read_path, &jar_environment))
+                .await?;
+        match metadata {
+            Ok(metadata) => jars.push((file_name, metadata)),
+            // An unreadable jar is the loader's problem to report; don't block on it here
+            Err(e) => warn!("Skipping mod validation of {:?}: {}", path, e),
+        }
+    }
+    let report = validate_jars(&jars, &environment);
+    info!(
+        "Validated {} mod files: {} issue(s)",
+        report.checked_files,
+        report.issues.len()
+    );
+    Ok(report)
+}
+
+fn validate_jars(
+    jars: &[(String, JarMetadata)],
+    environment: &ModEnvironment,
+) -> ModValidationReport {
+    let mut report = ModValidationReport {
+        checked_files: jars.len(),
+        issues: Vec::new(),
+    };
+
+    // Every ID that will be present, with the versions it is present in
+    let mut available: HashMap<String, Vec<Option<String>>> = HashMap::new();
+    for (id, version) in environment.provided_ids() {
+        available.entry(id.to_string()).or_default().push(version);
+    }
+    // Which top-level file declares an ID, for duplicate detection
+    let mut declared_by: HashMap<String, String> = HashMap::new();
+
+    for (file_name, jar) in jars {
+        if jar.mods.is_empty() {
+            if let Some(format) = jar.formats.first() {
+                report.issues.push(ModValidationIssue {
+                    kind: ModValidationIssueKind::WrongLoader,
+                    mod_id: String::new(),
+                    file_name: file_name.clone(),
+                    message: format!(
+                        "{} is a {:?} mod and can't be loaded by {}",
+                        file_name,
+                        format,
+                        environment.loader.as_str()
+                    ),
+                });
+            }
+            continue;
+        }
+
+        for metadata in &jar.mods {
+            for id in std::iter::once(&metadata.mod_id).chain(&metadata.provides) {
+                if let Some(other_file) = declared_by.get(id) {
+                    if other_file != file_name {
+                        report.issues.push(ModValidationIssue {
+                            kind: ModValidationIssueKind::DuplicateModId,
+                            mod_id: id.clone(),
+                            file_name: file_name.clone(),
+                            message: format!(
+                                "Mod ID '{}' is provided by both {} and {}; remove one of them",
+                                id, other_file, file_name
+                            ),
+                        });
+                    }
+                    continue;
+                }
+                declared_by.insert(id.clone(), file_name.clone());
+            }
+        }
+
+        for metadata in jar.mods.iter().chain(&jar.nested) {
+            for id in std::iter::once(&metadata.mod_id).chain(&metadata.provides) {
+                available
+                    .entry(id.clone())
+                    .or_default()
+                    .push(Some(metadata.version.clone()));
+            }
+        }
+    }
+
+    for (file_name, jar) in jars {
+        for metadata in &jar.mods {
+            for dependency in &metadata.dependencies {
+                if let Some(issue) = check_dependency(file_name, metadata, dependency, &available) {
+                    report.issues.push(issue);
+                }
+            }
+        }
+    }
+
+    report
+}
+
+fn check_dependency(
+    file_name: &str,
+    metadata: &ModMetadata,
+    dependency: &ModDependency,
+    available: &HashMap<String, Vec<Option<String>>>,
+) -> Option<ModValidationIssue> {
+    let issue = |kind, message| {
+        Some(ModValidationIssue {
+            kind,
+            mod_id: metadata.mod_id.clone(),
+            file_name: file_name.to_string(),
+            message,
+        })
+    };
+    let requirement = dependency.requirement.describe();
+
+    let Some(versions) = available.get(&dependency.mod_id) else {
+        return match dependency.kind {
+            DependencyKind::Required => issue(
+                ModValidationIssueKind::MissingDependency,
+                format!(
+                    "{} ({}) requires '{}' ({}), which is not installed",
+                    metadata.name, file_name, dependency.mod_id, requirement
+                ),
+            ),
+            DependencyKind::Optional | DependencyKind::Incompatible => None,
+        };
+    };
+
+    // Unknown versions (e.g. Java) always count as a match
+    let matching = versions.iter().any(|version| {
+        version
+            .as_deref()
+            .is_none_or(|version| dependency.requirement.matches(version))
+    });
+    let present = versions
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match dependency.kind {
+        DependencyKind::Required | DependencyKind::Optional if !matching => issue(
+            ModValidationIssueKind::VersionMismatch,
+            format!(
+                "{} ({}) requires '{}' {}, but {} is installed",
+                metadata.name, file_name, dependency.mod_id, requirement, present
+            ),
+        ),
+        DependencyKind::Incompatible if matching => issue(
+            ModValidationIssueKind::Incompatible,
+            format!(
+                "{} ({}) is incompatible with '{}' {} (installed: {})",
+                metadata.name, file_name, dependency.mod_id, requirement, present
+            ),
+        ),
+        _ => None,
+    }
+}
+
+fn read_jar_file(path: &Path, environment: &ModEnvironment) -> Result<JarMetadata> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(std::io::BufReader::new(file))?;
+    let (mods, formats) = read_archive_metadata(&mut archive, environment);
+    let mut nested = Vec::new();
+    read_nested_jars(&mut archive, environment, 1, &mut nested);
+    debug!(
+        "Read {} mod(s) and {} nested mod(s) from {:?}",
+        mods.len(),
+        nested.len(),
+        path
+    );
+    Ok(JarMetadata {
+        mods,
+        nested,
+        formats,
+    })
+}
+
+fn read_nested_jars<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    environment: &ModEnvironment,
+    depth: usize,
+    nested: &mut Vec<ModMetadata>,
+) {
+    if depth > MAX_NESTING_DEPTH {
+        return;
+    }
+    let nested_names: Vec<String> = archive
+        .file_names()
+        .filter(|name| {
+            name.ends_with(".jar") && NESTED_JAR_DIRS.iter().any(|dir| name.starts_with(dir))
+        })
+        .map(|name| name.to_string())
+        .collect();
+
+    for name in nested_names {
+        let Some(bytes) = read_entry_bytes(archive, &name) else {
+            continue;
+        };
+        match zip::ZipArchive::new(Cursor::new(bytes)) {
+            Ok(mut inner) => {
+                let (mods, _) = read_archive_metadata(&mut inner, environment);
+                nested.extend(mods);
+                read_nested_jars(&mut inner, environment, depth + 1, nested);
+            }
+            Err(e) => debug!("Skipping unreadable nested jar {}: {}", name, e),
+        }
+    }
+}
+
+fn read_entry_bytes<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Option<Vec<u8>> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+fn read_entry_string<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Option<String> {
+    read_entry_bytes(archive, name).map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Reads the metadata the current loader would use, plus which formats the archive contains.
+fn read_archive_metadata<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    environment: &ModEnvironment,
+) -> (Vec<ModMetadata>, Vec<ModMetadataFormat>) {
+    let sources = [
+        (ModMetadataFormat::Quilt, "quilt.mod.json"),
+        (ModMetadataFormat::Fabric, "fabric.mod.json"),
+        (ModMetadataFormat::NeoForge, "META-INF/neoforge.mods.toml"),
+        (ModMetadataFormat::Forge, "META-INF/mods.toml"),
+    ];
+
+    let mut formats = Vec::new();
+    let mut mods = Vec::new();
+    for (format, entry_name) in sources {
+        let Some(content) = read_entry_string(archive, entry_name) else {
+            continue;
+        };
+        formats.push(format);
+        // The first format the loader understands wins (Quilt prefers quilt.mod.json)
+        if !mods.is_empty() || !environment.reads(format) {
+            continue;
+        }
+        let parsed = match format {
+            ModMetadataFormat::Quilt => parse_quilt_mod_json(&content),
+            ModMetadataFormat::Fabric => parse_fabric_mod_json(&content),
+            ModMetadataFormat::Forge | ModMetadataFormat::NeoForge => {
+                let jar_version = read_entry_string(archive, "META-INF/MANIFEST.MF")
+                    .and_then(|manifest| manifest_value(&manifest, "Implementation-Version"));
+                parse_mods_toml(&content, jar_version.as_deref())
+            }
+        };
+        match parsed {
+            Ok(parsed) => mods = parsed,
+            Err(e) => warn!("Failed to parse {}: {}", entry_name, e),
+        }
+    }
+    (mods, formats)
+}
+
+fn manifest_value(manifest: &str, key: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        line.strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .map(|value| value.trim().to_string())
+    })
+}
+
+fn json_string_list(value: Option<&JsonValue>) -> Vec<String> {
+    match value {
+        Some(JsonValue::String(s)) => vec![s.clone()],
+        Some(JsonValue::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_fabric_mod_json(content: &str) -> Result<Vec<ModMetadata>> {
+    let json: JsonValue = serde_json::from_str(content)?;
+    let mod_id = json["id"]
+        .as_str()
+        .ok_or_else(|| AppError::ParseError("fabric.mod.json has no id".to_string()))?
+        .to_string();
+
+    let mut dependencies = Vec::new();
+    let sections = [
+        ("depends", DependencyKind::Required),
+        ("breaks", DependencyKind::Incompatible),
+    ];
+    for (key, kind) in sections {
+        if let Some(entries) = json[key].as_object() {
+            for (id, value) in entries {
+                dependencies.push(ModDependency {
+                    mod_id: id.clone(),
+                    requirement: VersionRequirement::Predicates(json_string_list(Some(value))),
+                    kind,
+                });
+            }
+        }
+    }
+
+    Ok(vec![ModMetadata {
+        name: json["name"].as_str().unwrap_or(&mod_id).to_string(),
+        version: json["version"].as_str().unwrap_or("0").to_string(),
+        provides: json_string_list(json.get("provides")),
+        dependencies,
+        mod_id,
+    }])
+}
+
+fn parse_quilt_mod_json(content: &str) -> Result<Vec<ModMetadata>> {
+    let json: JsonValue = serde_json::from_str(content)?;
+    let loader = &json["quilt_loader"];
+    let mod_id = loader["id"]
+        .as_str()
+        .ok_or_else(|| AppError::ParseError("quilt.mod.json has no id".to_string()))?
+        .to_string();
+
+    let provides = loader["provides"]
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| entry.as_str().or_else(|| entry["id"].as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut dependencies = Vec::new();
+    let sections = [
+        ("depends", DependencyKind::Required),
+        ("breaks", DependencyKind::Incompatible),
+    ];
+    for (key, kind) in sections {
+        for entry in loader[key].as_array().into_iter().flatten() {
+            let (id, requirement, optional) = match entry {
+                JsonValue::String(id) => (id.clone(), VersionRequirement::Any, false),
+                JsonValue::Object(_) => {
+                    let Some(id) = entry["id"].as_str() else {
+                        continue;
+                    };
+                    let versions = match &entry["versions"] {
+                        // `{"any": [...]}`; `all` is rare enough to be treated the same
+                        JsonValue::Object(obj) => {
+                            json_string_list(obj.get("any").or_else(|| obj.get("all")))
+                        }
+                        other => json_string_list(Some(other)),
+                    };
+                    let optional = entry["optional"].as_bool().unwrap_or(false);
+                    (
+                        id.to_string(),
+                        VersionRequirement::Predicates(versions),
+                        optional,
+                    )
+                }
+                _ => continue,
+            };
+            // Quilt IDs may carry a maven group (`org.quiltmc:quilt_loader`)
+            let id = id.rsplit(':').next().unwrap_or(&id).to_string();
+            dependencies.push(ModDependency {
+                mod_id: id,
+                requirement,
+                kind: if kind == DependencyKind::Required && optional {
+                    DependencyKind::Optional
+                } else {
+                    kind
+                },
+            });
+        }
+    }
+
+    let metadata = &loader["metadata"];
+    Ok(vec![ModMetadata {
+        name: metadata["name"].as_str().unwrap_or(&mod_id).to_string(),
+        version: loader["version"].as_str().unwrap_or("0").to_string(),
+        provides,
+        dependencies,
+        mod_id,
+    }])
+}
+
+fn parse_mods_toml(content: &str, jar_version: Option<&str>) -> Result<Vec<ModMetadata>> {
+    let toml: toml::Table =
+        toml::from_str(content).map_err(|e| AppError::ParseError(e.to_string()))?;
+    let dependency_tables = toml.get("dependencies").and_then(|d| d.as_table());
+
+    let mut mods = Vec::new();
+    for entry in toml
+        .get("mods")
+        .and_then(|m| m.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let Some(mod_id) = entry.get("modId").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let version = match entry.get("version").and_then(|v| v.as_str()) {
+            Some("${file.jarVersion}") | None => jar_version.unwrap_or("0"),
+            Some(version) => version,
+        };
+
+        let mut dependencies = Vec::new();
+        let declared = dependency_tables
+            .and_then(|tables| tables.get(mod_id))
+            .and_then(|d| d.as_array());
+        for dependency in declared.into_iter().flatten() {
+            let Some(dependency_id) = dependency.get("modId").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            // Server-only dependencies don't matter on the client
+            if dependency.get("side").and_then(|v| v.as_str()) == Some("SERVER") {
+                continue;
+            }
+            // NeoForge uses `type`, Forge the older `mandatory` flag
+            let kind = match dependency.get("type").and_then(|v| v.as_str()) {
+                Some(t) if t.eq_ignore_ascii_case("required") => DependencyKind::Required,
+                Some(t) if t.eq_ignore_ascii_case("incompatible") => DependencyKind::Incompatible,
+                Some(_) => DependencyKind::Optional,
+                None => match dependency.get("mandatory").and_then(|v| v.as_bool()) {
+                    Some(false) => DependencyKind::Optional,
+                    _ => DependencyKind::Required,
+                },
+            };
+            let range = dependency
+                .get("versionRange")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            dependencies.push(ModDependency {
+                mod_id: dependency_id.to_string(),
+                requirement: if range.trim().is_empty() || range.trim() == "*" {
+                    VersionRequirement::Any
+                } else {
+                    VersionRequirement::MavenRange(range.trim().to_string())
+                },
+                kind,
+            });
+        }
+
+        mods.push(ModMetadata {
+            mod_id: mod_id.to_string(),
+            version: version.to_string(),
+            name: entry
+                .get("displayName")
+                .and_then(|v| v.as_str())
+                .unwrap_or(mod_id)
+                .to_string(),
+            provides: Vec::new(),
+            dependencies,
+        });
+    }
+    Ok(mods)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum VersionSegment {
+    Number(u64),
+    Text(String),
+}
+
+/// Splits a version into release segments and an optional pre-release part.
+/// Build metadata (`+...`) is ignored, as in semver.
+fn parse_version(version: &str) -> (Vec<VersionSegment>, Option<&str>) {
+    let version = version.trim();
+    let version = version.split('+').next().unwrap_or(version);
+    let (release, pre_release) = match version.split_once('-') {
+        Some((release, pre)) => (release, Some(pre)),
+        None => (version, None),
+    };
+    let segments = release
+        .split('.')
+        .map(|segment| match segment.parse::<u64>() {
+            Ok(number) => VersionSegment::Number(number),
+            Err(_) => VersionSegment::Text(segment.to_string()),
+        })
+        .collect();
+    (segments, pre_release)
+}
+
+/// Lenient semver-like comparison that also copes with Minecraft and Forge versions.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a_segments, a_pre) = parse_version(a);
+    let (b_segments, b_pre) = parse_version(b);
+    let zero = VersionSegment::Number(0);
+    for i in 0..a_segments.len().max(b_segments.len()) {
+        let left = a_segments.get(i).unwrap_or(&zero);
+        let right = b_segments.get(i).unwrap_or(&zero);
+        let ordering = match (left, right) {
+            (VersionSegment::Number(l), VersionSegment::Number(r)) => l.cmp(r),
+            (VersionSegment::Number(_), VersionSegment::Text(_)) => Ordering::Greater,
+            (VersionSegment::Text(_), VersionSegment::Number(_)) => Ordering::Less,
+            (VersionSegment::Text(l), VersionSegment::Text(r)) => l.cmp(r),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(l), Some(r)) => compare_versions(&l.replace('-', "."), &r.replace('-', ".")),
+    }
+}
+
+/// Fabric/Quilt version predicate, e.g. `>=0.14.0`, `~1.20`, `1.20.x` or `>=1.19 <1.21`.
+fn predicate_matches(predicate: &str, version: &str) -> bool {
+    predicate
+        .split_whitespace()
+        .all(|term| predicate_term_matches(term, version))
+}
+
+fn predicate_term_matches(term: &str, version: &str) -> bool {
+    if term == "*" {
+        return true;
+    }
+    let operators = [">=", "<=", ">", "<", "=", "~", "^"];
+    let (operator, target) = operators
+        .iter()
+        .find_map(|op| term.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("=", term));
+
+    // `1.20.x`: the given segments have to be equal
+    if target
+        .split('.')
+        .any(|segment| matches!(segment, "x" | "X" | "*"))
+    {
+        let prefix: Vec<&str> = target
+            .split('.')
+            .take_while(|segment| !matches!(*segment, "x" | "X" | "*"))
+            .collect();
+        let (segments, _) = parse_version(version);
+        let (prefix_segments, _) = parse_version(&prefix.join("."));
+        return prefix.is_empty()
+            || (segments.len() >= prefix_segments.len()
+                && segments.iter().zip(&prefix_segments).all(|(a, b)| a == b));
+    }
+
+    let ordering = compare_versions(version, target);
+    match operator {
+        ">=" => ordering != Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        "<" => ordering == Ordering::Less,
+        "~" | "^" => {
+            if ordering == Ordering::Less {
+                return false;
+            }
+            // `~` allows changes below the minor version, `^` below the major version
+            let (segments, _) = parse_version(target);
+            let keep = if operator == "~" { 2 } else { 1 };
+            let (version_segments, _) = parse_version(version);
+            segments
+                .iter()
+                .take(keep.min(segments.len()))
+                .zip(&version_segments)
+                .all(|(a, b)| a == b)
+        }
+        _ => ordering == Ordering::Equal,
+    }
+}
+
+/// Maven version range as used in `mods.toml`, e.g. `[1.20,1.21)`, `[47,)` or `[1.0],[2.0,)`.
+fn maven_range_matches(range: &str, version: &str) -> bool {
+    let range = range.trim();
+    // A bare version is only a recommendation in Maven and matches everything
+    if !range.starts_with(['[', '(']) {
+        return true;
+    }
+    MAVEN_RANGE_REGEX.find_iter(range).any(|restriction| {
+        let restriction = restriction.as_str();
+        let lower_inclusive = restriction.starts_with('[');
+        let upper_inclusive = restriction.ends_with(']');
+        let inner = &restriction[1..restriction.len() - 1];
+        match inner.split_once(',') {
+            None => compare_versions(version, inner.trim()) == Ordering::Equal,
+            Some((lower, upper)) => {
+                let (lower, upper) = (lower.trim(), upper.trim());
+                let above_lower = lower.is_empty()
+                    || match compare_versions(version, lower) {
+                        Ordering::Greater => true,
+                        Ordering::Equal => lower_inclusive,
+                        Ordering::Less => false,
+                    };
+                let below_upper = upper.is_empty()
+                    || match compare_versions(version, upper) {
+                        Ordering::Less => true,
+                        Ordering::Equal => upper_inclusive,
+                        Ordering::Greater => false,
+                    };
+                above_lower && below_upper
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jar(mods: Vec<ModMetadata>) -> JarMetadata {
+        JarMetadata {
+            mods,
+            nested: Vec::new(),
+            formats: vec![ModMetadataFormat::Fabric],
+        }
+    }
+
+    #[test]
+    fn fabric_predicates() {
+        assert!(predicate_matches(">=0.14.0", "0.15.11"));
+        assert!(!predicate_matches(">=0.15.0", "0.14.24"));
+        assert!(predicate_matches("1.20.x", "1.20.4"));
+        assert!(!predicate_matches("1.20.x", "1.21"));
+        assert!(predicate_matches("~1.20", "1.20.6"));
+        assert!(!predicate_matches("~1.20.1", "1.21.0"));
+        assert!(predicate_matches(">=1.19 <1.21", "1.20.1"));
+        assert!(predicate_matches("*", "23w31a"));
+        assert!(predicate_matches(">=0.5.0", "0.5.1+mc1.20.1"));
+        assert!(!predicate_matches(">=1.0.0", "1.0.0-beta.2"));
+    }
+
+    #[test]
+    fn maven_ranges() {
+        assert!(maven_range_matches("[47,)", "47.2.0"));
+        assert!(!maven_range_matches("[47,)", "46.0.14"));
+        assert!(maven_range_matches("[1.20,1.21)", "1.20.1"));
+        assert!(!maven_range_matches("[1.20,1.21)", "1.21"));
+        assert!(maven_range_matches("[1.20.1]", "1.20.1"));
+        assert!(maven_range_matches("[1.0],[2.0,)", "2.3"));
+        assert!(maven_range_matches("1.0", "0.1"));
+    }
+
+    #[test]
+    fn parses_mods_toml_with_jar_version() {
+        let toml = r#"
+            modLoader = "javafml"
+            [[mods]]
+            modId = "alexsmobs"
+            version = "${file.jarVersion}"
+            displayName = "Alex's Mobs"
+            [[dependencies.alexsmobs]]
+            modId = "citadel"
+            mandatory = true
+            versionRange = "[2.1.4,)"
+            [[dependencies.alexsmobs]]
+            modId = "servercore"
+            type = "required"
+            side = "SERVER"
+            [[dependencies.alexsmobs]]
+            modId = "optifine"
+            type = "incompatible"
+        "#;
+        let mods = parse_mods_toml(toml, Some("1.22.8")).unwrap();
+        assert_eq!(mods[0].version, "1.22.8");
+        assert_eq!(mods[0].dependencies.len(), 2);
+        assert_eq!(mods[0].dependencies[0].kind, DependencyKind::Required);
+        assert_eq!(mods[0].dependencies[1].kind, DependencyKind::Incompatible);
+    }
+
+    #[test]
+    fn reports_missing_mismatched_duplicate_and_incompatible_mods() {
+        let sodium_extra = parse_fabric_mod_json(
+            r#"{"id": "sodium-extra", "name": "Sodium Extra", "version": "0.5.1",
+                "depends": {"sodium": ">=0.5.0", "minecraft": "1.20.x", "fabric-api": "*"},
+                "breaks": {"optifabric": "*"}}"#,
+        )
+        .unwrap();
+        let sodium = parse_fabric_mod_json(r#"{"id": "sodium", "version": "0.4.10"}"#).unwrap();
+        let sodium_copy = parse_fabric_mod_json(r#"{"id": "sodium", "version": "0.5.3"}"#).unwrap();
+        let optifabric =
+            parse_fabric_mod_json(r#"{"id": "optifabric", "version": "1.14"}"#).unwrap();
+
+        let jars = vec![
+            ("sodium-extra.jar".to_string(), jar(sodium_extra)),
+            ("sodium-0.4.jar".to_string(), jar(sodium)),
+            ("sodium-0.5.jar".to_string(), jar(sodium_copy)),
+            ("optifabric.jar".to_string(), jar(optifabric)),
+        ];
+        let environment = ModEnvironment::new(ModLoader::Fabric, "1.20.1");
+        let kinds: Vec<_> = validate_jars(&jars, &environment)
+            .issues
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                ModValidationIssueKind::DuplicateModId,
+                ModValidationIssueKind::MissingDependency,
+                ModValidationIssueKind::Incompatible,
+            ]
+        );
+    }
+
+    #[test]
+    fn forge_environment_provides_loader_version_without_minecraft() {
+        let toml = r#"
+            modLoader = "javafml"
+            [[mods]]
+            modId = "create"
+            version = "0.5.1"
+            [[dependencies.create]]
+            modId = "forge"
+            mandatory = true
+            versionRange = "[47,)"
+            [[dependencies.create]]
+            modId = "minecraft"
+            mandatory = true
+            versionRange = "[1.20.1,1.20.2)"
+        "#;
+        let mut create = jar(parse_mods_toml(toml, None).unwrap());
+        create.formats = vec![ModMetadataFormat::Forge];
+        let jars = vec![("create.jar".to_string(), create)];
+
+        let forge = ModEnvironment::new(ModLoader::Forge, "1.20.1")
+            .with_loader_version(Some("1.20.1-47.2.0".to_string()));
+        assert!(validate_jars(&jars, &forge).is_ok());
+
+        let old_forge = ModEnvironment::new(ModLoader::Forge, "1.20.1")
+            .with_loader_version(Some("1.20.1-46.0.14".to_string()));
+        let issues = validate_jars(&jars, &old_forge).issues;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ModValidationIssueKind::VersionMismatch);
+
+        let neoforge = ModEnvironment::new(ModLoader::NeoForge, "1.20.1")
+            .with_loader_version(Some("1.20.1-47.1.106".to_string()));
+        assert!(validate_jars(&jars, &neoforge).is_ok());
+    }
+
+    #[test]
+    fn environment_and_nested_jars_satisfy_dependencies() {
+        let mod_a = parse_fabric_mod_json(
+            r#"{"id": "a", "version": "1.0", "depends": {"minecraft": ">=1.21", "fabric-api-base": "*"}}"#,
+        )
+        .unwrap();
+        let mut fabric_api =
+            jar(parse_fabric_mod_json(r#"{"id": "fabric-api", "version": "0.100.0"}"#).unwrap());
+        fabric_api.nested =
+            parse_fabric_mod_json(r#"{"id": "fabric-api-base", "version": "0.4.0"}"#).unwrap();
+
+        let jars = vec![
+            ("a.jar".to_string(), jar(mod_a)),
+            ("fabric-api.jar".to_string(), fabric_api),
+        ];
+        let issues = validate_jars(&jars, &ModEnvironment::new(ModLoader::Fabric, "1.20.1")).issues;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ModValidationIssueKind::VersionMismatch);
+        assert!(issues[0]
+            .message
+            .contains("'minecraft' >=1.21, but 1.20.1 is installed"));
+    }
+}
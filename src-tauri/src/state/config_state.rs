@@ -2,6 +2,9 @@ use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY, update_custom_game_dir};
 use crate::error::Result;
 use crate::state::post_init::PostInitializationHandler;
 use crate::state::profile_state::MemorySettings;
+use crate::state::server_status_state::MIN_REFRESH_INTERVAL_MINUTES;
+use crate::utils::bandwidth_utils::update_bandwidth_limits;
+use crate::utils::mirror_utils::update_mirror_rules;
 use async_trait::async_trait;
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
@@ -22,6 +25,55 @@ pub struct Hooks {
     pub post_exit: Option<String>,
 }
 
+impl Hooks {
+    /// Applies per-profile overrides on top of these (global) hooks.
+    /// A set override wins, an empty override disables the global hook for the profile.
+    pub fn merged_with(&self, overrides: &Hooks) -> Hooks {
+        fn pick(global: &Option<String>, profile: &Option<String>) -> Option<String> {
+            match profile {
+                Some(value) if value.trim().is_empty() => None,
+                Some(value) => Some(value.clone()),
+                None => global.clone().filter(|value| !value.trim().is_empty()),
+            }
+        }
+
+        Hooks {
+            pre_launch: pick(&self.pre_launch, &overrides.pre_launch),
+            wrapper: pick(&self.wrapper, &overrides.wrapper),
+            post_exit: pick(&self.post_exit, &overrides.post_exit),
+        }
+    }
+}
+
+/// How a mirror rule matches request URLs
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MirrorMatch {
+    /// Exact host name, e.g. `libraries.minecraft.net`
+    Host,
+    /// URL prefix, e.g. `https://maven.fabricmc.net/`
+    Prefix,
+}
+
+/// Rewrites matching download URLs (GET/HEAD through `DOWNLOAD_CLIENT`) to mirrors.
+/// Rules are checked in order, the first match wins.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MirrorRule {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    pub match_type: MirrorMatch,
+    pub pattern: String,
+    /// Tried in order. Host rules take a host or a base URL, prefix rules a replacement prefix.
+    pub mirrors: Vec<String>,
+    /// Try the original URL after all mirrors failed
+    #[serde(default = "default_true")]
+    pub fallback_to_origin: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LauncherConfig {
     #[serde(default = "default_config_version")]
@@ -53,6 +105,18 @@ pub struct LauncherConfig {
     pub global_memory_settings: MemorySettings,
     #[serde(default)]
     pub custom_game_directory: Option<PathBuf>,
+    #[serde(default)]
+    pub offline_mode: bool, // Keine Token-Refreshes, Start mit den zuletzt gespeicherten Credentials
+    #[serde(default)]
+    pub download_mirrors: Vec<MirrorRule>,
+    #[serde(default)]
+    pub download_limit_kbps: Option<u32>, // Globales Download-Limit in KB/s, None = unbegrenzt
+    #[serde(default)]
+    pub download_limit_while_playing_kbps: Option<u32>, // Gilt statt dessen, solange ein Spiel läuft
+    #[serde(default)]
+    pub server_status_auto_refresh: bool, // Server der Serverlisten im Hintergrund anpingen
+    #[serde(default = "default_server_status_refresh_interval_minutes")]
+    pub server_status_refresh_interval_minutes: u32,
 }
 
 fn default_config_version() -> u32 {
@@ -83,6 +147,10 @@ fn default_hide_on_process_start() -> bool {
     false
 }
 
+fn default_server_status_refresh_interval_minutes() -> u32 {
+    5
+}
+
 fn default_global_memory_settings() -> MemorySettings {
     MemorySettings {
         min: 3072, // 2GB
@@ -107,6 +175,13 @@ impl Default for LauncherConfig {
             hide_on_process_start: default_hide_on_process_start(),
             global_memory_settings: default_global_memory_settings(),
             custom_game_directory: None,
+            offline_mode: false,
+            download_mirrors: Vec::new(),
+            download_limit_kbps: None,
+            download_limit_while_playing_kbps: None,
+            server_status_auto_refresh: false,
+            server_status_refresh_interval_minutes: default_server_status_refresh_interval_minutes(
+            ),
         }
     }
 }
@@ -156,6 +231,11 @@ impl ConfigManager {
                 *config = loaded_config.clone();
                 
                 // Update cache
+                update_bandwidth_limits(
+                    loaded_config.download_limit_kbps,
+                    loaded_config.download_limit_while_playing_kbps,
+                );
+                update_mirror_rules(loaded_config.download_mirrors);
                 update_custom_game_dir(loaded_config.custom_game_directory);
             }
             Err(e) => {
@@ -199,6 +279,9 @@ impl ConfigManager {
                             if let Some(hide) = obj.get("hide_on_process_start").and_then(|v| v.as_bool()) {
                                 migrated_config.hide_on_process_start = hide;
                             }
+                            if let Some(offline) = obj.get("offline_mode").and_then(|v| v.as_bool()) {
+                                migrated_config.offline_mode = offline;
+                            }
                             
                             // Migrate numeric fields
                             if let Some(downloads) = obj.get("concurrent_downloads").and_then(|v| v.as_u64()) {
@@ -240,6 +323,42 @@ impl ConfigManager {
                             if let Some(custom_dir_str) = obj.get("custom_game_directory").and_then(|v| v.as_str()) {
                                 migrated_config.custom_game_directory = Some(PathBuf::from(custom_dir_str));
                             }
+
+                            // Migrate mirror rules (dropped as a whole if they don't parse)
+                            if let Some(mirrors) = obj.get("download_mirrors") {
+                                match serde_json::from_value::<Vec<MirrorRule>>(mirrors.clone()) {
+                                    Ok(rules) => migrated_config.download_mirrors = rules,
+                                    Err(e) => warn!("Dropping invalid download mirrors: {}", e),
+                                }
+                            }
+
+                            // Migrate bandwidth limits
+                            if let Some(limit) = obj.get("download_limit_kbps").and_then(|v| v.as_u64()) {
+                                migrated_config.download_limit_kbps = u32::try_from(limit).ok();
+                            }
+                            if let Some(limit) = obj
+                                .get("download_limit_while_playing_kbps")
+                                .and_then(|v| v.as_u64())
+                            {
+                                migrated_config.download_limit_while_playing_kbps = u32::try_from(limit).ok();
+                            }
+
+                            // Migrate background server status refresh
+                            if let Some(auto_refresh) = obj
+                                .get("server_status_auto_refresh")
+                                .and_then(|v| v.as_bool())
+                            {
+                                migrated_config.server_status_auto_refresh = auto_refresh;
+                            }
+                            if let Some(minutes) = obj
+                                .get("server_status_refresh_interval_minutes")
+                                .and_then(|v| v.as_u64())
+                            {
+                                migrated_config.server_status_refresh_interval_minutes =
+                                    u32::try_from(minutes)
+                                        .unwrap_or(u32::MAX)
+                                        .max(MIN_REFRESH_INTERVAL_MINUTES);
+                            }
                         }
                         
                         info!("Migration completed, saving migrated configuration");
@@ -251,6 +370,11 @@ impl ConfigManager {
                         self.save_config().await?;
                         
                         // Update cache
+                        update_bandwidth_limits(
+                            migrated_config.download_limit_kbps,
+                            migrated_config.download_limit_while_playing_kbps,
+                        );
+                        update_mirror_rules(migrated_config.download_mirrors);
                         update_custom_game_dir(migrated_config.custom_game_directory);
                     }
                     Err(json_err) => {
@@ -308,6 +432,10 @@ impl ConfigManager {
         self.config.read().await.is_experimental
     }
 
+    pub async fn is_offline_mode(&self) -> bool {
+        self.config.read().await.offline_mode
+    }
+
     pub async fn set_config(&self, new_config: LauncherConfig) -> Result<()> {
         let should_save = {
             let mut config = self.config.write().await;
@@ -328,6 +456,14 @@ impl ConfigManager {
                 && current.global_memory_settings.min == new_config.global_memory_settings.min
                 && current.global_memory_settings.max == new_config.global_memory_settings.max
                 && current.custom_game_directory == new_config.custom_game_directory
+                && current.offline_mode == new_config.offline_mode
+                && current.download_mirrors == new_config.download_mirrors
+                && current.download_limit_kbps == new_config.download_limit_kbps
+                && current.download_limit_while_playing_kbps
+                    == new_config.download_limit_while_playing_kbps
+                && current.server_status_auto_refresh == new_config.server_status_auto_refresh
+                && current.server_status_refresh_interval_minutes
+                    == new_config.server_status_refresh_interval_minutes
             {
                 debug!("No config changes detected, skipping save");
                 false
@@ -417,6 +553,44 @@ impl ConfigManager {
                     );
                 }
 
+                if current.offline_mode != new_config.offline_mode {
+                    info!(
+                        "Changing offline mode: {} -> {}",
+                        current.offline_mode, new_config.offline_mode
+                    );
+                }
+                if current.download_mirrors != new_config.download_mirrors {
+                    info!(
+                        "Changing download mirrors: {} -> {} rules",
+                        current.download_mirrors.len(),
+                        new_config.download_mirrors.len()
+                    );
+                }
+                if current.download_limit_kbps != new_config.download_limit_kbps
+                    || current.download_limit_while_playing_kbps
+                        != new_config.download_limit_while_playing_kbps
+                {
+                    info!(
+                        "Changing download limit: {:?}/{:?} -> {:?}/{:?} KB/s (normal/while playing)",
+                        current.download_limit_kbps,
+                        current.download_limit_while_playing_kbps,
+                        new_config.download_limit_kbps,
+                        new_config.download_limit_while_playing_kbps
+                    );
+                }
+                if current.server_status_auto_refresh != new_config.server_status_auto_refresh
+                    || current.server_status_refresh_interval_minutes
+                        != new_config.server_status_refresh_interval_minutes
+                {
+                    info!(
+                        "Changing server status refresh: {} every {} min -> {} every {} min",
+                        current.server_status_auto_refresh,
+                        current.server_status_refresh_interval_minutes,
+                        new_config.server_status_auto_refresh,
+                        new_config.server_status_refresh_interval_minutes
+                    );
+                }
+
                 // Update config while preserving version
                 *config = LauncherConfig {
                     version,
@@ -433,6 +607,13 @@ impl ConfigManager {
                     hide_on_process_start: new_config.hide_on_process_start,
                     global_memory_settings: new_config.global_memory_settings,
                     custom_game_directory: new_config.custom_game_directory.clone(),
+                    offline_mode: new_config.offline_mode,
+                    download_mirrors: new_config.download_mirrors.clone(),
+                    download_limit_kbps: new_config.download_limit_kbps,
+                    download_limit_while_playing_kbps: new_config.download_limit_while_playing_kbps,
+                    server_status_auto_refresh: new_config.server_status_auto_refresh,
+                    server_status_refresh_interval_minutes: new_config
+                        .server_status_refresh_interval_minutes,
                 };
 
                 true
@@ -445,6 +626,11 @@ impl ConfigManager {
 
             // Update cache
             update_custom_game_dir(new_config.custom_game_directory.clone());
+            update_mirror_rules(new_config.download_mirrors.clone());
+            update_bandwidth_limits(
+                new_config.download_limit_kbps,
+                new_config.download_limit_while_playing_kbps,
+            );
 
             // Update Discord status if it changed
             if let Ok(state) = crate::state::State::get().await {
@@ -465,7 +651,7 @@ impl ConfigManager {
 
 #[async_trait]
 impl PostInitializationHandler for ConfigManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("ConfigManager: on_state_ready called. Loading configuration...");
         self.load_config_internal().await?;
         info!("ConfigManager: Successfully loaded configuration in on_state_ready.");
@@ -0,0 +1,609 @@
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::Emitter;
+use tokio::sync::{watch, Notify};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+// Jobs below `Launch` priority that may download at the same time
+const MAX_ACTIVE_JOBS: usize = 2;
+// Finished jobs stay in the queue so the frontend can show how they ended
+const MAX_FINISHED_JOBS: usize = 50;
+// Byte progress of running jobs is pushed to the frontend at most this often
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(500);
+const QUEUE_UPDATED_EVENT: &str = "download_queue_updated";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadPriority {
+    /// Only starts when no other job is queued or running
+    Background,
+    Normal,
+    /// Never waits for a free slot
+    Launch,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadJobKind {
+    /// Installing everything a profile needs right before starting it
+    Launch,
+    /// Installing a profile without starting it
+    Install,
+    ModpackImport,
+    UpdateCheck,
+}
+
+impl DownloadJobKind {
+    pub fn default_priority(self) -> DownloadPriority {
+        match self {
+            DownloadJobKind::Launch => DownloadPriority::Launch,
+            DownloadJobKind::Install | DownloadJobKind::ModpackImport => DownloadPriority::Normal,
+            DownloadJobKind::UpdateCheck => DownloadPriority::Background,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadJobStatus {
+    Queued,
+    Running,
+    Paused,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl DownloadJobStatus {
+    pub fn is_finished(self) -> bool {
+        matches!(
+            self,
+            DownloadJobStatus::Completed | DownloadJobStatus::Failed | DownloadJobStatus::Cancelled
+        )
+    }
+}
+
+/// A unit of download work as shown in the frontend queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadJob {
+    pub id: Uuid,
+    pub kind: DownloadJobKind,
+    pub label: String,
+    pub profile_id: Option<Uuid>,
+    pub priority: DownloadPriority,
+    pub status: DownloadJobStatus,
+    /// Cancellation was requested, the job stops at its next checkpoint
+    pub cancel_requested: bool,
+    pub downloaded_bytes: u64,
+    pub downloaded_files: u64,
+    pub queued_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+/// What a caller wants to run through the queue.
+#[derive(Debug, Clone)]
+pub struct DownloadJobRequest {
+    pub kind: DownloadJobKind,
+    pub label: String,
+    pub profile_id: Option<Uuid>,
+    pub priority: DownloadPriority,
+}
+
+impl DownloadJobRequest {
+    pub fn new<S: Into<String>>(kind: DownloadJobKind, label: S) -> Self {
+        Self {
+            kind,
+            label: label.into(),
+            profile_id: None,
+            priority: kind.default_priority(),
+        }
+    }
+
+    pub fn for_profile(mut self, profile_id: Uuid) -> Self {
+        self.profile_id = Some(profile_id);
+        self
+    }
+
+    pub fn with_priority(mut self, priority: DownloadPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// Pause/cancel switches of a job, shared with the code running inside it.
+struct JobControl {
+    id: Uuid,
+    cancel: CancellationToken,
+    paused: watch::Sender<bool>,
+    downloaded_bytes: AtomicU64,
+    downloaded_files: AtomicU64,
+}
+
+impl JobControl {
+    fn new(id: Uuid) -> Self {
+        Self {
+            id,
+            cancel: CancellationToken::new(),
+            paused: watch::channel(false).0,
+            downloaded_bytes: AtomicU64::new(0),
+            downloaded_files: AtomicU64::new(0),
+        }
+    }
+
+    /// Waits while the job is paused and fails once it was cancelled
+    async fn checkpoint(&self) -> Result<()> {
+        let mut paused = self.paused.subscribe();
+        loop {
+            if self.cancel.is_cancelled() {
+                return Err(AppError::DownloadCancelled(self.id));
+            }
+            if !*paused.borrow_and_update() {
+                return Ok(());
+            }
+            tokio::select! {
+                _ = self.cancel.cancelled() => {}
+                _ = paused.changed() => {}
+            }
+        }
+    }
+}
+
+tokio::task_local! {
+    static CURRENT_JOB: Arc<JobControl>;
+}
+
+/// Pause and cancellation point for code running inside a download job. Called by
+/// `DownloadUtils` between chunks and by the installer between phases; a no-op outside of jobs.
+pub async fn checkpoint() -> Result<()> {
+    match CURRENT_JOB.try_with(Arc::clone) {
+        Ok(control) => control.checkpoint().await,
+        Err(_) => Ok(()),
+    }
+}
+
+/// Accounts received bytes to the current job, if any
+pub fn record_downloaded_bytes(bytes: u64) {
+    let _ = CURRENT_JOB.try_with(|control| {
+        control.downloaded_bytes.fetch_add(bytes, Ordering::Relaxed)
+    });
+}
+
+/// Accounts a completed file to the current job, if any
+pub fn record_downloaded_file() {
+    let _ = CURRENT_JOB.try_with(|control| control.downloaded_files.fetch_add(1, Ordering::Relaxed));
+}
+
+/// Whether the job `id` may leave the queue now. `Launch` jobs always may, everything else
+/// needs one of `MAX_ACTIVE_JOBS` slots and has to be the oldest queued job of the highest
+/// waiting priority. `Background` jobs additionally wait until all other work is done.
+fn may_start(jobs: &[DownloadJob], id: Uuid) -> bool {
+    let Some(job) = jobs.iter().find(|j| j.id == id) else {
+        return false;
+    };
+    if job.status != DownloadJobStatus::Queued {
+        return false;
+    }
+    if job.priority == DownloadPriority::Launch {
+        return true;
+    }
+
+    let running = jobs
+        .iter()
+        .filter(|j| j.status == DownloadJobStatus::Running && j.priority != DownloadPriority::Launch)
+        .count();
+    if running >= MAX_ACTIVE_JOBS {
+        return false;
+    }
+    if job.priority == DownloadPriority::Background
+        && jobs.iter().any(|j| {
+            j.priority > DownloadPriority::Background
+                && matches!(j.status, DownloadJobStatus::Queued | DownloadJobStatus::Running)
+        })
+    {
+        return false;
+    }
+
+    // `min_by_key` keeps the first of equal keys, so jobs of one priority start in queue order
+    jobs.iter()
+        .filter(|j| j.status == DownloadJobStatus::Queued)
+        .min_by_key(|j| Reverse(j.priority))
+        .is_some_and(|next| next.id == id)
+}
+
+/// Finishes a job as cancelled if `run_job` is dropped before it could (launch aborted,
+/// task aborted); a no-op once the job was finished regularly.
+struct JobGuard<'a> {
+    queue: &'a DownloadQueueManager,
+    id: Uuid,
+}
+
+impl Drop for JobGuard<'_> {
+    fn drop(&mut self) {
+        if self.queue.lock().controls.contains_key(&self.id) {
+            warn!("Download job {} was dropped before it finished", self.id);
+            self.queue.finish(
+                self.id,
+                DownloadJobStatus::Cancelled,
+                Some("Aborted".to_string()),
+            );
+        }
+    }
+}
+
+struct QueueInner {
+    /// In the order the jobs were queued
+    jobs: Vec<DownloadJob>,
+    /// Controls of the jobs that have not finished yet
+    controls: HashMap<Uuid, Arc<JobControl>>,
+}
+
+/// Central queue all larger download work runs through.
+///
+/// Jobs are futures passed to `run_job`; they wait for a slot according to their priority
+/// and can be paused, resumed and cancelled from the frontend. Pausing and cancelling are
+/// cooperative: `DownloadUtils` calls `checkpoint` between chunks, so a cancelled job stops
+/// with `AppError::DownloadCancelled` after removing its partial file instead of being
+/// aborted mid-write. A paused job keeps its HTTP connection, long pauses may make the
+/// server drop it and the file is downloaded again (or resumed, for resumable downloads).
+pub struct DownloadQueueManager {
+    app: Option<Arc<tauri::AppHandle>>,
+    inner: Mutex<QueueInner>,
+    slot_freed: Notify,
+}
+
+impl DownloadQueueManager {
+    pub fn new(app: Option<Arc<tauri::AppHandle>>) -> Self {
+        info!("DownloadQueueManager: Initializing (max {} active jobs)", MAX_ACTIVE_JOBS);
+        Self {
+            app,
+            inner: Mutex::new(QueueInner {
+                jobs: Vec::new(),
+                controls: HashMap::new(),
+            }),
+            slot_freed: Notify::new(),
+        }
+    }
+
+    /// Runs `job` as a queued download job. Waits until the queue admits the job, then drives
+    /// it to completion. A cancelled job always ends with `AppError::DownloadCancelled`.
+    /// Calls from inside a running job run inline as part of that job.
+    pub async fn run_job<T, F>(&self, request: DownloadJobRequest, job: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        if CURRENT_JOB.try_with(|_| ()).is_ok() {
+            return job.await;
+        }
+
+        let id = Uuid::new_v4();
+        let control = Arc::new(JobControl::new(id));
+        {
+            let mut inner = self.lock();
+            inner.jobs.push(DownloadJob {
+                id,
+                kind: request.kind,
+                label: request.label.clone(),
+                profile_id: request.profile_id,
+                priority: request.priority,
+                status: DownloadJobStatus::Queued,
+                cancel_requested: false,
+                downloaded_bytes: 0,
+                downloaded_files: 0,
+                queued_at: Utc::now(),
+                started_at: None,
+                finished_at: None,
+                error: None,
+            });
+            inner.controls.insert(id, control.clone());
+        }
+        let _guard = JobGuard { queue: self, id };
+        debug!(
+            "Queued download job {} '{}' ({:?})",
+            id, request.label, request.priority
+        );
+        self.emit_queue();
+
+        // Wait for a slot. The `Notified` future is created before checking, so a slot freed
+        // in between is not missed.
+        loop {
+            let slot_freed = self.slot_freed.notified();
+            if self.try_start(id) {
+                break;
+            }
+            tokio::select! {
+                _ = slot_freed => {}
+                _ = control.cancel.cancelled() => {
+                    self.finish(id, DownloadJobStatus::Cancelled, None);
+                    return Err(AppError::DownloadCancelled(id));
+                }
+            }
+        }
+        info!("Starting download job {} '{}'", id, request.label);
+        self.emit_queue();
+
+        let job = CURRENT_JOB.scope(control.clone(), job);
+        tokio::pin!(job);
+        let mut ticker = tokio::time::interval(PROGRESS_EMIT_INTERVAL);
+        let mut emitted_bytes = 0;
+        let result = loop {
+            tokio::select! {
+                result = &mut job => break result,
+                _ = ticker.tick() => {
+                    let bytes = control.downloaded_bytes.load(Ordering::Relaxed);
+                    if bytes != emitted_bytes {
+                        emitted_bytes = bytes;
+                        self.emit_queue();
+                    }
+                }
+            }
+        };
+
+        match result {
+            Ok(value) => {
+                self.finish(id, DownloadJobStatus::Completed, None);
+                Ok(value)
+            }
+            // Downloaders may wrap the cancellation error, the token is the reliable signal
+            Err(_) if control.cancel.is_cancelled() => {
+                info!("Download job {} '{}' was cancelled", id, request.label);
+                self.finish(id, DownloadJobStatus::Cancelled, None);
+                Err(AppError::DownloadCancelled(id))
+            }
+            Err(e) => {
+                self.finish(id, DownloadJobStatus::Failed, Some(e.to_string()));
+                Err(e)
+            }
+        }
+    }
+
+    /// Snapshot of the queue including finished jobs, in queue order
+    pub fn list_jobs(&self) -> Vec<DownloadJob> {
+        let inner = self.lock();
+        inner
+            .jobs
+            .iter()
+            .map(|job| {
+                let mut job = job.clone();
+                if let Some(control) = inner.controls.get(&job.id) {
+                    job.downloaded_bytes = control.downloaded_bytes.load(Ordering::Relaxed);
+                    job.downloaded_files = control.downloaded_files.load(Ordering::Relaxed);
+                }
+                job
+            })
+            .collect()
+    }
+
+    /// Pauses a queued or running job. A paused job does not occupy a slot.
+    pub fn pause_job(&self, id: Uuid) -> Result<()> {
+        self.update_job(id, |job, control| match job.status {
+            DownloadJobStatus::Queued | DownloadJobStatus::Running => {
+                job.status = DownloadJobStatus::Paused;
+                control.paused.send_replace(true);
+                Ok(())
+            }
+            DownloadJobStatus::Paused => Ok(()),
+            status => Err(AppError::InvalidOperation(format!(
+                "Download job {} cannot be paused while {:?}",
+                id, status
+            ))),
+        })
+    }
+
+    /// Resumes a paused job. A job that was already running continues right away, even if
+    /// that exceeds the slot limit for a moment.
+    pub fn resume_job(&self, id: Uuid) -> Result<()> {
+        self.update_job(id, |job, control| {
+            if job.status != DownloadJobStatus::Paused {
+                return Ok(());
+            }
+            job.status = if job.started_at.is_some() {
+                DownloadJobStatus::Running
+            } else {
+                DownloadJobStatus::Queued
+            };
+            control.paused.send_replace(false);
+            Ok(())
+        })
+    }
+
+    /// Requests cancellation; the job cleans up and ends at its next checkpoint
+    pub fn cancel_job(&self, id: Uuid) -> Result<()> {
+        self.update_job(id, |job, control| {
+            job.cancel_requested = true;
+            control.cancel.cancel();
+            Ok(())
+        })
+    }
+
+    /// Cancels all unfinished jobs of a profile and returns how many there were
+    pub fn cancel_profile_jobs(&self, profile_id: Uuid) -> usize {
+        let ids: Vec<Uuid> = self
+            .lock()
+            .jobs
+            .iter()
+            .filter(|j| j.profile_id == Some(profile_id) && !j.status.is_finished())
+            .map(|j| j.id)
+            .collect();
+        ids.iter().filter(|id| self.cancel_job(**id).is_ok()).count()
+    }
+
+    pub fn set_job_priority(&self, id: Uuid, priority: DownloadPriority) -> Result<()> {
+        self.update_job(id, |job, _| {
+            job.priority = priority;
+            Ok(())
+        })
+    }
+
+    /// Whether any job is queued, running or paused
+    pub fn has_unfinished_jobs(&self) -> bool {
+        !self.lock().controls.is_empty()
+    }
+
+    /// Drops finished jobs from the queue
+    pub fn clear_finished_jobs(&self) {
+        self.lock().jobs.retain(|j| !j.status.is_finished());
+        self.emit_queue();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueInner> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn update_job<F>(&self, id: Uuid, update: F) -> Result<()>
+    where
+        F: FnOnce(&mut DownloadJob, &JobControl) -> Result<()>,
+    {
+        {
+            let mut inner = self.lock();
+            let control = inner.controls.get(&id).cloned().ok_or_else(|| {
+                AppError::NotFound(format!("No unfinished download job with ID {}", id))
+            })?;
+            let job = inner
+                .jobs
+                .iter_mut()
+                .find(|j| j.id == id)
+                .ok_or_else(|| AppError::NotFound(format!("Download job {}", id)))?;
+            update(job, &control)?;
+        }
+        // Pausing frees a slot, resuming or reprioritizing may change who is next
+        self.slot_freed.notify_waiters();
+        self.emit_queue();
+        Ok(())
+    }
+
+    fn try_start(&self, id: Uuid) -> bool {
+        let mut inner = self.lock();
+        if !may_start(&inner.jobs, id) {
+            return false;
+        }
+        if let Some(job) = inner.jobs.iter_mut().find(|j| j.id == id) {
+            job.status = DownloadJobStatus::Running;
+            job.started_at = Some(Utc::now());
+        }
+        true
+    }
+
+    fn finish(&self, id: Uuid, status: DownloadJobStatus, error: Option<String>) {
+        {
+            let mut inner = self.lock();
+            let control = inner.controls.remove(&id);
+            if let Some(job) = inner.jobs.iter_mut().find(|j| j.id == id) {
+                job.status = status;
+                job.finished_at = Some(Utc::now());
+                job.error = error;
+                if let Some(control) = control {
+                    job.downloaded_bytes = control.downloaded_bytes.load(Ordering::Relaxed);
+                    job.downloaded_files = control.downloaded_files.load(Ordering::Relaxed);
+                }
+            }
+
+            let finished = inner.jobs.iter().filter(|j| j.status.is_finished()).count();
+            let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+            inner.jobs.retain(|j| {
+                if excess > 0 && j.status.is_finished() {
+                    excess -= 1;
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        self.slot_freed.notify_waiters();
+        self.emit_queue();
+    }
+
+    fn emit_queue(&self) {
+        if let Some(app) = &self.app {
+            if let Err(e) = app.emit(QUEUE_UPDATED_EVENT, self.list_jobs()) {
+                warn!("Failed to emit download queue update: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(priority: DownloadPriority, status: DownloadJobStatus) -> DownloadJob {
+        DownloadJob {
+            id: Uuid::new_v4(),
+            kind: DownloadJobKind::Install,
+            label: String::new(),
+            profile_id: None,
+            priority,
+            status,
+            cancel_requested: false,
+            downloaded_bytes: 0,
+            downloaded_files: 0,
+            queued_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn highest_priority_starts_first_then_queue_order() {
+        let first = job(DownloadPriority::Normal, DownloadJobStatus::Queued);
+        let second = job(DownloadPriority::Normal, DownloadJobStatus::Queued);
+        let background = job(DownloadPriority::Background, DownloadJobStatus::Queued);
+        let jobs = vec![background.clone(), first.clone(), second.clone()];
+
+        assert!(may_start(&jobs, first.id));
+        assert!(!may_start(&jobs, second.id));
+        assert!(!may_start(&jobs, background.id));
+    }
+
+    #[test]
+    fn launch_jobs_skip_the_slot_limit() {
+        let launch = job(DownloadPriority::Launch, DownloadJobStatus::Queued);
+        let normal = job(DownloadPriority::Normal, DownloadJobStatus::Queued);
+        let jobs = vec![
+            job(DownloadPriority::Normal, DownloadJobStatus::Running),
+            job(DownloadPriority::Normal, DownloadJobStatus::Running),
+            normal.clone(),
+            launch.clone(),
+        ];
+
+        assert!(may_start(&jobs, launch.id));
+        assert!(!may_start(&jobs, normal.id));
+    }
+
+    #[test]
+    fn background_jobs_wait_for_all_other_work() {
+        let background = job(DownloadPriority::Background, DownloadJobStatus::Queued);
+        let mut jobs = vec![
+            job(DownloadPriority::Normal, DownloadJobStatus::Running),
+            background.clone(),
+        ];
+        assert!(!may_start(&jobs, background.id));
+
+        jobs[0].status = DownloadJobStatus::Paused;
+        assert!(may_start(&jobs, background.id));
+    }
+
+    #[tokio::test]
+    async fn dropped_jobs_do_not_stay_running() {
+        let queue = DownloadQueueManager::new(None);
+        let request = DownloadJobRequest::new(DownloadJobKind::Install, "test");
+        let job = queue.run_job(request, std::future::pending::<Result<()>>());
+        assert!(tokio::time::timeout(Duration::from_millis(50), job)
+            .await
+            .is_err());
+
+        assert!(!queue.has_unfinished_jobs());
+        assert_eq!(queue.list_jobs()[0].status, DownloadJobStatus::Cancelled);
+    }
+}
@@ -1,13 +1,19 @@
 use crate::error::Result;
 use crate::state::process_state::ProcessMetadata;
+use crate::utils::bandwidth_utils::{self, DownloadThroughput};
+use crate::utils::crash_analyzer::CrashAnalysis;
 use dashmap::DashMap;
 use log::info;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::Emitter;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+// Number of events buffered per subscriber before the slowest one starts lagging
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum EventType {
     InstallingJava,
@@ -43,7 +49,7 @@ pub enum EventType {
     MigrationFailed,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, Debug)]
 pub struct EventPayload {
     pub event_id: Uuid,
     pub event_type: EventType,
@@ -53,6 +59,30 @@ pub struct EventPayload {
     pub error: Option<String>,
 }
 
+impl EventType {
+    /// Progress events of these types get throughput and ETA attached
+    fn is_download(&self) -> bool {
+        matches!(
+            self,
+            EventType::InstallingJava
+                | EventType::DownloadingLibraries
+                | EventType::DownloadingAssets
+                | EventType::DownloadingGEGAssets
+                | EventType::DownloadingClient
+                | EventType::DownloadingMods
+        )
+    }
+}
+
+/// What the frontend receives: the payload plus throughput for download progress
+#[derive(Serialize, Clone, Debug)]
+struct EmittedEventPayload<'a> {
+    #[serde(flatten)]
+    payload: &'a EventPayload,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    throughput: Option<DownloadThroughput>,
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct MinecraftProcessExitedPayload {
     pub profile_id: Uuid,
@@ -61,6 +91,8 @@ pub struct MinecraftProcessExitedPayload {
     pub success: bool,
     pub process_metadata: Option<ProcessMetadata>,
     pub crash_report_content: Option<String>,
+    /// Known causes found in the crash report and logs (only for unsuccessful exits)
+    pub crash_analysis: Option<CrashAnalysis>,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -80,14 +112,20 @@ struct EventInfo {
 pub struct EventState {
     app: Option<Arc<tauri::AppHandle>>,
     active_events: DashMap<Uuid, EventInfo>,
+    subscribers: broadcast::Sender<EventPayload>,
+    // Start of the current download phase per event type and target, for the ETA
+    download_phases: DashMap<(EventType, Option<Uuid>), std::time::Instant>,
 }
 
 impl EventState {
     pub fn new(app: Option<Arc<tauri::AppHandle>>) -> Self {
         info!("Initializing EventState...");
+        let (subscribers, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
         let state = Self {
             app,
             active_events: DashMap::new(),
+            subscribers,
+            download_phases: DashMap::new(),
         };
         info!("Successfully initialized EventState.");
         state
@@ -106,15 +144,45 @@ impl EventState {
             );
         }
 
+        // Forward to in-process listeners (headless CLI, recorders). No receivers is fine.
+        let _ = self.subscribers.send(payload.clone());
+
         // Emit the event to the frontend
+        let throughput = self.download_throughput(&payload);
         if let Some(app) = &self.app {
-            app.emit("state_event", payload)
-                .map_err(|e| crate::error::AppError::TauriError(e))?;
+            app.emit(
+                "state_event",
+                EmittedEventPayload {
+                    payload: &payload,
+                    throughput,
+                },
+            )
+            .map_err(|e| crate::error::AppError::TauriError(e))?;
         }
 
         Ok(())
     }
 
+    fn download_throughput(&self, payload: &EventPayload) -> Option<DownloadThroughput> {
+        if !payload.event_type.is_download() {
+            return None;
+        }
+        let progress = payload.progress?;
+        let key = (payload.event_type.clone(), payload.target_id);
+        if progress >= 1.0 || payload.error.is_some() {
+            self.download_phases.remove(&key);
+            return Some(bandwidth_utils::throughput_for_progress(
+                1.0,
+                std::time::Instant::now(),
+            ));
+        }
+        let started = *self
+            .download_phases
+            .entry(key)
+            .or_insert_with(std::time::Instant::now);
+        Some(bandwidth_utils::throughput_for_progress(progress, started))
+    }
+
     /// Specific helper to emit a TriggerProfileUpdate event.
     pub async fn trigger_profile_update(&self, profile_id: Uuid) -> Result<()> {
         let payload = EventPayload {
@@ -128,6 +196,12 @@ impl EventState {
         self.emit(payload).await // Use the existing emit method
     }
 
+    /// Subscribes to every event emitted through this `EventState`.
+    /// Used where no webview is listening, e.g. to render progress in a terminal.
+    pub fn subscribe(&self) -> broadcast::Receiver<EventPayload> {
+        self.subscribers.subscribe()
+    }
+
     pub async fn complete_event(&self, event_id: Uuid) -> Result<()> {
         self.active_events.remove(&event_id);
         Ok(())
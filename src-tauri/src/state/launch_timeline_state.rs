@@ -0,0 +1,367 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::Result;
+use crate::state::event_state::{EventPayload, EventState, EventType};
+use crate::state::post_init::PostInitializationHandler;
+use crate::utils::download_utils::DownloadUtils;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::{broadcast, Mutex, Notify, RwLock};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+const LAUNCH_TIMELINES_FILENAME: &str = "launch_timelines.json";
+// Older timelines of a profile are dropped
+const MAX_TIMELINES_PER_PROFILE: usize = 25;
+
+/// One phase of a launch, e.g. `DownloadingLibraries`.
+/// A phase lasts from its first event until the first event of the next phase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchPhase {
+    pub phase: EventType,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// Bytes/files fetched through `DownloadUtils` during the phase. Process-wide counters,
+    /// so parallel launches of different profiles can inflate each other's numbers.
+    pub bytes_downloaded: u64,
+    pub files_downloaded: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchOutcome {
+    /// Everything installed and the game process was started
+    Launched,
+    /// Install-only run (e.g. `geg-cli install`)
+    Installed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchTimeline {
+    pub id: Uuid,
+    pub profile_id: Uuid,
+    pub minecraft_version: String,
+    pub modloader: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub outcome: LaunchOutcome,
+    pub error: Option<String>,
+    pub phases: Vec<LaunchPhase>,
+}
+
+/// Event types that mark a launch phase (everything else, like game output, is ignored)
+fn is_launch_phase(event_type: &EventType) -> bool {
+    !matches!(
+        event_type,
+        EventType::MinecraftOutput
+            | EventType::AccountLogin
+            | EventType::AccountRefresh
+            | EventType::AccountLogout
+            | EventType::ProfileUpdate
+            | EventType::TriggerProfileUpdate
+            | EventType::MinecraftProcessExited
+            | EventType::StarlightSkinUpdated
+            | EventType::Error
+            | EventType::LaunchSuccessful
+            | EventType::CrashReportContentAvailable
+    )
+}
+
+fn millis_between(start: DateTime<Utc>, end: DateTime<Utc>) -> u64 {
+    (end - start).num_milliseconds().max(0) as u64
+}
+
+struct OpenPhase {
+    phase: EventType,
+    start: DateTime<Utc>,
+    bytes_at_start: u64,
+    files_at_start: u64,
+}
+
+struct RecorderState {
+    profile_id: Uuid,
+    events: broadcast::Receiver<EventPayload>,
+    current: Option<OpenPhase>,
+    phases: Vec<LaunchPhase>,
+}
+
+impl RecorderState {
+    /// `received_at` is when the recorder got the event, not when it gets around to applying it.
+    fn apply(&mut self, event: &EventPayload, received_at: DateTime<Utc>) {
+        if event.target_id != Some(self.profile_id) || !is_launch_phase(&event.event_type) {
+            return;
+        }
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|open| open.phase == event.event_type)
+        {
+            return;
+        }
+
+        self.close_current(received_at);
+        let (bytes, files) = DownloadUtils::download_totals();
+        self.current = Some(OpenPhase {
+            phase: event.event_type.clone(),
+            start: received_at,
+            bytes_at_start: bytes,
+            files_at_start: files,
+        });
+    }
+
+    fn close_current(&mut self, end: DateTime<Utc>) {
+        if let Some(open) = self.current.take() {
+            let (bytes, files) = DownloadUtils::download_totals();
+            self.phases.push(LaunchPhase {
+                phase: open.phase,
+                start: open.start,
+                end,
+                duration_ms: millis_between(open.start, end),
+                bytes_downloaded: bytes.saturating_sub(open.bytes_at_start),
+                files_downloaded: files.saturating_sub(open.files_at_start),
+            });
+        }
+    }
+
+    fn drain_pending(&mut self) {
+        loop {
+            match self.events.try_recv() {
+                Ok(event) => self.apply(&event, Utc::now()),
+                Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+                    warn!("[Launch Timeline] Missed {} events while draining", skipped);
+                }
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+/// Records the phases of a single launch from the `EventState` stream.
+///
+/// Subscribes on `start`, so no event emitted afterwards is lost. The background task handles
+/// pending events before the stop signal and drains the rest once stopped. Dropping the recorder
+/// without `finish` (aborted launch) stops the task.
+pub struct LaunchTimelineRecorder {
+    id: Uuid,
+    profile_id: Uuid,
+    minecraft_version: String,
+    modloader: String,
+    start: DateTime<Utc>,
+    stop: Arc<Notify>,
+    task: Option<JoinHandle<RecorderState>>,
+}
+
+impl LaunchTimelineRecorder {
+    pub fn start(
+        event_state: &EventState,
+        profile_id: Uuid,
+        minecraft_version: &str,
+        modloader: &str,
+    ) -> Self {
+        let stop = Arc::new(Notify::new());
+        let mut recorder_state = RecorderState {
+            profile_id,
+            events: event_state.subscribe(),
+            current: None,
+            phases: Vec::new(),
+        };
+
+        let stop_signal = Arc::clone(&stop);
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    biased;
+                    event = recorder_state.events.recv() => match event {
+                        Ok(event) => recorder_state.apply(&event, Utc::now()),
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("[Launch Timeline] Lagged behind, skipped {} events", skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = stop_signal.notified() => {
+                        recorder_state.drain_pending();
+                        break;
+                    }
+                }
+            }
+            recorder_state
+        });
+
+        Self {
+            id: Uuid::new_v4(),
+            profile_id,
+            minecraft_version: minecraft_version.to_string(),
+            modloader: modloader.to_string(),
+            start: Utc::now(),
+            stop,
+            task: Some(task),
+        }
+    }
+
+    pub async fn finish(
+        mut self,
+        outcome: LaunchOutcome,
+        error: Option<String>,
+    ) -> Option<LaunchTimeline> {
+        self.stop.notify_one();
+        let task = self.task.take()?;
+        let mut recorder_state = match task.await {
+            Ok(state) => state,
+            Err(e) => {
+                error!("[Launch Timeline] Recorder task failed: {}", e);
+                return None;
+            }
+        };
+
+        let end = Utc::now();
+        recorder_state.close_current(end);
+
+        Some(LaunchTimeline {
+            id: self.id,
+            profile_id: self.profile_id,
+            minecraft_version: std::mem::take(&mut self.minecraft_version),
+            modloader: std::mem::take(&mut self.modloader),
+            start: self.start,
+            end,
+            duration_ms: millis_between(self.start, end),
+            outcome,
+            error,
+            phases: recorder_state.phases,
+        })
+    }
+}
+
+impl Drop for LaunchTimelineRecorder {
+    fn drop(&mut self) {
+        // Still set if the launch future was dropped before `finish`
+        if let Some(task) = self.task.take() {
+            debug!(
+                "[Launch Timeline] Launch of profile {} ended without finish, stopping recorder",
+                self.profile_id
+            );
+            task.abort();
+        }
+    }
+}
+
+/// Persists launch timelines per profile in `launch_timelines.json` (next to `processes.json`).
+pub struct LaunchTimelineManager {
+    timelines: Arc<RwLock<HashMap<Uuid, Vec<LaunchTimeline>>>>,
+    timelines_path: PathBuf,
+    save_lock: Mutex<()>,
+}
+
+impl LaunchTimelineManager {
+    pub fn new(timelines_path: PathBuf) -> Result<Self> {
+        info!(
+            "LaunchTimelineManager: Initializing with path: {:?} (loading deferred)",
+            timelines_path
+        );
+        Ok(Self {
+            timelines: Arc::new(RwLock::new(HashMap::new())),
+            timelines_path,
+            save_lock: Mutex::new(()),
+        })
+    }
+
+    async fn load_timelines_internal(&self) -> Result<()> {
+        if !self.timelines_path.exists() {
+            info!("Launch timelines file not found, starting empty");
+            return Ok(());
+        }
+
+        let data = fs::read_to_string(&self.timelines_path).await?;
+        match serde_json::from_str::<HashMap<Uuid, Vec<LaunchTimeline>>>(&data) {
+            Ok(loaded) => {
+                info!("Loaded launch timelines for {} profiles", loaded.len());
+                *self.timelines.write().await = loaded;
+            }
+            Err(e) => {
+                error!(
+                    "Failed to parse launch timelines file: {}. Starting empty.",
+                    e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn save_timelines(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+        if let Some(parent_dir) = self.timelines_path.parent() {
+            if !parent_dir.exists() {
+                fs::create_dir_all(parent_dir).await?;
+            }
+        }
+
+        let data = {
+            let timelines = self.timelines.read().await;
+            serde_json::to_string_pretty(&*timelines)?
+        };
+        fs::write(&self.timelines_path, data).await?;
+        debug!("Saved launch timelines to {:?}", self.timelines_path);
+        Ok(())
+    }
+
+    pub async fn add_timeline(&self, timeline: LaunchTimeline) -> Result<()> {
+        info!(
+            "Launch of profile {} took {} ms ({:?}): {}",
+            timeline.profile_id,
+            timeline.duration_ms,
+            timeline.outcome,
+            timeline
+                .phases
+                .iter()
+                .map(|p| format!("{:?}={}ms", p.phase, p.duration_ms))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        {
+            let mut timelines = self.timelines.write().await;
+            let entries = timelines.entry(timeline.profile_id).or_default();
+            entries.push(timeline);
+            if entries.len() > MAX_TIMELINES_PER_PROFILE {
+                let excess = entries.len() - MAX_TIMELINES_PER_PROFILE;
+                entries.drain(..excess);
+            }
+        }
+        self.save_timelines().await
+    }
+
+    /// Most recent timelines of a profile, newest first.
+    pub async fn get_recent_timelines(
+        &self,
+        profile_id: Uuid,
+        limit: usize,
+    ) -> Vec<LaunchTimeline> {
+        self.timelines
+            .read()
+            .await
+            .get(&profile_id)
+            .map(|entries| entries.iter().rev().take(limit).cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl PostInitializationHandler for LaunchTimelineManager {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
+        info!("LaunchTimelineManager: on_state_ready called. Loading timelines...");
+        self.load_timelines_internal().await
+    }
+}
+
+pub fn default_launch_timelines_path() -> PathBuf {
+    LAUNCHER_DIRECTORY
+        .root_dir()
+        .join(LAUNCH_TIMELINES_FILENAME)
+}
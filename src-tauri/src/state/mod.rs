@@ -1,12 +1,17 @@
 pub mod config_state;
 pub mod discord_state;
+pub mod download_queue_state;
 pub mod event_state;
+pub mod launch_timeline_state;
 pub mod norisk_packs_state;
 pub mod norisk_versions_state;
+pub mod play_session_state;
 pub mod post_init;
 pub mod process_state;
 pub mod profile_state;
+pub mod server_status_state;
 pub mod skin_state;
 pub mod state_manager;
+pub mod world_backup_state;
 
 pub use state_manager::State;
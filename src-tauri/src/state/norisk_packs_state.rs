@@ -184,7 +184,7 @@ impl NoriskPackManager {
 
 #[async_trait]
 impl PostInitializationHandler for NoriskPackManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("NoriskPackManager: on_state_ready called. Loading configuration...");
         // Select load path based on experimental mode if accessible
         let load_path = if let Ok(state) = crate::state::state_manager::State::get().await {
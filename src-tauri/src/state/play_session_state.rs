@@ -0,0 +1,370 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::Result;
+use crate::state::post_init::PostInitializationHandler;
+use crate::state::process_state::ProcessMetadata;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tokio::fs;
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+const PLAY_SESSIONS_FILENAME: &str = "play_sessions.json";
+// Older sessions are dropped from the history, the cumulative playtime keeps counting them
+const MAX_SESSIONS_PER_PROFILE: usize = 200;
+// Heartbeats of running sessions are persisted at most this often
+const HEARTBEAT_SAVE_INTERVAL_SECS: i64 = 60;
+
+/// One run of the game, from process start to exit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaySession {
+    /// Same ID as the `ProcessMetadata` of the game process
+    pub id: Uuid,
+    pub profile_id: Uuid,
+    pub start: DateTime<Utc>,
+    /// `None` while the game is still running
+    pub end: Option<DateTime<Utc>>,
+    /// Last time the process was seen alive, used as the end of sessions that ended while
+    /// the launcher was closed
+    pub last_seen: DateTime<Utc>,
+    pub duration_secs: u64,
+    pub exit_code: Option<i32>,
+    pub crashed: bool,
+    /// The end was not observed directly but reconstructed by the periodic process check
+    #[serde(default)]
+    pub reconciled: bool,
+    /// OS process ID of the launcher (GUI or `geg-cli`) that opened the session
+    #[serde(default)]
+    pub launcher_pid: Option<u32>,
+    pub account_uuid: Option<String>,
+    pub account_name: Option<String>,
+    pub minecraft_version: Option<String>,
+    pub modloader: Option<String>,
+    pub modloader_version: Option<String>,
+}
+
+impl PlaySession {
+    fn close(&mut self, end: DateTime<Utc>, exit_code: Option<i32>, crashed: bool) {
+        let end = end.max(self.start);
+        self.end = Some(end);
+        self.last_seen = end;
+        self.duration_secs = (end - self.start).num_seconds().max(0) as u64;
+        self.exit_code = exit_code;
+        self.crashed = crashed;
+    }
+}
+
+/// Session history and cumulative playtime of a single profile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfilePlayHistory {
+    pub total_playtime_secs: u64,
+    pub session_count: u64,
+    pub sessions: Vec<PlaySession>,
+}
+
+impl ProfilePlayHistory {
+    fn add_finished(&mut self, duration_secs: u64) {
+        self.total_playtime_secs += duration_secs;
+        self.session_count += 1;
+    }
+
+    /// Drops the oldest closed sessions beyond the history limit; running sessions are kept.
+    fn trim(&mut self) {
+        let closed = self.sessions.iter().filter(|s| s.end.is_some()).count();
+        let mut excess = closed.saturating_sub(MAX_SESSIONS_PER_PROFILE);
+        if excess == 0 {
+            return;
+        }
+        self.sessions.retain(|s| {
+            if excess > 0 && s.end.is_some() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+/// Persists play sessions per profile in `play_sessions.json`.
+///
+/// Sessions are opened by `ProcessManager::start_process` and closed by its monitor task.
+/// Sessions whose monitor task is gone (launcher closed while playing) are closed by
+/// `ProcessManager::periodic_process_check` via `reconcile`.
+pub struct PlaySessionManager {
+    histories: Arc<RwLock<HashMap<Uuid, ProfilePlayHistory>>>,
+    sessions_path: PathBuf,
+    save_lock: Mutex<()>,
+    last_heartbeat_save: Mutex<DateTime<Utc>>,
+}
+
+impl PlaySessionManager {
+    pub fn new(sessions_path: PathBuf) -> Result<Self> {
+        info!(
+            "PlaySessionManager: Initializing with path: {:?} (loading deferred)",
+            sessions_path
+        );
+        Ok(Self {
+            histories: Arc::new(RwLock::new(HashMap::new())),
+            sessions_path,
+            save_lock: Mutex::new(()),
+            last_heartbeat_save: Mutex::new(DateTime::<Utc>::MIN_UTC),
+        })
+    }
+
+    async fn load_sessions_internal(&self) -> Result<()> {
+        if !self.sessions_path.exists() {
+            info!("Play sessions file not found, starting empty");
+            return Ok(());
+        }
+
+        let data = fs::read_to_string(&self.sessions_path).await?;
+        match serde_json::from_str::<HashMap<Uuid, ProfilePlayHistory>>(&data) {
+            Ok(loaded) => {
+                let open = loaded
+                    .values()
+                    .flat_map(|h| h.sessions.iter())
+                    .filter(|s| s.end.is_none())
+                    .count();
+                info!(
+                    "Loaded play history for {} profiles ({} sessions still open)",
+                    loaded.len(),
+                    open
+                );
+                *self.histories.write().await = loaded;
+            }
+            Err(e) => {
+                error!("Failed to parse play sessions file: {}. Starting empty.", e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn save_sessions(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+        if let Some(parent_dir) = self.sessions_path.parent() {
+            if !parent_dir.exists() {
+                fs::create_dir_all(parent_dir).await?;
+            }
+        }
+
+        let data = {
+            let histories = self.histories.read().await;
+            serde_json::to_string_pretty(&*histories)?
+        };
+        fs::write(&self.sessions_path, data).await?;
+        debug!("Saved play sessions to {:?}", self.sessions_path);
+        Ok(())
+    }
+
+    /// Opens a session for a freshly started game process.
+    pub async fn begin_session(&self, metadata: &ProcessMetadata) -> Result<()> {
+        info!(
+            "Starting play session {} for profile {}",
+            metadata.id, metadata.profile_id
+        );
+        {
+            let mut histories = self.histories.write().await;
+            histories
+                .entry(metadata.profile_id)
+                .or_default()
+                .sessions
+                .push(PlaySession {
+                    id: metadata.id,
+                    profile_id: metadata.profile_id,
+                    start: metadata.start_time,
+                    end: None,
+                    last_seen: metadata.start_time,
+                    duration_secs: 0,
+                    exit_code: None,
+                    crashed: false,
+                    reconciled: false,
+                    launcher_pid: Some(std::process::id()),
+                    account_uuid: metadata.account_uuid.clone(),
+                    account_name: metadata.account_name.clone(),
+                    minecraft_version: metadata.minecraft_version.clone(),
+                    modloader: metadata.modloader.clone(),
+                    modloader_version: metadata.modloader_version.clone(),
+                });
+        }
+        self.save_sessions().await
+    }
+
+    /// Closes the session of a game process. Does nothing if it is already closed or unknown.
+    pub async fn end_session(
+        &self,
+        process_id: Uuid,
+        exit_code: Option<i32>,
+        crashed: bool,
+    ) -> Result<()> {
+        let closed = {
+            let mut histories = self.histories.write().await;
+            Self::close_open_session(&mut histories, process_id, |session| {
+                session.close(Utc::now(), exit_code, crashed);
+            })
+        };
+        match closed {
+            Some(session) => {
+                info!(
+                    "Play session {} of profile {} ended after {}s (exit code {:?}, crashed: {})",
+                    session.id, session.profile_id, session.duration_secs, exit_code, crashed
+                );
+                self.save_sessions().await
+            }
+            None => {
+                debug!("No open play session for process {}", process_id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Called by the periodic process check. `running_process_ids` yields the IDs of all game
+    /// processes still alive; it is taken while the sessions are locked, so a process that
+    /// is registered meanwhile can't have its fresh session closed. Refreshes the heartbeat of
+    /// running sessions and closes every other open session: its process is gone but the exit
+    /// was never observed (e.g. the launcher was closed while playing). Sessions of another
+    /// launcher that is still running (e.g. `geg-cli`) are left to it.
+    pub async fn reconcile<F, Fut>(&self, running_process_ids: F) -> Result<()>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = HashSet<Uuid>>,
+    {
+        let now = Utc::now();
+        let own_pid = std::process::id();
+        let foreign_pids: Vec<u32> = self
+            .histories
+            .read()
+            .await
+            .values()
+            .flat_map(|h| h.sessions.iter())
+            .filter(|s| s.end.is_none())
+            .filter_map(|s| s.launcher_pid.filter(|pid| *pid != own_pid))
+            .collect();
+        let live_launchers = live_pids(&foreign_pids);
+
+        let mut reconciled_count = 0;
+        let mut has_running = false;
+        {
+            let mut histories = self.histories.write().await;
+            let running_process_ids = running_process_ids().await;
+            for history in histories.values_mut() {
+                let mut finished_durations = Vec::new();
+                for session in history.sessions.iter_mut().filter(|s| s.end.is_none()) {
+                    if session
+                        .launcher_pid
+                        .is_some_and(|pid| live_launchers.contains(&pid))
+                    {
+                        continue;
+                    }
+                    if running_process_ids.contains(&session.id) {
+                        session.last_seen = now;
+                        has_running = true;
+                        continue;
+                    }
+                    // Exit code and crash state are unknown at this point
+                    let end = session.last_seen;
+                    session.close(end, None, false);
+                    session.reconciled = true;
+                    warn!(
+                        "Reconciled play session {} of profile {}: process is gone, assuming it ended at {}",
+                        session.id, session.profile_id, end
+                    );
+                    finished_durations.push(session.duration_secs);
+                }
+                if !finished_durations.is_empty() {
+                    reconciled_count += finished_durations.len();
+                    for duration_secs in finished_durations {
+                        history.add_finished(duration_secs);
+                    }
+                    history.trim();
+                }
+            }
+        }
+
+        let mut last_save = self.last_heartbeat_save.lock().await;
+        let heartbeat_due =
+            has_running && (now - *last_save).num_seconds() >= HEARTBEAT_SAVE_INTERVAL_SECS;
+        if reconciled_count > 0 || heartbeat_due {
+            *last_save = now;
+            drop(last_save);
+            self.save_sessions().await?;
+        }
+        Ok(())
+    }
+
+    fn close_open_session(
+        histories: &mut HashMap<Uuid, ProfilePlayHistory>,
+        process_id: Uuid,
+        close: impl FnOnce(&mut PlaySession),
+    ) -> Option<PlaySession> {
+        for history in histories.values_mut() {
+            if let Some(index) = history
+                .sessions
+                .iter()
+                .position(|s| s.id == process_id && s.end.is_none())
+            {
+                close(&mut history.sessions[index]);
+                let session = history.sessions[index].clone();
+                history.add_finished(session.duration_secs);
+                history.trim();
+                return Some(session);
+            }
+        }
+        None
+    }
+
+    /// Cumulative playtime and the most recent sessions of a profile (newest first).
+    pub async fn get_play_history(&self, profile_id: Uuid, limit: usize) -> ProfilePlayHistory {
+        let histories = self.histories.read().await;
+        let Some(history) = histories.get(&profile_id) else {
+            return ProfilePlayHistory::default();
+        };
+        ProfilePlayHistory {
+            total_playtime_secs: history.total_playtime_secs,
+            session_count: history.session_count,
+            sessions: history.sessions.iter().rev().take(limit).cloned().collect(),
+        }
+    }
+
+    /// Cumulative playtime in seconds for every profile that has been played.
+    pub async fn get_total_playtimes(&self) -> HashMap<Uuid, u64> {
+        self.histories
+            .read()
+            .await
+            .iter()
+            .map(|(profile_id, history)| (*profile_id, history.total_playtime_secs))
+            .collect()
+    }
+}
+
+/// The given process IDs that belong to running processes.
+fn live_pids(pids: &[u32]) -> HashSet<u32> {
+    if pids.is_empty() {
+        return HashSet::new();
+    }
+    let sys_pids: Vec<Pid> = pids.iter().map(|pid| Pid::from(*pid as usize)).collect();
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::Some(&sys_pids), false);
+    pids.iter()
+        .copied()
+        .filter(|pid| sys.process(Pid::from(*pid as usize)).is_some())
+        .collect()
+}
+
+#[async_trait]
+impl PostInitializationHandler for PlaySessionManager {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
+        info!("PlaySessionManager: on_state_ready called. Loading play sessions...");
+        self.load_sessions_internal().await
+    }
+}
+
+pub fn default_play_sessions_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.root_dir().join(PLAY_SESSIONS_FILENAME)
+}
@@ -4,5 +4,5 @@ use std::sync::Arc;
 
 #[async_trait]
 pub trait PostInitializationHandler {
-    async fn on_state_ready(&self, app_handle: Arc<tauri::AppHandle>) -> Result<()>;
+    async fn on_state_ready(&self, app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()>;
 }
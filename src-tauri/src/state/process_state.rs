@@ -4,13 +4,15 @@ use crate::state::event_state::{
     EventPayload, EventState, EventType, MinecraftProcessExitedPayload,
 };
 use crate::state::{self, post_init::PostInitializationHandler, State};
+use crate::utils::bandwidth_utils;
+use crate::utils::crash_analyzer::{self, CrashAnalysis};
+use crate::utils::hook_utils::{split_command, HookContext};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use log;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::process::ExitStatus;
 use std::sync::Arc;
@@ -35,7 +37,7 @@ const PROCESSES_FILENAME: &str = "processes.json";
 const NOTIFY_EVENT_CHANNEL_BUFFER: usize = 100;
 
 pub struct ProcessManager {
-    app_handle: Arc<tauri::AppHandle>,
+    app_handle: Option<Arc<tauri::AppHandle>>,
     processes: Arc<RwLock<HashMap<Uuid, Process>>>,
     processes_file_path: PathBuf,
     save_lock: Mutex<()>,
@@ -61,6 +63,8 @@ pub struct ProcessMetadata {
     pub GEG_pack: Option<String>,
     pub profile_name: Option<String>,
     pub post_exit_hook: Option<String>,
+    #[serde(default)]
+    pub environment: HashMap<String, String>, // Profil-Umgebungsvariablen, auch für den Post-Exit-Hook
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -88,7 +92,7 @@ struct CrashReportNotification {
 impl ProcessManager {
     pub async fn new(
         processes_file_path: PathBuf,
-        app_handle: Arc<tauri::AppHandle>,
+        app_handle: Option<Arc<tauri::AppHandle>>,
     ) -> Result<Self> {
         log::info!(
             "Initializing ProcessManager with state file: {:?}",
@@ -106,7 +110,7 @@ impl ProcessManager {
             mpsc::channel::<CrashReportNotification>(NOTIFY_EVENT_CHANNEL_BUFFER);
 
         Ok(Self {
-            app_handle: Arc::clone(&app_handle),
+            app_handle,
             processes,
             processes_file_path,
             save_lock,
@@ -119,7 +123,7 @@ impl ProcessManager {
     }
 
     async fn process_crash_report_events(
-        app_handle: Arc<tauri::AppHandle>,
+        app_handle: Option<Arc<tauri::AppHandle>>,
         mut receiver: mpsc::Receiver<CrashReportNotification>,
     ) {
         log::info!("Starting crash report event processor task.");
@@ -339,6 +343,7 @@ impl ProcessManager {
                     "Created {} active Process entries from loaded metadata.",
                     loaded_count
                 );
+                bandwidth_utils::set_game_running(!processes_map_writer.is_empty());
             }
             Err(e) => {
                 log::error!(
@@ -521,6 +526,7 @@ impl ProcessManager {
         GEG_pack: Option<String>,
         profile_name: Option<String>,
         post_exit_hook: Option<String>,
+        environment: HashMap<String, String>,
     ) -> Result<Uuid> {
         log::info!("Attempting to start process for profile {}", profile_id);
 
@@ -542,6 +548,8 @@ impl ProcessManager {
             command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
         }
 
+        command.envs(&environment);
+
         let mut tokio_command = tokio::process::Command::from(command);
         let mut child = tokio_command.spawn().map_err(|e| {
             log::error!(
@@ -571,6 +579,7 @@ impl ProcessManager {
             GEG_pack,
             profile_name: profile_name.clone(),
             post_exit_hook,
+            environment,
         };
 
         log::info!(
@@ -587,6 +596,7 @@ impl ProcessManager {
         {
             let mut processes_map = self.processes.write().await;
             processes_map.insert(process_id, process_entry);
+            bandwidth_utils::set_game_running(true);
         }
 
         // Watcher für Crash-Reports starten
@@ -650,12 +660,22 @@ impl ProcessManager {
             );
         }
 
-        // Hide main window if configured to do so
-        if let Ok(global_state) = State::get().await {
+        if let Ok(state) = State::get().await {
+            if let Err(e) = state.play_session_manager.begin_session(&metadata).await {
+                log::error!(
+                    "Failed to record play session start for process {}: {}",
+                    process_id,
+                    e
+                );
+            }
+        }
+
+        // Hide main window if configured to do so (only when running with a UI)
+        if let (Ok(global_state), Some(app_handle)) = (State::get().await, &self.app_handle) {
             let launcher_config = global_state.config_manager.get_config().await;
             if launcher_config.hide_on_process_start {
                 log::info!("Hiding main window as configured (hide_on_process_start = true)");
-                if let Some(main_window) = self.app_handle.get_webview_window("main") {
+                if let Some(main_window) = app_handle.get_webview_window("main") {
                     if let Err(e) = main_window.hide() {
                         log::error!("Failed to hide main window: {}", e);
                     } else {
@@ -667,7 +687,7 @@ impl ProcessManager {
             } else {
                 log::debug!("Main window hiding disabled (hide_on_process_start = false)");
             }
-        } else {
+        } else if self.app_handle.is_some() {
             log::error!("Could not get global state to check hide_on_process_start setting");
         }
 
@@ -688,7 +708,7 @@ impl ProcessManager {
         // Alternative: Der Monitor-Task entfernt nur aus processes, und periodic_process_check räumt Watcher auf.
         // ODER: Der Monitor Task sendet eine "ProcessEnded" Nachricht, auf die der PM reagiert.
 
-        let app_handle_clone_for_monitor = Arc::clone(&self.app_handle);
+        let app_handle_clone_for_monitor = self.app_handle.clone();
 
         tokio::spawn(async move {
             // State holen, um Zugriff auf den ProcessManager für das Stoppen des Watchers zu haben.
@@ -766,6 +786,59 @@ impl ProcessManager {
                 }
             };
 
+            // Session abschließen, bevor der Prozess aus der Map entfernt wird
+            if let Ok(state) = &state_for_monitor_res {
+                if let Err(e) = state
+                    .play_session_manager
+                    .end_session(process_id, exit_code, !success)
+                    .await
+                {
+                    log::error!(
+                        "Failed to record play session end for process {}: {}",
+                        process_id,
+                        e
+                    );
+                }
+            }
+
+            // Absturzursache lokal analysieren (Crash-Report, latest.log, hs_err_pid*.log)
+            let crash_analysis: Option<CrashAnalysis> = match &state_for_monitor_res {
+                Ok(state) if !success => {
+                    match state
+                        .profile_manager
+                        .get_profile_instance_path(profile_id)
+                        .await
+                    {
+                        Ok(instance_path) => {
+                            let analysis = crash_analyzer::analyze_instance(
+                                &instance_path,
+                                exiting_process_metadata_clone
+                                    .as_ref()
+                                    .map(|m| m.start_time),
+                                Some(pid),
+                                crash_content_for_payload.clone(),
+                            )
+                            .await;
+                            log::info!(
+                                "Crash analysis for process {} found {} known issue(s)",
+                                process_id,
+                                analysis.diagnoses.len()
+                            );
+                            Some(analysis)
+                        }
+                        Err(e) => {
+                            log::warn!(
+                                "Could not get instance path to analyze crash of process {}: {}",
+                                process_id,
+                                e
+                            );
+                            None
+                        }
+                    }
+                }
+                _ => None,
+            };
+
             // Event an UI senden
             if let Ok(state) = &state_for_monitor_res {
                 // Re-access state for this block, or ensure it's still valid
@@ -776,6 +849,7 @@ impl ProcessManager {
                     success,
                     process_metadata: exiting_process_metadata_clone,
                     crash_report_content: crash_content_for_payload,
+                    crash_analysis,
                 };
                 let specific_payload_json = serde_json::to_string(&specific_payload)
                     .unwrap_or_else(|e| {
@@ -825,6 +899,7 @@ impl ProcessManager {
             );
             let mut processes_map_writer_monitor = processes_arc_clone.write().await;
             let removed_process_metadata = processes_map_writer_monitor.remove(&process_id);
+            bandwidth_utils::set_game_running(!processes_map_writer_monitor.is_empty());
             drop(processes_map_writer_monitor);
 
             if removed_process_metadata.is_none() {
@@ -858,6 +933,15 @@ impl ProcessManager {
                     &removed_process_metadata,
                 )
                 .await;
+
+                // World backups run in the background, the world is unlocked by now
+                let backup_state = state.clone();
+                tokio::spawn(async move {
+                    backup_state
+                        .world_backup_manager
+                        .on_session_end(profile_id)
+                        .await;
+                });
             } else {
                 log::error!("Monitor task for process {} could not get state to stop watcher or save processes.", process_id);
             }
@@ -958,7 +1042,7 @@ impl ProcessManager {
     }
 
     async fn periodic_process_check(
-        app_handle: Arc<tauri::AppHandle>,
+        app_handle: Option<Arc<tauri::AppHandle>>,
         processes_arc: Arc<RwLock<HashMap<Uuid, Process>>>,
         active_watchers_arc: Arc<RwLock<HashMap<Uuid, RecommendedWatcher>>>,
         notify_tx: mpsc::Sender<CrashReportNotification>,
@@ -1054,6 +1138,7 @@ impl ProcessManager {
                 for id in &dead_process_ids_from_map {
                     processes_map_writer.remove(id);
                 }
+                bandwidth_utils::set_game_running(!processes_map_writer.is_empty());
                 drop(processes_map_writer);
                 // Speichere Änderungen an der Prozessliste
                 if let Err(e) = global_state.process_manager.save_processes().await {
@@ -1064,6 +1149,15 @@ impl ProcessManager {
                 }
             }
 
+            // Offene Play-Sessions ohne laufenden Prozess abschließen (z.B. Launcher war beim Spielende geschlossen)
+            if let Err(e) = global_state
+                .play_session_manager
+                .reconcile(|| async { processes_arc.read().await.keys().copied().collect() })
+                .await
+            {
+                log::error!("Periodic check: Failed to reconcile play sessions: {}", e);
+            }
+
             // Jetzt Watcher aufräumen: Entferne Watcher für Prozesse, die nicht mehr in der `processes_arc` Map sind
             // (entweder weil sie gerade entfernt wurden oder nie da waren, aber ein Watcher existiert)
             let mut orphaned_watcher_ids: Vec<Uuid> = Vec::new();
@@ -1481,19 +1575,35 @@ impl ProcessManager {
             }
         };
 
+        let hook_context = HookContext::new(game_directory.clone(), profile.id, profile.name.clone());
+        let mut hook_env = removed_process.metadata.environment.clone();
+        hook_env.extend(hook_context.variables());
+
         // Execute hook without waiting for completion (fire and forget)
         let hook_command = hook.clone();
         let game_dir_clone = game_directory.clone();
         tokio::spawn(async move {
-            let mut cmd = hook_command.split(' ');
-            let command = match cmd.next() {
+            // Placeholders were already expanded when the process was started
+            let hook_argv = match split_command(&hook_command) {
+                Ok(argv) => argv,
+                Err(e) => {
+                    log::error!(
+                        "Invalid post-exit hook for process {}: {}",
+                        process_id,
+                        e
+                    );
+                    return;
+                }
+            };
+            let (command, args) = match hook_argv.split_first() {
                 Some(c) => c,
                 None => return,
             };
 
             match std::process::Command::new(command)
-                .args(cmd.collect::<Vec<&str>>())
+                .args(args)
                 .current_dir(&game_dir_clone)
+                .envs(&hook_env)
                 .spawn()
             {
                 Ok(_) => {
@@ -1515,7 +1625,11 @@ impl ProcessManager {
 
     // Private helper to schedule the auto-opening of the log window
     fn schedule_auto_open_log_window(&self, process_id: Uuid) {
-        let app_handle_clone = Arc::clone(&self.app_handle);
+        // Without an app handle (headless) there is no window to open
+        let app_handle_clone = match &self.app_handle {
+            Some(app) => Arc::clone(app),
+            None => return,
+        };
 
         tokio::spawn(async move {
             match crate::state::State::get().await {
@@ -1560,7 +1674,7 @@ impl ProcessManager {
 
 #[async_trait]
 impl PostInitializationHandler for ProcessManager {
-    async fn on_state_ready(&self, app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         log::info!("ProcessManager: on_state_ready called. Performing post-initialization tasks.");
 
         // For process_crash_report_events: The task requires the receive end of an mpsc channel.
@@ -1575,7 +1689,7 @@ impl PostInitializationHandler for ProcessManager {
 
         let manager_clone_periodic_check_processes = Arc::clone(&self.processes);
         let manager_clone_periodic_check_watchers = Arc::clone(&self.active_watchers);
-        let app_handle_for_periodic_check = Arc::clone(&app_handle);
+        let app_handle_for_periodic_check = app_handle.clone();
         let notify_tx_for_periodic_check = self.notify_event_tx.clone();
 
         tokio::spawn(Self::periodic_process_check(
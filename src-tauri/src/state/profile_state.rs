@@ -2,6 +2,7 @@ use crate::config::LAUNCHER_DIRECTORY;
 use crate::error::AppError;
 use crate::error::Result;
 use crate::integrations::modrinth::{self, ModrinthDependencyType, ModrinthVersion};
+use crate::state::config_state::Hooks;
 use crate::state::post_init::PostInitializationHandler;
 use crate::utils::backup_utils::{self, BackupConfig, safe_write_with_backup};
 use crate::utils::hash_utils;
@@ -257,6 +258,33 @@ pub struct ProfileSettings {
     pub custom_jvm_args: Option<String>, // Zusätzliche JVM-Argumente als String
     #[serde(default)]
     pub quick_play_path: Option<String>, // Quick Play Pfad für direkten Welt-/Server-Start
+    #[serde(default)]
+    pub jvm_preset: JvmPreset, // GC-Preset, abhängig von der Java-Version
+    #[serde(default)]
+    pub env_vars: HashMap<String, String>, // Zusätzliche Umgebungsvariablen für Spiel und Hooks
+    #[serde(default)]
+    pub hooks: Hooks, // Überschreibt die globalen Hooks, leerer String deaktiviert sie
+    #[serde(default)]
+    pub skip_mod_validation: bool, // Startet auch bei fehlenden Abhängigkeiten oder Konflikten zwischen Mods
+}
+
+/// Garbage-collector presets for the game JVM.
+/// The concrete flags depend on the Java major version, see `minecraft::launch::jvm_presets`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum JvmPreset {
+    /// No GC flags at all, the JVM picks its defaults
+    None,
+    /// The G1 tuning the launcher always used
+    #[default]
+    G1Default,
+    /// Aikar's well-known G1 flags
+    Aikar,
+    /// Generational ZGC on Java 21+, plain ZGC on 15-20
+    GenerationalZgc,
+    Shenandoah,
+    /// Only the profile's `custom_jvm_args` decide the GC
+    Custom,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -3139,7 +3167,7 @@ impl ProfileManager {
 
 #[async_trait]
 impl PostInitializationHandler for ProfileManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("ProfileManager: on_state_ready called. Loading profiles...");
         // PRIORITY 0: Create backup BEFORE ANYTHING else (including loading)
         info!("ProfileManager: Creating pre-load backup of profiles.json...");
@@ -3239,6 +3267,10 @@ impl Default for ProfileSettings {
             extra_game_args: Vec::new(),
             custom_jvm_args: None, // Standardmäßig keine benutzerdefinierten JVM-Args
             quick_play_path: None,
+            jvm_preset: JvmPreset::default(),
+            env_vars: HashMap::new(),
+            hooks: Hooks::default(),
+            skip_mod_validation: false,
         }
     }
 }
@@ -0,0 +1,353 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::Result;
+use crate::state::post_init::PostInitializationHandler;
+use crate::state::state_manager::State;
+use crate::utils::mc_utils::{self, ServerPingInfo};
+use crate::utils::server_list_utils::{self, ServerListEntry};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use futures::stream::{self, StreamExt};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::Emitter;
+use tokio::fs;
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::{sleep, timeout, Duration};
+use uuid::Uuid;
+
+const SERVER_STATUS_HISTORY_FILENAME: &str = "server_status_history.json";
+const SERVER_STATUS_UPDATED_EVENT: &str = "server_status_updated";
+// Servers pinged at the same time during a batch
+const MAX_CONCURRENT_PINGS: usize = 8;
+// Covers DNS, the modern ping and the legacy fallback of one server
+const PING_TIMEOUT_SECS: u64 = 15;
+// Lower bound of the configured background refresh interval
+pub const MIN_REFRESH_INTERVAL_MINUTES: u32 = 1;
+// Samples older than this are dropped when new ones are recorded
+const HISTORY_RETENTION_DAYS: i64 = 7;
+
+/// One ping result as kept in the history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerStatusSample {
+    pub timestamp: DateTime<Utc>,
+    pub online: bool,
+    pub latency_ms: Option<u64>,
+    pub players_online: Option<u32>,
+    pub players_max: Option<u32>,
+}
+
+impl ServerStatusSample {
+    fn from_ping(timestamp: DateTime<Utc>, status: &ServerPingInfo) -> Self {
+        Self {
+            timestamp,
+            online: status.error.is_none(),
+            latency_ms: status.latency_ms,
+            players_online: status.players_online,
+            players_max: status.players_max,
+        }
+    }
+}
+
+/// The latest status of a server of a profile's server list.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerStatusEntry {
+    pub name: String,
+    pub address: String,
+    pub status: ServerPingInfo,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Payload of the `server_status_updated` event, sent after each batch per profile.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileServerStatuses {
+    pub profile_id: Uuid,
+    pub servers: Vec<ServerStatusEntry>,
+}
+
+/// Batch pings of the servers in the profiles' server lists.
+///
+/// The latest results are kept in memory, latency and player counts of every ping are
+/// stored per address in `server_status_history.json`. With `server_status_auto_refresh`
+/// enabled, all server lists are refreshed in the background at the configured interval
+/// and each refresh is pushed as event. Otherwise servers are only pinged on request.
+pub struct ServerStatusManager {
+    /// Keyed by normalized address, so profiles that list the same server share it
+    history: Arc<RwLock<HashMap<String, Vec<ServerStatusSample>>>>,
+    latest: Arc<RwLock<HashMap<String, (ServerPingInfo, DateTime<Utc>)>>>,
+    history_path: PathBuf,
+    save_lock: Mutex<()>,
+    app: Option<Arc<tauri::AppHandle>>,
+}
+
+impl ServerStatusManager {
+    pub fn new(history_path: PathBuf, app: Option<Arc<tauri::AppHandle>>) -> Result<Self> {
+        info!(
+            "ServerStatusManager: Initializing with path: {:?} (loading deferred)",
+            history_path
+        );
+        Ok(Self {
+            history: Arc::new(RwLock::new(HashMap::new())),
+            latest: Arc::new(RwLock::new(HashMap::new())),
+            history_path,
+            save_lock: Mutex::new(()),
+            app,
+        })
+    }
+
+    async fn load_history_internal(&self) -> Result<()> {
+        if !self.history_path.exists() {
+            info!("Server status history file not found, starting empty");
+            return Ok(());
+        }
+
+        let data = fs::read_to_string(&self.history_path).await?;
+        match serde_json::from_str::<HashMap<String, Vec<ServerStatusSample>>>(&data) {
+            Ok(loaded) => {
+                info!("Loaded server status history of {} servers", loaded.len());
+                *self.history.write().await = loaded;
+            }
+            Err(e) => {
+                error!(
+                    "Failed to parse server status history file: {}. Starting empty.",
+                    e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn save_history(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+        if let Some(parent_dir) = self.history_path.parent() {
+            if !parent_dir.exists() {
+                fs::create_dir_all(parent_dir).await?;
+            }
+        }
+
+        let data = {
+            let history = self.history.read().await;
+            serde_json::to_string(&*history)?
+        };
+        fs::write(&self.history_path, data).await?;
+        debug!("Saved server status history to {:?}", self.history_path);
+        Ok(())
+    }
+
+    /// Pings every address once, `MAX_CONCURRENT_PINGS` at a time. SRV records are
+    /// resolved and pre-1.7 servers answered through the legacy ping by `ping_server_status`.
+    async fn ping_addresses(&self, addresses: Vec<String>) {
+        if addresses.is_empty() {
+            return;
+        }
+        let results: Vec<(String, ServerPingInfo)> = stream::iter(addresses)
+            .map(|address| async move {
+                let status = match timeout(
+                    Duration::from_secs(PING_TIMEOUT_SECS),
+                    mc_utils::ping_server_status(&address),
+                )
+                .await
+                {
+                    Ok(status) => status,
+                    Err(_) => ServerPingInfo::error(
+                        &address,
+                        format!("Ping timed out after {}s", PING_TIMEOUT_SECS),
+                        None,
+                    ),
+                };
+                (address, status)
+            })
+            .buffer_unordered(MAX_CONCURRENT_PINGS)
+            .collect()
+            .await;
+
+        let checked_at = Utc::now();
+        let cutoff = checked_at - ChronoDuration::days(HISTORY_RETENTION_DAYS);
+        {
+            let mut history = self.history.write().await;
+            let mut latest = self.latest.write().await;
+            for (address, status) in results {
+                let key = server_list_utils::normalize_address(&address);
+                let samples = history.entry(key.clone()).or_default();
+                samples.retain(|sample| sample.timestamp >= cutoff);
+                samples.push(ServerStatusSample::from_ping(checked_at, &status));
+                latest.insert(key, (status, checked_at));
+            }
+            // Drops servers that were not pinged during the whole retention period
+            history.retain(|_, samples| samples.last().is_some_and(|s| s.timestamp >= cutoff));
+        }
+        if let Err(e) = self.save_history().await {
+            warn!("Failed to save server status history: {}", e);
+        }
+    }
+
+    /// One address per server, profiles often list the same server in different spellings.
+    fn unique_addresses<'a>(servers: impl IntoIterator<Item = &'a ServerListEntry>) -> Vec<String> {
+        let mut addresses: HashMap<String, String> = HashMap::new();
+        for server in servers {
+            addresses
+                .entry(server_list_utils::normalize_address(&server.address))
+                .or_insert_with(|| server.address.clone());
+        }
+        addresses.into_values().collect()
+    }
+
+    /// Visible servers of a profile's list, hidden "Direct Connect" entries are skipped.
+    async fn visible_servers(profile_id: Uuid) -> Result<Vec<ServerListEntry>> {
+        Ok(server_list_utils::list_servers(profile_id)
+            .await?
+            .into_iter()
+            .filter(|server| !server.hidden && !server.address.trim().is_empty())
+            .collect())
+    }
+
+    async fn entries_for(&self, servers: &[ServerListEntry]) -> Vec<ServerStatusEntry> {
+        let latest = self.latest.read().await;
+        servers
+            .iter()
+            .filter_map(|server| {
+                let (status, checked_at) =
+                    latest.get(&server_list_utils::normalize_address(&server.address))?;
+                Some(ServerStatusEntry {
+                    name: server.name.clone(),
+                    address: server.address.clone(),
+                    status: status.clone(),
+                    checked_at: *checked_at,
+                })
+            })
+            .collect()
+    }
+
+    fn emit_statuses(&self, statuses: &ProfileServerStatuses) {
+        if let Some(app) = &self.app {
+            if let Err(e) = app.emit(SERVER_STATUS_UPDATED_EVENT, statuses) {
+                warn!("Failed to emit server status update: {}", e);
+            }
+        }
+    }
+
+    /// Pings all servers of a profile's server list and returns their status in list order.
+    pub async fn ping_profile_servers(&self, profile_id: Uuid) -> Result<Vec<ServerStatusEntry>> {
+        let servers = Self::visible_servers(profile_id).await?;
+        info!(
+            "Pinging {} servers of profile {}",
+            servers.len(),
+            profile_id
+        );
+        self.ping_addresses(Self::unique_addresses(&servers)).await;
+
+        let statuses = ProfileServerStatuses {
+            profile_id,
+            servers: self.entries_for(&servers).await,
+        };
+        self.emit_statuses(&statuses);
+        Ok(statuses.servers)
+    }
+
+    /// Last known status of a profile's servers without pinging. Servers that were not
+    /// pinged yet are missing.
+    pub async fn get_profile_statuses(&self, profile_id: Uuid) -> Result<Vec<ServerStatusEntry>> {
+        let servers = Self::visible_servers(profile_id).await?;
+        Ok(self.entries_for(&servers).await)
+    }
+
+    /// History of a server, oldest first, optionally only samples after `since`.
+    pub async fn get_history(
+        &self,
+        address: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Vec<ServerStatusSample> {
+        self.history
+            .read()
+            .await
+            .get(&server_list_utils::normalize_address(address))
+            .map(|samples| {
+                samples
+                    .iter()
+                    .filter(|sample| since.map_or(true, |since| sample.timestamp > since))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Pings the servers of all profiles, each address once, and emits one event per
+    /// profile that has servers.
+    pub async fn refresh_all(&self) -> Result<()> {
+        let state = State::get().await?;
+        let mut profile_servers = Vec::new();
+        for profile in state.profile_manager.list_profiles().await? {
+            match Self::visible_servers(profile.id).await {
+                Ok(servers) if !servers.is_empty() => profile_servers.push((profile.id, servers)),
+                Ok(_) => {}
+                Err(e) => warn!(
+                    "Skipping server list of profile '{}' in status refresh: {}",
+                    profile.name, e
+                ),
+            }
+        }
+
+        let addresses =
+            Self::unique_addresses(profile_servers.iter().flat_map(|(_, servers)| servers));
+        debug!(
+            "Refreshing status of {} servers in {} profiles",
+            addresses.len(),
+            profile_servers.len()
+        );
+        self.ping_addresses(addresses).await;
+
+        for (profile_id, servers) in profile_servers {
+            let statuses = ProfileServerStatuses {
+                profile_id,
+                servers: self.entries_for(&servers).await,
+            };
+            self.emit_statuses(&statuses);
+        }
+        Ok(())
+    }
+
+    /// Re-reads the config before every round, so toggling the setting or changing the
+    /// interval takes effect without a restart.
+    async fn periodic_refresh() {
+        loop {
+            let state = match State::get().await {
+                Ok(state) => state,
+                Err(e) => {
+                    error!("Server status refresh could not get state: {}", e);
+                    return;
+                }
+            };
+            let config = state.config_manager.get_config().await;
+            if config.server_status_auto_refresh {
+                if let Err(e) = state.server_status_manager.refresh_all().await {
+                    warn!("Server status refresh failed: {}", e);
+                }
+            }
+            let minutes = config
+                .server_status_refresh_interval_minutes
+                .max(MIN_REFRESH_INTERVAL_MINUTES);
+            sleep(Duration::from_secs(u64::from(minutes) * 60)).await;
+        }
+    }
+}
+
+#[async_trait]
+impl PostInitializationHandler for ServerStatusManager {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
+        info!("ServerStatusManager: on_state_ready called. Loading history...");
+        self.load_history_internal().await?;
+        // Headless runs have nobody to show the results to
+        if self.app.is_some() {
+            tokio::spawn(Self::periodic_refresh());
+        }
+        Ok(())
+    }
+}
+
+pub fn default_server_status_history_path() -> PathBuf {
+    LAUNCHER_DIRECTORY
+        .root_dir()
+        .join(SERVER_STATUS_HISTORY_FILENAME)
+}
@@ -226,7 +226,7 @@ impl SkinManager {
 
 #[async_trait]
 impl PostInitializationHandler for SkinManager {
-    async fn on_state_ready(&self, _app_handle: Arc<tauri::AppHandle>) -> Result<()> {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         info!("SkinManager: on_state_ready called. Loading skins...");
         self.load_skins_internal().await?;
         info!("SkinManager: Successfully loaded skins in on_state_ready.");
@@ -3,13 +3,18 @@ use crate::error::{AppError, Result};
 use crate::minecraft::minecraft_auth::MinecraftAuthStore;
 use crate::state::config_state::ConfigManager;
 use crate::state::discord_state::DiscordManager;
+use crate::state::download_queue_state::DownloadQueueManager;
 use crate::state::event_state::{EventPayload, EventState};
+use crate::state::launch_timeline_state::{default_launch_timelines_path, LaunchTimelineManager};
 use crate::state::norisk_packs_state::{default_norisk_packs_path, NoriskPackManager};
 use crate::state::norisk_versions_state::{default_norisk_versions_path, NoriskVersionManager};
+use crate::state::play_session_state::{default_play_sessions_path, PlaySessionManager};
 use crate::state::post_init::PostInitializationHandler;
 use crate::state::process_state::{default_processes_path, ProcessManager};
 use crate::state::profile_state::ProfileManager;
+use crate::state::server_status_state::{default_server_status_history_path, ServerStatusManager};
 use crate::state::skin_state::{default_skins_path, SkinManager};
+use crate::state::world_backup_state::{default_world_backups_path, WorldBackupManager};
 use std::sync::Arc;
 use tokio::sync::{OnceCell, Semaphore};
 
@@ -22,6 +27,11 @@ pub struct State {
     pub profile_manager: ProfileManager,
     pub event_state: EventState,
     pub process_manager: ProcessManager,
+    pub download_queue: DownloadQueueManager,
+    pub launch_timeline_manager: LaunchTimelineManager,
+    pub play_session_manager: PlaySessionManager,
+    pub world_backup_manager: WorldBackupManager,
+    pub server_status_manager: ServerStatusManager,
     pub minecraft_account_manager_v2: MinecraftAuthStore,
     pub GEG_pack_manager: NoriskPackManager,
     pub GEG_version_manager: NoriskVersionManager,
@@ -32,21 +42,28 @@ pub struct State {
 }
 
 impl State {
-    // Initialize the global state
-    pub async fn init(app: Arc<tauri::AppHandle>) -> Result<()> {
+    // Initialize the global state.
+    // `app` is `None` when running headless (e.g. the `geg-cli` binary); events are then only
+    // delivered to `EventState` subscribers instead of the webview.
+    pub async fn init(app: Option<Arc<tauri::AppHandle>>) -> Result<()> {
         let initial_state_arc = LAUNCHER_STATE
             .get_or_try_init(|| async {
                 log::info!("State::init - Starting primary initialization of managers (Phase 1 - Lightweight Instantiation)...");
                 let config_manager = ConfigManager::new()?;
                 let discord_manager = DiscordManager::new(false).await?;
                 let io_semaphore = Arc::new(Semaphore::new(10));
-                let event_state = EventState::new(Some(app.clone()));
+                let event_state = EventState::new(app.clone());
+                let download_queue = DownloadQueueManager::new(app.clone());
                 let minecraft_account_manager_v2 = MinecraftAuthStore::new().await?;
                 let GEG_pack_manager = NoriskPackManager::new(default_norisk_packs_path())?;
                 let GEG_version_manager = NoriskVersionManager::new(default_norisk_versions_path())?;
                 let skin_manager = SkinManager::new(default_skins_path())?;
                 let profile_manager = ProfileManager::new(LAUNCHER_DIRECTORY.root_dir().join("profiles.json"))?;
                 let process_manager = ProcessManager::new(default_processes_path(), app.clone()).await?;
+                let launch_timeline_manager = LaunchTimelineManager::new(default_launch_timelines_path())?;
+                let play_session_manager = PlaySessionManager::new(default_play_sessions_path())?;
+                let world_backup_manager = WorldBackupManager::new(default_world_backups_path())?;
+                let server_status_manager = ServerStatusManager::new(default_server_status_history_path(), app.clone())?;
 
                 log::info!("State::init - Primary initialization of managers complete (Phase 1). Constructing State struct with initialized: false.");
                 Ok::<Arc<State>, AppError>(Arc::new(Self {
@@ -54,6 +71,11 @@ impl State {
                     profile_manager,
                     event_state,
                     process_manager,
+                    download_queue,
+                    launch_timeline_manager,
+                    play_session_manager,
+                    world_backup_manager,
+                    server_status_manager,
                     minecraft_account_manager_v2,
                     GEG_pack_manager,
                     GEG_version_manager,
@@ -110,18 +132,43 @@ impl State {
             .await?;
         log::info!("State::init - ProfileManager post-initialization complete.");
 
+        // Before the ProcessManager, whose periodic check reconciles open sessions
+        initial_state_arc
+            .play_session_manager
+            .on_state_ready(app.clone())
+            .await?;
+        log::info!("State::init - PlaySessionManager post-initialization complete.");
+
         initial_state_arc
             .process_manager
             .on_state_ready(app.clone())
             .await?;
         log::info!("State::init - ProcessManager post-initialization complete.");
 
+        initial_state_arc
+            .launch_timeline_manager
+            .on_state_ready(app.clone())
+            .await?;
+        log::info!("State::init - LaunchTimelineManager post-initialization complete.");
+
         initial_state_arc
             .skin_manager
             .on_state_ready(app.clone())
             .await?;
         log::info!("State::init - SkinManager post-initialization complete.");
 
+        initial_state_arc
+            .world_backup_manager
+            .on_state_ready(app.clone())
+            .await?;
+        log::info!("State::init - WorldBackupManager post-initialization complete.");
+
+        initial_state_arc
+            .server_status_manager
+            .on_state_ready(app.clone())
+            .await?;
+        log::info!("State::init - ServerStatusManager post-initialization complete.");
+
         initial_state_arc
             .GEG_pack_manager
             .print_current_config()
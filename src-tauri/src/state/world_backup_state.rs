@@ -0,0 +1,805 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::state::post_init::PostInitializationHandler;
+use crate::state::state_manager::State;
+use crate::utils::world_utils::{self, WorldArchiveFormat};
+use crate::utils::{backup_utils, trash_utils};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDateTime, Utc};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::{interval, Duration};
+use uuid::Uuid;
+
+const WORLD_BACKUPS_FILENAME: &str = "world_backups.json";
+const WORLD_BACKUP_DIR: &str = "worlds";
+const WORLD_TRASH_CATEGORY: &str = "worlds";
+// How often the scheduler looks for worlds whose backup interval has passed
+const SCHEDULE_CHECK_INTERVAL_SECS: u64 = 10 * 60;
+// Backup files are named `<timestamp>_<trigger>.<zip|tar.zst>`
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S_%3f";
+const BACKUP_TIMESTAMP_LEN: usize = 19;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldBackupTrigger {
+    Manual,
+    SessionEnd,
+    Schedule,
+    /// Taken of the current world right before a backup is restored over it
+    PreRestore,
+    /// Taken right before level.dat is edited
+    PreEdit,
+}
+
+impl WorldBackupTrigger {
+    fn as_str(&self) -> &'static str {
+        match self {
+            WorldBackupTrigger::Manual => "manual",
+            WorldBackupTrigger::SessionEnd => "session_end",
+            WorldBackupTrigger::Schedule => "schedule",
+            WorldBackupTrigger::PreRestore => "pre_restore",
+            WorldBackupTrigger::PreEdit => "pre_edit",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "manual" => Some(WorldBackupTrigger::Manual),
+            "session_end" => Some(WorldBackupTrigger::SessionEnd),
+            "schedule" => Some(WorldBackupTrigger::Schedule),
+            "pre_restore" => Some(WorldBackupTrigger::PreRestore),
+            "pre_edit" => Some(WorldBackupTrigger::PreEdit),
+            _ => None,
+        }
+    }
+}
+
+/// Which automatic backups survive pruning. Manual backups are never pruned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldBackupRetention {
+    /// The newest N backups are always kept (at least one)
+    pub keep_last: usize,
+    /// Keeps the newest backup of each of the last N days that have backups
+    pub keep_daily: usize,
+    /// Keeps the newest backup of each of the last N weeks that have backups
+    pub keep_weekly: usize,
+}
+
+impl Default for WorldBackupRetention {
+    fn default() -> Self {
+        Self {
+            keep_last: 5,
+            keep_daily: 7,
+            keep_weekly: 4,
+        }
+    }
+}
+
+/// Backup settings of one profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldBackupPolicy {
+    #[serde(default)]
+    pub format: WorldArchiveFormat,
+    #[serde(default)]
+    pub backup_on_session_end: bool,
+    /// Hours between scheduled backups, `None` disables the schedule
+    #[serde(default)]
+    pub interval_hours: Option<u32>,
+    /// World folders that are backed up automatically, `None` means all worlds
+    #[serde(default)]
+    pub worlds: Option<Vec<String>>,
+    #[serde(default)]
+    pub retention: WorldBackupRetention,
+}
+
+impl Default for WorldBackupPolicy {
+    fn default() -> Self {
+        Self {
+            format: WorldArchiveFormat::Zip,
+            backup_on_session_end: false,
+            interval_hours: None,
+            worlds: None,
+            retention: WorldBackupRetention::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldBackupEntry {
+    pub profile_id: Uuid,
+    pub world_folder: String,
+    pub file_name: String,
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    pub trigger: WorldBackupTrigger,
+    pub format: WorldArchiveFormat,
+    pub size_bytes: u64,
+}
+
+/// Outcome of backing up several worlds of a profile at once.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorldBackupRunResult {
+    pub created: Vec<WorldBackupEntry>,
+    /// Worlds that were skipped because they are open in Minecraft
+    pub locked: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+fn backup_file_name(
+    created_at: DateTime<Utc>,
+    trigger: WorldBackupTrigger,
+    format: WorldArchiveFormat,
+) -> String {
+    format!(
+        "{}_{}.{}",
+        created_at.format(BACKUP_TIMESTAMP_FORMAT),
+        trigger.as_str(),
+        format.extension()
+    )
+}
+
+fn parse_backup_file_name(
+    file_name: &str,
+) -> Option<(DateTime<Utc>, WorldBackupTrigger, WorldArchiveFormat)> {
+    let format = WorldArchiveFormat::from_file_name(file_name)?;
+    let stem = file_name.strip_suffix(&format!(".{}", format.extension()))?;
+    let timestamp = stem.get(..BACKUP_TIMESTAMP_LEN)?;
+    let trigger = stem.get(BACKUP_TIMESTAMP_LEN..)?.strip_prefix('_')?;
+    let created_at = NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT)
+        .ok()?
+        .and_utc();
+    Some((created_at, WorldBackupTrigger::parse(trigger)?, format))
+}
+
+/// Names of the backups `retention` does not keep. `protected` is never pruned,
+/// e.g. the archive a restore reads from.
+fn select_backups_to_prune(
+    backups: &[(String, DateTime<Utc>)],
+    retention: &WorldBackupRetention,
+    protected: Option<&str>,
+) -> Vec<String> {
+    let mut sorted = backups.to_vec();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let mut keep: HashSet<&str> = sorted
+        .iter()
+        .take(retention.keep_last.max(1))
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    let mut days = Vec::new();
+    let mut weeks = Vec::new();
+    for (name, created_at) in &sorted {
+        let day = created_at.date_naive();
+        if days.len() < retention.keep_daily && !days.contains(&day) {
+            days.push(day);
+            keep.insert(name);
+        }
+        let week = created_at.iso_week();
+        let week = (week.year(), week.week());
+        if weeks.len() < retention.keep_weekly && !weeks.contains(&week) {
+            weeks.push(week);
+            keep.insert(name);
+        }
+    }
+
+    keep.extend(protected);
+
+    sorted
+        .iter()
+        .filter(|(name, _)| !keep.contains(name.as_str()))
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// Backups of singleplayer worlds, on demand, after a game session and on a schedule.
+///
+/// Policies are stored per profile in `world_backups.json`, the archives themselves in
+/// `<meta>/backups/worlds/<profile id>/<world folder>/`.
+pub struct WorldBackupManager {
+    policies: Arc<RwLock<HashMap<Uuid, WorldBackupPolicy>>>,
+    policies_path: PathBuf,
+    save_lock: Mutex<()>,
+    /// Serializes backups and restores, the scheduler and session ends may fire at once
+    operation_lock: Mutex<()>,
+}
+
+impl WorldBackupManager {
+    pub fn new(policies_path: PathBuf) -> Result<Self> {
+        info!(
+            "WorldBackupManager: Initializing with path: {:?} (loading deferred)",
+            policies_path
+        );
+        Ok(Self {
+            policies: Arc::new(RwLock::new(HashMap::new())),
+            policies_path,
+            save_lock: Mutex::new(()),
+            operation_lock: Mutex::new(()),
+        })
+    }
+
+    async fn load_policies_internal(&self) -> Result<()> {
+        if !self.policies_path.exists() {
+            info!("World backup policies file not found, starting empty");
+            return Ok(());
+        }
+
+        let data = fs::read_to_string(&self.policies_path).await?;
+        match serde_json::from_str::<HashMap<Uuid, WorldBackupPolicy>>(&data) {
+            Ok(loaded) => {
+                info!("Loaded world backup policies for {} profiles", loaded.len());
+                *self.policies.write().await = loaded;
+            }
+            Err(e) => {
+                error!(
+                    "Failed to parse world backup policies file: {}. Starting empty.",
+                    e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn save_policies(&self) -> Result<()> {
+        let _guard = self.save_lock.lock().await;
+        if let Some(parent_dir) = self.policies_path.parent() {
+            if !parent_dir.exists() {
+                fs::create_dir_all(parent_dir).await?;
+            }
+        }
+
+        let data = {
+            let policies = self.policies.read().await;
+            serde_json::to_string_pretty(&*policies)?
+        };
+        fs::write(&self.policies_path, data).await?;
+        debug!("Saved world backup policies to {:?}", self.policies_path);
+        Ok(())
+    }
+
+    pub async fn get_policy(&self, profile_id: Uuid) -> WorldBackupPolicy {
+        self.policies
+            .read()
+            .await
+            .get(&profile_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub async fn set_policy(&self, profile_id: Uuid, policy: WorldBackupPolicy) -> Result<()> {
+        info!(
+            "Setting world backup policy of profile {}: {:?}",
+            profile_id, policy
+        );
+        self.policies.write().await.insert(profile_id, policy);
+        self.save_policies().await
+    }
+
+    fn backup_dir(profile_id: Uuid, world_folder: &str) -> PathBuf {
+        backup_utils::get_backup_root()
+            .join(WORLD_BACKUP_DIR)
+            .join(profile_id.to_string())
+            .join(world_folder)
+    }
+
+    async fn saves_dir(profile_id: Uuid) -> Result<PathBuf> {
+        let state = State::get().await?;
+        Ok(state
+            .profile_manager
+            .get_profile_instance_path(profile_id)
+            .await?
+            .join("saves"))
+    }
+
+    /// Fails with `WorldLocked` if Minecraft has the world open.
+    async fn ensure_unlocked(
+        profile_id: Uuid,
+        world_folder: &str,
+        world_path: &Path,
+    ) -> Result<()> {
+        match world_utils::check_world_session_lock(world_path).await {
+            Err(AppError::WorldLocked { .. }) => Err(AppError::WorldLocked {
+                profile_id,
+                world_folder: world_folder.to_string(),
+            }),
+            other => other,
+        }
+    }
+
+    /// Folder names of the worlds of a profile, optionally limited to `only`.
+    async fn list_world_folders(profile_id: Uuid, only: Option<&[String]>) -> Result<Vec<String>> {
+        let saves_dir = Self::saves_dir(profile_id).await?;
+        let mut folders = Vec::new();
+        if !saves_dir.is_dir() {
+            return Ok(folders);
+        }
+        let mut entries = fs::read_dir(&saves_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !entry.path().join("level.dat").is_file() {
+                continue;
+            }
+            if only.is_some_and(|only| !only.contains(&name)) {
+                continue;
+            }
+            folders.push(name);
+        }
+        folders.sort();
+        Ok(folders)
+    }
+
+    /// Backs up a single world. Fails with `WorldLocked` while the world is open.
+    pub async fn backup_world(
+        &self,
+        profile_id: Uuid,
+        world_folder: &str,
+        trigger: WorldBackupTrigger,
+    ) -> Result<WorldBackupEntry> {
+        let _guard = self.operation_lock.lock().await;
+        self.backup_world_locked(profile_id, world_folder, trigger)
+            .await
+    }
+
+    async fn backup_world_locked(
+        &self,
+        profile_id: Uuid,
+        world_folder: &str,
+        trigger: WorldBackupTrigger,
+    ) -> Result<WorldBackupEntry> {
+        world_utils::validate_world_folder_name(world_folder)?;
+        let world_path = Self::saves_dir(profile_id).await?.join(world_folder);
+        if !world_path.is_dir() {
+            return Err(AppError::WorldNotFound {
+                profile_id,
+                world_folder: world_folder.to_string(),
+            });
+        }
+        Self::ensure_unlocked(profile_id, world_folder, &world_path).await?;
+
+        let policy = self.get_policy(profile_id).await;
+        let created_at = Utc::now();
+        let file_name = backup_file_name(created_at, trigger, policy.format);
+        let path = Self::backup_dir(profile_id, world_folder).join(&file_name);
+        let size_bytes =
+            world_utils::create_world_archive(&world_path, &path, policy.format).await?;
+        info!(
+            "Backed up world '{}' of profile {} ({:?}, {} bytes)",
+            world_folder, profile_id, trigger, size_bytes
+        );
+
+        // A restore prunes once it has extracted its archive, which pruning could delete
+        if !matches!(
+            trigger,
+            WorldBackupTrigger::Manual | WorldBackupTrigger::PreRestore
+        ) {
+            if let Err(e) = self
+                .prune_backups(profile_id, world_folder, &policy.retention, None)
+                .await
+            {
+                warn!(
+                    "Failed to prune backups of world '{}' in profile {}: {}",
+                    world_folder, profile_id, e
+                );
+            }
+        }
+
+        Ok(WorldBackupEntry {
+            profile_id,
+            world_folder: world_folder.to_string(),
+            file_name,
+            path,
+            created_at,
+            trigger,
+            format: policy.format,
+            size_bytes,
+        })
+    }
+
+    /// Backs up every world of a profile (or the listed ones), skipping open worlds.
+    pub async fn backup_profile_worlds(
+        &self,
+        profile_id: Uuid,
+        only: Option<&[String]>,
+        trigger: WorldBackupTrigger,
+    ) -> Result<WorldBackupRunResult> {
+        let _guard = self.operation_lock.lock().await;
+        let mut result = WorldBackupRunResult::default();
+        for world_folder in Self::list_world_folders(profile_id, only).await? {
+            match self
+                .backup_world_locked(profile_id, &world_folder, trigger)
+                .await
+            {
+                Ok(entry) => result.created.push(entry),
+                Err(AppError::WorldLocked { .. }) => result.locked.push(world_folder),
+                Err(e) => {
+                    error!(
+                        "Failed to back up world '{}' of profile {}: {}",
+                        world_folder, profile_id, e
+                    );
+                    result.failed.push(world_folder);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Backups of a profile, or of one of its worlds, newest first.
+    pub async fn list_backups(
+        &self,
+        profile_id: Uuid,
+        world_folder: Option<&str>,
+    ) -> Result<Vec<WorldBackupEntry>> {
+        let profile_dir = backup_utils::get_backup_root()
+            .join(WORLD_BACKUP_DIR)
+            .join(profile_id.to_string());
+        let mut world_folders = Vec::new();
+        match world_folder {
+            Some(world_folder) => {
+                world_utils::validate_world_folder_name(world_folder)?;
+                world_folders.push(world_folder.to_string());
+            }
+            None if profile_dir.is_dir() => {
+                let mut entries = fs::read_dir(&profile_dir).await?;
+                while let Some(entry) = entries.next_entry().await? {
+                    if entry.path().is_dir() {
+                        world_folders.push(entry.file_name().to_string_lossy().to_string());
+                    }
+                }
+            }
+            None => {}
+        }
+
+        let mut backups = Vec::new();
+        for world_folder in world_folders {
+            let dir = profile_dir.join(&world_folder);
+            if !dir.is_dir() {
+                continue;
+            }
+            let mut entries = fs::read_dir(&dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                // Skips archives that are still being written
+                let Some((created_at, trigger, format)) = parse_backup_file_name(&file_name) else {
+                    continue;
+                };
+                backups.push(WorldBackupEntry {
+                    profile_id,
+                    world_folder: world_folder.clone(),
+                    size_bytes: entry.metadata().await.map(|m| m.len()).unwrap_or(0),
+                    path: entry.path(),
+                    file_name,
+                    created_at,
+                    trigger,
+                    format,
+                });
+            }
+        }
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(backups)
+    }
+
+    async fn prune_backups(
+        &self,
+        profile_id: Uuid,
+        world_folder: &str,
+        retention: &WorldBackupRetention,
+        protected: Option<&str>,
+    ) -> Result<()> {
+        let automatic: Vec<(String, DateTime<Utc>)> = self
+            .list_backups(profile_id, Some(world_folder))
+            .await?
+            .into_iter()
+            .filter(|b| b.trigger != WorldBackupTrigger::Manual)
+            .map(|b| (b.file_name, b.created_at))
+            .collect();
+
+        let dir = Self::backup_dir(profile_id, world_folder);
+        for file_name in select_backups_to_prune(&automatic, retention, protected) {
+            debug!("Pruning world backup {:?}", dir.join(&file_name));
+            fs::remove_file(dir.join(&file_name)).await?;
+        }
+        Ok(())
+    }
+
+    fn backup_path(profile_id: Uuid, world_folder: &str, file_name: &str) -> Result<PathBuf> {
+        world_utils::validate_world_folder_name(world_folder)?;
+        if parse_backup_file_name(file_name).is_none()
+            || file_name.contains('/')
+            || file_name.contains('\\')
+        {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid world backup name: '{}'",
+                file_name
+            )));
+        }
+        let path = Self::backup_dir(profile_id, world_folder).join(file_name);
+        if !path.is_file() {
+            return Err(AppError::Other(format!(
+                "World backup '{}' of '{}' not found",
+                file_name, world_folder
+            )));
+        }
+        Ok(path)
+    }
+
+    /// Restores a backup over its world, or into a new world folder if `new_world_name`
+    /// is given. Overwritten worlds are backed up first and moved into the trash.
+    /// Returns the folder name the backup was restored into.
+    pub async fn restore_backup(
+        &self,
+        profile_id: Uuid,
+        world_folder: &str,
+        file_name: &str,
+        new_world_name: Option<&str>,
+    ) -> Result<String> {
+        let _guard = self.operation_lock.lock().await;
+        let backup_path = Self::backup_path(profile_id, world_folder, file_name)?;
+        let format = WorldArchiveFormat::from_file_name(file_name)
+            .ok_or_else(|| AppError::InvalidInput(format!("Unknown archive: {}", file_name)))?;
+        let saves_dir = Self::saves_dir(profile_id).await?;
+        fs::create_dir_all(&saves_dir).await?;
+
+        let mut backed_up_first = false;
+        let target_folder = match new_world_name {
+            Some(name) => world_utils::find_unique_world_folder_name(&saves_dir, name).await?,
+            None => {
+                let world_path = saves_dir.join(world_folder);
+                if world_path.is_dir() {
+                    Self::ensure_unlocked(profile_id, world_folder, &world_path).await?;
+                    self.backup_world_locked(
+                        profile_id,
+                        world_folder,
+                        WorldBackupTrigger::PreRestore,
+                    )
+                    .await?;
+                    backed_up_first = true;
+                    trash_utils::move_path_to_trash(&world_path, Some(WORLD_TRASH_CATEGORY))
+                        .await?;
+                }
+                world_folder.to_string()
+            }
+        };
+
+        world_utils::extract_world_archive(&backup_path, &saves_dir.join(&target_folder), format)
+            .await?;
+        info!(
+            "Restored world backup '{}' of '{}' into '{}' (profile {})",
+            file_name, world_folder, target_folder, profile_id
+        );
+
+        if backed_up_first {
+            let retention = self.get_policy(profile_id).await.retention;
+            if let Err(e) = self
+                .prune_backups(profile_id, world_folder, &retention, Some(file_name))
+                .await
+            {
+                warn!(
+                    "Failed to prune backups of world '{}' in profile {}: {}",
+                    world_folder, profile_id, e
+                );
+            }
+        }
+        Ok(target_folder)
+    }
+
+    pub async fn delete_backup(
+        &self,
+        profile_id: Uuid,
+        world_folder: &str,
+        file_name: &str,
+    ) -> Result<()> {
+        let path = Self::backup_path(profile_id, world_folder, file_name)?;
+        info!("Deleting world backup {:?}", path);
+        fs::remove_file(&path).await?;
+        Ok(())
+    }
+
+    /// Called by the process monitor once a game process of the profile has exited.
+    pub async fn on_session_end(&self, profile_id: Uuid) {
+        let policy = self.get_policy(profile_id).await;
+        if !policy.backup_on_session_end {
+            return;
+        }
+        info!(
+            "Backing up worlds of profile {} after session end",
+            profile_id
+        );
+        match self
+            .backup_profile_worlds(
+                profile_id,
+                policy.worlds.as_deref(),
+                WorldBackupTrigger::SessionEnd,
+            )
+            .await
+        {
+            Ok(result) => info!(
+                "Session end backup of profile {}: {} created, {} locked, {} failed",
+                profile_id,
+                result.created.len(),
+                result.locked.len(),
+                result.failed.len()
+            ),
+            Err(e) => error!("Session end backup of profile {} failed: {}", profile_id, e),
+        }
+    }
+
+    /// Backs up worlds whose last backup is older than the profile's interval and
+    /// that changed since. Open worlds are left for the session end backup.
+    async fn run_scheduled_backups(&self) {
+        let scheduled: Vec<(Uuid, WorldBackupPolicy)> = self
+            .policies
+            .read()
+            .await
+            .iter()
+            .filter(|(_, policy)| policy.interval_hours.is_some_and(|hours| hours > 0))
+            .map(|(profile_id, policy)| (*profile_id, policy.clone()))
+            .collect();
+
+        for (profile_id, policy) in scheduled {
+            let interval_hours = policy.interval_hours.unwrap_or_default() as i64;
+            let world_folders =
+                match Self::list_world_folders(profile_id, policy.worlds.as_deref()).await {
+                    Ok(world_folders) => world_folders,
+                    Err(e) => {
+                        warn!(
+                            "Scheduled world backup: cannot list worlds of profile {}: {}",
+                            profile_id, e
+                        );
+                        continue;
+                    }
+                };
+
+            for world_folder in world_folders {
+                let last_backup = match self.list_backups(profile_id, Some(&world_folder)).await {
+                    Ok(backups) => backups.first().map(|b| b.created_at),
+                    Err(_) => None,
+                };
+                if let Some(last_backup) = last_backup {
+                    if (Utc::now() - last_backup).num_hours() < interval_hours {
+                        continue;
+                    }
+                    let changed = match Self::saves_dir(profile_id).await {
+                        Ok(saves_dir) => {
+                            fs::metadata(saves_dir.join(&world_folder).join("level.dat"))
+                                .await
+                                .and_then(|m| m.modified())
+                                .map(|modified| DateTime::<Utc>::from(modified) > last_backup)
+                                .unwrap_or(true)
+                        }
+                        Err(_) => false,
+                    };
+                    if !changed {
+                        continue;
+                    }
+                }
+
+                match self
+                    .backup_world(profile_id, &world_folder, WorldBackupTrigger::Schedule)
+                    .await
+                {
+                    Ok(_) => {}
+                    Err(AppError::WorldLocked { .. }) => debug!(
+                        "Scheduled backup of '{}' skipped, the world is open",
+                        world_folder
+                    ),
+                    Err(e) => warn!(
+                        "Scheduled backup of world '{}' in profile {} failed: {}",
+                        world_folder, profile_id, e
+                    ),
+                }
+            }
+        }
+    }
+
+    async fn periodic_schedule_check() {
+        let mut interval = interval(Duration::from_secs(SCHEDULE_CHECK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            match State::get().await {
+                Ok(state) => state.world_backup_manager.run_scheduled_backups().await,
+                Err(e) => error!("World backup scheduler could not get state: {}", e),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl PostInitializationHandler for WorldBackupManager {
+    async fn on_state_ready(&self, _app_handle: Option<Arc<tauri::AppHandle>>) -> Result<()> {
+        info!("WorldBackupManager: on_state_ready called. Loading policies...");
+        self.load_policies_internal().await?;
+        tokio::spawn(async {
+            if let Err(e) = world_utils::remove_stale_import_folders().await {
+                warn!("Failed to clean up interrupted world imports: {}", e);
+            }
+        });
+        tokio::spawn(Self::periodic_schedule_check());
+        Ok(())
+    }
+}
+
+pub fn default_world_backups_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.root_dir().join(WORLD_BACKUPS_FILENAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn backup_file_names_round_trip() {
+        let created_at = Utc.with_ymd_and_hms(2026, 3, 14, 15, 9, 26).unwrap();
+        let name = backup_file_name(
+            created_at,
+            WorldBackupTrigger::SessionEnd,
+            WorldArchiveFormat::TarZst,
+        );
+        assert_eq!(name, "20260314_150926_000_session_end.tar.zst");
+        assert_eq!(
+            parse_backup_file_name(&name),
+            Some((
+                created_at,
+                WorldBackupTrigger::SessionEnd,
+                WorldArchiveFormat::TarZst
+            ))
+        );
+        assert_eq!(parse_backup_file_name(&format!("{}.partial", name)), None);
+    }
+
+    #[test]
+    fn retention_keeps_last_daily_and_weekly_slots() {
+        // Two backups per day at 08:00 and 20:00 over 20 days
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 8, 0, 0).unwrap();
+        let backups: Vec<(String, DateTime<Utc>)> = (0..40)
+            .map(|i| {
+                let at = start + chrono::Duration::hours(12 * i);
+                (format!("b{:02}", i), at)
+            })
+            .collect();
+        let retention = WorldBackupRetention {
+            keep_last: 3,
+            keep_daily: 2,
+            keep_weekly: 3,
+        };
+
+        let pruned = select_backups_to_prune(&backups, &retention, None);
+        let kept: Vec<&str> = backups
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .filter(|name| !pruned.iter().any(|p| p == name))
+            .collect();
+        // The last 3 cover both daily slots, the weekly slots add the newest backup
+        // of the two ISO weeks before
+        assert_eq!(kept, vec!["b21", "b35", "b37", "b38", "b39"]);
+    }
+
+    #[test]
+    fn restore_source_survives_pruning() {
+        let restored = Utc.with_ymd_and_hms(2026, 1, 1, 8, 0, 0).unwrap();
+        let backups = vec![
+            ("restored".to_string(), restored),
+            (
+                "pre_restore".to_string(),
+                restored + chrono::Duration::days(3),
+            ),
+        ];
+        let retention = WorldBackupRetention {
+            keep_last: 1,
+            keep_daily: 0,
+            keep_weekly: 0,
+        };
+
+        assert_eq!(
+            select_backups_to_prune(&backups, &retention, None),
+            vec!["restored"]
+        );
+        assert!(select_backups_to_prune(&backups, &retention, Some("restored")).is_empty());
+    }
+}
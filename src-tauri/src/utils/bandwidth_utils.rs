@@ -0,0 +1,242 @@
+use log::info;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Meter window after which a new throughput sample is taken
+const METER_WINDOW: Duration = Duration::from_millis(1000);
+/// Without new bytes for this long the throughput drops to zero
+const METER_IDLE_RESET: Duration = Duration::from_secs(3);
+
+/// Limits in bytes per second, `None` means unlimited
+#[derive(Debug, Clone, Copy, Default)]
+struct BandwidthLimits {
+    normal: Option<u64>,
+    while_playing: Option<u64>,
+}
+
+/// Token bucket shared by all `DownloadUtils` streams. Chunks larger than the current
+/// balance go into debt, the caller then waits until the debt is paid off.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(now: Instant) -> Self {
+        Self {
+            tokens: 0.0,
+            last_refill: now,
+        }
+    }
+
+    /// Takes `bytes` out of the bucket and returns how long the caller has to wait.
+    /// The bucket holds at most one second worth of tokens as burst.
+    fn reserve(&mut self, bytes: u64, rate: u64, now: Instant) -> Duration {
+        let rate = rate.max(1) as f64;
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.last_refill = now;
+        self.tokens = (self.tokens + elapsed * rate).min(rate);
+        self.tokens -= bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / rate)
+        }
+    }
+}
+
+/// Smoothed bytes per second over everything that went through `throttle`.
+#[derive(Debug)]
+struct ThroughputMeter {
+    window_start: Instant,
+    window_bytes: u64,
+    last_bytes_at: Option<Instant>,
+    bytes_per_second: f64,
+}
+
+impl ThroughputMeter {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            window_bytes: 0,
+            last_bytes_at: None,
+            bytes_per_second: 0.0,
+        }
+    }
+
+    fn record(&mut self, bytes: u64, now: Instant) {
+        if self
+            .last_bytes_at
+            .is_none_or(|last| now.saturating_duration_since(last) > METER_IDLE_RESET)
+        {
+            // Start over after an idle phase instead of averaging with stale samples
+            self.window_start = now;
+            self.window_bytes = 0;
+            self.bytes_per_second = 0.0;
+        }
+        self.last_bytes_at = Some(now);
+        self.window_bytes += bytes;
+
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed >= METER_WINDOW {
+            let sample = self.window_bytes as f64 / elapsed.as_secs_f64();
+            self.bytes_per_second = if self.bytes_per_second == 0.0 {
+                sample
+            } else {
+                self.bytes_per_second * 0.5 + sample * 0.5
+            };
+            self.window_start = now;
+            self.window_bytes = 0;
+        }
+    }
+
+    fn current(&self, now: Instant) -> u64 {
+        match self.last_bytes_at {
+            Some(last) if now.saturating_duration_since(last) <= METER_IDLE_RESET => {
+                self.bytes_per_second as u64
+            }
+            _ => 0,
+        }
+    }
+}
+
+static LIMITS: Lazy<Mutex<BandwidthLimits>> = Lazy::new(|| Mutex::new(BandwidthLimits::default()));
+static GAME_RUNNING: AtomicBool = AtomicBool::new(false);
+static BUCKET: Lazy<Mutex<TokenBucket>> =
+    Lazy::new(|| Mutex::new(TokenBucket::new(Instant::now())));
+static METER: Lazy<Mutex<ThroughputMeter>> =
+    Lazy::new(|| Mutex::new(ThroughputMeter::new(Instant::now())));
+
+/// Throughput information attached to download progress events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadThroughput {
+    pub bytes_per_second: u64,
+    pub eta_seconds: Option<u64>,
+    /// Active limit in bytes per second, `None` if unlimited
+    pub limit_bytes_per_second: Option<u64>,
+}
+
+fn kbps_to_bytes(kbps: Option<u32>) -> Option<u64> {
+    kbps.filter(|limit| *limit > 0)
+        .map(|limit| limit as u64 * 1024)
+}
+
+/// Applies the limits from the launcher config (KB/s, `None` or 0 means unlimited)
+pub fn update_bandwidth_limits(limit_kbps: Option<u32>, while_playing_kbps: Option<u32>) {
+    if let Ok(mut limits) = LIMITS.lock() {
+        *limits = BandwidthLimits {
+            normal: kbps_to_bytes(limit_kbps),
+            while_playing: kbps_to_bytes(while_playing_kbps),
+        };
+        info!(
+            "Download bandwidth limit: {:?} B/s ({:?} B/s while playing)",
+            limits.normal, limits.while_playing
+        );
+    }
+}
+
+/// Called by the process manager whenever the set of running games changes
+pub fn set_game_running(running: bool) {
+    GAME_RUNNING.store(running, Ordering::Relaxed);
+}
+
+/// Limit that applies right now, the "while playing" limit wins while a game is running
+pub fn effective_limit() -> Option<u64> {
+    let limits = LIMITS.lock().map(|l| *l).unwrap_or_default();
+    if GAME_RUNNING.load(Ordering::Relaxed) {
+        limits.while_playing.or(limits.normal)
+    } else {
+        limits.normal
+    }
+}
+
+/// Accounts `bytes` that were just received and waits as long as the global limit requires
+pub async fn throttle(bytes: u64) {
+    let now = Instant::now();
+    if let Ok(mut meter) = METER.lock() {
+        meter.record(bytes, now);
+    }
+
+    let Some(rate) = effective_limit() else {
+        return;
+    };
+    let wait = match BUCKET.lock() {
+        Ok(mut bucket) => bucket.reserve(bytes, rate, now),
+        Err(_) => Duration::ZERO,
+    };
+    if !wait.is_zero() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Current smoothed download rate across all streams
+pub fn current_throughput() -> u64 {
+    METER
+        .lock()
+        .map(|meter| meter.current(Instant::now()))
+        .unwrap_or(0)
+}
+
+/// Throughput plus an ETA extrapolated from the progress made since `started`
+pub fn throughput_for_progress(progress: f64, started: Instant) -> DownloadThroughput {
+    let eta_seconds = if progress > 0.01 && progress < 1.0 {
+        let elapsed = started.elapsed().as_secs_f64();
+        Some((elapsed * (1.0 - progress) / progress).round() as u64)
+    } else {
+        None
+    };
+    DownloadThroughput {
+        bytes_per_second: current_throughput(),
+        eta_seconds,
+        limit_bytes_per_second: effective_limit(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_a_burst_then_waits() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(start);
+
+        // One second of refill at 1000 B/s covers a 1000 byte chunk
+        let later = start + Duration::from_secs(1);
+        assert_eq!(bucket.reserve(1000, 1000, later), Duration::ZERO);
+        // The next 500 bytes go into debt and cost half a second
+        assert_eq!(bucket.reserve(500, 1000, later), Duration::from_millis(500));
+        // Idle time never builds up more than one second of burst
+        let much_later = later + Duration::from_secs(60);
+        assert_eq!(
+            bucket.reserve(1500, 1000, much_later),
+            Duration::from_millis(500)
+        );
+    }
+
+    #[test]
+    fn meter_smooths_and_resets_after_idle() {
+        let start = Instant::now();
+        let mut meter = ThroughputMeter::new(start);
+        meter.record(1000, start);
+        meter.record(1000, start + Duration::from_secs(1));
+        assert_eq!(meter.current(start + Duration::from_secs(1)), 2000);
+
+        meter.record(0, start + Duration::from_secs(2));
+        assert_eq!(meter.current(start + Duration::from_secs(2)), 1000);
+        assert_eq!(meter.current(start + Duration::from_secs(10)), 0);
+    }
+
+    #[test]
+    fn zero_limits_mean_unlimited() {
+        assert_eq!(kbps_to_bytes(Some(0)), None);
+        assert_eq!(kbps_to_bytes(None), None);
+        assert_eq!(kbps_to_bytes(Some(512)), Some(512 * 1024));
+    }
+}
@@ -0,0 +1,629 @@
+//! Local crash analysis: matches crash reports, `latest.log` and JVM `hs_err_pid*.log` files
+//! against known crash signatures and turns them into a diagnosis with suggested actions.
+//!
+//! The built-in rules can be extended or overridden without recompiling by placing a
+//! `crash_rules.json` into the launcher directory:
+//!
+//! ```json
+//! {
+//!   "rules": [
+//!     {
+//!       "id": "my_mod_broken",
+//!       "category": "other",
+//!       "title": "MyMod crashed",
+//!       "pattern": "MyModException: (?P<reason>.+)",
+//!       "description": "MyMod failed: {reason}",
+//!       "suggestions": ["Update MyMod"]
+//!     },
+//!     { "id": "out_of_memory", "enabled": false }
+//!   ]
+//! }
+//! ```
+//!
+//! A rule with the same `id` as a built-in rule replaces it (or disables it with `"enabled": false`).
+//! Named capture groups of `pattern` can be used as `{name}` placeholders in the texts.
+
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+const CRASH_RULES_FILENAME: &str = "crash_rules.json";
+// Only the end of huge logs is relevant for a crash
+const MAX_LOG_BYTES: u64 = 4 * 1024 * 1024;
+// A rule that matches over and over (e.g. log spam) only yields this many diagnoses
+const MAX_MATCHES_PER_RULE: usize = 5;
+const MAX_MATCHED_LINE_LENGTH: usize = 300;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashCategory {
+    MixinFailure,
+    MissingDependency,
+    IncompatibleMods,
+    WrongJava,
+    OutOfMemory,
+    DuplicateMod,
+    GraphicsDriver,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashSource {
+    CrashReport,
+    LatestLog,
+    JvmErrorLog,
+}
+
+/// A known crash signature. All fields except `id` are optional in `crash_rules.json`
+/// when the rule only overrides (or disables) a built-in one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashRule {
+    pub id: String,
+    #[serde(default)]
+    pub category: Option<CrashCategory>,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Regular expression, matched against each source as a whole (use `(?m)` for `^`/`$`)
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub suggestions: Option<Vec<String>>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl CrashRule {
+    fn builtin(
+        id: &str,
+        category: CrashCategory,
+        title: &str,
+        pattern: &str,
+        description: &str,
+        suggestions: &[&str],
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            category: Some(category),
+            title: Some(title.to_string()),
+            pattern: Some(pattern.to_string()),
+            description: Some(description.to_string()),
+            suggestions: Some(suggestions.iter().map(|s| s.to_string()).collect()),
+            enabled: true,
+        }
+    }
+
+    /// Applies the fields set in `other` on top of this rule.
+    fn merge(&mut self, other: CrashRule) {
+        self.category = other.category.or(self.category);
+        self.title = other.title.or(self.title.take());
+        self.pattern = other.pattern.or(self.pattern.take());
+        self.description = other.description.or(self.description.take());
+        self.suggestions = other.suggestions.or(self.suggestions.take());
+        self.enabled = other.enabled;
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CrashRuleFile {
+    #[serde(default)]
+    rules: Vec<CrashRule>,
+}
+
+/// One recognized cause of a crash.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrashDiagnosis {
+    pub rule_id: String,
+    pub category: CrashCategory,
+    pub title: String,
+    pub description: String,
+    pub suggestions: Vec<String>,
+    pub source: CrashSource,
+    /// The (shortened) line the signature was found in
+    pub matched_line: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrashAnalysis {
+    pub diagnoses: Vec<CrashDiagnosis>,
+    pub analyzed_sources: Vec<CrashSource>,
+    pub crash_report_path: Option<PathBuf>,
+}
+
+struct CompiledRule {
+    id: String,
+    category: CrashCategory,
+    title: String,
+    regex: Regex,
+    description: String,
+    suggestions: Vec<String>,
+}
+
+pub struct CrashAnalyzer {
+    rules: Vec<CompiledRule>,
+}
+
+impl CrashAnalyzer {
+    /// Analyzer with the built-in rules merged with the user's `crash_rules.json`.
+    pub async fn load() -> Self {
+        let path = crash_rules_path();
+        let mut rules = builtin_rules();
+        if path.exists() {
+            match fs::read_to_string(&path).await {
+                Ok(data) => match serde_json::from_str::<CrashRuleFile>(&data) {
+                    Ok(file) => {
+                        info!(
+                            "Loaded {} custom crash rules from {:?}",
+                            file.rules.len(),
+                            path
+                        );
+                        merge_rules(&mut rules, file.rules);
+                    }
+                    Err(e) => warn!("Ignoring invalid crash rule file {:?}: {}", path, e),
+                },
+                Err(e) => warn!("Failed to read crash rule file {:?}: {}", path, e),
+            }
+        }
+        Self::from_rules(rules)
+    }
+
+    fn from_rules(rules: Vec<CrashRule>) -> Self {
+        let rules = rules
+            .into_iter()
+            .filter(|rule| rule.enabled)
+            .filter_map(|rule| {
+                let Some(pattern) = rule.pattern.as_deref() else {
+                    warn!("Crash rule '{}' has no pattern, skipping", rule.id);
+                    return None;
+                };
+                let regex = match Regex::new(pattern) {
+                    Ok(regex) => regex,
+                    Err(e) => {
+                        warn!("Crash rule '{}' has an invalid pattern: {}", rule.id, e);
+                        return None;
+                    }
+                };
+                Some(CompiledRule {
+                    category: rule.category.unwrap_or(CrashCategory::Other),
+                    title: rule.title.clone().unwrap_or_else(|| rule.id.clone()),
+                    description: rule.description.unwrap_or_default(),
+                    suggestions: rule.suggestions.unwrap_or_default(),
+                    id: rule.id,
+                    regex,
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Matches all rules against the given texts. Diagnoses are ordered by rule, then source.
+    pub fn analyze(&self, sources: &[(CrashSource, &str)]) -> Vec<CrashDiagnosis> {
+        let mut diagnoses = Vec::new();
+        let mut seen = HashSet::new();
+
+        for rule in &self.rules {
+            let mut matches = 0;
+            'sources: for (source, text) in sources {
+                for captures in rule.regex.captures_iter(text) {
+                    let description = fill_placeholders(&rule.description, &rule.regex, &captures);
+                    // The same problem is usually reported in the crash report and the log
+                    if !seen.insert((rule.id.clone(), description.clone())) {
+                        continue;
+                    }
+
+                    let whole = captures.get(0).expect("group 0 always exists");
+                    diagnoses.push(CrashDiagnosis {
+                        rule_id: rule.id.clone(),
+                        category: rule.category,
+                        title: fill_placeholders(&rule.title, &rule.regex, &captures),
+                        description,
+                        suggestions: rule
+                            .suggestions
+                            .iter()
+                            .map(|s| fill_placeholders(s, &rule.regex, &captures))
+                            .collect(),
+                        source: *source,
+                        matched_line: line_at(text, whole.start()),
+                    });
+
+                    matches += 1;
+                    if matches >= MAX_MATCHES_PER_RULE {
+                        break 'sources;
+                    }
+                }
+            }
+        }
+        diagnoses
+    }
+
+    /// Analyzes the files a game process left behind in its instance directory.
+    /// `crash_report_content` is used when the crash report watcher already captured it,
+    /// otherwise the newest crash report written since `started_at` is read.
+    pub async fn analyze_instance(
+        &self,
+        instance_path: &Path,
+        started_at: Option<DateTime<Utc>>,
+        pid: Option<u32>,
+        crash_report_content: Option<String>,
+    ) -> CrashAnalysis {
+        let mut analysis = CrashAnalysis::default();
+
+        let crash_report = match crash_report_content {
+            Some(content) => Some(content),
+            None => match find_latest_crash_report(instance_path, started_at).await {
+                Some(path) => {
+                    let content = read_tail(&path).await;
+                    analysis.crash_report_path = Some(path);
+                    content
+                }
+                None => None,
+            },
+        };
+        let latest_log = read_tail(&instance_path.join("logs").join("latest.log")).await;
+        let jvm_error_log = match pid {
+            Some(pid) => read_tail(&instance_path.join(format!("hs_err_pid{}.log", pid))).await,
+            None => None,
+        };
+
+        let mut sources: Vec<(CrashSource, &str)> = Vec::new();
+        if let Some(content) = &crash_report {
+            sources.push((CrashSource::CrashReport, content));
+        }
+        if let Some(content) = &jvm_error_log {
+            sources.push((CrashSource::JvmErrorLog, content));
+        }
+        if let Some(content) = &latest_log {
+            sources.push((CrashSource::LatestLog, content));
+        }
+
+        analysis.analyzed_sources = sources.iter().map(|(source, _)| *source).collect();
+        analysis.diagnoses = self.analyze(&sources);
+        debug!(
+            "Crash analysis of {:?}: {} diagnoses from {:?}",
+            instance_path,
+            analysis.diagnoses.len(),
+            analysis.analyzed_sources
+        );
+        analysis
+    }
+}
+
+fn merge_rules(rules: &mut Vec<CrashRule>, custom: Vec<CrashRule>) {
+    for rule in custom {
+        match rules.iter_mut().find(|existing| existing.id == rule.id) {
+            Some(existing) => existing.merge(rule),
+            None => rules.push(rule),
+        }
+    }
+}
+
+/// Replaces `{name}` with the named capture group `name`. `{java_version}` and
+/// `{max_java_version}` are derived from `class_version` / `max_class_version` captures.
+fn fill_placeholders(template: &str, regex: &Regex, captures: &regex::Captures) -> String {
+    let mut result = template.to_string();
+    for name in regex.capture_names().flatten() {
+        let value = captures.name(name).map(|m| m.as_str().trim()).unwrap_or("");
+        result = result.replace(&format!("{{{}}}", name), value);
+
+        let derived = match name {
+            "class_version" => Some("java_version"),
+            "max_class_version" => Some("max_java_version"),
+            _ => None,
+        };
+        if let Some(derived) = derived {
+            // Class file version 52 is Java 8, every release adds one
+            let java_version = value
+                .parse::<u32>()
+                .ok()
+                .filter(|v| *v > 44)
+                .map(|v| (v - 44).to_string())
+                .unwrap_or_default();
+            result = result.replace(&format!("{{{}}}", derived), &java_version);
+        }
+    }
+    result
+}
+
+fn line_at(text: &str, offset: usize) -> String {
+    let start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let end = text[offset..]
+        .find('\n')
+        .map(|i| offset + i)
+        .unwrap_or(text.len());
+    let line = text[start..end].trim();
+    if line.chars().count() > MAX_MATCHED_LINE_LENGTH {
+        let shortened: String = line.chars().take(MAX_MATCHED_LINE_LENGTH).collect();
+        format!("{}...", shortened)
+    } else {
+        line.to_string()
+    }
+}
+
+/// Reads at most the last `MAX_LOG_BYTES` of a file, lossy UTF-8. `None` if it can't be read.
+async fn read_tail(path: &Path) -> Option<String> {
+    let mut file = fs::File::open(path).await.ok()?;
+    let len = file.metadata().await.ok()?.len();
+    if len > MAX_LOG_BYTES {
+        file.seek(std::io::SeekFrom::Start(len - MAX_LOG_BYTES))
+            .await
+            .ok()?;
+    }
+    let mut bytes = Vec::new();
+    if let Err(e) = file.read_to_end(&mut bytes).await {
+        warn!("Failed to read {:?} for crash analysis: {}", path, e);
+        return None;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+async fn find_latest_crash_report(
+    instance_path: &Path,
+    since: Option<DateTime<Utc>>,
+) -> Option<PathBuf> {
+    let mut entries = fs::read_dir(instance_path.join("crash-reports"))
+        .await
+        .ok()?;
+    let mut latest: Option<(std::time::SystemTime, PathBuf)> = None;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("txt") {
+            continue;
+        }
+        let Ok(modified) = entry.metadata().await.and_then(|m| m.modified()) else {
+            continue;
+        };
+        if since.is_some_and(|since| DateTime::<Utc>::from(modified) < since) {
+            continue;
+        }
+        if latest.as_ref().is_none_or(|(time, _)| modified > *time) {
+            latest = Some((modified, path));
+        }
+    }
+    latest.map(|(_, path)| path)
+}
+
+pub fn crash_rules_path() -> PathBuf {
+    LAUNCHER_DIRECTORY.root_dir().join(CRASH_RULES_FILENAME)
+}
+
+/// Convenience wrapper used by the process monitor: loads the rules and analyzes an instance.
+pub async fn analyze_instance(
+    instance_path: &Path,
+    started_at: Option<DateTime<Utc>>,
+    pid: Option<u32>,
+    crash_report_content: Option<String>,
+) -> CrashAnalysis {
+    CrashAnalyzer::load()
+        .await
+        .analyze_instance(instance_path, started_at, pid, crash_report_content)
+        .await
+}
+
+fn builtin_rules() -> Vec<CrashRule> {
+    use CrashCategory::*;
+    vec![
+        CrashRule::builtin(
+            "mixin_apply_failed",
+            MixinFailure,
+            "Mixin of '{mod}' failed to apply",
+            r"Mixin apply for mod (?P<mod>[\w.\-]+) failed (?P<config>[\w.\-]+\.json)",
+            "The mod '{mod}' could not apply its code changes ({config}). It is most likely not compatible with this Minecraft version or with another installed mod.",
+            &[
+                "Update '{mod}' to a version made for this Minecraft version",
+                "Temporarily disable '{mod}' to confirm it causes the crash",
+                "Look for other mods that change the same part of the game",
+            ],
+        ),
+        CrashRule::builtin(
+            "mixin_injection_failed",
+            MixinFailure,
+            "Mixin injection of '{mod}' failed",
+            r"(?:InvalidInjectionException|InjectionError|MixinTransformerError|InvalidMixinException)[^\n]*?from mod (?P<mod>[\w.\-]+)",
+            "The mod '{mod}' failed to inject its code into the game, usually because another mod or a game update changed the targeted code.",
+            &[
+                "Update '{mod}' to the newest version for this Minecraft version",
+                "Temporarily disable '{mod}' to confirm it causes the crash",
+            ],
+        ),
+        CrashRule::builtin(
+            "fabric_missing_dependency",
+            MissingDependency,
+            "'{mod}' is missing a dependency",
+            r"Mod '(?P<mod>[^']+)' \([\w.\-]+\) \S+ requires (?P<requirement>.+?) of (?:mod )?(?P<dependency>'[^']+'(?: \([\w.\-]+\))?|[\w.\-]+), which is missing",
+            "'{mod}' requires {requirement} of {dependency}, which is not installed.",
+            &[
+                "Install {dependency} from the mod browser",
+                "Or remove '{mod}' if you don't need it",
+            ],
+        ),
+        CrashRule::builtin(
+            "fabric_wrong_dependency_version",
+            MissingDependency,
+            "'{mod}' needs a different version of a dependency",
+            r"Mod '(?P<mod>[^']+)' \([\w.\-]+\) \S+ requires (?P<requirement>.+?) of (?:mod )?(?P<dependency>'[^']+'(?: \([\w.\-]+\))?|[\w.\-]+), but only the wrong version is present: (?P<present>[^!\s]+)",
+            "'{mod}' requires {requirement} of {dependency}, but {present} is installed.",
+            &[
+                "Install a matching version of {dependency}",
+                "Or use a version of '{mod}' that supports {dependency} {present}",
+            ],
+        ),
+        CrashRule::builtin(
+            "fabric_incompatible_mods",
+            IncompatibleMods,
+            "'{mod}' is incompatible with another mod",
+            r"Mod '(?P<mod>[^']+)' \([\w.\-]+\) \S+ is incompatible with (?P<other>[^,!\n]+)",
+            "'{mod}' declares that it does not work together with {other}.",
+            &["Remove either '{mod}' or {other} from the profile"],
+        ),
+        CrashRule::builtin(
+            "forge_missing_dependency",
+            MissingDependency,
+            "'{mod}' is missing a dependency",
+            r"Mod ID: '(?P<dependency>[^']+)', Requested by: '(?P<mod>[^']+)', Expected range: '(?P<range>[^']*)', Actual version: '(?P<actual>[^']*)'",
+            "'{mod}' requires '{dependency}' in version range {range}, found: {actual}.",
+            &[
+                "Install a version of '{dependency}' within {range}",
+                "Or remove '{mod}' if you don't need it",
+            ],
+        ),
+        CrashRule::builtin(
+            "duplicate_mod",
+            DuplicateMod,
+            "Mod '{mod_id}' is installed more than once",
+            r"(?i)duplicate mods?(?: found)?:?\s*(?:mod id:?\s*)?'?(?P<mod_id>[\w.\-]+)",
+            "The mod ID '{mod_id}' is provided by more than one installed file.",
+            &["Remove all but one copy of '{mod_id}' (check custom mods and the mods folder)"],
+        ),
+        CrashRule::builtin(
+            "unsupported_class_version",
+            WrongJava,
+            "Java {java_version} is required",
+            r"UnsupportedClassVersionError: (?P<class>\S+) has been compiled by a more recent version of the Java Runtime \(class file version (?P<class_version>\d+)\.\d+\), this version of the Java Runtime only recognizes class file versions up to (?P<max_class_version>\d+)",
+            "{class} needs Java {java_version}, but the game was started with Java {max_java_version}.",
+            &[
+                "Select Java {java_version} or newer in the profile settings",
+                "Or clear the custom Java path so the launcher picks a matching Java version",
+            ],
+        ),
+        CrashRule::builtin(
+            "legacy_loader_on_new_java",
+            WrongJava,
+            "This modloader needs Java 8",
+            r"ClassLoaders\$AppClassLoader cannot be cast to (?:class )?java\.net\.URLClassLoader",
+            "Older modloaders (LaunchWrapper, Forge before 1.17) only run on Java 8.",
+            &["Select a Java 8 installation in the profile settings"],
+        ),
+        CrashRule::builtin(
+            "out_of_memory",
+            OutOfMemory,
+            "The game ran out of memory",
+            r"java\.lang\.OutOfMemoryError",
+            "Java ran out of memory. Large modpacks, shaders and high resolution resource packs need more RAM.",
+            &[
+                "Increase the maximum memory in the profile settings",
+                "Remove memory-heavy mods, shaders or resource packs",
+            ],
+        ),
+        CrashRule::builtin(
+            "opengl_context_unavailable",
+            GraphicsDriver,
+            "No usable OpenGL driver",
+            r"(?i)(?:Pixel format not accelerated|WGL: The driver does not appear to support OpenGL|GLFW error (?:65542|65543)|Failed to create (?:the )?OpenGL context|OpenGL \d+\.\d+ (?:is )?(?:not supported|unavailable))",
+            "The graphics driver could not provide an OpenGL context for the game.",
+            &[
+                "Install the latest driver from your GPU vendor (not the one from Windows Update)",
+                "On laptops, make sure the game runs on the dedicated GPU",
+            ],
+        ),
+        CrashRule::builtin(
+            "graphics_driver_native_crash",
+            GraphicsDriver,
+            "The game crashed inside the graphics driver",
+            r"(?m)^#?\s*C\s+\[(?P<library>(?:atio6axx|atioglxx|amdxc64|nvoglv64|nvoglv32|ig\d+icd\d*|igxelpicd64|libnvidia-glcore|radeonsi_dri|iris_dri|i965_dri)[^\]+]*)",
+            "The game crashed in the graphics driver library {library}.",
+            &[
+                "Update or reinstall your graphics driver",
+                "Disable shaders and rendering mods to check whether they trigger the crash",
+            ],
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(text: &str) -> Vec<CrashDiagnosis> {
+        CrashAnalyzer::from_rules(builtin_rules()).analyze(&[(CrashSource::LatestLog, text)])
+    }
+
+    #[test]
+    fn detects_mixin_failure_with_mod_name() {
+        let diagnoses = analyze(
+            "Caused by: org.spongepowered.asm.mixin.transformer.throwables.MixinTransformerError: \
+             Mixin apply for mod sodium failed sodium.mixins.json:core.MixinWindow from mod sodium",
+        );
+        assert_eq!(diagnoses[0].rule_id, "mixin_apply_failed");
+        assert_eq!(diagnoses[0].category, CrashCategory::MixinFailure);
+        assert!(diagnoses[0].description.contains("'sodium'"));
+        assert!(diagnoses[0].description.contains("sodium.mixins.json"));
+    }
+
+    #[test]
+    fn detects_missing_dependencies() {
+        let fabric = analyze(
+            "\t - Mod 'Sodium Extra' (sodium-extra) 0.5.1 requires any version of sodium, which is missing!",
+        );
+        assert_eq!(fabric[0].category, CrashCategory::MissingDependency);
+        assert_eq!(
+            fabric[0].description,
+            "'Sodium Extra' requires any version of sodium, which is not installed."
+        );
+
+        let forge = analyze(
+            "Mod ID: 'geckolib', Requested by: 'alexsmobs', Expected range: '[4.0,)', Actual version: '[MISSING]'",
+        );
+        assert_eq!(forge[0].rule_id, "forge_missing_dependency");
+        assert!(forge[0].suggestions[0].contains("'geckolib' within [4.0,)"));
+    }
+
+    #[test]
+    fn derives_java_version_from_class_file_version() {
+        let diagnoses = analyze(
+            "java.lang.UnsupportedClassVersionError: net/minecraft/client/main/Main has been compiled by a more recent version of the Java Runtime (class file version 65.0), this version of the Java Runtime only recognizes class file versions up to 61.0",
+        );
+        assert_eq!(diagnoses[0].category, CrashCategory::WrongJava);
+        assert_eq!(diagnoses[0].title, "Java 21 is required");
+        assert!(diagnoses[0].description.ends_with("started with Java 17."));
+    }
+
+    #[test]
+    fn detects_oom_duplicates_and_driver_crashes_once() {
+        let log = "java.lang.OutOfMemoryError: Java heap space\n\
+                   java.lang.OutOfMemoryError: Java heap space\n\
+                   Found duplicate mods:\n\tMod ID: 'jei' from mod files: jei-1.jar, jei-2.jar\n\
+                   # C  [atio6axx.dll+0x1a2b3c]\n";
+        let categories: Vec<_> = analyze(log).iter().map(|d| d.category).collect();
+        assert_eq!(
+            categories,
+            vec![
+                CrashCategory::DuplicateMod,
+                CrashCategory::OutOfMemory,
+                CrashCategory::GraphicsDriver
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_rules_override_and_extend_builtins() {
+        let mut rules = builtin_rules();
+        let custom: CrashRuleFile = serde_json::from_str(
+            r#"{"rules": [
+                {"id": "out_of_memory", "enabled": false},
+                {"id": "custom", "pattern": "BadThing: (?P<what>\\w+)", "description": "Bad {what}"}
+            ]}"#,
+        )
+        .unwrap();
+        merge_rules(&mut rules, custom.rules);
+
+        let diagnoses = CrashAnalyzer::from_rules(rules).analyze(&[(
+            CrashSource::CrashReport,
+            "java.lang.OutOfMemoryError: Metaspace\nBadThing: widget",
+        )]);
+        assert_eq!(diagnoses.len(), 1);
+        assert_eq!(diagnoses[0].category, CrashCategory::Other);
+        assert_eq!(diagnoses[0].description, "Bad widget");
+    }
+}
@@ -0,0 +1,219 @@
+use crate::commands::profile_command;
+use crate::error::{AppError, Result};
+use crate::state::event_state::{EventPayload, EventType};
+use crate::state::profile_state::Profile;
+use crate::state::state_manager::State;
+use crate::utils::updater_utils;
+use log::{error, info, warn};
+use std::path::PathBuf;
+use url::Url;
+use uuid::Uuid;
+
+pub const DEEP_LINK_SCHEME: &str = "geg";
+
+/// A parsed `geg://launch/<profile-id>[?world=<name>|?server=<address>]` link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchDeepLink {
+    pub profile_id: Uuid,
+    pub quick_play_singleplayer: Option<String>,
+    pub quick_play_multiplayer: Option<String>,
+}
+
+impl LaunchDeepLink {
+    pub fn new(profile_id: Uuid, quick_play_path: Option<&str>) -> Self {
+        let (quick_play_singleplayer, quick_play_multiplayer) = quick_play_path
+            .filter(|path| !path.trim().is_empty())
+            .map(profile_command::split_quick_play_path)
+            .unwrap_or((None, None));
+        Self {
+            profile_id,
+            quick_play_singleplayer,
+            quick_play_multiplayer,
+        }
+    }
+
+    pub fn parse(arg: &str) -> Option<Self> {
+        let url = Url::parse(arg).ok()?;
+        if url.scheme() != DEEP_LINK_SCHEME || url.host_str() != Some("launch") {
+            return None;
+        }
+
+        let id_segment = url.path().trim_matches('/');
+        let profile_id = match Uuid::parse_str(id_segment) {
+            Ok(id) => id,
+            Err(_) => {
+                warn!("Ignoring deep link with invalid profile ID: {}", arg);
+                return None;
+            }
+        };
+
+        let mut link = Self::new(profile_id, None);
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "world" => link.quick_play_singleplayer = Some(value.into_owned()),
+                "server" => link.quick_play_multiplayer = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+        Some(link)
+    }
+
+    pub fn to_url(&self) -> String {
+        let mut url = Url::parse(&format!("{}://launch/{}", DEEP_LINK_SCHEME, self.profile_id))
+            .expect("deep link URL is always valid");
+        if let Some(world) = &self.quick_play_singleplayer {
+            url.query_pairs_mut().append_pair("world", world);
+        } else if let Some(server) = &self.quick_play_multiplayer {
+            url.query_pairs_mut().append_pair("server", server);
+        }
+        url.to_string()
+    }
+}
+
+/// Looks for a launch deep link in process arguments (our own argv on first start,
+/// or the forwarded argv of a second instance from the single-instance plugin).
+pub fn find_launch_deep_link(args: &[String]) -> Option<LaunchDeepLink> {
+    args.iter().find_map(|arg| LaunchDeepLink::parse(arg))
+}
+
+/// Launches the profile of a deep link if the arguments contain one.
+/// Goes through `launch_profile` like the play button; errors are reported via `EventState`.
+pub async fn handle_deep_link_args(args: Vec<String>) {
+    let Some(link) = find_launch_deep_link(&args) else {
+        return;
+    };
+    info!(
+        "Launching profile {} from deep link {}",
+        link.profile_id,
+        link.to_url()
+    );
+
+    let state = match State::get().await {
+        Ok(state) => state,
+        Err(e) => {
+            error!("Cannot handle deep link, state unavailable: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = profile_command::launch_profile(
+        link.profile_id,
+        link.quick_play_singleplayer,
+        link.quick_play_multiplayer,
+        None,
+        None,
+    )
+    .await
+    {
+        error!(
+            "Deep link launch of profile {} failed: {}",
+            link.profile_id, e.message
+        );
+        let payload = EventPayload {
+            event_id: Uuid::new_v4(),
+            event_type: EventType::Error,
+            target_id: Some(link.profile_id),
+            message: format!("Launch from shortcut failed: {}", e.message),
+            progress: None,
+            error: Some(e.message),
+        };
+        if let Err(emit_err) = state.emit_event(payload).await {
+            error!("Failed to emit deep link error event: {}", emit_err);
+        }
+    }
+}
+
+/// Writes a `.desktop` launcher for a profile into `~/.local/share/applications` (Linux only).
+/// The entry starts the launcher with a `geg://launch/...` argument, so it works without any
+/// system-wide URL scheme registration.
+pub async fn create_desktop_shortcut(
+    profile: &Profile,
+    quick_play_path: Option<&str>,
+) -> Result<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return Err(AppError::UnsupportedOS(
+            "Desktop shortcuts are only supported on Linux".to_string(),
+        ));
+    }
+
+    let link = LaunchDeepLink::new(profile.id, quick_play_path);
+    let (mut exec_args, icon) = launcher_exec_and_icon()?;
+    exec_args.push(link.to_url());
+
+    let exec = exec_args
+        .iter()
+        .map(|arg| quote_exec_arg(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let name = profile.name.replace(['\n', '\r'], " ");
+
+    let content = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Version=1.0\n\
+         Name={name}\n\
+         Comment=Launch {name} with GEG Launcher\n\
+         Exec={exec}\n\
+         Icon={icon}\n\
+         Terminal=false\n\
+         Categories=Game;\n\
+         StartupNotify=true\n"
+    );
+
+    let applications_dir = directories::BaseDirs::new()
+        .ok_or_else(|| AppError::Other("Could not determine the home directory".to_string()))?
+        .data_local_dir()
+        .join("applications");
+    tokio::fs::create_dir_all(&applications_dir).await?;
+
+    let desktop_file = applications_dir.join(format!("geg-launcher-profile-{}.desktop", profile.id));
+    tokio::fs::write(&desktop_file, content).await?;
+    info!(
+        "Created desktop shortcut for profile '{}' at {}",
+        profile.name,
+        desktop_file.display()
+    );
+    Ok(desktop_file)
+}
+
+/// How the launcher itself has to be started (Flatpak, AppImage or plain binary) and its icon name.
+fn launcher_exec_and_icon() -> Result<(Vec<String>, String)> {
+    if updater_utils::is_flatpak() {
+        let flatpak_id = std::env::var("FLATPAK_ID").unwrap_or_default();
+        return Ok((
+            vec!["flatpak".to_string(), "run".to_string(), flatpak_id.clone()],
+            flatpak_id,
+        ));
+    }
+
+    let exe = match std::env::var("APPIMAGE") {
+        Ok(appimage) => PathBuf::from(appimage),
+        Err(_) => std::env::current_exe()?,
+    };
+    // Bundles install the icon under the binary name
+    let icon = std::env::current_exe()?
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "geg-launcher-v3".to_string());
+    Ok((vec![exe.to_string_lossy().to_string()], icon))
+}
+
+/// Quotes an argument for the `Exec` key of a desktop entry (quoting rules plus `%` field codes).
+fn quote_exec_arg(arg: &str) -> String {
+    let mut quoted = String::with_capacity(arg.len() + 2);
+    quoted.push('"');
+    for c in arg.chars() {
+        match c {
+            // Escaped for the quoting rule and once more for the string value rule
+            '\\' => quoted.push_str("\\\\\\\\"),
+            '"' | '`' | '$' => {
+                quoted.push_str("\\\\");
+                quoted.push(c);
+            }
+            '%' => quoted.push_str("%%"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
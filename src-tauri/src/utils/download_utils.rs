@@ -1,11 +1,16 @@
-use crate::config::HTTP_CLIENT;
+use crate::config::DOWNLOAD_CLIENT;
 use crate::error::{AppError, Result};
+use crate::state::download_queue_state;
+use crate::utils::bandwidth_utils;
 use crate::utils::hash_utils;
 use crate::utils::disk_space_utils::DiskSpaceUtils;
 use futures::stream::StreamExt;
 use log::{debug, error, info, warn};
-use reqwest::Response;
+use reqwest::header::{CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::{Response, StatusCode};
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 
@@ -31,6 +36,9 @@ pub struct DownloadConfig {
     pub check_disk_space: bool,
     /// Buffer percentage for disk space check (default: 0.25 = 25%)
     pub disk_space_buffer: f64,
+    /// Keep interrupted streaming downloads as `.part` files and continue them with
+    /// HTTP range requests (default: false)
+    pub resumable: bool,
 }
 
 impl std::fmt::Debug for DownloadConfig {
@@ -46,6 +54,7 @@ impl std::fmt::Debug for DownloadConfig {
             .field("progress_callback", &"<callback function>")
             .field("check_disk_space", &self.check_disk_space)
             .field("disk_space_buffer", &self.disk_space_buffer)
+            .field("resumable", &self.resumable)
             .finish()
     }
 }
@@ -65,6 +74,7 @@ impl Clone for DownloadConfig {
             progress_callback: None,
             check_disk_space: self.check_disk_space,
             disk_space_buffer: self.disk_space_buffer,
+            resumable: self.resumable,
         }
     }
 }
@@ -85,6 +95,7 @@ impl Default for DownloadConfig {
             progress_callback: None,
             check_disk_space: true,
             disk_space_buffer: 0.25, // 25% buffer by default
+            resumable: false,
         }
     }
 }
@@ -146,12 +157,66 @@ impl DownloadConfig {
         self.disk_space_buffer = buffer_percentage;
         self
     }
+
+    /// Only has an effect for streaming downloads
+    pub fn with_resume(mut self, resumable: bool) -> Self {
+        self.resumable = resumable;
+        self
+    }
+}
+
+/// Sidecar of a `.part` file, persisted so an interrupted download can be continued later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PartialDownloadMeta {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    total_size: Option<u64>,
+    expected_sha1: Option<String>,
+    expected_sha256: Option<String>,
+}
+
+impl PartialDownloadMeta {
+    /// Validator for `If-Range`, weak ETags are not allowed there
+    fn validator(&self) -> Option<&str> {
+        self.etag
+            .as_deref()
+            .filter(|etag| !etag.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+
+    fn matches(&self, url: &str, config: &DownloadConfig) -> bool {
+        self.url == url
+            && self.expected_sha1 == config.expected_sha1
+            && self.expected_sha256 == config.expected_sha256
+    }
+}
+
+/// Parses `bytes <start>-<end>/<total>` into the start offset and the total size (if known)
+fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, end) = range.split_once('-')?;
+    let start = start.trim().parse::<u64>().ok()?;
+    end.trim().parse::<u64>().ok()?;
+    Some((start, total.trim().parse::<u64>().ok()))
 }
 
+// Process-wide totals of everything downloaded through `DownloadUtils` (e.g. for launch timelines)
+static DOWNLOADED_BYTES: AtomicU64 = AtomicU64::new(0);
+static DOWNLOADED_FILES: AtomicU64 = AtomicU64::new(0);
+
 /// Central download utility for robust file downloads
 pub struct DownloadUtils;
 
 impl DownloadUtils {
+    /// Bytes and files downloaded by this process so far. Callers take deltas of two snapshots.
+    pub fn download_totals() -> (u64, u64) {
+        (
+            DOWNLOADED_BYTES.load(Ordering::Relaxed),
+            DOWNLOADED_FILES.load(Ordering::Relaxed),
+        )
+    }
+
     /// Downloads a file from URL to target path with comprehensive verification and error handling
     pub async fn download_file<P: AsRef<Path>>(
         url: &str,
@@ -164,6 +229,10 @@ impl DownloadUtils {
         // Check if file already exists and is valid
         if !config.force_overwrite && Self::verify_existing_file(target_path, &config).await? {
             info!("File already exists and passes verification: {:?}", target_path);
+            if config.resumable {
+                let (part_path, meta_path) = Self::partial_paths(target_path);
+                Self::remove_partial_files(&part_path, &meta_path).await;
+            }
             return Ok(());
         }
 
@@ -192,9 +261,25 @@ impl DownloadUtils {
 
             match Self::download_attempt(url, target_path, &config).await {
                 Ok(()) => {
+                    DOWNLOADED_FILES.fetch_add(1, Ordering::Relaxed);
+                    download_queue_state::record_downloaded_file();
                     info!("Successfully downloaded: {} -> {:?}", url, target_path);
                     return Ok(());
                 }
+                Err(e @ AppError::DownloadCancelled(_)) => {
+                    // Cancelled jobs are not retried and leave no partial files behind
+                    info!("Download of {} cancelled, removing partial files", url);
+                    if config.resumable {
+                        let (part_path, meta_path) = Self::partial_paths(target_path);
+                        Self::remove_partial_files(&part_path, &meta_path).await;
+                    }
+                    if target_path.exists() {
+                        if let Err(cleanup_err) = fs::remove_file(target_path).await {
+                            warn!("Failed to clean up partial file {:?}: {}", target_path, cleanup_err);
+                        }
+                    }
+                    return Err(e);
+                }
                 Err(e) => {
                     error!("Download attempt {} failed for {}: {}", attempt + 1, url, e);
                     last_error = Some(e);
@@ -260,6 +345,8 @@ impl DownloadUtils {
         target_path: &Path,
         config: &DownloadConfig,
     ) -> Result<()> {
+        download_queue_state::checkpoint().await?;
+
         // Create parent directories
         if let Some(parent) = target_path.parent() {
             fs::create_dir_all(parent).await.map_err(|e| {
@@ -270,8 +357,12 @@ impl DownloadUtils {
             })?;
         }
 
+        if config.resumable && config.use_streaming {
+            return Self::download_resumable(url, target_path, config).await;
+        }
+
         // Make HTTP request
-        let mut request = HTTP_CLIENT.get(url);
+        let mut request = DOWNLOAD_CLIENT.get(url);
         
         if let Some(user_agent) = &config.user_agent {
             request = request.header("User-Agent", user_agent);
@@ -314,26 +405,201 @@ impl DownloadUtils {
         }
     }
 
-    /// Download using streaming (recommended for large files)
-    async fn download_streaming(
-        response: Response,
+    /// `<target>.part` holds the data, `<target>.part.json` the validators it was downloaded with
+    fn partial_paths(target_path: &Path) -> (PathBuf, PathBuf) {
+        let mut part_path = target_path.as_os_str().to_owned();
+        part_path.push(".part");
+        let mut meta_path = part_path.clone();
+        meta_path.push(".json");
+        (PathBuf::from(part_path), PathBuf::from(meta_path))
+    }
+
+    async fn remove_partial_files(part_path: &Path, meta_path: &Path) {
+        for path in [part_path, meta_path] {
+            if let Err(e) = fs::remove_file(path).await {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    warn!("Failed to remove partial download file {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+
+    /// Streaming download into a `.part` file that survives failed attempts. An existing
+    /// part is continued with a `Range` request guarded by `If-Range`, so the server sends
+    /// the whole file again if it changed in the meantime.
+    async fn download_resumable(
+        url: &str,
         target_path: &Path,
         config: &DownloadConfig,
-        content_length: Option<u64>,
     ) -> Result<()> {
-        debug!("Creating file for streaming download: {:?}", target_path);
-        let mut file = fs::File::create(target_path).await.map_err(|e| {
-            let error_msg = format!("Failed to create file {:?}: {}", target_path, e);
+        let (part_path, meta_path) = Self::partial_paths(target_path);
+
+        // Only resume parts that were started for the same URL and hashes
+        let previous = match fs::read_to_string(&meta_path).await {
+            Ok(content) => serde_json::from_str::<PartialDownloadMeta>(&content)
+                .ok()
+                .filter(|meta| meta.matches(url, config) && meta.validator().is_some()),
+            Err(_) => None,
+        };
+        let existing_len = match &previous {
+            Some(_) => fs::metadata(&part_path).await.map(|m| m.len()).unwrap_or(0),
+            None => 0,
+        };
+        let previous = previous.filter(|_| existing_len > 0);
+        if previous.is_none() {
+            Self::remove_partial_files(&part_path, &meta_path).await;
+        }
+
+        let mut request = DOWNLOAD_CLIENT.get(url);
+        if let Some(user_agent) = &config.user_agent {
+            request = request.header("User-Agent", user_agent);
+        }
+        if let Some(validator) = previous.as_ref().and_then(|m| m.validator()) {
+            debug!(
+                "Resuming download of {} at byte {} (If-Range: {})",
+                url, existing_len, validator
+            );
+            request = request
+                .header(RANGE, format!("bytes={}-", existing_len))
+                .header(IF_RANGE, validator);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            let error_msg = format!("HTTP request failed for {}: {}", url, e);
+            error!("{}", error_msg);
+            AppError::Download(error_msg)
+        })?;
+        let status = response.status();
+
+        if status == StatusCode::RANGE_NOT_SATISFIABLE {
+            // The part may already hold the complete file from an attempt that failed verification
+            let complete = previous
+                .as_ref()
+                .and_then(|m| m.total_size)
+                .is_some_and(|total| total == existing_len);
+            if complete {
+                debug!("Partial download of {} is already complete", url);
+                return Self::finish_partial_download(&part_path, &meta_path, target_path, config)
+                    .await;
+            }
+            Self::remove_partial_files(&part_path, &meta_path).await;
+            return Err(AppError::Download(format!(
+                "Server rejected resume range for {}, restarting download",
+                url
+            )));
+        }
+
+        if !status.is_success() {
+            let error_msg = format!(
+                "Download failed: {} returned status {} ({})",
+                url,
+                status.as_u16(),
+                status.canonical_reason().unwrap_or("Unknown")
+            );
+            error!("{}", error_msg);
+            return Err(AppError::Download(error_msg));
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let content_length = response.content_length();
+
+        let (offset, total_size) = if status == StatusCode::PARTIAL_CONTENT {
+            match header(CONTENT_RANGE).as_deref().and_then(parse_content_range) {
+                Some((start, total)) if start == existing_len => {
+                    (start, total.or(content_length.map(|len| len + start)))
+                }
+                other => {
+                    Self::remove_partial_files(&part_path, &meta_path).await;
+                    return Err(AppError::Download(format!(
+                        "Unexpected Content-Range {:?} for {} (expected start {}), restarting download",
+                        other, url, existing_len
+                    )));
+                }
+            }
+        } else {
+            if existing_len > 0 {
+                info!(
+                    "Server sent the full file for {} (validators changed or no range support), restarting",
+                    url
+                );
+            }
+            (0, content_length)
+        };
+
+        let meta = PartialDownloadMeta {
+            url: url.to_string(),
+            etag,
+            last_modified,
+            total_size,
+            expected_sha1: config.expected_sha1.clone(),
+            expected_sha256: config.expected_sha256.clone(),
+        };
+        let meta_json = serde_json::to_vec(&meta)?;
+        fs::write(&meta_path, meta_json).await.map_err(|e| {
+            AppError::Download(format!("Failed to write {:?}: {}", meta_path, e))
+        })?;
+
+        let mut file = if offset > 0 {
+            fs::OpenOptions::new().append(true).open(&part_path).await
+        } else {
+            fs::File::create(&part_path).await
+        }
+        .map_err(|e| {
+            let error_msg = format!("Failed to open file {:?}: {}", part_path, e);
             error!("{}", error_msg);
             AppError::Download(error_msg)
         })?;
 
+        Self::write_stream(response, &mut file, &part_path, config, offset, total_size).await?;
+        drop(file);
+
+        Self::finish_partial_download(&part_path, &meta_path, target_path, config).await
+    }
+
+    /// Moves a completed `.part` file into place and verifies it. A part that fails
+    /// verification is gone afterwards, so the next attempt starts from scratch.
+    async fn finish_partial_download(
+        part_path: &Path,
+        meta_path: &Path,
+        target_path: &Path,
+        config: &DownloadConfig,
+    ) -> Result<()> {
+        fs::rename(part_path, target_path).await.map_err(|e| {
+            AppError::Download(format!(
+                "Failed to move {:?} to {:?}: {}",
+                part_path, target_path, e
+            ))
+        })?;
+        Self::remove_partial_files(part_path, meta_path).await;
+        Self::verify_downloaded_file(target_path, config).await
+    }
+
+    /// Writes the response body to `file`, `offset` bytes are already on disk
+    async fn write_stream(
+        response: Response,
+        file: &mut fs::File,
+        path: &Path,
+        config: &DownloadConfig,
+        offset: u64,
+        total_size: Option<u64>,
+    ) -> Result<u64> {
         let mut stream = response.bytes_stream();
-        let mut downloaded = 0u64;
+        let mut downloaded = offset;
         let mut chunk_count = 0u64;
 
-        debug!("Starting streaming download (content_length: {:?})", content_length);
-        
+        debug!(
+            "Starting streaming download (offset: {}, total: {:?})",
+            offset, total_size
+        );
+
         while let Some(chunk_result) = stream.next().await {
             let chunk = chunk_result.map_err(|e| {
                 let error_msg = format!("Stream error during download: {}", e);
@@ -342,13 +608,18 @@ impl DownloadUtils {
             })?;
 
             file.write_all(&chunk).await.map_err(|e| {
-                let error_msg = format!("Write error for {:?}: {}", target_path, e);
+                let error_msg = format!("Write error for {:?}: {}", path, e);
                 error!("{}", error_msg);
                 AppError::Download(error_msg)
             })?;
 
             downloaded += chunk.len() as u64;
             chunk_count += 1;
+            DOWNLOADED_BYTES.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+            download_queue_state::record_downloaded_bytes(chunk.len() as u64);
+            bandwidth_utils::throttle(chunk.len() as u64).await;
+            // Blocks while the surrounding download job is paused
+            download_queue_state::checkpoint().await?;
 
             // Log progress every 1000 chunks or every 10MB for large downloads
             if chunk_count % 1000 == 0 || downloaded % (10 * 1024 * 1024) == 0 {
@@ -357,7 +628,7 @@ impl DownloadUtils {
 
             // Call progress callback if provided
             if let Some(callback) = &config.progress_callback {
-                callback(downloaded, content_length);
+                callback(downloaded, total_size);
             }
         }
 
@@ -365,9 +636,28 @@ impl DownloadUtils {
 
         // Ensure file is fully written to disk - CRITICAL for preventing corruption
         file.sync_all().await.map_err(|e| {
-            AppError::Download(format!("Failed to sync file {:?}: {}", target_path, e))
+            AppError::Download(format!("Failed to sync file {:?}: {}", path, e))
         })?;
 
+        Ok(downloaded)
+    }
+
+    /// Download using streaming (recommended for large files)
+    async fn download_streaming(
+        response: Response,
+        target_path: &Path,
+        config: &DownloadConfig,
+        content_length: Option<u64>,
+    ) -> Result<()> {
+        debug!("Creating file for streaming download: {:?}", target_path);
+        let mut file = fs::File::create(target_path).await.map_err(|e| {
+            let error_msg = format!("Failed to create file {:?}: {}", target_path, e);
+            error!("{}", error_msg);
+            AppError::Download(error_msg)
+        })?;
+
+        Self::write_stream(response, &mut file, target_path, config, 0, content_length).await?;
+
         // Explicitly close the file handle
         drop(file);
 
@@ -385,6 +675,10 @@ impl DownloadUtils {
         let bytes = response.bytes().await.map_err(|e| {
             AppError::Download(format!("Failed to read response bytes: {}", e))
         })?;
+        DOWNLOADED_BYTES.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        download_queue_state::record_downloaded_bytes(bytes.len() as u64);
+        bandwidth_utils::throttle(bytes.len() as u64).await;
+        download_queue_state::checkpoint().await?;
 
         // Call progress callback if provided
         if let Some(callback) = &config.progress_callback {
@@ -646,3 +940,41 @@ impl DownloadUtils {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_content_range() {
+        assert_eq!(parse_content_range("bytes 100-199/200"), Some((100, Some(200))));
+        assert_eq!(parse_content_range("bytes 0-9/*"), Some((0, None)));
+        assert_eq!(parse_content_range("bytes */200"), None);
+        assert_eq!(parse_content_range("items 0-9/10"), None);
+    }
+
+    #[test]
+    fn weak_etags_fall_back_to_last_modified() {
+        let mut meta = PartialDownloadMeta {
+            url: "https://example.com/file.zip".to_string(),
+            etag: Some("W/\"abc\"".to_string()),
+            last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+            total_size: None,
+            expected_sha1: None,
+            expected_sha256: None,
+        };
+        assert_eq!(meta.validator(), Some("Wed, 21 Oct 2015 07:28:00 GMT"));
+        meta.etag = Some("\"abc\"".to_string());
+        assert_eq!(meta.validator(), Some("\"abc\""));
+        meta.etag = None;
+        meta.last_modified = None;
+        assert_eq!(meta.validator(), None);
+    }
+
+    #[test]
+    fn partial_paths_keep_the_original_extension() {
+        let (part, meta) = DownloadUtils::partial_paths(Path::new("/tmp/java.tar.gz"));
+        assert_eq!(part, PathBuf::from("/tmp/java.tar.gz.part"));
+        assert_eq!(meta, PathBuf::from("/tmp/java.tar.gz.part.json"));
+    }
+}
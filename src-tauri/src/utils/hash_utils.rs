@@ -61,3 +61,72 @@ pub fn calculate_sha256_from_bytes(bytes: &[u8]) -> String {
     let hash_bytes = hasher.finalize();
     format!("{:x}", hash_bytes) // Format as hex string
 }
+
+/// Asynchronously calculates the CurseForge fingerprint of a file.
+pub async fn calculate_curseforge_fingerprint_from_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<u64, io::Error> {
+    let bytes = tokio::fs::read(path).await?;
+    Ok(calculate_curseforge_fingerprint_from_bytes(&bytes))
+}
+
+/// Calculates the CurseForge fingerprint of a byte slice: MurmurHash2 (seed 1)
+/// over the content with all whitespace bytes (tab, LF, CR, space) removed.
+pub fn calculate_curseforge_fingerprint_from_bytes(bytes: &[u8]) -> u64 {
+    let normalized: Vec<u8> = bytes
+        .iter()
+        .copied()
+        .filter(|b| !matches!(b, 9 | 10 | 13 | 32))
+        .collect();
+    u64::from(murmur2(&normalized, 1))
+}
+
+fn murmur2(data: &[u8], seed: u32) -> u32 {
+    const M: u32 = 0x5bd1_e995;
+    const R: u32 = 24;
+
+    let mut h = seed ^ data.len() as u32;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M) ^ k;
+    }
+
+    let tail = chunks.remainder();
+    if tail.len() >= 3 {
+        h ^= u32::from(tail[2]) << 16;
+    }
+    if tail.len() >= 2 {
+        h ^= u32::from(tail[1]) << 8;
+    }
+    if !tail.is_empty() {
+        h ^= u32::from(tail[0]);
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curseforge_fingerprint_ignores_whitespace() {
+        assert_eq!(calculate_curseforge_fingerprint_from_bytes(b""), 1_540_447_798);
+        assert_eq!(
+            calculate_curseforge_fingerprint_from_bytes(b"helloworld"),
+            2_824_650_221
+        );
+        assert_eq!(
+            calculate_curseforge_fingerprint_from_bytes(b"hello world\r\n\t"),
+            calculate_curseforge_fingerprint_from_bytes(b"helloworld")
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use crate::error::{AppError, Result};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+lazy_static! {
+    // Matches `$NAME` and `${NAME}`
+    static ref PLACEHOLDER_REGEX: Regex = Regex::new(r"\$\{(\w+)\}|\$(\w+)").unwrap();
+}
+
+/// Values available to hooks, wrappers and profile environment variables at launch time.
+///
+/// Placeholders like `$INST_DIR`, `$PROFILE_ID` or `$JAVA` are expanded from this context.
+/// Unknown names fall back to the launcher's own environment (so `LD_PRELOAD=$LD_PRELOAD:/x`
+/// works) and are left untouched if they aren't set there either.
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    pub instance_dir: PathBuf,
+    pub profile_id: Uuid,
+    pub profile_name: String,
+    pub java_path: Option<PathBuf>,
+    pub minecraft_version: Option<String>,
+}
+
+impl HookContext {
+    pub fn new(instance_dir: PathBuf, profile_id: Uuid, profile_name: String) -> Self {
+        Self {
+            instance_dir,
+            profile_id,
+            profile_name,
+            java_path: None,
+            minecraft_version: None,
+        }
+    }
+
+    pub fn with_java_path(mut self, java_path: PathBuf) -> Self {
+        self.java_path = Some(java_path);
+        self
+    }
+
+    pub fn with_minecraft_version(mut self, version: String) -> Self {
+        self.minecraft_version = Some(version);
+        self
+    }
+
+    /// Variables exported to hook processes, named like the placeholders.
+    pub fn variables(&self) -> Vec<(String, String)> {
+        let instance_dir = self.instance_dir.to_string_lossy().to_string();
+        let profile_id = self.profile_id.to_string();
+        let mut vars = vec![
+            ("INST_DIR".to_string(), instance_dir.clone()),
+            ("INST_MC_DIR".to_string(), instance_dir),
+            ("INST_ID".to_string(), profile_id.clone()),
+            ("PROFILE_ID".to_string(), profile_id),
+            ("INST_NAME".to_string(), self.profile_name.clone()),
+            ("PROFILE_NAME".to_string(), self.profile_name.clone()),
+        ];
+        if let Some(java_path) = &self.java_path {
+            let java = java_path.to_string_lossy().to_string();
+            vars.push(("INST_JAVA".to_string(), java.clone()));
+            vars.push(("JAVA".to_string(), java));
+        }
+        if let Some(version) = &self.minecraft_version {
+            vars.push(("MC_VERSION".to_string(), version.clone()));
+        }
+        vars
+    }
+
+    /// Expands `$NAME` / `${NAME}` placeholders in a hook command or env value.
+    pub fn expand(&self, input: &str) -> String {
+        let vars: HashMap<String, String> = self.variables().into_iter().collect();
+        PLACEHOLDER_REGEX
+            .replace_all(input, |caps: &Captures| {
+                let name = caps
+                    .get(1)
+                    .or_else(|| caps.get(2))
+                    .map(|m| m.as_str())
+                    .unwrap_or_default();
+                vars.get(name)
+                    .cloned()
+                    .or_else(|| std::env::var(name).ok())
+                    .unwrap_or_else(|| caps[0].to_string())
+            })
+            .into_owned()
+    }
+
+    /// Splits a hook command shell-style and expands placeholders per argument,
+    /// so expanded paths containing spaces stay a single argument.
+    pub fn expand_command(&self, input: &str) -> Result<Vec<String>> {
+        let tokens = split_command(input).map_err(|reason| {
+            AppError::InvalidInput(format!("Invalid hook command '{}': {}", input, reason))
+        })?;
+        Ok(tokens.iter().map(|token| self.expand(token)).collect())
+    }
+
+    /// Parses a (possibly chained) wrapper command, expands placeholders and checks that every
+    /// wrapper binary exists. Returns the argv the game's Java command has to be appended to.
+    ///
+    /// Several wrappers are chained with `;` or a line break (`prime-run --flag; mangohud --dlsym`).
+    /// Only the first token of each segment is checked, everything after it is passed to that
+    /// wrapper as arguments, so `gamemoderun mangohud` runs an unchecked `mangohud`.
+    pub fn wrapper_argv(&self, wrapper: &str) -> Result<Vec<String>> {
+        let invalid = |reason: String| AppError::InvalidWrapperCommand {
+            command: wrapper.to_string(),
+            reason,
+        };
+
+        let chain = split_wrapper_chain(wrapper).map_err(invalid)?;
+        let mut argv = Vec::new();
+        for segment in chain {
+            let segment: Vec<String> = segment.iter().map(|token| self.expand(token)).collect();
+            let program = &segment[0];
+            if find_executable(program).is_none() {
+                return Err(invalid(format!(
+                    "'{}' was not found or is not executable",
+                    program
+                )));
+            }
+            argv.extend(segment);
+        }
+
+        if argv.is_empty() {
+            return Err(invalid("no command given".to_string()));
+        }
+        Ok(argv)
+    }
+
+    /// Resolves a profile's environment variables, expanding placeholders in the values.
+    pub fn resolve_env(&self, env_vars: &HashMap<String, String>) -> HashMap<String, String> {
+        env_vars
+            .iter()
+            .filter(|(key, _)| !key.trim().is_empty())
+            .map(|(key, value)| (key.trim().to_string(), self.expand(value)))
+            .collect()
+    }
+}
+
+/// Splits a command line like a POSIX shell would (without any expansion).
+///
+/// Supports `'single'` and `"double"` quotes and backslash escapes. A backslash only escapes
+/// whitespace, quotes, `;` and itself so Windows paths like `C:\Tools\x.exe` survive unquoted.
+pub fn split_command(input: &str) -> std::result::Result<Vec<String>, String> {
+    let mut segments = tokenize(input, false)?;
+    Ok(segments.pop().unwrap_or_default())
+}
+
+/// Like [`split_command`] but additionally splits on unquoted `;` and line breaks, one entry per wrapper.
+pub fn split_wrapper_chain(input: &str) -> std::result::Result<Vec<Vec<String>>, String> {
+    tokenize(input, true)
+}
+
+/// Joins arguments back into a single command line that [`split_command`] parses to the same arguments.
+pub fn join_command(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            if !arg.is_empty()
+                && !arg
+                    .chars()
+                    .any(|c| c.is_whitespace() || matches!(c, '\'' | '"' | ';'))
+            {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn tokenize(input: &str, split_chain: bool) -> std::result::Result<Vec<Vec<String>>, String> {
+    #[derive(PartialEq)]
+    enum Quote {
+        None,
+        Single,
+        Double,
+    }
+
+    let mut segments: Vec<Vec<String>> = Vec::new();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut current = String::new();
+    // Needed so that "" yields an (empty) argument
+    let mut in_token = false;
+    let mut quote = Quote::None;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Quote::Single => {
+                if c == '\'' {
+                    quote = Quote::None;
+                } else {
+                    current.push(c);
+                }
+            }
+            Quote::Double => match c {
+                '"' => quote = Quote::None,
+                '\\' if matches!(chars.peek(), Some('"') | Some('\\')) => {
+                    current.push(chars.next().unwrap_or_default());
+                }
+                _ => current.push(c),
+            },
+            Quote::None => match c {
+                '\'' => {
+                    quote = Quote::Single;
+                    in_token = true;
+                }
+                '"' => {
+                    quote = Quote::Double;
+                    in_token = true;
+                }
+                '\\' if matches!(chars.peek(), Some(next) if next.is_whitespace() || matches!(next, '\'' | '"' | '\\' | ';')) =>
+                {
+                    current.push(chars.next().unwrap_or_default());
+                    in_token = true;
+                }
+                ';' | '\n' if split_chain => {
+                    if in_token {
+                        tokens.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                    if !tokens.is_empty() {
+                        segments.push(std::mem::take(&mut tokens));
+                    }
+                }
+                c if c.is_whitespace() => {
+                    if in_token {
+                        tokens.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                }
+                _ => {
+                    current.push(c);
+                    in_token = true;
+                }
+            },
+        }
+    }
+
+    if quote != Quote::None {
+        return Err("unterminated quote".to_string());
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    if !tokens.is_empty() {
+        segments.push(tokens);
+    }
+    Ok(segments)
+}
+
+/// Looks up a program like the OS would when spawning it: paths are checked directly,
+/// bare names are searched in `PATH` (with `PATHEXT` on Windows).
+pub fn find_executable(program: &str) -> Option<PathBuf> {
+    let path = Path::new(program);
+    if path.components().count() > 1 || path.is_absolute() {
+        return is_executable(path).then(|| path.to_path_buf());
+    }
+
+    let extensions: Vec<String> = if cfg!(windows) {
+        let pathext = std::env::var("PATHEXT").unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".to_string());
+        std::iter::once(String::new())
+            .chain(pathext.split(';').map(|ext| ext.to_string()))
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+
+    let search_path = std::env::var_os("PATH")?;
+    std::env::split_paths(&search_path).find_map(|dir| {
+        extensions.iter().find_map(|ext| {
+            let candidate = dir.join(format!("{}{}", program, ext));
+            is_executable(&candidate).then_some(candidate)
+        })
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_command_quoting() {
+        assert_eq!(
+            split_command(r#"prime-run --flag "a b" 'c "d"' e\ f """#).unwrap(),
+            vec!["prime-run", "--flag", "a b", "c \"d\"", "e f", ""]
+        );
+        assert_eq!(
+            split_command(r"C:\Tools\wrap.exe --x").unwrap(),
+            vec![r"C:\Tools\wrap.exe", "--x"]
+        );
+        assert!(split_command("mangohud 'oops").is_err());
+    }
+
+    #[test]
+    fn test_split_wrapper_chain() {
+        assert_eq!(
+            split_wrapper_chain("prime-run --flag; mangohud --dlsym\n gamemoderun").unwrap(),
+            vec![
+                vec!["prime-run", "--flag"],
+                vec!["mangohud", "--dlsym"],
+                vec!["gamemoderun"],
+            ]
+        );
+        assert_eq!(
+            split_wrapper_chain("echo 'a;b'").unwrap(),
+            vec![vec!["echo", "a;b"]]
+        );
+    }
+
+    #[test]
+    fn test_join_command_roundtrip() {
+        let args: Vec<String> = vec!["/opt/my tools/run".into(), "it's".into(), "".into(), "plain".into()];
+        assert_eq!(split_command(&join_command(&args)).unwrap(), args);
+    }
+}
@@ -6,11 +6,12 @@ use log::{info, warn};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::collections::HashSet;
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::process::Stdio;
-#[cfg(windows)]
-use std::os::windows::process::CommandExt;
 use std::sync::Arc;
 use tokio::fs;
 use tokio::sync::RwLock;
@@ -100,99 +101,8 @@ impl JavaInstallation {
     }
 }
 
-/// Detects Java installations in the launcher's meta/java directory
-async fn detect_java_in_launcher_dir() -> Result<Vec<JavaInstallation>> {
-    info!("Detecting Java installations in launcher directory");
-    let mut installations = Vec::new();
-
-    // Get the launcher's meta/java directory
-    let java_dir = crate::config::standard_meta_dir().join("java");
-    info!("Checking for Java installations in: {}", java_dir.display());
-
-    // Create the directory if it doesn't exist
-    if !java_dir.exists() {
-        info!("Creating launcher Java directory: {}", java_dir.display());
-        fs::create_dir_all(&java_dir).await?;
-        return Ok(installations); // Return empty, we just created the directory
-    }
-
-    // List all directories in the java_dir, each may contain a Java installation
-    let mut read_dir = fs::read_dir(&java_dir).await?;
-
-    while let Some(entry) = read_dir.next_entry().await? {
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
-        }
-
-        info!(
-            "Checking potential Java installation in: {}",
-            path.display()
-        );
-
-        // Try to find java executable
-        let java_exe = if cfg!(windows) {
-            path.join("bin").join("java.exe")
-        } else {
-            path.join("bin").join("java")
-        };
-
-        if java_exe.exists() {
-            match get_java_info(&java_exe).await {
-                Ok(mut info) => {
-                    let version_clone = info.version.clone(); // Clone version before move
-                    info.source = "Launcher Directory".to_string();
-                    installations.push(info);
-                    info!(
-                        "Found valid Java installation: {} ({})",
-                        java_exe.display(),
-                        version_clone
-                    );
-                }
-                Err(e) => {
-                    warn!("Invalid Java installation at {}: {}", java_exe.display(), e);
-                }
-            }
-        } else {
-            // Also check if the directory itself is a JRE without bin subdirectory
-            let direct_java_exe = if cfg!(windows) {
-                path.join("java.exe")
-            } else {
-                path.join("java")
-            };
-
-            if direct_java_exe.exists() {
-                match get_java_info(&direct_java_exe).await {
-                    Ok(mut info) => {
-                        let version_clone = info.version.clone(); // Clone version before move
-                        info.source = "Launcher Directory".to_string();
-                        installations.push(info);
-                        info!(
-                            "Found valid Java executable: {} ({})",
-                            direct_java_exe.display(),
-                            version_clone
-                        );
-                    }
-                    Err(e) => {
-                        warn!(
-                            "Invalid Java executable at {}: {}",
-                            direct_java_exe.display(),
-                            e
-                        );
-                    }
-                }
-            }
-        }
-    }
-
-    info!(
-        "Found {} Java installations in launcher directory",
-        installations.len()
-    );
-    Ok(installations)
-}
-
-/// Detects all Java installations on the system
+/// Detects all Java installations on the system. Runtimes the launcher downloaded
+/// itself are listed by `java_runtime_utils::list_managed_runtimes` instead.
 pub async fn detect_java_installations() -> Result<Vec<JavaInstallation>> {
     // Check if we have cached results
     {
@@ -206,21 +116,6 @@ pub async fn detect_java_installations() -> Result<Vec<JavaInstallation>> {
     info!("Detecting Java installations...");
     let mut installations = Vec::new();
 
-    // Check in launcher's meta/java directory first
-    match detect_java_in_launcher_dir().await {
-        Ok(launcher_javas) => {
-            for installation in launcher_javas {
-                info!(
-                    "Found Java in launcher directory: {} ({})",
-                    installation.path.display(),
-                    installation.version
-                );
-                installations.push(installation);
-            }
-        }
-        Err(e) => warn!("Failed to detect Java in launcher directory: {}", e),
-    }
-
     // Look in PATH
     match detect_java_in_system_path().await {
         Ok(java_paths) => {
@@ -236,6 +131,16 @@ pub async fn detect_java_installations() -> Result<Vec<JavaInstallation>> {
         Err(e) => warn!("Failed to detect Java in PATH: {}", e),
     }
 
+    // JAVA_HOME
+    if let Some(installation) = detect_java_from_java_home().await {
+        info!(
+            "Found Java in JAVA_HOME: {} ({})",
+            installation.path.display(),
+            installation.version
+        );
+        installations.push(installation);
+    }
+
     // OS-specific paths
     match OS {
         OperatingSystem::WINDOWS => {
@@ -289,7 +194,12 @@ pub async fn detect_java_installations() -> Result<Vec<JavaInstallation>> {
         _ => warn!("Unsupported OS for Java detection"),
     }
 
-    // Remove duplicates based on path
+    // Remove duplicates based on the canonical path, symlinks like /usr/bin/java or
+    // SDKMAN's `current` point at installations that are found on their own as well.
+    // The first hit wins, so the order of the sources above decides the reported path.
+    let mut seen = HashSet::new();
+    installations.retain(|installation| seen.insert(canonical_java_path(&installation.path)));
+
     installations.sort_by(|a, b| {
         let path_cmp = a.path.to_string_lossy().cmp(&b.path.to_string_lossy());
         if path_cmp == Ordering::Equal {
@@ -309,8 +219,6 @@ pub async fn detect_java_installations() -> Result<Vec<JavaInstallation>> {
         }
     });
 
-    installations.dedup_by(|a, b| a.path == b.path);
-
     info!("Found {} unique Java installations", installations.len());
 
     // Cache the results
@@ -421,6 +329,78 @@ pub async fn get_java_info(java_path: &Path) -> Result<JavaInstallation> {
     })
 }
 
+/// Like `get_java_info`, but reads the `release` file of the installation first and
+/// only spawns `java -version` if it is missing or incomplete. Used by the detection,
+/// which would otherwise start one JVM per installed JDK.
+pub async fn probe_java_info(java_path: &Path) -> Result<JavaInstallation> {
+    let canonical = canonical_java_path(java_path);
+    for java_home in java_home_candidates(&canonical) {
+        let Ok(content) = fs::read_to_string(java_home.join("release")).await else {
+            continue;
+        };
+        if let Some(mut installation) = parse_release_file(&content) {
+            installation.path = java_path.to_path_buf();
+            return Ok(installation);
+        }
+    }
+    get_java_info(java_path).await
+}
+
+/// `bin/java` sits in the Java home, or in `jre/` of it for Java 8 JDKs.
+fn java_home_candidates(java_binary: &Path) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(home) = java_binary.parent().and_then(Path::parent) {
+        candidates.push(home.to_path_buf());
+        if home.file_name().is_some_and(|name| name == "jre") {
+            if let Some(jdk_home) = home.parent() {
+                candidates.push(jdk_home.to_path_buf());
+            }
+        }
+    }
+    candidates
+}
+
+fn canonical_java_path(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Reads `JAVA_VERSION`, `IMPLEMENTOR`, `JVM_VARIANT` and `OS_ARCH` from a `release` file.
+/// Returns `None` if version or architecture are missing, the caller has to run Java then.
+fn parse_release_file(content: &str) -> Option<JavaInstallation> {
+    let value = |key: &str| {
+        content.lines().find_map(|line| {
+            let (k, v) = line.split_once('=')?;
+            (k.trim() == key).then(|| v.trim().trim_matches('"').to_string())
+        })
+    };
+
+    let version = value("JAVA_VERSION").filter(|v| !v.is_empty())?;
+    let major_version = parse_java_major_version(&version)?;
+    let architecture = match value("OS_ARCH")?.to_lowercase().as_str() {
+        "x86_64" | "amd64" | "x64" => Architecture::X64,
+        "aarch64" | "arm64" => Architecture::AARCH64,
+        "x86" | "i386" | "i586" | "i686" => Architecture::X86,
+        "arm" | "armv7" | "armv7l" | "aarch32" => Architecture::ARM,
+        _ => return None,
+    };
+    let vm_name = value("JVM_VARIANT").map(|variant| match variant.to_lowercase().as_str() {
+        "hotspot" => "HotSpot".to_string(),
+        "openj9" => "OpenJ9".to_string(),
+        _ => variant,
+    });
+
+    Some(JavaInstallation {
+        path: PathBuf::new(),
+        version,
+        major_version,
+        is_64bit: matches!(architecture, Architecture::X64 | Architecture::AARCH64),
+        vendor: value("IMPLEMENTOR").unwrap_or_else(|| "Unknown".to_string()),
+        vm_name,
+        source: "Manual".to_string(),
+        architecture,
+    })
+}
+
 /// Finds the best Java installation for the given Minecraft version
 pub async fn find_best_java_for_minecraft(mc_version: &str) -> Result<Option<JavaInstallation>> {
     let installations = detect_java_installations().await?;
@@ -497,7 +477,7 @@ async fn detect_java_in_system_path() -> Result<Vec<JavaInstallation>> {
 
         let java_path = path.join(java_exe);
         if java_path.exists() {
-            match get_java_info(&java_path).await {
+            match probe_java_info(&java_path).await {
                 Ok(mut info) => {
                     info.source = "PATH".to_string();
                     installations.push(info);
@@ -557,7 +537,7 @@ async fn detect_java_on_windows() -> Result<Vec<JavaInstallation>> {
                         // Check if there's a bin/java.exe
                         let java_exe = path.join("bin").join("java.exe");
                         if java_exe.exists() {
-                            match get_java_info(&java_exe).await {
+                            match probe_java_info(&java_exe).await {
                                 Ok(mut info) => {
                                     info.source = format!("Windows ({})", location.display());
                                     installations.push(info);
@@ -614,7 +594,7 @@ async fn detect_java_on_macos() -> Result<Vec<JavaInstallation>> {
                         let java_home = path.join("Contents").join("Home");
                         let java_exe = java_home.join("bin").join("java");
                         if java_exe.exists() {
-                            match get_java_info(&java_exe).await {
+                            match probe_java_info(&java_exe).await {
                                 Ok(mut info) => {
                                     info.source = format!("macOS ({})", location.display());
                                     installations.push(info);
@@ -651,7 +631,7 @@ async fn detect_java_on_macos() -> Result<Vec<JavaInstallation>> {
                                 .to_string();
                             let java_exe = Path::new(&path_str).join("bin").join("java");
                             if java_exe.exists() {
-                                match get_java_info(&java_exe).await {
+                                match probe_java_info(&java_exe).await {
                                     Ok(mut info) => {
                                         info.source = "java_home command".to_string();
                                         installations.push(info);
@@ -674,54 +654,152 @@ async fn detect_java_on_macos() -> Result<Vec<JavaInstallation>> {
     Ok(installations)
 }
 
+/// Returns the Java binary of a Java home, JDK 8 layouts keep it in `jre/bin`
+fn java_binary_in_home(java_home: &Path) -> Option<PathBuf> {
+    let java_exe = get_java_executable_name();
+    [
+        java_home.join("bin").join(java_exe),
+        java_home.join("jre").join("bin").join(java_exe),
+    ]
+    .into_iter()
+    .find(|path| path.exists())
+}
+
+/// Probes every child of `location` that is a Java home
+async fn scan_java_homes(location: &Path, source: &str) -> Vec<JavaInstallation> {
+    let mut installations = Vec::new();
+    if !location.is_dir() {
+        return installations;
+    }
+
+    match fs::read_dir(location).await {
+        Ok(mut read_dir) => {
+            while let Ok(Some(entry)) = read_dir.next_entry().await {
+                let path = entry.path();
+                if !path.is_dir() {
+                    continue;
+                }
+                let Some(java_exe) = java_binary_in_home(&path) else {
+                    continue;
+                };
+                match probe_java_info(&java_exe).await {
+                    Ok(mut info) => {
+                        info.source = source.to_string();
+                        installations.push(info);
+                    }
+                    Err(e) => warn!(
+                        "Failed to get info for Java at {}: {}",
+                        java_exe.display(),
+                        e
+                    ),
+                }
+            }
+        }
+        Err(e) => warn!("Failed to read directory {}: {}", location.display(), e),
+    }
+
+    installations
+}
+
+/// Detects the Java installation `JAVA_HOME` points to
+async fn detect_java_from_java_home() -> Option<JavaInstallation> {
+    let java_home = PathBuf::from(std::env::var_os("JAVA_HOME")?);
+    let java_exe = java_binary_in_home(&java_home)?;
+    match probe_java_info(&java_exe).await {
+        Ok(mut info) => {
+            info.source = "JAVA_HOME".to_string();
+            Some(info)
+        }
+        Err(e) => {
+            warn!(
+                "Failed to get info for Java in JAVA_HOME {}: {}",
+                java_exe.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
 /// Detects Java installations on Linux
 async fn detect_java_on_linux() -> Result<Vec<JavaInstallation>> {
     info!("Detecting Java installations on Linux");
     let mut installations = Vec::new();
 
-    // Common locations to check on Linux
-    let locations = vec![
+    let home = dirs::home_dir().unwrap_or_default();
+    // Version managers can be moved with an environment variable
+    let tool_dir = |var: &str, default: &str| {
+        std::env::var_os(var)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(default))
+    };
+
+    // Directories whose children are Java homes
+    let mut locations = vec![
         // System packages
-        PathBuf::from("/usr/lib/jvm"),
+        (
+            PathBuf::from("/usr/lib/jvm"),
+            "Linux (/usr/lib/jvm)".to_string(),
+        ),
+        (
+            PathBuf::from("/usr/lib64/jvm"),
+            "Linux (/usr/lib64/jvm)".to_string(),
+        ),
         // User installations
-        PathBuf::from("/opt/java"),
-        PathBuf::from("/opt/jdk"),
-        PathBuf::from("/opt/openjdk"),
+        (PathBuf::from("/opt/java"), "Linux (/opt/java)".to_string()),
+        (PathBuf::from("/opt/jdk"), "Linux (/opt/jdk)".to_string()),
+        (
+            PathBuf::from("/opt/openjdk"),
+            "Linux (/opt/openjdk)".to_string(),
+        ),
+        // Version managers
+        (
+            tool_dir("SDKMAN_DIR", ".sdkman")
+                .join("candidates")
+                .join("java"),
+            "SDKMAN".to_string(),
+        ),
+        (
+            tool_dir("ASDF_DATA_DIR", ".asdf")
+                .join("installs")
+                .join("java"),
+            "asdf".to_string(),
+        ),
+        (
+            tool_dir("JABBA_HOME", ".jabba").join("jdk"),
+            "jabba".to_string(),
+        ),
     ];
 
-    for location in locations {
-        if !location.exists() {
-            continue;
-        }
-
-        // Try to list all directories in this location
-        match fs::read_dir(&location).await {
-            Ok(mut read_dir) => {
-                while let Ok(Some(entry)) = read_dir.next_entry().await {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        // Check for bin/java
-                        let java_exe = path.join("bin").join("java");
-                        if java_exe.exists() {
-                            match get_java_info(&java_exe).await {
-                                Ok(mut info) => {
-                                    info.source = format!("Linux ({})", location.display());
-                                    installations.push(info);
-                                }
-                                Err(e) => warn!(
-                                    "Failed to get info for Java at {}: {}",
-                                    java_exe.display(),
-                                    e
-                                ),
-                            }
-                        }
+    // Flatpak SDK extensions, e.g. /usr/lib/sdk/openjdk17 inside the sandbox
+    let flatpak_sdk_dir = Path::new("/usr/lib/sdk");
+    if let Ok(mut read_dir) = fs::read_dir(flatpak_sdk_dir).await {
+        while let Ok(Some(entry)) = read_dir.next_entry().await {
+            let path = entry.path();
+            if !entry.file_name().to_string_lossy().starts_with("openjdk") {
+                continue;
+            }
+            if let Some(java_exe) = java_binary_in_home(&path) {
+                match probe_java_info(&java_exe).await {
+                    Ok(mut info) => {
+                        info.source = "Flatpak SDK".to_string();
+                        installations.push(info);
                     }
+                    Err(e) => warn!(
+                        "Failed to get info for Java at {}: {}",
+                        java_exe.display(),
+                        e
+                    ),
                 }
             }
-            Err(e) => warn!("Failed to read directory {}: {}", location.display(), e),
+            locations.push((path.join("jvm"), "Flatpak SDK".to_string()));
         }
     }
 
+    for (location, source) in &locations {
+        installations.extend(scan_java_homes(location, source).await);
+    }
+
     // Also try to detect using common system commands
     // 1. Try update-alternatives
     match Command::new("update-alternatives")
@@ -736,7 +814,7 @@ async fn detect_java_on_linux() -> Result<Vec<JavaInstallation>> {
                 if !java_path.is_empty() {
                     let java_exe = PathBuf::from(java_path);
                     if java_exe.exists() {
-                        match get_java_info(&java_exe).await {
+                        match probe_java_info(&java_exe).await {
                             Ok(mut info) => {
                                 info.source = "update-alternatives".to_string();
                                 installations.push(info);
@@ -763,7 +841,7 @@ async fn detect_java_on_linux() -> Result<Vec<JavaInstallation>> {
                 if !java_path.is_empty() {
                     let java_exe = PathBuf::from(java_path);
                     if java_exe.exists() {
-                        match get_java_info(&java_exe).await {
+                        match probe_java_info(&java_exe).await {
                             Ok(mut info) => {
                                 info.source = "which command".to_string();
                                 installations.push(info);
@@ -798,3 +876,34 @@ pub fn get_java_executable_name() -> &'static str {
         _ => "java",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn release_file_is_read_without_running_java() {
+        let release = "IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"1.8.0_412\"\nJVM_VARIANT=\"Hotspot\"\nOS_ARCH=\"amd64\"\n";
+        let info = parse_release_file(release).unwrap();
+        assert_eq!(info.version, "1.8.0_412");
+        assert_eq!(info.major_version, 8);
+        assert_eq!(info.vendor, "Eclipse Adoptium");
+        assert_eq!(info.vm_name.as_deref(), Some("HotSpot"));
+        assert!(info.is_64bit);
+
+        // Without an architecture the caller has to fall back to `java -version`
+        assert!(parse_release_file("JAVA_VERSION=\"17.0.8\"").is_none());
+    }
+
+    #[test]
+    fn java_home_of_jdk8_jre_binary_includes_the_jdk() {
+        let candidates = java_home_candidates(Path::new("/usr/lib/jvm/jdk8/jre/bin/java"));
+        assert_eq!(
+            candidates,
+            vec![
+                PathBuf::from("/usr/lib/jvm/jdk8/jre"),
+                PathBuf::from("/usr/lib/jvm/jdk8")
+            ]
+        );
+    }
+}
@@ -0,0 +1,771 @@
+use crate::config::DOWNLOAD_CLIENT;
+use crate::error::{AppError, Result};
+use crate::minecraft::api::mc_api::MinecraftApiService;
+use crate::minecraft::downloads::java_download::JavaDownloadService;
+use crate::minecraft::downloads::mojang_java_runtime_download::{
+    MojangJavaRuntimeService, MOJANG_RUNTIME_DIR_PREFIX,
+};
+use crate::minecraft::dto::java_runtime_manifest::JavaRuntimeIndex;
+use crate::minecraft::dto::{JavaDistribution, ZuluApiResponse};
+use crate::state::profile_state::Profile;
+use crate::state::state_manager::State;
+use crate::utils::system_info::{Architecture, ARCHITECTURE, OS};
+use crate::utils::{java_detector, path_utils, trash_utils};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+const JAVA_TRASH_CATEGORY: &str = "java";
+const X86_64_SUFFIX: &str = "_x86_64";
+
+/// A runtime the launcher downloaded into `standard_meta_dir()/java`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedJavaRuntime {
+    /// Folder name, e.g. `zulu_17` or `mojang_java-runtime-gamma`
+    pub id: String,
+    pub path: PathBuf,
+    pub java_binary: Option<PathBuf>,
+    pub distribution: Option<JavaDistribution>,
+    /// Mojang component, only set for Mojang runtimes
+    pub component: Option<String>,
+    pub major_version: Option<u32>,
+    pub version: Option<String>,
+    pub vendor: Option<String>,
+    /// x86_64 runtime on an ARM Mac, used for `jre-legacy`
+    pub x86_64: bool,
+    pub size_bytes: u64,
+    pub installed_at: Option<DateTime<Utc>>,
+    pub used_by: Vec<JavaRuntimeUser>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaRuntimeUser {
+    pub profile_id: Uuid,
+    pub profile_name: String,
+    /// True if the profile's custom Java path points into this runtime,
+    /// false if the launcher picks it automatically for the game version
+    pub pinned: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaRuntimeUpdateInfo {
+    pub runtime_id: String,
+    pub installed_version: Option<String>,
+    pub latest_version: Option<String>,
+    pub update_available: bool,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaRuntimeUpdateResult {
+    pub runtime_id: String,
+    pub previous_version: Option<String>,
+    pub version: Option<String>,
+    /// Profiles whose custom Java path was moved to the new binary
+    pub repointed_profiles: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaRuntimeRemovalResult {
+    pub runtime_id: String,
+    pub trash_path: PathBuf,
+    pub freed_bytes: u64,
+    /// Profiles that were pinned to the runtime and fall back to automatic selection now
+    pub unpinned_profiles: Vec<Uuid>,
+}
+
+/// What a runtime folder name tells about its content.
+#[derive(Debug, Clone, PartialEq)]
+struct RuntimeDirName {
+    distribution: JavaDistribution,
+    component: Option<String>,
+    major_version: Option<u32>,
+    x86_64: bool,
+}
+
+// Minimal view of the Adoptium assets API
+#[derive(Deserialize)]
+struct AdoptiumRelease {
+    version: AdoptiumVersion,
+}
+
+#[derive(Deserialize)]
+struct AdoptiumVersion {
+    major: u32,
+    minor: u32,
+    security: u32,
+}
+
+/// Folder names look like `<distribution>_<major>[_x86_64]` or `mojang_<component>[_x86_64]`.
+fn parse_runtime_dir_name(name: &str) -> Option<RuntimeDirName> {
+    let (base, x86_64) = match name.strip_suffix(X86_64_SUFFIX) {
+        Some(base) => (base, true),
+        None => (name, false),
+    };
+
+    if let Some(component) = base.strip_prefix(MOJANG_RUNTIME_DIR_PREFIX) {
+        if component.is_empty() {
+            return None;
+        }
+        return Some(RuntimeDirName {
+            distribution: JavaDistribution::Mojang,
+            component: Some(component.to_string()),
+            major_version: None,
+            x86_64,
+        });
+    }
+
+    let (distribution, major) = base.split_once('_')?;
+    Some(RuntimeDirName {
+        distribution: JavaDistribution::from_name(distribution)?,
+        component: None,
+        major_version: Some(major.parse().ok()?),
+        x86_64,
+    })
+}
+
+/// Reduces a Java version to `[feature, interim, update]`, so `1.8.0_412`,
+/// `8.0.412` and `8u412` all compare equal.
+fn version_key(version: &str) -> [u32; 3] {
+    let version = version.split('+').next().unwrap_or(version);
+    let mut numbers: Vec<u32> = version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .filter_map(|part| part.parse().ok())
+        .collect();
+    if numbers.len() > 1 && numbers[0] == 1 {
+        numbers.remove(0);
+    }
+    // `8u412` has no interim number
+    if numbers.len() == 2 && version.contains('u') {
+        numbers.insert(1, 0);
+    }
+    let mut key = [0; 3];
+    for (slot, number) in key.iter_mut().zip(numbers) {
+        *slot = number;
+    }
+    key
+}
+
+fn is_newer_version(latest: &str, installed: &str) -> bool {
+    version_key(latest) > version_key(installed)
+}
+
+fn java_runtimes_dir() -> PathBuf {
+    crate::config::standard_meta_dir().join("java")
+}
+
+/// Resolves a runtime id to its folder, rejecting anything that is not a direct child.
+fn runtime_path(runtime_id: &str) -> Result<PathBuf> {
+    if runtime_id.is_empty() || runtime_id.starts_with('.') || runtime_id.contains(['/', '\\']) {
+        return Err(AppError::Other(format!(
+            "Invalid Java runtime id '{}'",
+            runtime_id
+        )));
+    }
+    let path = java_runtimes_dir().join(runtime_id);
+    if !path.is_dir() {
+        return Err(AppError::Other(format!(
+            "Java runtime '{}' is not installed",
+            runtime_id
+        )));
+    }
+    Ok(path)
+}
+
+async fn find_runtime_binary(path: &Path, parsed: &RuntimeDirName) -> Option<PathBuf> {
+    let binary = match (&parsed.distribution, parsed.major_version) {
+        (JavaDistribution::Mojang, _) => MojangJavaRuntimeService::find_java_binary(path).await,
+        (distribution, Some(major)) => {
+            JavaDownloadService::new()
+                .find_java_binary(distribution, &major, parsed.x86_64)
+                .await
+        }
+        _ => return None,
+    };
+    binary.ok()
+}
+
+/// `java -version` does not print anything through `javaw.exe`, so ask its sibling.
+async fn query_java_info(binary: &Path) -> Option<java_detector::JavaInstallation> {
+    let console_binary = binary.with_file_name(java_detector::get_java_executable_name());
+    let binary = if console_binary.exists() {
+        console_binary
+    } else {
+        binary.to_path_buf()
+    };
+    match java_detector::get_java_info(&binary).await {
+        Ok(info) => Some(info),
+        Err(e) => {
+            warn!("[Java] Could not query {:?}: {}", binary, e);
+            None
+        }
+    }
+}
+
+fn is_pinned_to(profile: &Profile, runtime_path: &Path) -> bool {
+    profile.settings.use_custom_java_path
+        && profile
+            .settings
+            .java_path
+            .as_deref()
+            .is_some_and(|java_path| Path::new(java_path).starts_with(runtime_path))
+}
+
+fn uses_custom_java(profile: &Profile) -> bool {
+    profile.settings.use_custom_java_path
+        && profile
+            .settings
+            .java_path
+            .as_deref()
+            .is_some_and(|java_path| !java_path.is_empty())
+}
+
+/// Java component and major version the piston meta of each game version asks for.
+async fn java_requirements(profiles: &[Profile]) -> HashMap<String, (String, u32)> {
+    let mut requirements = HashMap::new();
+    let api_service = MinecraftApiService::new();
+    let manifest = match api_service.get_version_manifest().await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            warn!("[Java] Could not load the version manifest: {}", e);
+            return requirements;
+        }
+    };
+
+    for profile in profiles {
+        if requirements.contains_key(&profile.game_version) {
+            continue;
+        }
+        let Some(version) = manifest
+            .versions
+            .iter()
+            .find(|v| v.id == profile.game_version)
+        else {
+            continue;
+        };
+        match api_service.get_piston_meta(&version.url).await {
+            Ok(meta) => {
+                requirements.insert(
+                    profile.game_version.clone(),
+                    (
+                        meta.java_version.component.clone(),
+                        meta.java_version.major_version.max(0) as u32,
+                    ),
+                );
+            }
+            Err(e) => warn!(
+                "[Java] Could not load piston meta for '{}': {}",
+                profile.game_version, e
+            ),
+        }
+    }
+    requirements
+}
+
+/// Lists the launcher's runtimes with version, vendor, size and the profiles using them.
+pub async fn list_managed_runtimes() -> Result<Vec<ManagedJavaRuntime>> {
+    let base_dir = java_runtimes_dir();
+    let mut runtimes = Vec::new();
+    if !base_dir.is_dir() {
+        return Ok(runtimes);
+    }
+
+    let state = State::get().await?;
+    let profiles = state.profile_manager.list_profiles().await?;
+    let requirements = java_requirements(&profiles).await;
+    let java_service = JavaDownloadService::new();
+
+    let mut entries = fs::read_dir(&base_dir).await?;
+    let mut dirs = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        // Hidden folders are backups of a running update
+        if entry.path().is_dir() && !name.starts_with('.') {
+            dirs.push((name, entry.path()));
+        }
+    }
+    dirs.sort();
+    let installed_ids: Vec<String> = dirs.iter().map(|(name, _)| name.clone()).collect();
+
+    for (id, path) in dirs {
+        let parsed = parse_runtime_dir_name(&id);
+        let java_binary = match &parsed {
+            Some(parsed) => find_runtime_binary(&path, parsed).await,
+            None => None,
+        };
+        let info = match &java_binary {
+            Some(binary) => query_java_info(binary).await,
+            None => None,
+        };
+        let mojang_info = match parsed.as_ref().map(|p| &p.distribution) {
+            Some(JavaDistribution::Mojang) => MojangJavaRuntimeService::read_installed(&path).await,
+            _ => None,
+        };
+
+        let mut used_by = Vec::new();
+        for profile in &profiles {
+            let pinned = is_pinned_to(profile, &path);
+            let automatic = !uses_custom_java(profile)
+                && match (&parsed, requirements.get(&profile.game_version)) {
+                    (Some(parsed), Some((component, major))) => {
+                        let x86_64 = java_service.needs_x86_64_java(Some(component));
+                        let mojang_id = format!(
+                            "{}{}{}",
+                            MOJANG_RUNTIME_DIR_PREFIX,
+                            component,
+                            if x86_64 { X86_64_SUFFIX } else { "" }
+                        );
+                        match parsed.distribution {
+                            JavaDistribution::Mojang => id == mojang_id,
+                            // Zulu is the fallback when the Mojang runtime is unavailable
+                            JavaDistribution::Zulu => {
+                                parsed.major_version == Some(*major)
+                                    && parsed.x86_64 == x86_64
+                                    && !installed_ids.contains(&mojang_id)
+                            }
+                            _ => false,
+                        }
+                    }
+                    _ => false,
+                };
+            if pinned || automatic {
+                used_by.push(JavaRuntimeUser {
+                    profile_id: profile.id,
+                    profile_name: profile.name.clone(),
+                    pinned,
+                });
+            }
+        }
+
+        let installed_at = match &mojang_info {
+            Some(installed) => Some(installed.installed_at),
+            None => fs::metadata(&path)
+                .await
+                .ok()
+                .and_then(|m| m.modified().ok())
+                .map(DateTime::<Utc>::from),
+        };
+
+        runtimes.push(ManagedJavaRuntime {
+            size_bytes: path_utils::calculate_dir_size(&path).await.unwrap_or(0),
+            path,
+            java_binary,
+            distribution: parsed.as_ref().map(|p| p.distribution.clone()),
+            component: parsed.as_ref().and_then(|p| p.component.clone()),
+            major_version: info
+                .as_ref()
+                .map(|i| i.major_version)
+                .or_else(|| parsed.as_ref().and_then(|p| p.major_version)),
+            version: info
+                .as_ref()
+                .map(|i| i.version.clone())
+                .or_else(|| mojang_info.as_ref().map(|m| m.version_name.clone())),
+            vendor: info.map(|i| i.vendor),
+            x86_64: parsed.as_ref().is_some_and(|p| p.x86_64),
+            installed_at,
+            used_by,
+            id,
+        });
+    }
+
+    Ok(runtimes)
+}
+
+/// Pins a profile to a managed runtime, or back to automatic selection with `None`.
+pub async fn pin_profile_runtime(profile_id: Uuid, runtime_id: Option<&str>) -> Result<()> {
+    let state = State::get().await?;
+    let mut profile = state.profile_manager.get_profile(profile_id).await?;
+
+    match runtime_id {
+        Some(runtime_id) => {
+            let path = runtime_path(runtime_id)?;
+            let parsed = parse_runtime_dir_name(runtime_id).ok_or_else(|| {
+                AppError::Other(format!("'{}' is not a launcher Java runtime", runtime_id))
+            })?;
+            let binary = find_runtime_binary(&path, &parsed).await.ok_or_else(|| {
+                AppError::Other(format!("Java runtime '{}' has no Java binary", runtime_id))
+            })?;
+            info!(
+                "[Java] Pinning profile '{}' to runtime '{}'",
+                profile.name, runtime_id
+            );
+            profile.settings.java_path = Some(binary.to_string_lossy().to_string());
+            profile.settings.use_custom_java_path = true;
+        }
+        None => {
+            info!(
+                "[Java] Unpinning Java runtime of profile '{}'",
+                profile.name
+            );
+            profile.settings.java_path = None;
+            profile.settings.use_custom_java_path = false;
+        }
+    }
+
+    state
+        .profile_manager
+        .update_profile(profile_id, profile)
+        .await
+}
+
+async fn latest_distribution_version(
+    distribution: &JavaDistribution,
+    major: u32,
+    x86_64: bool,
+) -> Result<String> {
+    match distribution {
+        JavaDistribution::Temurin => {
+            let arch = if x86_64 {
+                "x64"
+            } else {
+                ARCHITECTURE.get_simple_name()?
+            };
+            let url = format!(
+                "https://api.adoptium.net/v3/assets/latest/{}/hotspot?architecture={}&image_type=jre&os={}&vendor=eclipse",
+                major,
+                arch,
+                OS.get_adoptium_name()?
+            );
+            let releases: Vec<AdoptiumRelease> = DOWNLOAD_CLIENT
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            releases
+                .first()
+                .map(|r| {
+                    format!(
+                        "{}.{}.{}",
+                        r.version.major, r.version.minor, r.version.security
+                    )
+                })
+                .ok_or_else(|| {
+                    AppError::JavaDownload(format!("Adoptium lists no Java {} release", major))
+                })
+        }
+        JavaDistribution::Zulu => {
+            let url = distribution.get_url(&major, x86_64)?;
+            let response: ZuluApiResponse = DOWNLOAD_CLIENT
+                .get(&url)
+                .header("Accept", "application/json")
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            if response.jdk_version.is_empty() {
+                return Err(AppError::JavaDownload(
+                    "Zulu API returned no version".to_string(),
+                ));
+            }
+            Ok(response
+                .jdk_version
+                .iter()
+                .take(3)
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join("."))
+        }
+        _ => Err(AppError::JavaDownload(format!(
+            "Update checks are not supported for {}",
+            distribution.get_name()
+        ))),
+    }
+}
+
+/// Checks every managed runtime against its distribution for a newer patch release.
+pub async fn check_runtime_updates() -> Result<Vec<JavaRuntimeUpdateInfo>> {
+    let runtimes = list_managed_runtimes().await?;
+    let mut mojang_index: Option<std::result::Result<JavaRuntimeIndex, String>> = None;
+    let mut updates = Vec::new();
+
+    for runtime in runtimes {
+        let mut update = JavaRuntimeUpdateInfo {
+            runtime_id: runtime.id.clone(),
+            installed_version: runtime.version.clone(),
+            latest_version: None,
+            update_available: false,
+            note: None,
+        };
+
+        match (&runtime.distribution, &runtime.component) {
+            (Some(JavaDistribution::Mojang), Some(component)) => {
+                // Fetched once for all Mojang runtimes
+                if mojang_index.is_none() {
+                    mojang_index = Some(
+                        MinecraftApiService::new()
+                            .refresh_java_runtime_index()
+                            .await
+                            .map_err(|e| e.to_string()),
+                    );
+                }
+                let index = mojang_index.as_ref().expect("index was fetched above");
+                let arch = if runtime.x86_64 {
+                    Architecture::X64
+                } else {
+                    ARCHITECTURE
+                };
+                let installed = MojangJavaRuntimeService::read_installed(&runtime.path).await;
+                match (index, OS.get_mojang_runtime_platform(&arch)) {
+                    (Ok(index), Ok(platform)) => {
+                        match index
+                            .get(platform)
+                            .and_then(|components| components.get(component))
+                            .and_then(|builds| builds.first())
+                        {
+                            Some(build) => {
+                                update.latest_version = Some(build.version.name.clone());
+                                // The manifest changes whenever Mojang republishes a build
+                                update.update_available =
+                                    installed.as_ref().is_none_or(|installed| {
+                                        installed.manifest_sha1 != build.manifest.sha1
+                                    });
+                            }
+                            None => {
+                                update.note = Some(format!(
+                                    "Mojang no longer provides '{}' for {}",
+                                    component, platform
+                                ))
+                            }
+                        }
+                    }
+                    (Err(e), _) => update.note = Some(e.clone()),
+                    (_, Err(e)) => update.note = Some(e.to_string()),
+                }
+            }
+            (Some(distribution), _) => match runtime.major_version {
+                Some(major) => {
+                    match latest_distribution_version(distribution, major, runtime.x86_64).await {
+                        Ok(latest) => {
+                            update.update_available = runtime
+                                .version
+                                .as_deref()
+                                .is_none_or(|installed| is_newer_version(&latest, installed));
+                            update.latest_version = Some(latest);
+                        }
+                        Err(e) => update.note = Some(e.to_string()),
+                    }
+                }
+                None => update.note = Some("Unknown Java version".to_string()),
+            },
+            _ => update.note = Some("Not installed by the launcher".to_string()),
+        }
+
+        updates.push(update);
+    }
+
+    Ok(updates)
+}
+
+async fn ensure_no_running_game(action: &str) -> Result<()> {
+    let state = State::get().await?;
+    if !state.process_manager.list_processes().await.is_empty() {
+        return Err(AppError::Other(format!(
+            "Cannot {} a Java runtime while Minecraft is running",
+            action
+        )));
+    }
+    Ok(())
+}
+
+/// Points custom Java paths into `runtime_path` at `new_binary` if their old target is gone.
+async fn repoint_profiles(runtime_path: &Path, new_binary: &Path) -> Result<Vec<Uuid>> {
+    let state = State::get().await?;
+    let mut repointed = Vec::new();
+    for mut profile in state.profile_manager.list_profiles().await? {
+        let Some(java_path) = profile.settings.java_path.clone() else {
+            continue;
+        };
+        let java_path = PathBuf::from(java_path);
+        if !java_path.starts_with(runtime_path) || java_path == new_binary || java_path.exists() {
+            continue;
+        }
+        info!(
+            "[Java] Moving Java path of profile '{}' to {:?}",
+            profile.name, new_binary
+        );
+        profile.settings.java_path = Some(new_binary.to_string_lossy().to_string());
+        let profile_id = profile.id;
+        state
+            .profile_manager
+            .update_profile(profile_id, profile)
+            .await?;
+        repointed.push(profile_id);
+    }
+    Ok(repointed)
+}
+
+/// Replaces a runtime with the latest release of its distribution. The old
+/// runtime is kept aside until the new one is in place and restored on failure.
+pub async fn update_runtime(runtime_id: &str) -> Result<JavaRuntimeUpdateResult> {
+    ensure_no_running_game("update").await?;
+    let path = runtime_path(runtime_id)?;
+    let parsed = parse_runtime_dir_name(runtime_id).ok_or_else(|| {
+        AppError::Other(format!("'{}' is not a launcher Java runtime", runtime_id))
+    })?;
+    let previous_version = match find_runtime_binary(&path, &parsed).await {
+        Some(binary) => query_java_info(&binary).await.map(|i| i.version),
+        None => None,
+    };
+
+    let new_binary = match (
+        &parsed.distribution,
+        &parsed.component,
+        parsed.major_version,
+    ) {
+        (JavaDistribution::Mojang, Some(component), _) => {
+            // Updated in place, unchanged files are kept
+            MinecraftApiService::new()
+                .refresh_java_runtime_index()
+                .await?;
+            MojangJavaRuntimeService::new()
+                .get_or_install(component, parsed.x86_64)
+                .await?
+        }
+        (distribution, _, Some(major)) => {
+            let backup = path.with_file_name(format!(".{}.old", runtime_id));
+            if backup.exists() {
+                fs::remove_dir_all(&backup).await?;
+            }
+            fs::rename(&path, &backup).await?;
+
+            let java_service = JavaDownloadService::new();
+            let installed = match java_service
+                .download_java(major, distribution, parsed.x86_64)
+                .await
+            {
+                Ok(_) => {
+                    java_service
+                        .find_java_binary(distribution, &major, parsed.x86_64)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            match installed {
+                Ok(binary) => {
+                    if let Err(e) = fs::remove_dir_all(&backup).await {
+                        warn!("[Java] Could not remove old runtime {:?}: {}", backup, e);
+                    }
+                    binary
+                }
+                Err(e) => {
+                    warn!(
+                        "[Java] Update of '{}' failed, restoring the old runtime: {}",
+                        runtime_id, e
+                    );
+                    if path.exists() {
+                        fs::remove_dir_all(&path).await?;
+                    }
+                    fs::rename(&backup, &path).await?;
+                    return Err(e);
+                }
+            }
+        }
+        _ => {
+            return Err(AppError::Other(format!(
+                "'{}' is not a launcher Java runtime",
+                runtime_id
+            )))
+        }
+    };
+
+    let repointed_profiles = repoint_profiles(&path, &new_binary).await?;
+    let version = query_java_info(&new_binary).await.map(|i| i.version);
+    info!(
+        "[Java] Updated runtime '{}' from {:?} to {:?}",
+        runtime_id, previous_version, version
+    );
+
+    Ok(JavaRuntimeUpdateResult {
+        runtime_id: runtime_id.to_string(),
+        previous_version,
+        version,
+        repointed_profiles,
+    })
+}
+
+/// Moves a runtime into the trash and unpins the profiles that used it.
+pub async fn remove_runtime(runtime_id: &str) -> Result<JavaRuntimeRemovalResult> {
+    ensure_no_running_game("remove").await?;
+    let path = runtime_path(runtime_id)?;
+    let freed_bytes = path_utils::calculate_dir_size(&path).await.unwrap_or(0);
+
+    let state = State::get().await?;
+    let mut unpinned_profiles = Vec::new();
+    for mut profile in state.profile_manager.list_profiles().await? {
+        if !profile
+            .settings
+            .java_path
+            .as_deref()
+            .is_some_and(|java_path| Path::new(java_path).starts_with(&path))
+        {
+            continue;
+        }
+        profile.settings.java_path = None;
+        profile.settings.use_custom_java_path = false;
+        let profile_id = profile.id;
+        state
+            .profile_manager
+            .update_profile(profile_id, profile)
+            .await?;
+        unpinned_profiles.push(profile_id);
+    }
+
+    let trash_path = trash_utils::move_path_to_trash(&path, Some(JAVA_TRASH_CATEGORY)).await?;
+    info!(
+        "[Java] Moved runtime '{}' to the trash, {} profile(s) unpinned",
+        runtime_id,
+        unpinned_profiles.len()
+    );
+
+    Ok(JavaRuntimeRemovalResult {
+        runtime_id: runtime_id.to_string(),
+        trash_path,
+        freed_bytes,
+        unpinned_profiles,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_dir_names_are_parsed() {
+        assert_eq!(
+            parse_runtime_dir_name("zulu_17"),
+            Some(RuntimeDirName {
+                distribution: JavaDistribution::Zulu,
+                component: None,
+                major_version: Some(17),
+                x86_64: false,
+            })
+        );
+        assert_eq!(
+            parse_runtime_dir_name("mojang_jre-legacy_x86_64"),
+            Some(RuntimeDirName {
+                distribution: JavaDistribution::Mojang,
+                component: Some("jre-legacy".to_string()),
+                major_version: None,
+                x86_64: true,
+            })
+        );
+        assert_eq!(parse_runtime_dir_name("openjdk_17"), None);
+        assert_eq!(parse_runtime_dir_name("temurin_latest"), None);
+    }
+
+    #[test]
+    fn java_versions_compare_by_update() {
+        assert!(is_newer_version("8.0.412", "1.8.0_402"));
+        assert!(is_newer_version("21.0.4", "21.0.3+9"));
+        assert!(!is_newer_version("17.0.12", "17.0.12+7-LTS"));
+        assert!(!is_newer_version("8.0.402", "8u402"));
+    }
+}
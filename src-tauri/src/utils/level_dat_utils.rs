@@ -0,0 +1,640 @@
+use crate::error::{AppError, Result};
+use crate::state::state_manager::State;
+use crate::state::world_backup_state::WorldBackupTrigger;
+use crate::utils::world_utils;
+use fastnbt::{from_bytes, to_bytes, IntArray, Value};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+type Compound = HashMap<String, Value>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LevelSpawn {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    #[serde(default)]
+    pub angle: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LevelWeather {
+    pub raining: bool,
+    pub thundering: bool,
+    /// Ticks until rain starts or stops
+    pub rain_time: i32,
+    /// Ticks until thunder starts or stops
+    pub thunder_time: i32,
+    /// Ticks of clear weather set by `/weather clear`
+    pub clear_weather_time: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelDataPacks {
+    pub enabled: Vec<String>,
+    pub disabled: Vec<String>,
+}
+
+/// The editable parts of a world's level.dat.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelDatSettings {
+    pub level_name: Option<String>,
+    /// Read only, the Minecraft version that saved the world last
+    pub version_name: Option<String>,
+    pub seed: Option<i64>,
+    pub spawn: Option<LevelSpawn>,
+    /// Values as strings (`"true"`, `"3"`), however the world stores them
+    pub game_rules: BTreeMap<String, String>,
+    pub day_time: Option<i64>,
+    /// Read only, ticks the world has been running
+    pub game_time: Option<i64>,
+    pub weather: Option<LevelWeather>,
+    pub difficulty: Option<i8>,
+    pub difficulty_locked: Option<bool>,
+    pub allow_commands: Option<bool>,
+    pub data_packs: Option<LevelDataPacks>,
+}
+
+/// Changes to a level.dat, fields that are `None` are left as they are.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LevelDatUpdate {
+    /// Only affects chunks that have not been generated yet
+    pub seed: Option<i64>,
+    pub spawn: Option<LevelSpawn>,
+    /// Only the given rules are changed
+    pub game_rules: Option<BTreeMap<String, String>>,
+    pub day_time: Option<i64>,
+    pub weather: Option<LevelWeather>,
+    pub difficulty: Option<i8>,
+    pub difficulty_locked: Option<bool>,
+    pub allow_commands: Option<bool>,
+    pub data_packs: Option<LevelDataPacks>,
+}
+
+/// Reads and parses a gzip compressed level.dat.
+pub async fn read_level_dat_nbt(level_dat_path: &Path) -> Result<Value> {
+    let compressed_bytes = fs::read(level_dat_path).await?;
+    let mut decompressed_bytes = Vec::new();
+    std::io::Read::read_to_end(
+        &mut GzDecoder::new(&compressed_bytes[..]),
+        &mut decompressed_bytes,
+    )?;
+    Ok(from_bytes(&decompressed_bytes)?)
+}
+
+/// Compresses and writes a level.dat. The file is replaced in one step, so Minecraft
+/// never sees a half written level.dat.
+pub async fn write_level_dat_nbt(level_dat_path: &Path, nbt_value: &Value) -> Result<()> {
+    let decompressed_bytes = to_bytes(nbt_value)
+        .map_err(|e| AppError::Other(format!("NBT serialization error: {}", e)))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    std::io::Write::write_all(&mut encoder, &decompressed_bytes)?;
+    let compressed_bytes = encoder.finish()?;
+
+    let temp_path = level_dat_path.with_file_name("level.dat.tmp");
+    fs::write(&temp_path, &compressed_bytes).await?;
+    fs::rename(&temp_path, level_dat_path).await?;
+    Ok(())
+}
+
+fn data_compound(nbt_value: &Value) -> Result<&Compound> {
+    match nbt_value {
+        Value::Compound(root) => match root.get("Data") {
+            Some(Value::Compound(data)) => Ok(data),
+            _ => Err(AppError::Other(
+                "level.dat has no Data compound".to_string(),
+            )),
+        },
+        _ => Err(AppError::Other(
+            "level.dat root is not a compound".to_string(),
+        )),
+    }
+}
+
+fn data_compound_mut(nbt_value: &mut Value) -> Result<&mut Compound> {
+    match nbt_value {
+        Value::Compound(root) => match root.get_mut("Data") {
+            Some(Value::Compound(data)) => Ok(data),
+            _ => Err(AppError::Other(
+                "level.dat has no Data compound".to_string(),
+            )),
+        },
+        _ => Err(AppError::Other(
+            "level.dat root is not a compound".to_string(),
+        )),
+    }
+}
+
+fn get_string(compound: &Compound, key: &str) -> Option<String> {
+    match compound.get(key) {
+        Some(Value::String(value)) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn get_long(compound: &Compound, key: &str) -> Option<i64> {
+    match compound.get(key)? {
+        Value::Long(value) => Some(*value),
+        Value::Int(value) => Some(*value as i64),
+        _ => None,
+    }
+}
+
+fn get_int(compound: &Compound, key: &str) -> Option<i32> {
+    match compound.get(key)? {
+        Value::Int(value) => Some(*value),
+        Value::Short(value) => Some(*value as i32),
+        Value::Byte(value) => Some(*value as i32),
+        _ => None,
+    }
+}
+
+fn get_byte(compound: &Compound, key: &str) -> Option<i8> {
+    match compound.get(key)? {
+        Value::Byte(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn get_float(compound: &Compound, key: &str) -> Option<f32> {
+    match compound.get(key)? {
+        Value::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn get_string_list(compound: &Compound, key: &str) -> Vec<String> {
+    match compound.get(key) {
+        Some(Value::List(values)) => values
+            .iter()
+            .filter_map(|value| match value {
+                Value::String(value) => Some(value.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn game_rule_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Byte(value) => Some((*value != 0).to_string()),
+        Value::Int(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Converts a game rule value to the tag type the world already uses for that rule.
+/// Older worlds store every rule as a string.
+fn game_rule_to_nbt(rule: &str, value: &str, existing: Option<&Value>) -> Result<Value> {
+    let invalid = || {
+        AppError::InvalidInput(format!(
+            "Invalid value '{}' for game rule '{}'",
+            value, rule
+        ))
+    };
+    Ok(match existing {
+        Some(Value::Byte(_)) => Value::Byte(value.parse::<bool>().map_err(|_| invalid())? as i8),
+        Some(Value::Int(_)) => Value::Int(value.parse().map_err(|_| invalid())?),
+        _ => Value::String(value.to_string()),
+    })
+}
+
+/// Reads the editable settings out of a parsed level.dat.
+pub fn settings_from_nbt(nbt_value: &Value) -> Result<LevelDatSettings> {
+    let data = data_compound(nbt_value)?;
+
+    // 1.16+ keeps the seed in WorldGenSettings, older worlds in RandomSeed
+    let seed = match data.get("WorldGenSettings") {
+        Some(Value::Compound(settings)) => get_long(settings, "seed"),
+        _ => None,
+    }
+    .or_else(|| get_long(data, "RandomSeed"));
+
+    // 1.21.9+ moved the spawn into a `spawn` compound
+    let spawn = match (
+        get_int(data, "SpawnX"),
+        get_int(data, "SpawnY"),
+        get_int(data, "SpawnZ"),
+    ) {
+        (Some(x), Some(y), Some(z)) => Some(LevelSpawn {
+            x,
+            y,
+            z,
+            angle: get_float(data, "SpawnAngle").unwrap_or_default(),
+        }),
+        _ => match data.get("spawn") {
+            Some(Value::Compound(spawn)) => match spawn.get("pos") {
+                Some(Value::IntArray(pos)) if pos.len() == 3 => Some(LevelSpawn {
+                    x: pos[0],
+                    y: pos[1],
+                    z: pos[2],
+                    angle: get_float(spawn, "yaw").unwrap_or_default(),
+                }),
+                _ => None,
+            },
+            _ => None,
+        },
+    };
+
+    let game_rules = match data.get("GameRules") {
+        Some(Value::Compound(rules)) => rules
+            .iter()
+            .filter_map(|(rule, value)| Some((rule.clone(), game_rule_to_string(value)?)))
+            .collect(),
+        _ => BTreeMap::new(),
+    };
+
+    let weather = match (get_byte(data, "raining"), get_byte(data, "thundering")) {
+        (Some(raining), Some(thundering)) => Some(LevelWeather {
+            raining: raining != 0,
+            thundering: thundering != 0,
+            rain_time: get_int(data, "rainTime").unwrap_or_default(),
+            thunder_time: get_int(data, "thunderTime").unwrap_or_default(),
+            clear_weather_time: get_int(data, "clearWeatherTime").unwrap_or_default(),
+        }),
+        _ => None,
+    };
+
+    let data_packs = match data.get("DataPacks") {
+        Some(Value::Compound(packs)) => Some(LevelDataPacks {
+            enabled: get_string_list(packs, "Enabled"),
+            disabled: get_string_list(packs, "Disabled"),
+        }),
+        _ => None,
+    };
+
+    Ok(LevelDatSettings {
+        level_name: get_string(data, "LevelName"),
+        version_name: match data.get("Version") {
+            Some(Value::Compound(version)) => get_string(version, "Name"),
+            _ => None,
+        },
+        seed,
+        spawn,
+        game_rules,
+        day_time: get_long(data, "DayTime"),
+        game_time: get_long(data, "Time"),
+        weather,
+        difficulty: get_byte(data, "Difficulty"),
+        difficulty_locked: get_byte(data, "DifficultyLocked").map(|value| value != 0),
+        allow_commands: get_byte(data, "allowCommands").map(|value| value != 0),
+        data_packs,
+    })
+}
+
+/// 1.16 to 1.18 repeat the world seed in the generator and biome source of every dimension,
+/// which is what terrain generation actually reads. Seeds that differ from the world seed
+/// belong to data pack dimensions with a fixed seed and are kept.
+fn replace_dimension_seeds(settings: &mut Compound, previous: i64, seed: i64) {
+    let Some(Value::Compound(dimensions)) = settings.get_mut("dimensions") else {
+        return;
+    };
+    let replace = |compound: &mut Compound| {
+        if get_long(compound, "seed") == Some(previous) {
+            compound.insert("seed".to_string(), Value::Long(seed));
+        }
+    };
+    for dimension in dimensions.values_mut() {
+        let Value::Compound(dimension) = dimension else {
+            continue;
+        };
+        let Some(Value::Compound(generator)) = dimension.get_mut("generator") else {
+            continue;
+        };
+        replace(generator);
+        if let Some(Value::Compound(biome_source)) = generator.get_mut("biome_source") {
+            replace(biome_source);
+        }
+    }
+}
+
+/// Applies an update to a parsed level.dat. Nothing is changed if any value is invalid.
+pub fn apply_update_to_nbt(nbt_value: &mut Value, update: &LevelDatUpdate) -> Result<()> {
+    if let Some(difficulty) = update.difficulty {
+        if !(0..=3).contains(&difficulty) {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid difficulty {}, expected 0 (peaceful) to 3 (hard)",
+                difficulty
+            )));
+        }
+    }
+    if let Some(packs) = &update.data_packs {
+        if let Some(pack) = packs.enabled.iter().find(|p| packs.disabled.contains(p)) {
+            return Err(AppError::InvalidInput(format!(
+                "Data pack '{}' can't be enabled and disabled at once",
+                pack
+            )));
+        }
+    }
+    // Converted up front, so an invalid rule doesn't leave a partial update behind
+    let game_rules = match &update.game_rules {
+        Some(rules) => {
+            let existing = match data_compound(nbt_value)?.get("GameRules") {
+                Some(Value::Compound(existing)) => Some(existing),
+                _ => None,
+            };
+            rules
+                .iter()
+                .map(|(rule, value)| {
+                    let current = existing.and_then(|existing| existing.get(rule));
+                    Ok((rule.clone(), game_rule_to_nbt(rule, value, current)?))
+                })
+                .collect::<Result<Vec<_>>>()?
+        }
+        None => Vec::new(),
+    };
+
+    let data = data_compound_mut(nbt_value)?;
+
+    if let Some(seed) = update.seed {
+        let mut written = false;
+        if let Some(Value::Compound(settings)) = data.get_mut("WorldGenSettings") {
+            let previous = get_long(settings, "seed");
+            settings.insert("seed".to_string(), Value::Long(seed));
+            if let Some(previous) = previous {
+                replace_dimension_seeds(settings, previous, seed);
+            }
+            written = true;
+        }
+        if !written || data.contains_key("RandomSeed") {
+            data.insert("RandomSeed".to_string(), Value::Long(seed));
+        }
+    }
+
+    if let Some(spawn) = update.spawn {
+        let legacy_spawn = data.contains_key("SpawnX");
+        match data.get_mut("spawn") {
+            Some(Value::Compound(spawn_compound)) if !legacy_spawn => {
+                spawn_compound.insert(
+                    "pos".to_string(),
+                    Value::IntArray(IntArray::new(vec![spawn.x, spawn.y, spawn.z])),
+                );
+                spawn_compound.insert("yaw".to_string(), Value::Float(spawn.angle));
+            }
+            _ => {
+                data.insert("SpawnX".to_string(), Value::Int(spawn.x));
+                data.insert("SpawnY".to_string(), Value::Int(spawn.y));
+                data.insert("SpawnZ".to_string(), Value::Int(spawn.z));
+                data.insert("SpawnAngle".to_string(), Value::Float(spawn.angle));
+            }
+        }
+    }
+
+    if !game_rules.is_empty() {
+        let rules = data
+            .entry("GameRules".to_string())
+            .or_insert_with(|| Value::Compound(HashMap::new()));
+        if let Value::Compound(rules) = rules {
+            rules.extend(game_rules);
+        }
+    }
+
+    if let Some(day_time) = update.day_time {
+        data.insert("DayTime".to_string(), Value::Long(day_time));
+    }
+
+    if let Some(weather) = update.weather {
+        data.insert("raining".to_string(), Value::Byte(weather.raining as i8));
+        data.insert(
+            "thundering".to_string(),
+            Value::Byte(weather.thundering as i8),
+        );
+        data.insert("rainTime".to_string(), Value::Int(weather.rain_time));
+        data.insert("thunderTime".to_string(), Value::Int(weather.thunder_time));
+        data.insert(
+            "clearWeatherTime".to_string(),
+            Value::Int(weather.clear_weather_time),
+        );
+    }
+
+    if let Some(difficulty) = update.difficulty {
+        data.insert("Difficulty".to_string(), Value::Byte(difficulty));
+    }
+    if let Some(locked) = update.difficulty_locked {
+        data.insert("DifficultyLocked".to_string(), Value::Byte(locked as i8));
+    }
+    if let Some(allow_commands) = update.allow_commands {
+        data.insert(
+            "allowCommands".to_string(),
+            Value::Byte(allow_commands as i8),
+        );
+    }
+
+    if let Some(packs) = &update.data_packs {
+        let to_list =
+            |names: &[String]| Value::List(names.iter().cloned().map(Value::String).collect());
+        let mut compound = HashMap::new();
+        compound.insert("Enabled".to_string(), to_list(&packs.enabled));
+        compound.insert("Disabled".to_string(), to_list(&packs.disabled));
+        data.insert("DataPacks".to_string(), Value::Compound(compound));
+    }
+
+    Ok(())
+}
+
+async fn level_dat_path(profile_id: Uuid, world_folder: &str) -> Result<PathBuf> {
+    world_utils::validate_world_folder_name(world_folder)?;
+    let state = State::get().await?;
+    let level_dat_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?
+        .join("saves")
+        .join(world_folder)
+        .join("level.dat");
+    if !level_dat_path.is_file() {
+        return Err(AppError::WorldNotFound {
+            profile_id,
+            world_folder: world_folder.to_string(),
+        });
+    }
+    Ok(level_dat_path)
+}
+
+/// Reads the editable settings of a world. Works while the world is open, but shows
+/// the state of its last save.
+pub async fn read_level_dat(profile_id: Uuid, world_folder: &str) -> Result<LevelDatSettings> {
+    let level_dat_path = level_dat_path(profile_id, world_folder).await?;
+    settings_from_nbt(&read_level_dat_nbt(&level_dat_path).await?)
+}
+
+/// Edits the level.dat of a world that is not open in Minecraft. The world is backed
+/// up first, so the edit can be undone by restoring that backup.
+pub async fn update_level_dat(
+    profile_id: Uuid,
+    world_folder: &str,
+    update: &LevelDatUpdate,
+) -> Result<LevelDatSettings> {
+    let level_dat_path = level_dat_path(profile_id, world_folder).await?;
+    let mut nbt_value = read_level_dat_nbt(&level_dat_path).await?;
+    // Validates the update before a backup is made for nothing
+    apply_update_to_nbt(&mut nbt_value, update)?;
+
+    // Refuses worlds that are locked by a running game
+    let state = State::get().await?;
+    let backup = state
+        .world_backup_manager
+        .backup_world(profile_id, world_folder, WorldBackupTrigger::PreEdit)
+        .await?;
+    info!(
+        "Editing level.dat of world '{}' in profile {} (backup '{}')",
+        world_folder, profile_id, backup.file_name
+    );
+
+    write_level_dat_nbt(&level_dat_path, &nbt_value).await?;
+    settings_from_nbt(&nbt_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level_dat(data: Vec<(&str, Value)>) -> Value {
+        let data = data
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect();
+        let mut root = HashMap::new();
+        root.insert("Data".to_string(), Value::Compound(data));
+        Value::Compound(root)
+    }
+
+    #[test]
+    fn applies_update_and_keeps_game_rule_types() {
+        let mut rules = HashMap::new();
+        rules.insert("keepInventory".to_string(), Value::String("false".into()));
+        rules.insert("spawn_radius".to_string(), Value::Int(10));
+        let mut nbt_value = level_dat(vec![
+            ("RandomSeed", Value::Long(1)),
+            ("SpawnX", Value::Int(0)),
+            ("SpawnY", Value::Int(64)),
+            ("SpawnZ", Value::Int(0)),
+            ("GameRules", Value::Compound(rules)),
+            ("allowCommands", Value::Byte(0)),
+        ]);
+
+        let update = LevelDatUpdate {
+            seed: Some(42),
+            spawn: Some(LevelSpawn {
+                x: 100,
+                y: 70,
+                z: -20,
+                angle: 0.0,
+            }),
+            game_rules: Some(BTreeMap::from([
+                ("keepInventory".to_string(), "true".to_string()),
+                ("spawn_radius".to_string(), "0".to_string()),
+            ])),
+            allow_commands: Some(true),
+            ..Default::default()
+        };
+        apply_update_to_nbt(&mut nbt_value, &update).unwrap();
+
+        let settings = settings_from_nbt(&nbt_value).unwrap();
+        assert_eq!(settings.seed, Some(42));
+        assert_eq!(
+            settings.spawn.map(|s| (s.x, s.y, s.z)),
+            Some((100, 70, -20))
+        );
+        assert_eq!(settings.game_rules["keepInventory"], "true");
+        assert_eq!(settings.allow_commands, Some(true));
+        let rules = match data_compound(&nbt_value).unwrap().get("GameRules") {
+            Some(Value::Compound(rules)) => rules,
+            _ => panic!("GameRules missing"),
+        };
+        assert_eq!(rules.get("spawn_radius"), Some(&Value::Int(0)));
+
+        // An invalid rule value leaves the level.dat untouched
+        let invalid = LevelDatUpdate {
+            day_time: Some(6000),
+            game_rules: Some(BTreeMap::from([(
+                "spawn_radius".to_string(),
+                "far".to_string(),
+            )])),
+            ..Default::default()
+        };
+        assert!(apply_update_to_nbt(&mut nbt_value, &invalid).is_err());
+        assert_eq!(settings_from_nbt(&nbt_value).unwrap().day_time, None);
+    }
+
+    #[test]
+    fn seed_update_reaches_dimension_generators() {
+        fn compound(entries: Vec<(&str, Value)>) -> Value {
+            Value::Compound(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect(),
+            )
+        }
+        fn generator(seed: i64) -> Value {
+            compound(vec![(
+                "generator",
+                compound(vec![
+                    ("type", Value::String("minecraft:noise".into())),
+                    ("seed", Value::Long(seed)),
+                    ("biome_source", compound(vec![("seed", Value::Long(seed))])),
+                ]),
+            )])
+        }
+        // 1.18 layout
+        let mut nbt_value = level_dat(vec![(
+            "WorldGenSettings",
+            compound(vec![
+                ("seed", Value::Long(1)),
+                (
+                    "dimensions",
+                    compound(vec![
+                        ("minecraft:overworld", generator(1)),
+                        ("minecraft:the_nether", generator(1)),
+                        ("custom:fixed", generator(7)),
+                    ]),
+                ),
+            ]),
+        )]);
+
+        let update = LevelDatUpdate {
+            seed: Some(42),
+            ..Default::default()
+        };
+        apply_update_to_nbt(&mut nbt_value, &update).unwrap();
+
+        assert_eq!(settings_from_nbt(&nbt_value).unwrap().seed, Some(42));
+        let Some(Value::Compound(settings)) =
+            data_compound(&nbt_value).unwrap().get("WorldGenSettings")
+        else {
+            panic!("WorldGenSettings missing");
+        };
+        let Some(Value::Compound(dimensions)) = settings.get("dimensions") else {
+            panic!("dimensions missing");
+        };
+        let seeds = |id: &str| {
+            let Some(Value::Compound(dimension)) = dimensions.get(id) else {
+                panic!("{} missing", id);
+            };
+            let Some(Value::Compound(generator)) = dimension.get("generator") else {
+                panic!("generator of {} missing", id);
+            };
+            let Some(Value::Compound(biome_source)) = generator.get("biome_source") else {
+                panic!("biome source of {} missing", id);
+            };
+            (get_long(generator, "seed"), get_long(biome_source, "seed"))
+        };
+        assert_eq!(seeds("minecraft:overworld"), (Some(42), Some(42)));
+        assert_eq!(seeds("minecraft:the_nether"), (Some(42), Some(42)));
+        assert_eq!(seeds("custom:fixed"), (Some(7), Some(7)));
+        assert!(!data_compound(&nbt_value)
+            .unwrap()
+            .contains_key("RandomSeed"));
+    }
+}
@@ -1483,7 +1483,7 @@ pub struct ServerPingInfo {
 // Simplified version for pinging, add more fields if needed
 impl ServerPingInfo {
     // Helper to create an error response
-    fn error(address: &str, error_msg: String, latency: Option<u64>) -> Self {
+    pub(crate) fn error(address: &str, error_msg: String, latency: Option<u64>) -> Self {
         warn!("[Server Ping] Error pinging {}: {}", address, error_msg);
         ServerPingInfo {
             description: None,
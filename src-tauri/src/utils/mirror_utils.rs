@@ -0,0 +1,381 @@
+use crate::state::config_state::{MirrorMatch, MirrorRule};
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION, PROXY_AUTHORIZATION};
+use reqwest::{Client, IntoUrl, Method, Request, Response, StatusCode, Url};
+use serde::Serialize;
+use std::fmt::Display;
+use std::ops::Deref;
+use std::sync::RwLock;
+
+// Cache of the mirror rules from the launcher config, read synchronously for every request
+static MIRROR_RULES: Lazy<RwLock<Vec<MirrorRule>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Replaces the active mirror rules (called whenever the launcher config is loaded or changed)
+pub fn update_mirror_rules(rules: Vec<MirrorRule>) {
+    if let Ok(mut guard) = MIRROR_RULES.write() {
+        *guard = rules;
+    }
+}
+
+/// URLs to try for `url` in order: the mirrors of the first matching rule and,
+/// unless disabled, the original URL as last resort.
+pub fn candidate_urls(url: &Url) -> Vec<Url> {
+    match MIRROR_RULES.read() {
+        Ok(rules) => rewrite_candidates(&rules, url),
+        Err(_) => vec![url.clone()],
+    }
+}
+
+fn rewrite_candidates(rules: &[MirrorRule], url: &Url) -> Vec<Url> {
+    let Some(rule) = rules
+        .iter()
+        .find(|rule| rule.enabled && rule_matches(rule, url))
+    else {
+        return vec![url.clone()];
+    };
+
+    let mut candidates: Vec<Url> = rule
+        .mirrors
+        .iter()
+        .filter_map(|mirror| match rewrite_url(rule, mirror, url) {
+            Some(rewritten) => Some(rewritten),
+            None => {
+                warn!("Ignoring invalid mirror '{}' for {}", mirror, url);
+                None
+            }
+        })
+        .collect();
+    if rule.fallback_to_origin || candidates.is_empty() {
+        candidates.push(url.clone());
+    }
+    candidates.dedup();
+    candidates
+}
+
+fn rule_matches(rule: &MirrorRule, url: &Url) -> bool {
+    match rule.match_type {
+        MirrorMatch::Host => url
+            .host_str()
+            .is_some_and(|host| host.eq_ignore_ascii_case(rule.pattern.trim())),
+        MirrorMatch::Prefix => !rule.pattern.is_empty() && url.as_str().starts_with(&rule.pattern),
+    }
+}
+
+fn rewrite_url(rule: &MirrorRule, mirror: &str, url: &Url) -> Option<Url> {
+    let mirror = mirror.trim();
+    match rule.match_type {
+        // A bare host only swaps the host, a base URL replaces scheme, host and port
+        MirrorMatch::Host if !mirror.contains("://") => {
+            let mut rewritten = url.clone();
+            rewritten.set_host(Some(mirror)).ok()?;
+            Some(rewritten)
+        }
+        MirrorMatch::Host => {
+            let mut path_and_query = url.path().to_string();
+            if let Some(query) = url.query() {
+                path_and_query.push('?');
+                path_and_query.push_str(query);
+            }
+            Url::parse(&format!(
+                "{}{}",
+                mirror.trim_end_matches('/'),
+                path_and_query
+            ))
+            .ok()
+        }
+        MirrorMatch::Prefix => {
+            let rest = url.as_str().strip_prefix(&rule.pattern)?;
+            Url::parse(&format!("{}{}", mirror, rest)).ok()
+        }
+    }
+}
+
+/// Responses that mean "this mirror can't serve it", the next candidate gets a chance.
+fn should_try_next(status: StatusCode) -> bool {
+    status.is_server_error()
+        || matches!(
+            status,
+            StatusCode::NOT_FOUND
+                | StatusCode::FORBIDDEN
+                | StatusCode::REQUEST_TIMEOUT
+                | StatusCode::TOO_MANY_REQUESTS
+        )
+}
+
+/// Only these are sent to mirrors, anything else may not be repeated and goes to the origin.
+fn is_mirrorable(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD)
+}
+
+// API key header of the CurseForge API
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Credentials are meant for the origin, a mirror on another host never gets them.
+fn strip_credentials_for(request: &mut Request, origin: &Url) {
+    if request.url().host_str() != origin.host_str()
+        || request.url().port_or_known_default() != origin.port_or_known_default()
+    {
+        let headers = request.headers_mut();
+        let had_api_key = headers.remove(API_KEY_HEADER).is_some();
+        if headers.remove(AUTHORIZATION).is_some() || had_api_key {
+            debug!(
+                "Not sending credentials for {} to mirror {}",
+                origin,
+                request.url()
+            );
+        }
+        headers.remove(PROXY_AUTHORIZATION);
+    }
+}
+
+/// `reqwest::Client` that applies the mirror rules to GET and HEAD requests and fails
+/// over to the next mirror if one is unreachable or can't serve the file.
+#[derive(Clone, Debug)]
+pub struct MirroredClient {
+    inner: Client,
+}
+
+impl MirroredClient {
+    pub fn new(inner: Client) -> Self {
+        Self { inner }
+    }
+
+    pub fn get<U: IntoUrl>(&self, url: U) -> MirroredRequestBuilder {
+        self.request(Method::GET, url)
+    }
+
+    pub fn post<U: IntoUrl>(&self, url: U) -> MirroredRequestBuilder {
+        self.request(Method::POST, url)
+    }
+
+    pub fn put<U: IntoUrl>(&self, url: U) -> MirroredRequestBuilder {
+        self.request(Method::PUT, url)
+    }
+
+    pub fn delete<U: IntoUrl>(&self, url: U) -> MirroredRequestBuilder {
+        self.request(Method::DELETE, url)
+    }
+
+    pub fn request<U: IntoUrl>(&self, method: Method, url: U) -> MirroredRequestBuilder {
+        MirroredRequestBuilder {
+            client: self.clone(),
+            builder: self.inner.request(method, url),
+        }
+    }
+
+    /// Executes `request` against its mirror candidates in order. The response of the
+    /// last candidate is returned as is, even if it is an error status. Requests other
+    /// than GET and HEAD always go to their original URL.
+    pub async fn execute(&self, request: Request) -> reqwest::Result<Response> {
+        if !is_mirrorable(request.method()) {
+            return self.inner.execute(request).await;
+        }
+        let candidates = candidate_urls(request.url());
+        if candidates.len() == 1 && &candidates[0] == request.url() {
+            return self.inner.execute(request).await;
+        }
+
+        let origin = request.url().clone();
+        let mut request = request;
+        let last_index = candidates.len() - 1;
+        for (index, candidate) in candidates.into_iter().enumerate() {
+            // Streaming bodies can only be sent once
+            let Some(mut attempt) = request.try_clone() else {
+                *request.url_mut() = candidate;
+                strip_credentials_for(&mut request, &origin);
+                return self.inner.execute(request).await;
+            };
+            debug!("Requesting {} (mirror candidate {})", candidate, index + 1);
+            *attempt.url_mut() = candidate.clone();
+            strip_credentials_for(&mut attempt, &origin);
+
+            match self.inner.execute(attempt).await {
+                Ok(response) if index < last_index && should_try_next(response.status()) => {
+                    warn!(
+                        "Mirror {} answered {}, trying next candidate",
+                        candidate,
+                        response.status()
+                    );
+                }
+                Ok(response) => return Ok(response),
+                Err(e) if index < last_index => {
+                    warn!("Mirror {} failed: {}, trying next candidate", candidate, e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        unreachable!("candidate_urls always returns at least one URL")
+    }
+}
+
+impl Deref for MirroredClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.inner
+    }
+}
+
+/// Thin wrapper around `reqwest::RequestBuilder` whose `send` goes through the mirror failover.
+pub struct MirroredRequestBuilder {
+    client: MirroredClient,
+    builder: reqwest::RequestBuilder,
+}
+
+impl MirroredRequestBuilder {
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.builder = self.builder.header(key, value);
+        self
+    }
+
+    pub fn bearer_auth<T: Display>(mut self, token: T) -> Self {
+        self.builder = self.builder.bearer_auth(token);
+        self
+    }
+
+    pub fn query<T: Serialize + ?Sized>(mut self, query: &T) -> Self {
+        self.builder = self.builder.query(query);
+        self
+    }
+
+    pub fn json<T: Serialize + ?Sized>(mut self, json: &T) -> Self {
+        self.builder = self.builder.json(json);
+        self
+    }
+
+    pub fn form<T: Serialize + ?Sized>(mut self, form: &T) -> Self {
+        self.builder = self.builder.form(form);
+        self
+    }
+
+    pub fn body<T: Into<reqwest::Body>>(mut self, body: T) -> Self {
+        self.builder = self.builder.body(body);
+        self
+    }
+
+    pub fn build(self) -> reqwest::Result<Request> {
+        self.builder.build()
+    }
+
+    pub async fn send(self) -> reqwest::Result<Response> {
+        let request = self.builder.build()?;
+        self.client.execute(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(match_type: MirrorMatch, pattern: &str, mirrors: &[&str]) -> MirrorRule {
+        MirrorRule {
+            enabled: true,
+            match_type,
+            pattern: pattern.to_string(),
+            mirrors: mirrors.iter().map(|m| m.to_string()).collect(),
+            fallback_to_origin: true,
+        }
+    }
+
+    fn candidates(rules: &[MirrorRule], url: &str) -> Vec<String> {
+        rewrite_candidates(rules, &Url::parse(url).unwrap())
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn host_rules_rewrite_to_base_urls_and_hosts() {
+        let rules = vec![rule(
+            MirrorMatch::Host,
+            "libraries.minecraft.net",
+            &["https://bmclapi2.bangbang93.com/maven/", "mirror.local"],
+        )];
+        assert_eq!(
+            candidates(&rules, "https://libraries.minecraft.net/com/a/b.jar?x=1"),
+            vec![
+                "https://bmclapi2.bangbang93.com/maven/com/a/b.jar?x=1",
+                "https://mirror.local/com/a/b.jar?x=1",
+                "https://libraries.minecraft.net/com/a/b.jar?x=1",
+            ]
+        );
+    }
+
+    #[test]
+    fn prefix_rules_replace_the_prefix() {
+        let mut prefix = rule(
+            MirrorMatch::Prefix,
+            "https://maven.fabricmc.net/",
+            &["http://localhost:8080/fabric/"],
+        );
+        prefix.fallback_to_origin = false;
+        assert_eq!(
+            candidates(&[prefix], "https://maven.fabricmc.net/net/fabricmc/x.jar"),
+            vec!["http://localhost:8080/fabric/net/fabricmc/x.jar"]
+        );
+    }
+
+    #[test]
+    fn first_enabled_matching_rule_wins() {
+        let mut disabled = rule(MirrorMatch::Host, "piston-meta.mojang.com", &["a.example"]);
+        disabled.enabled = false;
+        let rules = vec![
+            disabled,
+            rule(MirrorMatch::Host, "piston-meta.mojang.com", &["b.example"]),
+            rule(MirrorMatch::Host, "piston-meta.mojang.com", &["c.example"]),
+        ];
+        assert_eq!(
+            candidates(&rules, "https://piston-meta.mojang.com/v1/x.json"),
+            vec![
+                "https://b.example/v1/x.json",
+                "https://piston-meta.mojang.com/v1/x.json",
+            ]
+        );
+        assert_eq!(
+            candidates(&rules, "https://api.modrinth.com/v2/project"),
+            vec!["https://api.modrinth.com/v2/project"]
+        );
+    }
+
+    #[test]
+    fn credentials_stay_with_the_origin() {
+        let origin = Url::parse("https://libraries.minecraft.net/a.jar").unwrap();
+        let client = Client::new();
+        let request = |url: &str| {
+            client
+                .get(url)
+                .bearer_auth("token")
+                .header(API_KEY_HEADER, "key")
+                .build()
+                .unwrap()
+        };
+
+        let mut mirrored = request("https://mirror.local/a.jar");
+        strip_credentials_for(&mut mirrored, &origin);
+        assert!(mirrored.headers().get(AUTHORIZATION).is_none());
+        assert!(mirrored.headers().get(API_KEY_HEADER).is_none());
+
+        let mut same_host = request("https://libraries.minecraft.net/mirror/a.jar");
+        strip_credentials_for(&mut same_host, &origin);
+        assert!(same_host.headers().get(AUTHORIZATION).is_some());
+        assert!(same_host.headers().get(API_KEY_HEADER).is_some());
+
+        assert!(is_mirrorable(&Method::GET));
+        assert!(!is_mirrorable(&Method::POST));
+    }
+
+    #[test]
+    fn failover_statuses() {
+        assert!(should_try_next(StatusCode::NOT_FOUND));
+        assert!(should_try_next(StatusCode::BAD_GATEWAY));
+        assert!(!should_try_next(StatusCode::RANGE_NOT_SATISFIABLE));
+        assert!(!should_try_next(StatusCode::UNAUTHORIZED));
+    }
+}
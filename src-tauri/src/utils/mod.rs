@@ -1,19 +1,28 @@
+pub mod bandwidth_utils; // Globales Download-Limit (Token Bucket) und Durchsatzmessung
+pub mod crash_analyzer; // Lokale Absturzanalyse mit erweiterbaren Regeln (crash_rules.json)
 pub mod datapack_utils; // DataPack-Utils für das Scannen und Verwalten von DataPacks
 pub mod debug_utils;
+pub mod deep_link_utils; // geg://launch/<profile-id> Deep Links und Desktop-Verknüpfungen
 pub mod disk_space_utils; // Disk space utility for checking available space before downloads
 pub mod download_utils; // Central download utility for robust file downloads
 pub mod file_utils; // Utilities for file operations like reading archives
 pub mod hash_utils;
+pub mod hook_utils; // Placeholder expansion and environment for launch hooks
 pub mod java_detector; // Java detector to find Java installations
+pub mod java_runtime_utils; // Vom Launcher verwaltete Java-Runtimes auflisten, pinnen, aktualisieren und entfernen
+pub mod level_dat_utils; // level.dat lesen und bearbeiten (Seed, Spawn, Gamerules, Wetter, DataPacks)
 pub mod mc_utils; // Utilities for Minecraft-related operations
 pub mod migration_utils; // Migration utilities for profile and config updates
+pub mod mirror_utils; // Download mirrors and URL rewrite rules with failover for DOWNLOAD_CLIENT
 pub mod path_utils; // Deklariert das neue Modul und macht seinen Inhalt (wenn `pub`) nutzbar
 pub mod profile_utils; // Utility-Funktionen für Profilinhalte wie das Installieren von Modrinth-Content
 pub mod repair_utils; // Repair utilities for fixing profile issues
 pub mod resourcepack_utils; // ResourcePack-Utils für das Scannen und Verwalten von ResourcePacks
 pub mod security_utils; // Security utilities for masking sensitive data
+pub mod server_list_utils; // servers.dat bearbeiten, zwischen Profilen kopieren und an Gruppen verteilen
 pub mod server_ping; // Server-Ping-Modul für die Kommunikation mit Minecraft-Servern
 pub mod shaderpack_utils; // ShaderPack-Utils für das Scannen und Verwalten von ShaderPacks
+pub mod storage_utils; // Speicheranalyse des Meta-Ordners und Aufräumen verwaister Artefakte
 pub mod system_info; // <-- Hinzufügen
 pub mod updater_utils;
 pub mod world_utils; // <-- Hinzugefügt
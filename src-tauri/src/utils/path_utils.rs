@@ -1,6 +1,6 @@
-use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
 use crate::error::{AppError, Result}; // Dein Result- und Fehlertyp
 use crate::integrations::norisk_packs::{get_GEG_pack_mod_filename, NoriskModEntryDefinition};
+use crate::minecraft::downloads::mod_cache::ModCache;
 use crate::state::State;
 use crate::utils::download_utils; // Added for DownloadUtils
 use futures::future::try_join_all; // Added for joining futures
@@ -649,9 +649,6 @@ pub async fn copy_profile_with_exclusions(
     Ok(files_copied)
 }
 
-// Konstante aus dem mod_downloader/GEG_pack_downloader übernehmen
-const MOD_CACHE_DIR_NAME: &str = "mod_cache";
-
 /// Gibt den vollständigen Pfad zu einem GEG-Mod im Cache-Verzeichnis zurück.
 ///
 /// # Arguments
@@ -662,7 +659,7 @@ const MOD_CACHE_DIR_NAME: &str = "mod_cache";
 ///
 /// # Returns
 ///
-/// * `Ok(PathBuf)` - Den Pfad zur .jar Datei im Cache-Verzeichnis
+/// * `Ok(PathBuf)` - Den Pfad zur .jar Datei im Cache-Verzeichnis (existiert evtl. noch nicht)
 /// * `Err(AppError)` - Wenn kein kompatibler Mod gefunden wurde oder der Dateiname nicht ermittelt werden konnte
 pub fn get_GEG_mod_cache_path(
     mod_entry: &NoriskModEntryDefinition,
@@ -683,15 +680,19 @@ pub fn get_GEG_mod_cache_path(
         })?
         .clone();
 
+    // Gleiche Quelle wie beim Download verwenden (Override aus dem Compatibility-Target)
+    let effective_source = compatibility_target
+        .source
+        .as_ref()
+        .unwrap_or(&mod_entry.source);
+
     // Ermittle den Dateinamen mit der vorhandenen Hilfsfunktion
     let filename =
-        get_GEG_pack_mod_filename(&mod_entry.source, &compatibility_target, &mod_entry.id)?;
-
-    // Erstelle den vollständigen Pfad zum Cache-Verzeichnis
-    let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join(MOD_CACHE_DIR_NAME);
+        get_GEG_pack_mod_filename(effective_source, &compatibility_target, &mod_entry.id)?;
 
-    // Gib den vollständigen Pfad zur .jar-Datei zurück
-    Ok(mod_cache_dir.join(filename))
+    // Cache-Key auflösen (content-addressed Objekt oder alter flacher Pfad)
+    let cache_key = ModCache::pack_mod_key(effective_source, &mod_entry.id, &filename);
+    Ok(ModCache::path_for(&cache_key, &filename))
 }
 
 /// Helper function to recursively collect file copy operations.
@@ -802,6 +803,36 @@ pub async fn count_files_recursively(dir_path: &Path) -> Result<usize> {
     Ok(count)
 }
 
+/// Sums up the size of all files below `dir_path` (symlinks are not followed).
+/// Returns the file size if `dir_path` points to a file and 0 if it does not exist.
+pub async fn calculate_dir_size(dir_path: &Path) -> Result<u64> {
+    let metadata = match fs::symlink_metadata(dir_path).await {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    if !metadata.is_dir() {
+        return Ok(if metadata.is_file() { metadata.len() } else { 0 });
+    }
+
+    let mut size = 0;
+    let mut dirs_to_check = vec![dir_path.to_path_buf()];
+
+    while let Some(current_dir) = dirs_to_check.pop() {
+        let mut entries = fs::read_dir(&current_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_type = entry.file_type().await?;
+            if file_type.is_file() {
+                size += entry.metadata().await?.len();
+            } else if file_type.is_dir() {
+                dirs_to_check.push(entry.path());
+            }
+        }
+    }
+
+    Ok(size)
+}
+
 /// Copies directory with progress events for each file
 pub async fn copy_dir_with_progress(
     source: &Path,
@@ -2355,9 +2355,11 @@ impl LocalContentLoader {
                         | crate::state::profile_state::ModSource::Url { .. }
                         | crate::state::profile_state::ModSource::Maven { .. }
                         | crate::state::profile_state::ModSource::CurseForge { .. } => {
-                            crate::config::ProjectDirsExt::meta_dir(&*crate::config::LAUNCHER_DIRECTORY)
-                                .join("mod_cache")
-                                .join(&actual_filename)
+                            use crate::minecraft::downloads::mod_cache::ModCache;
+                            match ModCache::profile_mod_key(&mod_item.source) {
+                                Some(key) => ModCache::path_for(&key, &actual_filename),
+                                None => ModCache::cache_dir().join(&actual_filename),
+                            }
                         }
                         _ => content_dirs[0].join(&actual_filename),
                     }
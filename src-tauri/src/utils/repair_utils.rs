@@ -1,22 +1,208 @@
-use crate::error::{AppError, Result};
-use crate::state::state_manager::State;
-use crate::state::profile_state::{ModSource, get_profile_mod_filename};
 use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
 use crate::integrations::norisk_packs;
-use crate::utils::path_utils;
+use crate::minecraft::api::fabric_api::FabricApi;
+use crate::minecraft::api::forge_api::ForgeApi;
+use crate::minecraft::api::mc_api::MinecraftApiService;
+use crate::minecraft::api::neo_forge_api::NeoForgeApi;
+use crate::minecraft::api::quilt_api::QuiltApi;
+use crate::minecraft::downloads::forge_installer_download::ForgeInstallerDownloadService;
+use crate::minecraft::downloads::mod_cache::ModCache;
+use crate::minecraft::downloads::mod_downloader::ModDownloadService;
+use crate::minecraft::downloads::neo_forge_installer_download::NeoForgeInstallerDownloadService;
+use crate::minecraft::dto::piston_meta::{AssetIndexContent, DownloadInfo, PistonMeta};
+use crate::minecraft::modloader::ModloaderFactory;
+use crate::state::profile_state::{self, ModLoader, ModSource, Profile};
+use crate::state::state_manager::State;
+use crate::utils::download_utils::{DownloadConfig, DownloadUtils};
+use crate::utils::hash_utils;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
 use log::{debug, info, warn};
-use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tokio::fs;
+use uuid::Uuid;
 
-const MOD_CACHE_DIR_NAME: &str = "mod_cache";
+/// Files hashed in parallel during verification.
+const VERIFY_CONCURRENCY: usize = 16;
+/// Files downloaded in parallel during repair.
+const REPAIR_CONCURRENCY: usize = 8;
+const ASSET_OBJECTS_URL: &str = "https://resources.download.minecraft.net";
+const MAVEN_CENTRAL_URL: &str = "https://repo1.maven.org/maven2/";
+const FABRIC_MAVEN_URL: &str = "https://maven.fabricmc.net/";
+const QUILT_MAVEN_URL: &str = "https://maven.quiltmc.org/repository/release/";
+const FORGE_MAVEN_URL: &str = "https://maven.minecraftforge.net";
+const NEOFORGE_MAVEN_URL: &str = "https://maven.neoforged.net";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityCategory {
+    Client,
+    Library,
+    Native,
+    LoaderInstaller,
+    LoaderLibrary,
+    AssetIndex,
+    Asset,
+    Mod,
+    PackMod,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityProblem {
+    Missing,
+    SizeMismatch,
+    HashMismatch,
+    /// No hash is known and the archive could not be read completely.
+    Corrupt,
+    Unreadable,
+}
+
+/// How [`repair_profile`] fixes a broken file. Not sent to the frontend.
+#[derive(Debug, Clone, Default)]
+enum RepairAction {
+    /// Download it again from where the installer got it.
+    Download {
+        url: String,
+        sha1: Option<String>,
+        size: Option<u64>,
+    },
+    /// Refetch the profile mod into the mod cache and, if `deploy`, deploy it again.
+    ProfileMod {
+        cache_key: String,
+        filename: String,
+        url: String,
+        sha1: Option<String>,
+        deploy: bool,
+    },
+    /// Deploy it again from the mod cache if the cached copy is intact, otherwise drop the
+    /// cached copy so the next launch fetches it again.
+    NextLaunch { cache_key: String, deploy: bool },
+    #[default]
+    Unavailable,
+}
 
-/// Repairs a profile by checking and fixing common issues
-/// 
-/// This function performs various repair operations on a profile including:
-/// - Verifying and repairing file integrity
-/// - Checking mod compatibility
-/// - Fixing configuration issues
-/// - Redownloading missing or corrupted files
+struct ExpectedFile {
+    category: IntegrityCategory,
+    name: String,
+    path: PathBuf,
+    sha1: Option<String>,
+    size: Option<u64>,
+    fingerprint: Option<u64>,
+    /// Missing files are fine (e.g. pack mods the user disabled), only corrupt ones count.
+    optional: bool,
+    action: RepairAction,
+}
+
+impl ExpectedFile {
+    fn new(category: IntegrityCategory, name: impl Into<String>, path: PathBuf) -> Self {
+        Self {
+            category,
+            name: name.into(),
+            path,
+            sha1: None,
+            size: None,
+            fingerprint: None,
+            optional: false,
+            action: RepairAction::Unavailable,
+        }
+    }
+
+    fn from_download(
+        category: IntegrityCategory,
+        name: impl Into<String>,
+        path: PathBuf,
+        info: &DownloadInfo,
+    ) -> Self {
+        let size = u64::try_from(info.size).ok().filter(|s| *s > 0);
+        let mut file = Self::new(category, name, path);
+        file.sha1 = Some(info.sha1.clone());
+        file.size = size;
+        file.action = RepairAction::Download {
+            url: info.url.clone(),
+            sha1: Some(info.sha1.clone()),
+            size,
+        };
+        file
+    }
+
+    fn downloadable(mut self, url: String) -> Self {
+        self.action = RepairAction::Download {
+            url,
+            sha1: self.sha1.clone(),
+            size: self.size,
+        };
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityIssue {
+    pub category: IntegrityCategory,
+    pub name: String,
+    pub path: PathBuf,
+    pub problem: IntegrityProblem,
+    /// Whether [`repair_profile`] knows how to fix this file.
+    pub repairable: bool,
+    #[serde(skip)]
+    action: RepairAction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityCategorySummary {
+    pub category: IntegrityCategory,
+    pub checked: usize,
+    pub broken: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileIntegrityReport {
+    pub profile_id: Uuid,
+    pub game_version: String,
+    pub loader: ModLoader,
+    pub loader_version: Option<String>,
+    pub categories: Vec<IntegrityCategorySummary>,
+    pub issues: Vec<IntegrityIssue>,
+    /// Parts that could not be verified, e.g. because metadata was unavailable.
+    pub notes: Vec<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl ProfileIntegrityReport {
+    pub fn is_intact(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// A broken asset index or loader installer hides the files listed inside it,
+    /// so after repairing one of them the profile has to be verified again.
+    fn blocks_deeper_checks(&self) -> bool {
+        self.issues.iter().any(|issue| {
+            matches!(
+                issue.category,
+                IntegrityCategory::AssetIndex | IntegrityCategory::LoaderInstaller
+            )
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileRepairResult {
+    pub profile_id: Uuid,
+    /// Everything verification found broken, across all passes.
+    pub issues: Vec<IntegrityIssue>,
+    pub repaired: usize,
+    /// Broken pack mods that were cleared and are fetched again by the next launch.
+    pub deferred: usize,
+    /// Files that could not be repaired, with the reason.
+    pub failed: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+/// Repairs a profile by verifying its files and refetching only the broken or
+/// missing ones. Intact files, including the rest of the mods folder, stay untouched.
 ///
 /// # Arguments
 ///
@@ -24,166 +210,834 @@ const MOD_CACHE_DIR_NAME: &str = "mod_cache";
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on successful repair, or `Err(AppError)` if repair fails
-pub async fn repair_profile(profile_id: Uuid) -> Result<()> {
+/// Returns what was found and repaired, or `Err(AppError)` if the profile could not be verified
+pub async fn repair_profile(profile_id: Uuid) -> Result<ProfileRepairResult> {
     info!("Starting repair process for profile {}", profile_id);
 
-    // Get the profile and state
-    let state = State::get().await?;
-    let profile = state.profile_manager.get_profile(profile_id).await?;
+    let mut result = ProfileRepairResult {
+        profile_id,
+        issues: Vec::new(),
+        repaired: 0,
+        deferred: 0,
+        failed: Vec::new(),
+        notes: Vec::new(),
+    };
 
-    debug!("Repairing profile: {} ({})", profile.name, profile.id);
-    debug!("Game version: {}, Loader: {}", profile.game_version, profile.loader.as_str());
+    // A second pass covers files that only become visible once their index is repaired
+    for pass in 1..=2 {
+        let report = verify_profile_integrity(profile_id).await?;
+        result.notes = report.notes.clone();
+        if report.is_intact() {
+            break;
+        }
+        info!(
+            "Repair pass {}: {} broken files in profile {}",
+            pass,
+            report.issues.len(),
+            profile_id
+        );
 
-    // Call individual repair functions
-    repair_profile_mods(profile_id).await?;
-    
-    info!("Profile repair completed successfully for profile {}", profile_id);
-    Ok(())
+        let outcomes: Vec<(IntegrityIssue, Result<bool>)> = stream::iter(report.issues.clone())
+            .map(|issue| async move {
+                let outcome = repair_issue(&issue).await;
+                (issue, outcome)
+            })
+            .buffer_unordered(REPAIR_CONCURRENCY)
+            .collect()
+            .await;
+
+        for (issue, outcome) in outcomes {
+            match outcome {
+                Ok(true) => result.repaired += 1,
+                Ok(false) => result.deferred += 1,
+                Err(e) => {
+                    warn!("Could not repair {:?}: {}", issue.path, e);
+                    result.failed.push(format!("{}: {}", issue.name, e));
+                }
+            }
+        }
+        result.issues.extend(report.issues.iter().cloned());
+
+        if !report.blocks_deeper_checks() || !result.failed.is_empty() {
+            break;
+        }
+    }
+
+    if let Err(e) = ModCache::save_index().await {
+        warn!("Failed to save mod cache index after repair: {}", e);
+    }
+
+    info!(
+        "Profile repair finished for {}: {} repaired, {} deferred to next launch, {} failed",
+        profile_id,
+        result.repaired,
+        result.deferred,
+        result.failed.len()
+    );
+    Ok(result)
 }
 
-/// Verifies the integrity of profile files
-/// 
-/// # Arguments
-///
-/// * `profile_id` - The UUID of the profile to verify
-///
-/// # Returns
-///
-/// Returns `Ok(())` if all files are valid, or `Err(AppError)` if issues are found
-pub async fn verify_profile_integrity(profile_id: Uuid) -> Result<()> {
-    info!("Verifying profile integrity for profile {}", profile_id);
+/// Fixes one broken file. Returns `Ok(false)` when it was only cleared and the
+/// next launch fetches it again.
+async fn repair_issue(issue: &IntegrityIssue) -> Result<bool> {
+    match &issue.action {
+        RepairAction::Download { url, sha1, size } => {
+            remove_broken_file(&issue.path).await?;
+            let mut config = DownloadConfig::new()
+                .with_force_overwrite(true)
+                .with_retries(3);
+            if let Some(sha1) = sha1 {
+                config = config.with_sha1(sha1.clone());
+            }
+            if let Some(size) = size {
+                config = config.with_size(*size);
+            }
+            DownloadUtils::download_file(url, &issue.path, config).await?;
+            Ok(true)
+        }
+        RepairAction::ProfileMod {
+            cache_key,
+            filename,
+            url,
+            sha1,
+            deploy,
+        } => {
+            if *deploy {
+                remove_broken_file(&issue.path).await?;
+            }
+            // A linked profile file is the cached object, so check it before deploying it again
+            ModCache::reverify(cache_key).await?;
+            let cached = ModCache::ensure_cached(cache_key, filename, url, sha1.as_deref()).await?;
+            if *deploy {
+                ModDownloadService::deploy_file(&cached, &issue.path).await?;
+            }
+            Ok(true)
+        }
+        RepairAction::NextLaunch { cache_key, deploy } => {
+            if *deploy {
+                remove_broken_file(&issue.path).await?;
+            }
+            if !ModCache::reverify(cache_key).await? {
+                return Ok(false);
+            }
+            if *deploy {
+                let filename = issue.path.file_name().unwrap_or_default().to_string_lossy();
+                let cached = ModCache::path_for(cache_key, &filename);
+                ModDownloadService::deploy_file(&cached, &issue.path).await?;
+            }
+            Ok(true)
+        }
+        RepairAction::Unavailable => Err(AppError::Other(format!(
+            "No download source known for {}",
+            issue.path.display()
+        ))),
+    }
+}
 
-    // TODO: Implement file integrity verification
-    // - Check mod file hashes
-    // - Verify configuration files
-    // - Check for missing dependencies
-    
+async fn remove_broken_file(path: &Path) -> Result<()> {
+    if fs::symlink_metadata(path).await.is_ok() {
+        fs::remove_file(path).await?;
+    }
     Ok(())
 }
 
-/// Repairs mod-related issues in a profile
-/// 
+/// Verifies the files a profile needs against the metadata they were installed from:
+/// the client jar, libraries and natives from the piston meta, the loader libraries,
+/// the asset index and its objects, and the profile (and pack) mods.
+///
 /// # Arguments
 ///
-/// * `profile_id` - The UUID of the profile to repair
+/// * `profile_id` - The UUID of the profile to verify
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on successful mod repair, or `Err(AppError)` if repair fails
-pub async fn repair_profile_mods(profile_id: Uuid) -> Result<()> {
-    info!("Repairing mods for profile {}", profile_id);
+/// Returns a report listing every missing or corrupt file; nothing is changed on disk
+pub async fn verify_profile_integrity(profile_id: Uuid) -> Result<ProfileIntegrityReport> {
+    info!("Verifying profile integrity for profile {}", profile_id);
 
     let state = State::get().await?;
     let profile = state.profile_manager.get_profile(profile_id).await?;
-    
-    // 1. Delete the mods folder
-    let mods_dir = state.profile_manager.get_profile_mods_path(&profile)?;
-    if mods_dir.exists() {
-        info!("Deleting mods directory: {:?}", mods_dir);
-        fs::remove_dir_all(&mods_dir).await.map_err(|e| {
-            warn!("Failed to delete mods directory {:?}: {}", mods_dir, e);
-            AppError::Io(e)
-        })?;
-        info!("Successfully deleted mods directory");
+    let pack_config = state.GEG_pack_manager.get_config().await;
+    let meta_dir = LAUNCHER_DIRECTORY.meta_dir().to_path_buf();
+
+    let loader_version = if profile.loader == ModLoader::Vanilla {
+        None
+    } else {
+        ModloaderFactory::resolve_loader_version(
+            &profile,
+            &profile.game_version,
+            Some(&pack_config),
+        )
+        .await
+        .version
+        .or_else(|| profile.loader_version.clone())
+        .filter(|v| !v.is_empty())
+    };
+
+    let mut notes = Vec::new();
+    let mut expected = Vec::new();
+
+    match load_piston_meta(&profile.game_version).await {
+        Ok(meta) => {
+            collect_game_files(&meta, &meta_dir, &mut expected);
+            collect_asset_files(&meta, &meta_dir, &mut expected, &mut notes).await;
+        }
+        Err(e) => notes.push(format!(
+            "Game files were not verified, version metadata for {} is unavailable: {}",
+            profile.game_version, e
+        )),
+    }
+
+    if let Err(e) = collect_loader_files(
+        &profile,
+        loader_version.as_deref(),
+        &meta_dir,
+        &mut expected,
+    )
+    .await
+    {
+        notes.push(format!(
+            "{} libraries were not verified: {}",
+            profile.loader.as_str(),
+            e
+        ));
+    }
+
+    // Fabric loads the resolved mods straight from the cache via `-Dfabric.addMods`,
+    // nothing is synced into its mods folder
+    let mods_dir = if profile.loader == ModLoader::Fabric {
+        Ok(None)
     } else {
-        debug!("Mods directory does not exist, skipping deletion: {:?}", mods_dir);
-    }
-
-    // 2. Delete mod files from mod cache (profile mods)
-    let mod_cache_dir = LAUNCHER_DIRECTORY.meta_dir().join(MOD_CACHE_DIR_NAME);
-    if mod_cache_dir.exists() {
-        info!("Cleaning mod cache for profile {} mods", profile_id);
-        
-        let mut cache_files_removed = 0;
-        let mut cache_errors = 0;
-        
-        for mod_info in &profile.mods {
-            // Get the filename for this mod
-            match get_profile_mod_filename(&mod_info.source) {
-                Ok(filename) => {
-                    let cache_file_path = mod_cache_dir.join(&filename);
-                    if cache_file_path.exists() {
-                        match fs::remove_file(&cache_file_path).await {
-                            Ok(_) => {
-                                debug!("Removed profile mod cache file: {}", filename);
-                                cache_files_removed += 1;
-                            }
-                            Err(e) => {
-                                warn!("Failed to remove profile mod cache file {}: {}", filename, e);
-                                cache_errors += 1;
-                            }
-                        }
-                    } else {
-                        debug!("Profile mod cache file does not exist: {}", filename);
+        state
+            .profile_manager
+            .get_profile_mods_path(&profile)
+            .map(Some)
+    };
+    match mods_dir {
+        Ok(mods_dir) => {
+            collect_profile_mods(&profile, mods_dir.as_deref(), &mut expected);
+            if let Some(pack_id) = &profile.selected_GEG_pack_id {
+                match pack_config.get_resolved_pack_definition(pack_id) {
+                    Ok(pack) => {
+                        collect_pack_mods(&profile, &pack.mods, mods_dir.as_deref(), &mut expected)
                     }
-                }
-                Err(e) => {
-                    warn!("Could not determine filename for profile mod {:?}: {}", 
-                          mod_info.display_name.as_deref().unwrap_or("unknown"), e);
-                    cache_errors += 1;
+                    Err(e) => notes.push(format!("Pack mods were not verified: {}", e)),
                 }
             }
         }
+        Err(e) => notes.push(format!("Mods were not verified: {}", e)),
+    }
+
+    let mut summaries: HashMap<IntegrityCategory, IntegrityCategorySummary> = HashMap::new();
+    for file in &expected {
+        summaries
+            .entry(file.category)
+            .or_insert(IntegrityCategorySummary {
+                category: file.category,
+                checked: 0,
+                broken: 0,
+            })
+            .checked += 1;
+    }
+
+    debug!(
+        "Verifying {} files for profile {}",
+        expected.len(),
+        profile_id
+    );
+    let mut issues: Vec<IntegrityIssue> = stream::iter(expected)
+        .map(|file| async move {
+            let problem = check_file(&file).await?;
+            Some(IntegrityIssue {
+                category: file.category,
+                name: file.name,
+                path: file.path,
+                problem,
+                repairable: !matches!(file.action, RepairAction::Unavailable),
+                action: file.action,
+            })
+        })
+        .buffer_unordered(VERIFY_CONCURRENCY)
+        .filter_map(|issue| async move { issue })
+        .collect()
+        .await;
+    issues.sort_by(|a, b| a.path.cmp(&b.path));
 
-        // 3. Delete GEG pack mod files from cache
-        if let Some(pack_id) = &profile.selected_GEG_pack_id {
-            info!("Cleaning GEG pack mod cache for pack: {}", pack_id);
-            
-            let GEG_config = state.GEG_pack_manager.get_config().await;
-            match GEG_config.get_resolved_pack_definition(pack_id) {
-                Ok(resolved_pack) => {
-                    for GEG_mod in &resolved_pack.mods {
-                        // Get the cache path for this GEG mod
-                        match path_utils::get_GEG_mod_cache_path(
-                            GEG_mod,
-                            &profile.game_version,
-                            profile.loader.as_str(),
-                        ) {
-                            Ok(cache_path) => {
-                                if cache_path.exists() {
-                                    match fs::remove_file(&cache_path).await {
-                                        Ok(_) => {
-                                            debug!("Removed GEG pack mod cache file: {:?}", cache_path);
-                                            cache_files_removed += 1;
-                                        }
-                                        Err(e) => {
-                                            warn!("Failed to remove GEG pack mod cache file {:?}: {}", cache_path, e);
-                                            cache_errors += 1;
-                                        }
-                                    }
-                                } else {
-                                    debug!("GEG pack mod cache file does not exist: {:?}", cache_path);
-                                }
-                            }
-                            Err(e) => {
-                                warn!("Could not determine cache path for GEG pack mod {}: {}", 
-                                      GEG_mod.display_name.as_deref().unwrap_or(&GEG_mod.id), e);
-                                cache_errors += 1;
-                            }
-                        }
+    for issue in &issues {
+        if let Some(summary) = summaries.get_mut(&issue.category) {
+            summary.broken += 1;
+        }
+    }
+    let mut categories: Vec<IntegrityCategorySummary> = summaries.into_values().collect();
+    categories.sort_by_key(|s| s.category as u8);
+
+    info!(
+        "Integrity check of profile {} finished: {} broken files",
+        profile_id,
+        issues.len()
+    );
+    Ok(ProfileIntegrityReport {
+        profile_id,
+        game_version: profile.game_version.clone(),
+        loader: profile.loader,
+        loader_version,
+        categories,
+        issues,
+        notes,
+        checked_at: Utc::now(),
+    })
+}
+
+async fn check_file(file: &ExpectedFile) -> Option<IntegrityProblem> {
+    let metadata = match fs::metadata(&file.path).await {
+        Ok(metadata) if metadata.is_file() => metadata,
+        _ if file.optional => return None,
+        _ => return Some(IntegrityProblem::Missing),
+    };
+    if file.size.is_some_and(|size| size != metadata.len()) {
+        return Some(IntegrityProblem::SizeMismatch);
+    }
+
+    if let Some(expected) = &file.sha1 {
+        return match hash_utils::calculate_sha1_from_file(&file.path).await {
+            Ok(actual) if actual.eq_ignore_ascii_case(expected) => None,
+            Ok(_) => Some(IntegrityProblem::HashMismatch),
+            Err(_) => Some(IntegrityProblem::Unreadable),
+        };
+    }
+    if let Some(expected) = file.fingerprint {
+        return match hash_utils::calculate_curseforge_fingerprint_from_file(&file.path).await {
+            Ok(actual) if actual == expected => None,
+            Ok(_) => Some(IntegrityProblem::HashMismatch),
+            Err(_) => Some(IntegrityProblem::Unreadable),
+        };
+    }
+
+    let is_archive = file
+        .path
+        .extension()
+        .is_some_and(|ext| ext == "jar" || ext == "zip");
+    if is_archive && !DownloadUtils::is_zip_file_complete(&file.path).await {
+        return Some(IntegrityProblem::Corrupt);
+    }
+    None
+}
+
+async fn load_piston_meta(version_id: &str) -> Result<PistonMeta> {
+    let api_service = MinecraftApiService::new();
+    let manifest = api_service.get_version_manifest().await?;
+    let version = manifest
+        .versions
+        .iter()
+        .find(|v| v.id == version_id)
+        .ok_or_else(|| AppError::VersionNotFound(version_id.to_string()))?;
+    api_service.get_piston_meta(&version.url).await
+}
+
+/// Client jar, libraries and natives. The installer downloads every library of
+/// the piston meta regardless of its rules, so all of them are expected.
+fn collect_game_files(meta: &PistonMeta, meta_dir: &Path, expected: &mut Vec<ExpectedFile>) {
+    let client_path = meta_dir
+        .join("versions")
+        .join(&meta.id)
+        .join(format!("{}.jar", meta.id));
+    expected.push(ExpectedFile::from_download(
+        IntegrityCategory::Client,
+        format!("{}.jar", meta.id),
+        client_path,
+        &meta.downloads.client,
+    ));
+
+    let libraries_dir = meta_dir.join("libraries");
+    for library in &meta.libraries {
+        if let Some(artifact) = &library.downloads.artifact {
+            if let Some(path) = &artifact.path {
+                expected.push(ExpectedFile::from_download(
+                    IntegrityCategory::Library,
+                    library.name.clone(),
+                    libraries_dir.join(path),
+                    artifact,
+                ));
+            }
+        }
+        for (classifier, native) in library.downloads.classifiers.iter().flatten() {
+            if let Some(path) = &native.path {
+                expected.push(ExpectedFile::from_download(
+                    IntegrityCategory::Native,
+                    format!("{} ({})", library.name, classifier),
+                    libraries_dir.join(path),
+                    native,
+                ));
+            }
+        }
+    }
+}
+
+/// The asset index and, if it is intact, every object listed in it.
+async fn collect_asset_files(
+    meta: &PistonMeta,
+    meta_dir: &Path,
+    expected: &mut Vec<ExpectedFile>,
+    notes: &mut Vec<String>,
+) {
+    let assets_dir = meta_dir.join("assets");
+    let asset_index = &meta.asset_index;
+    let index_path = assets_dir
+        .join("indexes")
+        .join(format!("{}.json", asset_index.id));
+    let size = u64::try_from(asset_index.size).ok().filter(|s| *s > 0);
+
+    let mut index_file = ExpectedFile::new(
+        IntegrityCategory::AssetIndex,
+        format!("{}.json", asset_index.id),
+        index_path.clone(),
+    );
+    index_file.sha1 = Some(asset_index.sha1.clone());
+    index_file.size = size;
+    let index_file = index_file.downloadable(asset_index.url.clone());
+
+    if check_file(&index_file).await.is_some() {
+        notes.push(format!(
+            "Asset objects were not verified because the asset index {} is broken",
+            asset_index.id
+        ));
+        expected.push(index_file);
+        return;
+    }
+    expected.push(index_file);
+
+    let content = match fs::read_to_string(&index_path).await {
+        Ok(content) => content,
+        Err(e) => {
+            notes.push(format!(
+                "Could not read asset index {}: {}",
+                asset_index.id, e
+            ));
+            return;
+        }
+    };
+    let index: AssetIndexContent = match serde_json::from_str(&content) {
+        Ok(index) => index,
+        Err(e) => {
+            notes.push(format!(
+                "Could not parse asset index {}: {}",
+                asset_index.id, e
+            ));
+            return;
+        }
+    };
+
+    let objects_dir = assets_dir.join("objects");
+    for (name, object) in index.objects {
+        let Some(prefix) = object.hash.get(..2) else {
+            continue;
+        };
+        let mut file = ExpectedFile::new(
+            IntegrityCategory::Asset,
+            name,
+            objects_dir.join(prefix).join(&object.hash),
+        );
+        file.sha1 = Some(object.hash.clone());
+        file.size = u64::try_from(object.size).ok();
+        let url = format!("{}/{}/{}", ASSET_OBJECTS_URL, prefix, object.hash);
+        expected.push(file.downloadable(url));
+    }
+}
+
+/// Libraries of the profile's mod loader, resolved the same way the installer does.
+async fn collect_loader_files(
+    profile: &Profile,
+    loader_version: Option<&str>,
+    meta_dir: &Path,
+    expected: &mut Vec<ExpectedFile>,
+) -> Result<()> {
+    let libraries_dir = meta_dir.join("libraries");
+    let game_version = profile.game_version.as_str();
+
+    match profile.loader {
+        ModLoader::Vanilla => {}
+        ModLoader::Fabric => {
+            let fabric_api = FabricApi::new();
+            let target = loader_version.map(|v| v.trim_end_matches(" (stable)").trim());
+            let version = match target {
+                Some(target) => fabric_api
+                    .get_loader_versions(game_version)
+                    .await?
+                    .into_iter()
+                    .find(|v| v.loader.version == target),
+                None => None,
+            };
+            let version = match version {
+                Some(version) => version,
+                None => fabric_api.get_latest_stable_version(game_version).await?,
+            };
+
+            let libraries = &version.launcher_meta.libraries;
+            push_maven_file(
+                &libraries_dir,
+                &version.loader.maven,
+                Some(FABRIC_MAVEN_URL),
+                None,
+                expected,
+            );
+            push_maven_file(
+                &libraries_dir,
+                &version.intermediary.maven,
+                Some(FABRIC_MAVEN_URL),
+                None,
+                expected,
+            );
+            for library in libraries.common.iter().chain(libraries.client.iter()) {
+                push_maven_file(
+                    &libraries_dir,
+                    &library.name,
+                    library.url.as_deref(),
+                    library.sha1.as_deref(),
+                    expected,
+                );
+            }
+        }
+        ModLoader::Quilt => {
+            let quilt_api = QuiltApi::new();
+            let target = loader_version.map(|v| v.trim_end_matches(" (stable)").trim());
+            let version = match target {
+                Some(target) => quilt_api
+                    .get_loader_versions(game_version)
+                    .await?
+                    .into_iter()
+                    .find(|v| v.loader.version == target),
+                None => None,
+            };
+            let version = match version {
+                Some(version) => version,
+                None => quilt_api.get_latest_stable_version(game_version).await?,
+            };
+
+            let libraries = &version.launcher_meta.libraries;
+            push_maven_file(
+                &libraries_dir,
+                &version.loader.maven,
+                Some(QUILT_MAVEN_URL),
+                None,
+                expected,
+            );
+            // Same split as the Quilt installer: Fabric's intermediary comes from Fabric's maven
+            let intermediary_repository = if version.intermediary.maven.starts_with("net.fabricmc:")
+            {
+                FABRIC_MAVEN_URL
+            } else {
+                QUILT_MAVEN_URL
+            };
+            push_maven_file(
+                &libraries_dir,
+                &version.intermediary.maven,
+                Some(intermediary_repository),
+                None,
+                expected,
+            );
+            for library in libraries.common.iter().chain(libraries.client.iter()) {
+                push_maven_file(
+                    &libraries_dir,
+                    &library.name,
+                    library.url.as_deref(),
+                    library.sha1.as_deref(),
+                    expected,
+                );
+            }
+        }
+        ModLoader::Forge => {
+            let compatible = ForgeApi::new()
+                .get_all_versions()
+                .await?
+                .get_versions_for_minecraft(game_version);
+            let version =
+                pick_forge_style_version(loader_version, &compatible, "Forge", game_version)?;
+            let maven_path = format!(
+                "net/minecraftforge/forge/{0}/forge-{0}-installer.jar",
+                version
+            );
+            if !push_installer(&libraries_dir, &maven_path, FORGE_MAVEN_URL, expected).await {
+                return Ok(());
+            }
+
+            let forge_version = ForgeInstallerDownloadService::new()
+                .extract_version_json(&version)
+                .await?;
+            for library in &forge_version.libraries {
+                let Some(downloads) = &library.downloads else {
+                    continue;
+                };
+                for info in downloads
+                    .artifact
+                    .iter()
+                    .chain(downloads.classifiers.values())
+                {
+                    // Files without a URL are produced by the install processors
+                    if info.url.is_empty() {
+                        continue;
                     }
+                    let mut file = ExpectedFile::new(
+                        IntegrityCategory::LoaderLibrary,
+                        library.name.clone(),
+                        libraries_dir.join(&info.path),
+                    );
+                    file.sha1 = info.sha1.clone();
+                    file.size = u64::try_from(info.size).ok().filter(|s| *s > 0);
+                    expected.push(file.downloadable(info.url.clone()));
                 }
-                Err(e) => {
-                    warn!("Failed to get GEG pack definition for {}: {}", pack_id, e);
-                    cache_errors += 1;
+            }
+        }
+        ModLoader::NeoForge => {
+            let compatible = NeoForgeApi::new()
+                .get_all_versions()
+                .await?
+                .get_versions_for_minecraft(game_version);
+            let version =
+                pick_forge_style_version(loader_version, &compatible, "NeoForge", game_version)?;
+            let maven_path = format!(
+                "net/neoforged/neoforge/{0}/neoforge-{0}-installer.jar",
+                version
+            );
+            if !push_installer(&libraries_dir, &maven_path, NEOFORGE_MAVEN_URL, expected).await {
+                return Ok(());
+            }
+
+            let neoforge_version = NeoForgeInstallerDownloadService::new()
+                .extract_version_json(&version)
+                .await?;
+            for library in &neoforge_version.libraries {
+                let Some(downloads) = &library.downloads else {
+                    continue;
+                };
+                for info in downloads
+                    .artifact
+                    .iter()
+                    .chain(downloads.classifiers.values())
+                {
+                    if info.url.is_empty() {
+                        continue;
+                    }
+                    let mut file = ExpectedFile::new(
+                        IntegrityCategory::LoaderLibrary,
+                        library.name.clone(),
+                        libraries_dir.join(&info.path),
+                    );
+                    file.sha1 = info.sha1.clone();
+                    file.size = u64::try_from(info.size).ok().filter(|s| *s > 0);
+                    expected.push(file.downloadable(info.url.clone()));
                 }
             }
-        } else {
-            debug!("Profile has no selected GEG pack, skipping GEG mod cache cleanup");
         }
-        
-        info!("Cache cleanup completed: {} files removed, {} errors", cache_files_removed, cache_errors);
-    } else {
-        debug!("Mod cache directory does not exist: {:?}", mod_cache_dir);
     }
-    
-    info!("Mod repair completed for profile {}", profile_id);
     Ok(())
 }
 
+/// Same choice as the Forge and NeoForge installers: the configured version if it
+/// fits the game version, otherwise the newest compatible one.
+fn pick_forge_style_version(
+    loader_version: Option<&str>,
+    compatible: &[String],
+    loader_name: &str,
+    game_version: &str,
+) -> Result<String> {
+    if let Some(version) = loader_version.filter(|v| compatible.iter().any(|c| c == v)) {
+        return Ok(version.to_string());
+    }
+    compatible.first().cloned().ok_or_else(|| {
+        AppError::VersionNotFound(format!(
+            "No {} versions found for Minecraft {}",
+            loader_name, game_version
+        ))
+    })
+}
+
+/// Adds the loader installer jar. Returns false if it is broken, in which case
+/// the libraries listed inside it cannot be enumerated yet.
+async fn push_installer(
+    libraries_dir: &Path,
+    maven_path: &str,
+    maven_url: &str,
+    expected: &mut Vec<ExpectedFile>,
+) -> bool {
+    let name = maven_path
+        .rsplit('/')
+        .next()
+        .unwrap_or(maven_path)
+        .to_string();
+    let file = ExpectedFile::new(
+        IntegrityCategory::LoaderInstaller,
+        name,
+        libraries_dir.join(maven_path),
+    )
+    .downloadable(format!("{}/{}", maven_url, maven_path));
+    let intact = check_file(&file).await.is_none();
+    expected.push(file);
+    intact
+}
+
+/// Adds a Fabric/Quilt style library given by its `group:artifact:version` coordinates.
+fn push_maven_file(
+    libraries_dir: &Path,
+    coordinates: &str,
+    repository_url: Option<&str>,
+    sha1: Option<&str>,
+    expected: &mut Vec<ExpectedFile>,
+) {
+    let Some(relative_path) = maven_relative_path(coordinates) else {
+        debug!(
+            "Skipping library with unexpected coordinates: {}",
+            coordinates
+        );
+        return;
+    };
+    let url = format!(
+        "{}{}",
+        repository_url.unwrap_or(MAVEN_CENTRAL_URL),
+        relative_path
+    );
+    let mut file = ExpectedFile::new(
+        IntegrityCategory::LoaderLibrary,
+        coordinates,
+        libraries_dir.join(&relative_path),
+    );
+    file.sha1 = sha1.map(str::to_string);
+    expected.push(file.downloadable(url));
+}
+
+fn maven_relative_path(coordinates: &str) -> Option<String> {
+    let parts: Vec<&str> = coordinates.split(':').collect();
+    let [group, artifact, version] = parts.as_slice() else {
+        return None;
+    };
+    Some(format!(
+        "{}/{}/{}/{}-{}.jar",
+        group.replace('.', "/"),
+        artifact,
+        version,
+        artifact,
+        version
+    ))
+}
+
+/// Where the game loads a resolved mod from: the mods folder it is deployed to, or the
+/// mod cache object itself when there is no mods folder to deploy to (Fabric).
+fn resolved_mod_path(mods_dir: Option<&Path>, cache_key: &str, filename: &str) -> PathBuf {
+    match mods_dir {
+        Some(mods_dir) => mods_dir.join(filename),
+        None => ModCache::path_for(cache_key, filename),
+    }
+}
+
+/// Enabled Modrinth and CurseForge mods that the resolver deploys for this profile.
+fn collect_profile_mods(
+    profile: &Profile,
+    mods_dir: Option<&Path>,
+    expected: &mut Vec<ExpectedFile>,
+) {
+    for mod_info in &profile.mods {
+        if !mod_info.enabled || mod_info.associated_loader != Some(profile.loader) {
+            continue;
+        }
+        if let Some(game_versions) = &mod_info.game_versions {
+            if !game_versions.is_empty() && !game_versions.contains(&profile.game_version) {
+                continue;
+            }
+        }
+
+        let (download_url, sha1, fingerprint) = match &mod_info.source {
+            ModSource::Modrinth {
+                download_url,
+                file_hash_sha1,
+                ..
+            } => (download_url, file_hash_sha1.clone(), None),
+            ModSource::CurseForge {
+                download_url,
+                file_hash_sha1,
+                file_fingerprint,
+                ..
+            } => (download_url, file_hash_sha1.clone(), *file_fingerprint),
+            _ => continue,
+        };
+        let Ok(filename) = profile_state::get_profile_mod_filename(&mod_info.source) else {
+            continue;
+        };
+        let Some(cache_key) = ModCache::profile_mod_key(&mod_info.source) else {
+            continue;
+        };
+
+        let mut file = ExpectedFile::new(
+            IntegrityCategory::Mod,
+            mod_info
+                .display_name
+                .clone()
+                .unwrap_or_else(|| filename.clone()),
+            resolved_mod_path(mods_dir, &cache_key, &filename),
+        );
+        file.sha1 = sha1.clone();
+        file.fingerprint = fingerprint;
+        file.action = RepairAction::ProfileMod {
+            cache_key,
+            filename,
+            url: download_url.clone(),
+            sha1,
+            deploy: mods_dir.is_some(),
+        };
+        expected.push(file);
+    }
+}
+
+/// Deployed GEG pack mods, checked against the hash recorded in the mod cache.
+/// Pack mods can be disabled per profile, so missing ones are not reported.
+fn collect_pack_mods(
+    profile: &Profile,
+    pack_mods: &[norisk_packs::NoriskModEntryDefinition],
+    mods_dir: Option<&Path>,
+    expected: &mut Vec<ExpectedFile>,
+) {
+    for pack_mod in pack_mods {
+        let Some(target) = pack_mod
+            .compatibility
+            .get(&profile.game_version)
+            .and_then(|loader_map| loader_map.get(profile.loader.as_str()))
+        else {
+            continue;
+        };
+        let source = target.source.as_ref().unwrap_or(&pack_mod.source);
+        let filename = match norisk_packs::get_GEG_pack_mod_filename(source, target, &pack_mod.id) {
+            Ok(filename) => filename,
+            Err(e) => {
+                warn!(
+                    "Could not determine filename of pack mod {}: {}",
+                    pack_mod.id, e
+                );
+                continue;
+            }
+        };
+        let cache_key = ModCache::pack_mod_key(source, &pack_mod.id, &filename);
+
+        let mut file = ExpectedFile::new(
+            IntegrityCategory::PackMod,
+            pack_mod
+                .display_name
+                .clone()
+                .unwrap_or_else(|| pack_mod.id.clone()),
+            resolved_mod_path(mods_dir, &cache_key, &filename),
+        );
+        if let Some(entry) = ModCache::get_entry(&cache_key) {
+            file.sha1 = Some(entry.sha1);
+            file.size = Some(entry.size);
+        }
+        file.optional = true;
+        file.action = RepairAction::NextLaunch {
+            cache_key,
+            deploy: mods_dir.is_some(),
+        };
+        expected.push(file);
+    }
+}
+
 /// Repairs configuration files for a profile
-/// 
+///
 /// # Arguments
 ///
 /// * `profile_id` - The UUID of the profile to repair
@@ -198,6 +1052,64 @@ pub async fn repair_profile_config(profile_id: Uuid) -> Result<()> {
     // - Check for corrupted config files
     // - Reset to defaults if necessary
     // - Verify settings validity
-    
+
     Ok(())
-} 
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile_with_mod(loader: ModLoader) -> Profile {
+        serde_json::from_value(serde_json::json!({
+            "name": "Test",
+            "path": "test",
+            "game_version": "1.21.1",
+            "loader": loader,
+            "mods": [{
+                "source": {
+                    "type": "modrinth",
+                    "project_id": "AANobbMI",
+                    "version_id": "tFw0iWAk",
+                    "file_name": "sodium.jar",
+                    "download_url": "https://cdn.modrinth.com/sodium.jar",
+                    "file_hash_sha1": null
+                },
+                "enabled": true,
+                "associated_loader": loader
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn fabric_mods_are_verified_in_the_cache() {
+        let mut expected = Vec::new();
+        collect_profile_mods(&profile_with_mod(ModLoader::Fabric), None, &mut expected);
+
+        assert_eq!(expected.len(), 1);
+        assert!(expected[0].path.starts_with(ModCache::cache_dir()));
+        assert!(matches!(
+            expected[0].action,
+            RepairAction::ProfileMod { deploy: false, .. }
+        ));
+    }
+
+    #[test]
+    fn synced_mods_are_verified_in_the_mods_folder() {
+        let mods_dir = PathBuf::from("instance").join("mods");
+        let mut expected = Vec::new();
+        collect_profile_mods(
+            &profile_with_mod(ModLoader::Forge),
+            Some(&mods_dir),
+            &mut expected,
+        );
+
+        assert_eq!(expected.len(), 1);
+        assert_eq!(expected[0].path, mods_dir.join("sodium.jar"));
+        assert!(matches!(
+            expected[0].action,
+            RepairAction::ProfileMod { deploy: true, .. }
+        ));
+    }
+}
@@ -0,0 +1,570 @@
+use crate::error::{AppError, Result};
+use crate::state::State;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use fastnbt::{from_bytes, to_bytes, Value};
+use flate2::read::GzDecoder;
+use image::{imageops::FilterType, GenericImageView, ImageFormat};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+type Compound = HashMap<String, Value>;
+
+/// Vanilla only shows server icons that are 64x64 PNGs.
+const SERVER_ICON_SIZE: u32 = 64;
+const DEFAULT_MINECRAFT_PORT: &str = "25565";
+
+/// Serializes all servers.dat edits, they are read-modify-write.
+static SERVER_LIST_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// What the game does with a server's resource pack, stored as `acceptTextures`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerResourcePackPolicy {
+    /// No `acceptTextures` tag, the game asks on join
+    #[default]
+    Prompt,
+    Enabled,
+    Disabled,
+}
+
+/// An entry of a profile's multiplayer server list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerListEntry {
+    pub name: String,
+    pub address: String,
+    /// Base64 encoded 64x64 PNG
+    #[serde(default)]
+    pub icon_base64: Option<String>,
+    #[serde(default)]
+    pub resource_pack_policy: ServerResourcePackPolicy,
+    /// Entries written by "Direct Connect", not shown in the server list
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// How `copy_server_list` treats the servers the target already has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerListCopyMode {
+    /// The target ends up with exactly the source list
+    Replace,
+    /// Servers are matched by address, new ones are appended and known ones updated
+    Merge,
+}
+
+/// Outcome of pushing a server list into one profile of a group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerListPushResult {
+    pub profile_id: Uuid,
+    pub profile_name: String,
+    pub added: usize,
+    pub updated: usize,
+    pub error: Option<String>,
+}
+
+/// Resolves servers.dat of a user profile or a standard version profile.
+async fn servers_dat_path(profile_id: Uuid) -> Result<PathBuf> {
+    let state = State::get().await?;
+    let profile = match state.profile_manager.get_profile(profile_id).await {
+        Ok(profile) => profile,
+        Err(AppError::ProfileNotFound(_)) => state
+            .GEG_version_manager
+            .get_profile_by_id(profile_id)
+            .await
+            .ok_or(AppError::ProfileNotFound(profile_id))?,
+        Err(e) => return Err(e),
+    };
+    Ok(state
+        .profile_manager
+        .calculate_instance_path_for_profile(&profile)?
+        .join("servers.dat"))
+}
+
+/// Reads the server compounds of a servers.dat. Unknown tags are kept, so writing the
+/// list back doesn't lose data newer game versions store there.
+async fn read_server_compounds(servers_dat_path: &Path) -> Result<Vec<Compound>> {
+    if !servers_dat_path.is_file() {
+        return Ok(Vec::new());
+    }
+    let bytes = fs::read(servers_dat_path).await?;
+    // servers.dat is uncompressed, but some tools write it gzipped
+    let root: Value = match from_bytes(&bytes) {
+        Ok(root) => root,
+        Err(e) => {
+            let mut decompressed_bytes = Vec::new();
+            if std::io::Read::read_to_end(&mut GzDecoder::new(&bytes[..]), &mut decompressed_bytes)
+                .is_err()
+            {
+                return Err(AppError::Nbt(e));
+            }
+            from_bytes(&decompressed_bytes)?
+        }
+    };
+
+    let servers = match root {
+        Value::Compound(mut root) => root.remove("servers"),
+        _ => None,
+    };
+    Ok(match servers {
+        Some(Value::List(servers)) => servers
+            .into_iter()
+            .filter_map(|server| match server {
+                Value::Compound(server) => Some(server),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    })
+}
+
+/// Writes an uncompressed servers.dat, replacing the old file in one step.
+async fn write_server_compounds(servers_dat_path: &Path, servers: Vec<Compound>) -> Result<()> {
+    let mut root = HashMap::new();
+    root.insert(
+        "servers".to_string(),
+        Value::List(servers.into_iter().map(Value::Compound).collect()),
+    );
+    let bytes = to_bytes(&Value::Compound(root))
+        .map_err(|e| AppError::Other(format!("NBT serialization error: {}", e)))?;
+
+    if let Some(parent) = servers_dat_path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let temp_path = servers_dat_path.with_file_name("servers.dat.tmp");
+    fs::write(&temp_path, &bytes).await?;
+    fs::rename(&temp_path, servers_dat_path).await?;
+    Ok(())
+}
+
+fn entry_from_compound(server: &Compound) -> ServerListEntry {
+    let string = |key: &str| match server.get(key) {
+        Some(Value::String(value)) => Some(value.clone()),
+        _ => None,
+    };
+    ServerListEntry {
+        name: string("name").unwrap_or_default(),
+        address: string("ip").unwrap_or_default(),
+        icon_base64: string("icon"),
+        resource_pack_policy: match server.get("acceptTextures") {
+            Some(Value::Byte(0)) => ServerResourcePackPolicy::Disabled,
+            Some(Value::Byte(_)) => ServerResourcePackPolicy::Enabled,
+            _ => ServerResourcePackPolicy::Prompt,
+        },
+        hidden: matches!(server.get("hidden"), Some(Value::Byte(value)) if *value != 0),
+    }
+}
+
+/// Writes the fields of `entry` into `server`, tags it doesn't know stay untouched.
+fn apply_entry_to_compound(server: &mut Compound, entry: &ServerListEntry) {
+    server.insert("name".to_string(), Value::String(entry.name.clone()));
+    server.insert("ip".to_string(), Value::String(entry.address.clone()));
+    match &entry.icon_base64 {
+        Some(icon) => server.insert("icon".to_string(), Value::String(icon.clone())),
+        None => server.remove("icon"),
+    };
+    match entry.resource_pack_policy {
+        ServerResourcePackPolicy::Prompt => server.remove("acceptTextures"),
+        ServerResourcePackPolicy::Enabled => {
+            server.insert("acceptTextures".to_string(), Value::Byte(1))
+        }
+        ServerResourcePackPolicy::Disabled => {
+            server.insert("acceptTextures".to_string(), Value::Byte(0))
+        }
+    };
+    if entry.hidden {
+        server.insert("hidden".to_string(), Value::Byte(1));
+    } else {
+        server.remove("hidden");
+    }
+}
+
+/// Lowercased address with the default port removed, so `Example.org` and
+/// `example.org:25565` count as the same server.
+pub(crate) fn normalize_address(address: &str) -> String {
+    let address = address.trim().to_lowercase();
+    if let Some((host, DEFAULT_MINECRAFT_PORT)) = address.rsplit_once(':') {
+        // `[::1]:25565` and `host:25565`, but not a bare IPv6 address
+        if !host.contains(':') || host.ends_with(']') {
+            return host.to_string();
+        }
+    }
+    address
+}
+
+/// Rejects entries vanilla would refuse or silently drop.
+fn validate_entry(entry: &ServerListEntry) -> Result<()> {
+    if entry.address.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "Server address must not be empty".to_string(),
+        ));
+    }
+    if let Some(icon) = &entry.icon_base64 {
+        let bytes = STANDARD
+            .decode(icon)
+            .map_err(|e| AppError::InvalidInput(format!("Server icon is not base64: {}", e)))?;
+        let image = image::load_from_memory_with_format(&bytes, ImageFormat::Png)
+            .map_err(|e| AppError::InvalidInput(format!("Server icon is not a PNG: {}", e)))?;
+        if image.dimensions() != (SERVER_ICON_SIZE, SERVER_ICON_SIZE) {
+            return Err(AppError::InvalidInput(format!(
+                "Server icon must be {0}x{0}, got {1}x{2}",
+                SERVER_ICON_SIZE,
+                image.width(),
+                image.height()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_index(servers: &[Compound], index: usize) -> Result<()> {
+    if index >= servers.len() {
+        return Err(AppError::InvalidInput(format!(
+            "Server index {} is out of range, the list has {} entries",
+            index,
+            servers.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Writes the fields `entry` sets into a known server: an entry without icon or with the
+/// `Prompt` policy keeps the icon and `acceptTextures` the server already has.
+fn merge_entry_into_compound(server: &mut Compound, entry: &ServerListEntry) {
+    server.insert("name".to_string(), Value::String(entry.name.clone()));
+    server.insert("ip".to_string(), Value::String(entry.address.clone()));
+    if let Some(icon) = &entry.icon_base64 {
+        server.insert("icon".to_string(), Value::String(icon.clone()));
+    }
+    match entry.resource_pack_policy {
+        ServerResourcePackPolicy::Prompt => {}
+        ServerResourcePackPolicy::Enabled => {
+            server.insert("acceptTextures".to_string(), Value::Byte(1));
+        }
+        ServerResourcePackPolicy::Disabled => {
+            server.insert("acceptTextures".to_string(), Value::Byte(0));
+        }
+    }
+}
+
+/// Merges `entries` into `servers` by address. Returns how many were added and updated.
+fn merge_entries(servers: &mut Vec<Compound>, entries: &[ServerListEntry]) -> (usize, usize) {
+    let (mut added, mut updated) = (0, 0);
+    for entry in entries {
+        let address = normalize_address(&entry.address);
+        let existing = servers
+            .iter_mut()
+            .find(|server| normalize_address(&entry_from_compound(server).address) == address);
+        match existing {
+            Some(server) => {
+                let before = entry_from_compound(server);
+                merge_entry_into_compound(server, entry);
+                if entry_from_compound(server) != before {
+                    updated += 1;
+                }
+            }
+            None => {
+                let mut server = Compound::new();
+                apply_entry_to_compound(&mut server, entry);
+                servers.push(server);
+                added += 1;
+            }
+        }
+    }
+    (added, updated)
+}
+
+/// Whether a running game uses the servers.dat at `path`. Profiles on the shared Minecraft
+/// folder all use the same file, so every running profile is resolved, not just one.
+async fn is_used_by_running_game(path: &Path) -> Result<bool> {
+    let state = State::get().await?;
+    let running_profiles: HashSet<Uuid> = state
+        .process_manager
+        .list_processes()
+        .await
+        .into_iter()
+        .map(|process| process.profile_id)
+        .collect();
+    for profile_id in running_profiles {
+        match servers_dat_path(profile_id).await {
+            Ok(running_path) if running_path == path => return Ok(true),
+            Ok(_) => {}
+            // E.g. the profile was deleted while its game kept running
+            Err(e) => warn!(
+                "Could not resolve servers.dat of running profile {}: {}",
+                profile_id, e
+            ),
+        }
+    }
+    Ok(false)
+}
+
+/// Runs `edit` on the server list of a profile and saves the result. Refused while a game
+/// using that servers.dat runs: it writes the file from memory and would drop the edit.
+async fn edit_server_list<F>(profile_id: Uuid, edit: F) -> Result<Vec<ServerListEntry>>
+where
+    F: FnOnce(&mut Vec<Compound>) -> Result<()>,
+{
+    let path = servers_dat_path(profile_id).await?;
+    if is_used_by_running_game(&path).await? {
+        return Err(AppError::Other(
+            "Cannot edit the server list while Minecraft is running with it".to_string(),
+        ));
+    }
+    let _guard = SERVER_LIST_LOCK.lock().await;
+    let mut servers = read_server_compounds(&path).await?;
+    edit(&mut servers)?;
+    let entries = servers.iter().map(entry_from_compound).collect();
+    write_server_compounds(&path, servers).await?;
+    Ok(entries)
+}
+
+/// Lists all entries of a profile's servers.dat, including hidden ones.
+pub async fn list_servers(profile_id: Uuid) -> Result<Vec<ServerListEntry>> {
+    let path = servers_dat_path(profile_id).await?;
+    Ok(read_server_compounds(&path)
+        .await?
+        .iter()
+        .map(entry_from_compound)
+        .collect())
+}
+
+/// Adds a server at `position` (the end if `None`). Returns the new list.
+pub async fn add_server(
+    profile_id: Uuid,
+    entry: ServerListEntry,
+    position: Option<usize>,
+) -> Result<Vec<ServerListEntry>> {
+    validate_entry(&entry)?;
+    info!(
+        "[Servers] Adding server '{}' ({}) to profile {}",
+        entry.name, entry.address, profile_id
+    );
+    edit_server_list(profile_id, |servers| {
+        let mut server = Compound::new();
+        apply_entry_to_compound(&mut server, &entry);
+        let position = position.unwrap_or(servers.len()).min(servers.len());
+        servers.insert(position, server);
+        Ok(())
+    })
+    .await
+}
+
+/// Replaces name, address, icon, resource pack policy and hidden flag of a server.
+pub async fn update_server(
+    profile_id: Uuid,
+    index: usize,
+    entry: ServerListEntry,
+) -> Result<Vec<ServerListEntry>> {
+    validate_entry(&entry)?;
+    edit_server_list(profile_id, |servers| {
+        check_index(servers, index)?;
+        apply_entry_to_compound(&mut servers[index], &entry);
+        Ok(())
+    })
+    .await
+}
+
+/// Moves a server from one position to another.
+pub async fn move_server(profile_id: Uuid, from: usize, to: usize) -> Result<Vec<ServerListEntry>> {
+    edit_server_list(profile_id, |servers| {
+        check_index(servers, from)?;
+        check_index(servers, to)?;
+        let server = servers.remove(from);
+        servers.insert(to, server);
+        Ok(())
+    })
+    .await
+}
+
+/// Removes a server from the list.
+pub async fn remove_server(profile_id: Uuid, index: usize) -> Result<Vec<ServerListEntry>> {
+    edit_server_list(profile_id, |servers| {
+        check_index(servers, index)?;
+        let removed = servers.remove(index);
+        info!(
+            "[Servers] Removed server '{}' from profile {}",
+            entry_from_compound(&removed).name,
+            profile_id
+        );
+        Ok(())
+    })
+    .await
+}
+
+/// Scales an image file to a 64x64 PNG and returns it base64 encoded.
+pub async fn load_server_icon(image_path: &Path) -> Result<String> {
+    let image_path = image_path.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<String> {
+        let image = image::open(&image_path)
+            .map_err(|e| AppError::ImageProcessingError(format!("Failed to open image: {}", e)))?;
+        let icon = if image.dimensions() == (SERVER_ICON_SIZE, SERVER_ICON_SIZE) {
+            image
+        } else {
+            image.resize_exact(SERVER_ICON_SIZE, SERVER_ICON_SIZE, FilterType::Lanczos3)
+        };
+        let mut bytes: Vec<u8> = Vec::new();
+        icon.write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Png)
+            .map_err(|e| {
+                AppError::ImageProcessingError(format!("Failed to encode icon to PNG: {}", e))
+            })?;
+        Ok(STANDARD.encode(bytes))
+    })
+    .await?
+}
+
+/// Sets a server's icon from an image file, or removes it if `image_path` is `None`.
+pub async fn set_server_icon(
+    profile_id: Uuid,
+    index: usize,
+    image_path: Option<&Path>,
+) -> Result<Vec<ServerListEntry>> {
+    let icon = match image_path {
+        Some(path) => Some(load_server_icon(path).await?),
+        None => None,
+    };
+    edit_server_list(profile_id, |servers| {
+        check_index(servers, index)?;
+        let mut entry = entry_from_compound(&servers[index]);
+        entry.icon_base64 = icon;
+        apply_entry_to_compound(&mut servers[index], &entry);
+        Ok(())
+    })
+    .await
+}
+
+/// Copies the server list of one profile into another. Returns the target's new list.
+pub async fn copy_server_list(
+    source_profile_id: Uuid,
+    target_profile_id: Uuid,
+    mode: ServerListCopyMode,
+) -> Result<Vec<ServerListEntry>> {
+    if source_profile_id == target_profile_id {
+        return Err(AppError::InvalidInput(
+            "Source and target profile are the same".to_string(),
+        ));
+    }
+    let source = read_server_compounds(&servers_dat_path(source_profile_id).await?).await?;
+    info!(
+        "[Servers] Copying {} servers from profile {} to {} ({:?})",
+        source.len(),
+        source_profile_id,
+        target_profile_id,
+        mode
+    );
+    edit_server_list(target_profile_id, |servers| {
+        match mode {
+            ServerListCopyMode::Replace => *servers = source,
+            ServerListCopyMode::Merge => {
+                let entries: Vec<ServerListEntry> =
+                    source.iter().map(entry_from_compound).collect();
+                merge_entries(servers, &entries);
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Merges a team-defined server list into every profile of a group. A failing profile
+/// doesn't stop the others, its error is reported in the result.
+pub async fn push_server_list_to_group(
+    group: &str,
+    entries: &[ServerListEntry],
+) -> Result<Vec<ServerListPushResult>> {
+    for entry in entries {
+        validate_entry(entry)?;
+    }
+    let state = State::get().await?;
+    let profiles: Vec<_> = state
+        .profile_manager
+        .list_profiles()
+        .await?
+        .into_iter()
+        .filter(|profile| {
+            profile
+                .group
+                .as_deref()
+                .is_some_and(|profile_group| profile_group.eq_ignore_ascii_case(group))
+        })
+        .collect();
+    info!(
+        "[Servers] Pushing {} servers to {} profiles of group '{}'",
+        entries.len(),
+        profiles.len(),
+        group
+    );
+
+    let mut results = Vec::with_capacity(profiles.len());
+    for profile in profiles {
+        let mut counts = (0, 0);
+        let edited = edit_server_list(profile.id, |servers| {
+            counts = merge_entries(servers, entries);
+            Ok(())
+        })
+        .await;
+        if let Err(e) = &edited {
+            warn!(
+                "[Servers] Failed to push server list to profile '{}': {}",
+                profile.name, e
+            );
+        }
+        results.push(ServerListPushResult {
+            profile_id: profile.id,
+            profile_name: profile.name,
+            added: counts.0,
+            updated: counts.1,
+            error: edited.err().map(|e| e.to_string()),
+        });
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, address: &str) -> ServerListEntry {
+        ServerListEntry {
+            name: name.to_string(),
+            address: address.to_string(),
+            icon_base64: None,
+            resource_pack_policy: ServerResourcePackPolicy::Prompt,
+            hidden: false,
+        }
+    }
+
+    #[test]
+    fn merges_by_address_and_keeps_unknown_tags() {
+        let mut known = Compound::new();
+        apply_entry_to_compound(&mut known, &entry("Old name", "Play.Example.org"));
+        known.insert("previewsChat".to_string(), Value::Byte(1));
+        known.insert("icon".to_string(), Value::String("aWNvbg==".to_string()));
+        let mut servers = vec![known];
+
+        let mut renamed = entry("Team Server", "play.example.org:25565");
+        renamed.resource_pack_policy = ServerResourcePackPolicy::Enabled;
+        let counts = merge_entries(
+            &mut servers,
+            &[renamed, entry("Lobby", "lobby.example.org")],
+        );
+
+        assert_eq!(counts, (1, 1));
+        assert_eq!(servers.len(), 2);
+        assert_eq!(entry_from_compound(&servers[0]).name, "Team Server");
+        assert_eq!(servers[0].get("acceptTextures"), Some(&Value::Byte(1)));
+        assert_eq!(servers[0].get("previewsChat"), Some(&Value::Byte(1)));
+        // The team entry has no icon, the user's stays
+        assert_eq!(
+            servers[0].get("icon"),
+            Some(&Value::String("aWNvbg==".to_string()))
+        );
+        assert_eq!(normalize_address("[::1]:25565"), "[::1]");
+        assert_eq!(normalize_address("::1"), "::1");
+    }
+}
@@ -51,13 +51,111 @@ pub async fn get_server_status(
     protocol_version: Option<i32>,
 ) -> Result<ServerStatus> {
     select! {
-        res = modern::status(address, original_address, protocol_version) => res,
+        res = status_with_legacy_fallback(address, original_address, protocol_version) => res,
         _ = time::sleep(Duration::from_secs(30)) => Err(AppError::Other(
             format!("Ping of {}:{} timed out", original_address.0, original_address.1)
         ))
     }
 }
 
+async fn status_with_legacy_fallback(
+    address: &impl ToSocketAddrs,
+    original_address: (&str, u16),
+    protocol_version: Option<i32>,
+) -> Result<ServerStatus> {
+    match modern::status(address, original_address, protocol_version).await {
+        Ok(status) => Ok(status),
+        // Servers before 1.7 drop the connection on the modern handshake
+        Err(modern_error) => legacy::status(address).await.map_err(|_| modern_error),
+    }
+}
+
+mod legacy {
+    use super::{ServerPlayers, ServerStatus, ServerVersion};
+    use crate::error::{AppError, Result};
+    use serde_json::value::RawValue;
+    use std::time::Instant;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpStream, ToSocketAddrs};
+
+    const KICK_PACKET_ID: u8 = 0xFF;
+    // Long enough for any MOTD, guards against reading garbage from non-Minecraft servers
+    const MAX_RESPONSE_CHARS: usize = 1024;
+
+    /// The `0xFE 0x01` server list ping of 1.4 to 1.6. Beta 1.8 to 1.3 servers ignore the
+    /// second byte and answer in their older format.
+    pub async fn status(address: &impl ToSocketAddrs) -> Result<ServerStatus> {
+        let start_time = Instant::now();
+        let mut stream = TcpStream::connect(address).await?;
+        stream.write_all(&[0xFE, 0x01]).await?;
+        stream.flush().await?;
+
+        if stream.read_u8().await? != KICK_PACKET_ID {
+            return Err(AppError::Other(
+                "Unexpected legacy ping response".to_string(),
+            ));
+        }
+        let length = stream.read_u16().await? as usize;
+        if length > MAX_RESPONSE_CHARS {
+            return Err(AppError::Other(
+                "Legacy ping response is too long".to_string(),
+            ));
+        }
+        let mut response = vec![0_u8; length * 2];
+        stream.read_exact(&mut response).await?;
+        let latency = start_time.elapsed().as_millis() as i64;
+
+        let utf16: Vec<u16> = response
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        let mut status = parse_response(&String::from_utf16_lossy(&utf16))?;
+        status.ping = Some(latency);
+        Ok(status)
+    }
+
+    /// Parses `§1\0<protocol>\0<version>\0<motd>\0<online>\0<max>` (1.4+)
+    /// or `<motd>§<online>§<max>` (Beta 1.8 to 1.3).
+    pub(super) fn parse_response(response: &str) -> Result<ServerStatus> {
+        let invalid = || AppError::Other(format!("Invalid legacy ping response: {:?}", response));
+
+        let (version, motd, online, max) = match response.strip_prefix("\u{a7}1\0") {
+            Some(fields) => {
+                let fields: Vec<&str> = fields.split('\0').collect();
+                if fields.len() < 5 {
+                    return Err(invalid());
+                }
+                let version = ServerVersion {
+                    name: fields[1].to_string(),
+                    protocol: fields[0].parse().unwrap_or(-1),
+                };
+                (Some(version), fields[2], fields[3], fields[4])
+            }
+            None => {
+                // The MOTD itself may contain `§` color codes, the counts come last
+                let mut fields = response.rsplitn(3, '\u{a7}');
+                let max = fields.next().ok_or_else(invalid)?;
+                let online = fields.next().ok_or_else(invalid)?;
+                let motd = fields.next().ok_or_else(invalid)?;
+                (None, motd, online, max)
+            }
+        };
+
+        Ok(ServerStatus {
+            description: Some(RawValue::from_string(serde_json::to_string(motd)?)?),
+            players: Some(ServerPlayers {
+                max: max.trim().parse().map_err(|_| invalid())?,
+                online: online.trim().parse().map_err(|_| invalid())?,
+                sample: Vec::new(),
+            }),
+            version,
+            favicon: None,
+            enforces_secure_chat: false,
+            ping: None,
+        })
+    }
+}
+
 mod modern {
     use super::ServerStatus;
     use crate::error::{AppError, Result};
@@ -205,3 +303,32 @@ mod modern {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::legacy::parse_response;
+
+    #[test]
+    fn parses_legacy_ping_responses() {
+        assert!(parse_response("\u{a7}1\0").is_err());
+
+        let status =
+            parse_response("\u{a7}1\u{0}74\u{0}1.6.4\u{0}A \u{a7}cred\u{a7}r MOTD\u{0}3\u{0}20")
+                .unwrap();
+        let version = status.version.unwrap();
+        assert_eq!((version.name.as_str(), version.protocol), ("1.6.4", 74));
+        assert_eq!(
+            status.description.unwrap().get(),
+            "\"A \u{a7}cred\u{a7}r MOTD\""
+        );
+        let players = status.players.unwrap();
+        assert_eq!((players.online, players.max), (3, 20));
+
+        // Beta 1.8 to 1.3, the MOTD contains the separator itself
+        let status = parse_response("Old \u{a7}aserver\u{a7}5\u{a7}10").unwrap();
+        assert!(status.version.is_none());
+        assert_eq!(status.description.unwrap().get(), "\"Old \u{a7}aserver\"");
+        let players = status.players.unwrap();
+        assert_eq!((players.online, players.max), (5, 10));
+    }
+}
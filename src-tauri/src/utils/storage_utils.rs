@@ -0,0 +1,929 @@
+use crate::config::{ProjectDirsExt, LAUNCHER_DIRECTORY};
+use crate::error::{AppError, Result};
+use crate::integrations::norisk_packs::GEGModpacksConfig;
+use crate::minecraft::api::mc_api::MinecraftApiService;
+use crate::minecraft::downloads::mod_cache::ModCache;
+use crate::minecraft::downloads::mojang_java_runtime_download::MOJANG_RUNTIME_DIR_PREFIX;
+use crate::minecraft::dto::piston_meta::{AssetIndexContent, PistonMeta};
+use crate::minecraft::modloader::ModloaderFactory;
+use crate::state::profile_state::{ModLoader, Profile};
+use crate::state::state_manager::State;
+use crate::utils::{path_utils, trash_utils};
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+/// Upper bound for the orphan list of a single category in the report sent to the frontend.
+const MAX_LISTED_ORPHANS: usize = 200;
+const STORAGE_TRASH_CATEGORY: &str = "storage";
+const JAVA_TRASH_CATEGORY: &str = "java";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageCategory {
+    Versions,
+    Libraries,
+    Natives,
+    AssetIndexes,
+    AssetObjects,
+    GegAssets,
+    JavaRuntimes,
+    ModCache,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageOrphan {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageCategoryUsage {
+    pub category: StorageCategory,
+    pub total_bytes: u64,
+    pub orphaned_bytes: u64,
+    pub orphan_count: usize,
+    /// At most `MAX_LISTED_ORPHANS` entries, `orphan_count` holds the real number
+    pub orphans: Vec<StorageOrphan>,
+    /// False if the needed artifacts could not be determined completely
+    pub cleanable: bool,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileStorageUsage {
+    pub profile_id: Uuid,
+    pub profile_name: String,
+    /// Size of the instance folder (shared with other profiles if `shared_instance` is set)
+    pub instance_bytes: u64,
+    /// Size of the meta artifacts the profile needs (may be shared with other profiles)
+    pub shared_bytes: u64,
+    pub shared_instance: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageReport {
+    pub meta_dir: PathBuf,
+    pub total_bytes: u64,
+    pub orphaned_bytes: u64,
+    pub categories: Vec<StorageCategoryUsage>,
+    pub profiles: Vec<ProfileStorageUsage>,
+    /// Game versions whose piston meta could not be loaded
+    pub incomplete_versions: Vec<String>,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageCleanupResult {
+    pub moved_items: usize,
+    pub freed_bytes: u64,
+    pub trash_paths: Vec<PathBuf>,
+}
+
+/// A loader artifact folder below `libraries/` whose children are versions.
+struct LoaderArtifact {
+    path: &'static str,
+    loaders: &'static [ModLoader],
+    /// Children are named after the Minecraft version instead of the loader version
+    keyed_by_game_version: bool,
+}
+
+const LOADER_ARTIFACTS: &[LoaderArtifact] = &[
+    LoaderArtifact {
+        path: "net/fabricmc/fabric-loader",
+        loaders: &[ModLoader::Fabric],
+        keyed_by_game_version: false,
+    },
+    LoaderArtifact {
+        path: "net/fabricmc/intermediary",
+        loaders: &[ModLoader::Fabric, ModLoader::Quilt],
+        keyed_by_game_version: true,
+    },
+    LoaderArtifact {
+        path: "org/quiltmc/quilt-loader",
+        loaders: &[ModLoader::Quilt],
+        keyed_by_game_version: false,
+    },
+    LoaderArtifact {
+        path: "org/quiltmc/hashed",
+        loaders: &[ModLoader::Quilt],
+        keyed_by_game_version: true,
+    },
+    LoaderArtifact {
+        path: "net/minecraftforge/forge",
+        loaders: &[ModLoader::Forge],
+        keyed_by_game_version: false,
+    },
+    LoaderArtifact {
+        path: "net/neoforged/neoforge",
+        loaders: &[ModLoader::NeoForge],
+        keyed_by_game_version: false,
+    },
+    LoaderArtifact {
+        path: "net/neoforged/forge",
+        loaders: &[ModLoader::NeoForge],
+        keyed_by_game_version: false,
+    },
+];
+
+/// Everything the current profiles need from the meta dir.
+#[derive(Default)]
+struct Requirements {
+    game_versions: HashSet<String>,
+    incomplete_versions: Vec<String>,
+    asset_indexes: HashSet<String>,
+    asset_objects: HashSet<String>,
+    /// Set if an asset index of a used version exists but could not be read
+    asset_objects_incomplete: bool,
+    libraries: HashSet<String>,
+    /// Libraries of installed versions that no profile uses anymore
+    unused_libraries: HashSet<String>,
+    java_majors: HashSet<u32>,
+    /// `javaVersion.component` of the used versions, for Mojang runtimes
+    java_components: HashSet<String>,
+    custom_java_paths: Vec<PathBuf>,
+    geg_assets: HashSet<String>,
+    geg_assets_incomplete: bool,
+    loader_game_versions: HashMap<ModLoader, HashSet<String>>,
+    /// `None` if at least one profile lets the installer pick the loader version
+    loader_versions: HashMap<ModLoader, Option<HashSet<String>>>,
+    version_java_major: HashMap<String, u32>,
+    version_asset_index: HashMap<String, String>,
+    asset_index_bytes: HashMap<String, u64>,
+    profile_geg_assets: HashMap<Uuid, Vec<String>>,
+}
+
+impl Requirements {
+    fn is_version_needed(&self, name: &str) -> bool {
+        self.game_versions.iter().any(|version| {
+            name == version
+                || name
+                    .strip_prefix(version.as_str())
+                    .is_some_and(|rest| rest.starts_with('-'))
+        })
+    }
+
+    fn is_loader_artifact_needed(&self, artifact: &LoaderArtifact, name: &str) -> bool {
+        artifact.loaders.iter().any(|loader| {
+            if artifact.keyed_by_game_version {
+                return self
+                    .loader_game_versions
+                    .get(loader)
+                    .is_some_and(|versions| versions.contains(name));
+            }
+            match self.loader_versions.get(loader) {
+                None => false,
+                Some(None) => true,
+                Some(Some(versions)) => versions.iter().any(|v| name.contains(v.as_str())),
+            }
+        })
+    }
+
+    fn is_java_runtime_needed(&self, path: &Path, name: &str) -> bool {
+        if self
+            .custom_java_paths
+            .iter()
+            .any(|java_path| java_path.starts_with(path))
+        {
+            return true;
+        }
+        if let Some(component) = name.strip_prefix(MOJANG_RUNTIME_DIR_PREFIX) {
+            let component = component.strip_suffix("_x86_64").unwrap_or(component);
+            return self.java_components.contains(component);
+        }
+        // Folder names look like `<distribution>_<major>[_x86_64]`
+        name.split('_')
+            .nth(1)
+            .and_then(|major| major.parse::<u32>().ok())
+            .is_none_or(|major| self.java_majors.contains(&major))
+    }
+}
+
+/// Recursive size of every direct child of `dir`.
+async fn list_children(dir: &Path) -> Result<Vec<(String, PathBuf, u64)>> {
+    let mut children = Vec::new();
+    if !dir.is_dir() {
+        return Ok(children);
+    }
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let size = path_utils::calculate_dir_size(&path).await?;
+        children.push((name, path, size));
+    }
+    Ok(children)
+}
+
+fn java_runtimes_dir() -> PathBuf {
+    crate::config::standard_meta_dir().join("java")
+}
+
+fn library_paths(meta: &PistonMeta) -> impl Iterator<Item = String> + '_ {
+    meta.libraries.iter().flat_map(|library| {
+        let artifact = library
+            .downloads
+            .artifact
+            .iter()
+            .filter_map(|info| info.path.clone());
+        let classifiers = library
+            .downloads
+            .classifiers
+            .iter()
+            .flat_map(|c| c.values())
+            .filter_map(|info| info.path.clone());
+        artifact.chain(classifiers)
+    })
+}
+
+async fn collect_requirements(
+    profiles: &[Profile],
+    pack_config: &GEGModpacksConfig,
+    meta_dir: &Path,
+) -> Result<Requirements> {
+    let mut req = Requirements {
+        game_versions: profiles.iter().map(|p| p.game_version.clone()).collect(),
+        ..Default::default()
+    };
+
+    // Piston metas of used and of installed versions (the latter only for their libraries)
+    let installed_versions: HashSet<String> = list_children(&meta_dir.join("versions"))
+        .await?
+        .into_iter()
+        .map(|(name, _, _)| name)
+        .collect();
+    let api_service = MinecraftApiService::new();
+    let manifest = match api_service.get_version_manifest().await {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            warn!("[Storage] Could not load the version manifest: {}", e);
+            None
+        }
+    };
+
+    let mut metas: HashMap<String, PistonMeta> = HashMap::new();
+    for version_id in req.game_versions.iter().chain(installed_versions.iter()) {
+        if metas.contains_key(version_id) {
+            continue;
+        }
+        let url = manifest
+            .as_ref()
+            .and_then(|m| m.versions.iter().find(|v| &v.id == version_id))
+            .map(|v| v.url.clone());
+        let Some(url) = url else {
+            debug!("[Storage] Version '{}' is not in the manifest", version_id);
+            continue;
+        };
+        match api_service.get_piston_meta(&url).await {
+            Ok(meta) => {
+                metas.insert(version_id.clone(), meta);
+            }
+            Err(e) => warn!(
+                "[Storage] Could not load piston meta for '{}': {}",
+                version_id, e
+            ),
+        }
+    }
+
+    let indexes_dir = meta_dir.join("assets").join("indexes");
+    let mut used_versions: Vec<String> = req.game_versions.iter().cloned().collect();
+    used_versions.sort();
+    for version_id in &used_versions {
+        let Some(meta) = metas.get(version_id) else {
+            req.incomplete_versions.push(version_id.clone());
+            continue;
+        };
+        req.libraries.extend(library_paths(meta));
+        let major = meta.java_version.major_version.max(0) as u32;
+        req.java_majors.insert(major);
+        req.java_components
+            .insert(meta.java_version.component.clone());
+        req.version_java_major.insert(version_id.clone(), major);
+
+        let index_id = meta.asset_index.id.clone();
+        req.version_asset_index
+            .insert(version_id.clone(), index_id.clone());
+        if !req.asset_indexes.insert(index_id.clone()) {
+            continue;
+        }
+        let index_path = indexes_dir.join(format!("{}.json", index_id));
+        if !index_path.exists() {
+            // Not downloaded yet, so none of its objects can be on disk either
+            continue;
+        }
+        let parsed = fs::read_to_string(&index_path)
+            .await
+            .map_err(AppError::from)
+            .and_then(|content| {
+                serde_json::from_str::<AssetIndexContent>(&content).map_err(AppError::from)
+            });
+        match parsed {
+            Ok(index) => {
+                let bytes = index.objects.values().map(|o| o.size.max(0) as u64).sum();
+                req.asset_index_bytes.insert(index_id, bytes);
+                req.asset_objects
+                    .extend(index.objects.into_values().map(|o| o.hash));
+            }
+            Err(e) => {
+                warn!(
+                    "[Storage] Could not read asset index {:?}: {}",
+                    index_path, e
+                );
+                req.asset_objects_incomplete = true;
+            }
+        }
+    }
+
+    for (version_id, meta) in &metas {
+        if !req.game_versions.contains(version_id) {
+            req.unused_libraries.extend(library_paths(meta));
+        }
+    }
+    let needed_libraries = std::mem::take(&mut req.libraries);
+    req.unused_libraries
+        .retain(|path| !needed_libraries.contains(path));
+    req.libraries = needed_libraries;
+
+    for profile in profiles {
+        if profile.settings.use_custom_java_path {
+            if let Some(java_path) = profile
+                .settings
+                .java_path
+                .as_ref()
+                .filter(|p| !p.is_empty())
+            {
+                req.custom_java_paths.push(PathBuf::from(java_path));
+            }
+        }
+
+        if profile.loader != ModLoader::Vanilla {
+            req.loader_game_versions
+                .entry(profile.loader)
+                .or_default()
+                .insert(profile.game_version.clone());
+            let resolved = ModloaderFactory::resolve_loader_version(
+                profile,
+                &profile.game_version,
+                Some(pack_config),
+            )
+            .await;
+            let version = resolved
+                .version
+                .or_else(|| profile.loader_version.clone())
+                .filter(|v| !v.is_empty());
+            let entry = req
+                .loader_versions
+                .entry(profile.loader)
+                .or_insert_with(|| Some(HashSet::new()));
+            match version {
+                Some(version) => {
+                    if let Some(versions) = entry {
+                        versions.insert(version);
+                    }
+                }
+                None => *entry = None,
+            }
+        }
+
+        if let Some(pack_id) = &profile.selected_GEG_pack_id {
+            match pack_config.get_resolved_pack_definition(pack_id) {
+                Ok(pack) => {
+                    req.geg_assets.extend(pack.assets.iter().cloned());
+                    req.profile_geg_assets.insert(profile.id, pack.assets);
+                }
+                Err(e) => {
+                    warn!(
+                        "[Storage] Could not resolve pack '{}' of profile '{}': {}",
+                        pack_id, profile.name, e
+                    );
+                    req.geg_assets_incomplete = true;
+                }
+            }
+        }
+    }
+
+    Ok(req)
+}
+
+fn category_usage(
+    category: StorageCategory,
+    total_bytes: u64,
+    orphans: Vec<StorageOrphan>,
+    cleanable: bool,
+    note: Option<String>,
+) -> StorageCategoryUsage {
+    StorageCategoryUsage {
+        category,
+        total_bytes,
+        orphaned_bytes: orphans.iter().map(|o| o.size_bytes).sum(),
+        orphan_count: orphans.len(),
+        orphans,
+        cleanable,
+        note,
+    }
+}
+
+/// Scans a folder whose direct children are either needed or orphaned as a whole.
+async fn scan_children(
+    dir: &Path,
+    sizes: &mut HashMap<PathBuf, u64>,
+    is_needed: impl Fn(&Path, &str) -> bool,
+) -> Result<(u64, Vec<StorageOrphan>)> {
+    let mut total = 0;
+    let mut orphans = Vec::new();
+    for (name, path, size) in list_children(dir).await? {
+        total += size;
+        if !is_needed(&path, &name) {
+            orphans.push(StorageOrphan {
+                path: path.clone(),
+                size_bytes: size,
+            });
+        }
+        sizes.insert(path, size);
+    }
+    Ok((total, orphans))
+}
+
+async fn scan_asset_objects(
+    objects_dir: &Path,
+    req: &Requirements,
+) -> Result<(u64, Vec<StorageOrphan>)> {
+    let mut total = 0;
+    let mut orphans = Vec::new();
+    if !objects_dir.is_dir() {
+        return Ok((total, orphans));
+    }
+    let mut shards = fs::read_dir(objects_dir).await?;
+    while let Some(shard) = shards.next_entry().await? {
+        if !shard.file_type().await?.is_dir() {
+            continue;
+        }
+        let mut objects = fs::read_dir(shard.path()).await?;
+        while let Some(object) = objects.next_entry().await? {
+            let size = object.metadata().await.map(|m| m.len()).unwrap_or(0);
+            total += size;
+            let hash = object.file_name().to_string_lossy().to_string();
+            if !req.asset_objects.contains(&hash) {
+                orphans.push(StorageOrphan {
+                    path: object.path(),
+                    size_bytes: size,
+                });
+            }
+        }
+    }
+    Ok((total, orphans))
+}
+
+async fn scan_libraries(
+    libraries_dir: &Path,
+    req: &Requirements,
+) -> Result<(u64, Vec<StorageOrphan>)> {
+    let total = path_utils::calculate_dir_size(libraries_dir).await?;
+    let mut orphans = Vec::new();
+
+    let mut unused: Vec<&String> = req.unused_libraries.iter().collect();
+    unused.sort();
+    for relative in unused {
+        let path = libraries_dir.join(relative);
+        if let Ok(metadata) = fs::metadata(&path).await {
+            orphans.push(StorageOrphan {
+                path,
+                size_bytes: metadata.len(),
+            });
+        }
+    }
+
+    for artifact in LOADER_ARTIFACTS {
+        let dir = libraries_dir.join(artifact.path);
+        for (name, path, size) in list_children(&dir).await? {
+            if !req.is_loader_artifact_needed(artifact, &name) {
+                orphans.push(StorageOrphan {
+                    path,
+                    size_bytes: size,
+                });
+            }
+        }
+    }
+
+    Ok((total, orphans))
+}
+
+/// Builds the full report, orphan lists are not truncated here.
+async fn analyze(profiles: &[Profile], pack_config: &GEGModpacksConfig) -> Result<StorageReport> {
+    let meta_dir = LAUNCHER_DIRECTORY.meta_dir().to_path_buf();
+    let req = collect_requirements(profiles, pack_config, &meta_dir).await?;
+    let versions_complete = req.incomplete_versions.is_empty();
+    let incomplete_note = (!versions_complete).then(|| {
+        format!(
+            "Could not load version metadata for {}, nothing is removed",
+            req.incomplete_versions.join(", ")
+        )
+    });
+    let mut sizes: HashMap<PathBuf, u64> = HashMap::new();
+    let mut categories = Vec::new();
+
+    let versions_dir = meta_dir.join("versions");
+    let (total, orphans) = scan_children(&versions_dir, &mut sizes, |_, name| {
+        req.is_version_needed(name)
+    })
+    .await?;
+    categories.push(category_usage(
+        StorageCategory::Versions,
+        total,
+        orphans,
+        true,
+        None,
+    ));
+
+    let (total, orphans) = scan_libraries(&meta_dir.join("libraries"), &req).await?;
+    categories.push(category_usage(
+        StorageCategory::Libraries,
+        total,
+        orphans,
+        versions_complete,
+        incomplete_note.clone(),
+    ));
+
+    let natives_dir = meta_dir.join("natives");
+    let (total, orphans) = scan_children(&natives_dir, &mut sizes, |_, name| {
+        req.is_version_needed(name)
+    })
+    .await?;
+    categories.push(category_usage(
+        StorageCategory::Natives,
+        total,
+        orphans,
+        true,
+        None,
+    ));
+
+    let assets_dir = meta_dir.join("assets");
+    let (total, orphans) = scan_children(&assets_dir.join("indexes"), &mut sizes, |path, _| {
+        path.file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|id| req.asset_indexes.contains(id))
+    })
+    .await?;
+    categories.push(category_usage(
+        StorageCategory::AssetIndexes,
+        total,
+        orphans,
+        versions_complete,
+        incomplete_note.clone(),
+    ));
+
+    let (total, orphans) = scan_asset_objects(&assets_dir.join("objects"), &req).await?;
+    let objects_complete = versions_complete && !req.asset_objects_incomplete;
+    let objects_note = if req.asset_objects_incomplete {
+        Some("An asset index could not be read, nothing is removed".to_string())
+    } else {
+        incomplete_note.clone()
+    };
+    categories.push(category_usage(
+        StorageCategory::AssetObjects,
+        total,
+        orphans,
+        objects_complete,
+        objects_note,
+    ));
+
+    let geg_assets_dir = assets_dir.join("GEG");
+    let (total, orphans) = scan_children(&geg_assets_dir, &mut sizes, |_, name| {
+        req.geg_assets.contains(name)
+    })
+    .await?;
+    categories.push(category_usage(
+        StorageCategory::GegAssets,
+        total,
+        orphans,
+        !req.geg_assets_incomplete,
+        req.geg_assets_incomplete
+            .then(|| "A GEG pack could not be resolved, nothing is removed".to_string()),
+    ));
+
+    let java_dir = java_runtimes_dir();
+    let (total, orphans) = scan_children(&java_dir, &mut sizes, |path, name| {
+        req.is_java_runtime_needed(path, name)
+    })
+    .await?;
+    categories.push(category_usage(
+        StorageCategory::JavaRuntimes,
+        total,
+        orphans,
+        versions_complete,
+        incomplete_note.clone(),
+    ));
+
+    let mod_references = ModCache::collect_references(profiles, pack_config);
+    let gc_report = ModCache::collect_garbage(&mod_references, true).await?;
+    let orphans = gc_report
+        .items
+        .iter()
+        .map(|item| StorageOrphan {
+            path: item.path.clone(),
+            size_bytes: item.size_bytes,
+        })
+        .collect();
+    let mod_cache_total = path_utils::calculate_dir_size(&ModCache::cache_dir()).await?;
+    categories.push(category_usage(
+        StorageCategory::ModCache,
+        mod_cache_total,
+        orphans,
+        true,
+        None,
+    ));
+
+    // Everything else in the meta dir (loader caches, trash, backups, ...)
+    let meta_total = path_utils::calculate_dir_size(&meta_dir).await?;
+    let java_inside_meta = java_dir.starts_with(&meta_dir);
+    let covered: u64 = categories
+        .iter()
+        .filter(|c| java_inside_meta || c.category != StorageCategory::JavaRuntimes)
+        .map(|c| c.total_bytes)
+        .sum();
+    categories.push(category_usage(
+        StorageCategory::Other,
+        meta_total.saturating_sub(covered),
+        Vec::new(),
+        false,
+        Some("Loader caches, trash, backups and configuration files".to_string()),
+    ));
+
+    let java_total = categories
+        .iter()
+        .find(|c| c.category == StorageCategory::JavaRuntimes)
+        .map_or(0, |c| c.total_bytes);
+    let total_bytes = if java_inside_meta {
+        meta_total
+    } else {
+        meta_total + java_total
+    };
+
+    let mut profile_usages = Vec::with_capacity(profiles.len());
+    let state = State::get().await?;
+    for profile in profiles {
+        let shared_instance = profile.should_use_shared_minecraft_folder();
+        let instance_bytes = match state
+            .profile_manager
+            .calculate_instance_path_for_profile(profile)
+        {
+            Ok(path) => path_utils::calculate_dir_size(&path).await.unwrap_or(0),
+            Err(_) => 0,
+        };
+
+        let version = &profile.game_version;
+        let mut shared_bytes = sizes.get(&versions_dir.join(version)).copied().unwrap_or(0)
+            + sizes.get(&natives_dir.join(version)).copied().unwrap_or(0);
+        if let Some(major) = req.version_java_major.get(version) {
+            shared_bytes += sizes
+                .iter()
+                .filter(|(path, _)| {
+                    path.parent() == Some(java_dir.as_path())
+                        && path
+                            .file_name()
+                            .and_then(|n| n.to_str())
+                            .and_then(|n| n.split('_').nth(1))
+                            == Some(major.to_string().as_str())
+                })
+                .map(|(_, size)| *size)
+                .sum::<u64>();
+        }
+        if let Some(index_id) = req.version_asset_index.get(version) {
+            shared_bytes += req.asset_index_bytes.get(index_id).copied().unwrap_or(0);
+        }
+        for asset_id in req
+            .profile_geg_assets
+            .get(&profile.id)
+            .into_iter()
+            .flatten()
+        {
+            shared_bytes += sizes
+                .get(&geg_assets_dir.join(asset_id))
+                .copied()
+                .unwrap_or(0);
+        }
+        let owner = format!("profile:{}", profile.id);
+        let mod_hashes: HashMap<String, u64> = mod_references
+            .iter()
+            .filter(|(_, owners)| owners.contains(&owner))
+            .filter_map(|(key, _)| ModCache::get_entry(key))
+            .map(|entry| (entry.sha1, entry.size))
+            .collect();
+        shared_bytes += mod_hashes.values().sum::<u64>();
+
+        profile_usages.push(ProfileStorageUsage {
+            profile_id: profile.id,
+            profile_name: profile.name.clone(),
+            instance_bytes,
+            shared_bytes,
+            shared_instance,
+        });
+    }
+    profile_usages.sort_by(|a, b| {
+        (b.instance_bytes + b.shared_bytes).cmp(&(a.instance_bytes + a.shared_bytes))
+    });
+
+    let orphaned_bytes = categories.iter().map(|c| c.orphaned_bytes).sum();
+    Ok(StorageReport {
+        meta_dir,
+        total_bytes,
+        orphaned_bytes,
+        categories,
+        profiles: profile_usages,
+        incomplete_versions: req.incomplete_versions,
+        generated_at: Utc::now(),
+    })
+}
+
+/// Reports the disk usage of the meta dir per category and per profile, including
+/// the artifacts no profile needs anymore.
+pub async fn analyze_storage() -> Result<StorageReport> {
+    info!("[Storage] Analyzing meta directory usage");
+    let state = State::get().await?;
+    let profiles = state.profile_manager.list_profiles().await?;
+    let pack_config = state.GEG_pack_manager.get_config().await;
+
+    let mut report = analyze(&profiles, &pack_config).await?;
+    for category in &mut report.categories {
+        category
+            .orphans
+            .sort_by_key(|orphan| std::cmp::Reverse(orphan.size_bytes));
+        category.orphans.truncate(MAX_LISTED_ORPHANS);
+    }
+    info!(
+        "[Storage] Meta directory uses {} bytes, {} bytes are orphaned",
+        report.total_bytes, report.orphaned_bytes
+    );
+    Ok(report)
+}
+
+/// Moves the orphans of the given categories into the trash so they can be restored.
+/// Orphans are determined again here, paths from an older report are never trusted.
+pub async fn cleanup_storage(categories: &[StorageCategory]) -> Result<StorageCleanupResult> {
+    let state = State::get().await?;
+    if !state.process_manager.list_processes().await.is_empty() {
+        return Err(AppError::Other(
+            "Cannot clean up storage while Minecraft is running".to_string(),
+        ));
+    }
+    // Installs and downloads write into the same directories the orphans are taken from
+    if state.download_queue.has_unfinished_jobs() {
+        return Err(AppError::Other(
+            "Cannot clean up storage while downloads are in progress".to_string(),
+        ));
+    }
+    let profiles = state.profile_manager.list_profiles().await?;
+    let pack_config = state.GEG_pack_manager.get_config().await;
+    let report = analyze(&profiles, &pack_config).await?;
+    let meta_dir = report.meta_dir.clone();
+
+    let mut result = StorageCleanupResult::default();
+    let staging_dir = meta_dir.join(format!(
+        ".storage_cleanup_{}",
+        Utc::now().format("%Y%m%d_%H%M%S")
+    ));
+    let mut staged_any = false;
+
+    for usage in &report.categories {
+        if !categories.contains(&usage.category) {
+            continue;
+        }
+        if !usage.cleanable {
+            warn!(
+                "[Storage] Skipping {:?}: needed artifacts are not fully known",
+                usage.category
+            );
+            continue;
+        }
+
+        for orphan in &usage.orphans {
+            match orphan.path.strip_prefix(&meta_dir) {
+                // Artifacts inside the meta dir are collected in one trash entry
+                Ok(relative) => {
+                    let target = staging_dir.join(relative);
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent).await?;
+                    }
+                    match fs::rename(&orphan.path, &target).await {
+                        Ok(_) => {
+                            staged_any = true;
+                            result.moved_items += 1;
+                            result.freed_bytes += orphan.size_bytes;
+                        }
+                        Err(e) => warn!("[Storage] Failed to move {:?}: {}", orphan.path, e),
+                    }
+                }
+                // Java runtimes can live outside of a custom meta dir
+                Err(_) => {
+                    match trash_utils::move_path_to_trash(&orphan.path, Some(JAVA_TRASH_CATEGORY))
+                        .await
+                    {
+                        Ok(trash_path) => {
+                            result.moved_items += 1;
+                            result.freed_bytes += orphan.size_bytes;
+                            result.trash_paths.push(trash_path);
+                        }
+                        Err(e) => warn!("[Storage] Failed to trash {:?}: {}", orphan.path, e),
+                    }
+                }
+            }
+        }
+
+        if usage.category == StorageCategory::ModCache {
+            // Drops the index entries of the objects that were just moved
+            let references = ModCache::collect_references(&profiles, &pack_config);
+            ModCache::collect_garbage(&references, false).await?;
+        }
+    }
+
+    if staged_any {
+        let trash_path =
+            trash_utils::move_path_to_trash(&staging_dir, Some(STORAGE_TRASH_CATEGORY)).await?;
+        result.trash_paths.push(trash_path);
+    }
+
+    info!(
+        "[Storage] Moved {} items ({} bytes) to the trash",
+        result.moved_items, result.freed_bytes
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirements() -> Requirements {
+        let mut req = Requirements::default();
+        req.game_versions.insert("1.20.1".to_string());
+        req.java_majors.insert(17);
+        req.java_components.insert("java-runtime-gamma".to_string());
+        req.loader_game_versions
+            .entry(ModLoader::Fabric)
+            .or_default()
+            .insert("1.20.1".to_string());
+        req.loader_versions.insert(
+            ModLoader::Fabric,
+            Some(HashSet::from(["0.15.11".to_string()])),
+        );
+        req.loader_versions.insert(ModLoader::Forge, None);
+        req
+    }
+
+    #[test]
+    fn version_folders_match_exactly_or_with_suffix() {
+        let req = requirements();
+        assert!(req.is_version_needed("1.20.1"));
+        assert!(req.is_version_needed("1.20.1-forge"));
+        assert!(!req.is_version_needed("1.20.10"));
+        assert!(!req.is_version_needed("1.19.4"));
+    }
+
+    #[test]
+    fn loader_artifacts_follow_profile_loaders() {
+        let req = requirements();
+        let find = |path: &str| LOADER_ARTIFACTS.iter().find(|a| a.path == path).unwrap();
+
+        let fabric_loader = find("net/fabricmc/fabric-loader");
+        assert!(req.is_loader_artifact_needed(fabric_loader, "0.15.11"));
+        assert!(!req.is_loader_artifact_needed(fabric_loader, "0.14.21"));
+
+        let intermediary = find("net/fabricmc/intermediary");
+        assert!(req.is_loader_artifact_needed(intermediary, "1.20.1"));
+        assert!(!req.is_loader_artifact_needed(intermediary, "1.19.2"));
+
+        // Forge version is picked at install time, so every version stays
+        assert!(req.is_loader_artifact_needed(find("net/minecraftforge/forge"), "1.20.1-47.2.0"));
+        // No profile uses Quilt or NeoForge
+        assert!(!req.is_loader_artifact_needed(find("org/quiltmc/quilt-loader"), "0.26.0"));
+        assert!(!req.is_loader_artifact_needed(find("net/neoforged/neoforge"), "21.1.1"));
+    }
+
+    #[test]
+    fn java_runtimes_are_kept_by_major_or_custom_path() {
+        let mut req = requirements();
+        let java_dir = PathBuf::from("/meta/java");
+        assert!(req.is_java_runtime_needed(&java_dir.join("zulu_17"), "zulu_17"));
+        assert!(req.is_java_runtime_needed(&java_dir.join("zulu_17_x86_64"), "zulu_17_x86_64"));
+        assert!(!req.is_java_runtime_needed(&java_dir.join("zulu_8"), "zulu_8"));
+        assert!(req.is_java_runtime_needed(
+            &java_dir.join("mojang_java-runtime-gamma"),
+            "mojang_java-runtime-gamma"
+        ));
+        assert!(!req.is_java_runtime_needed(
+            &java_dir.join("mojang_jre-legacy_x86_64"),
+            "mojang_jre-legacy_x86_64"
+        ));
+        // Unknown layouts are never removed
+        assert!(req.is_java_runtime_needed(&java_dir.join("custom"), "custom"));
+
+        req.custom_java_paths
+            .push(java_dir.join("zulu_8").join("bin").join("java"));
+        assert!(req.is_java_runtime_needed(&java_dir.join("zulu_8"), "zulu_8"));
+    }
+}
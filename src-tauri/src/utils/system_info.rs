@@ -64,6 +64,25 @@ impl OperatingSystem {
         })
     }
 
+    /// Platform key of Mojang's java-runtime index for this OS and architecture.
+    pub fn get_mojang_runtime_platform(&self, arch: &Architecture) -> Result<&'static str> {
+        Ok(match (self, arch) {
+            (OperatingSystem::WINDOWS, Architecture::X64) => "windows-x64",
+            (OperatingSystem::WINDOWS, Architecture::X86) => "windows-x86",
+            (OperatingSystem::WINDOWS, Architecture::AARCH64) => "windows-arm64",
+            (OperatingSystem::LINUX, Architecture::X64) => "linux",
+            (OperatingSystem::LINUX, Architecture::X86) => "linux-i386",
+            (OperatingSystem::OSX, Architecture::X64) => "mac-os",
+            (OperatingSystem::OSX, Architecture::AARCH64) => "mac-os-arm64",
+            _ => {
+                return Err(AppError::JavaDownload(format!(
+                    "Mojang provides no Java runtimes for {:?} {:?}",
+                    self, arch
+                )))
+            }
+        })
+    }
+
     pub fn get_archive_type(&self) -> Result<&'static str> {
         Ok(match self {
             OperatingSystem::WINDOWS => "zip",
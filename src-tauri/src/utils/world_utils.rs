@@ -1,5 +1,6 @@
 use crate::error::{AppError, Result};
 use crate::state::State;
+use crate::utils::level_dat_utils;
 use fastnbt::{from_bytes, to_bytes, Value};
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
@@ -8,10 +9,15 @@ use fs4::tokio::AsyncFileExt;
 use fs_extra::dir::{copy as copy_dir, CopyOptions};
 use log::{error, info, warn};
 use sanitize_filename;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 use uuid::Uuid;
 
+// Folder name prefix of world archives being extracted, next to the saves folder
+const IMPORT_STAGING_PREFIX: &str = ".import-";
+
 /// Generates a unique, sanitized folder name for a world within a given saves directory.
 ///
 /// Takes a desired display name, sanitizes it for use as a folder name, and appends
@@ -617,6 +623,579 @@ pub async fn delete_world_directory(profile_id: Uuid, world_folder: &str) -> Res
     Ok(())
 }
 
+/// Archive formats for world backups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorldArchiveFormat {
+    #[default]
+    Zip,
+    /// Smaller and faster to write than zip, but needs an external tool to open by hand
+    TarZst,
+}
+
+impl WorldArchiveFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            WorldArchiveFormat::Zip => "zip",
+            WorldArchiveFormat::TarZst => "tar.zst",
+        }
+    }
+
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        if file_name.ends_with(".tar.zst") {
+            Some(WorldArchiveFormat::TarZst)
+        } else if file_name.ends_with(".zip") {
+            Some(WorldArchiveFormat::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Rejects empty world folder names and names that would leave the saves directory.
+pub fn validate_world_folder_name(world_folder: &str) -> Result<()> {
+    if world_folder.is_empty()
+        || world_folder == "."
+        || world_folder == ".."
+        || world_folder.contains('/')
+        || world_folder.contains('\\')
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid world folder name: '{}'",
+            world_folder
+        )));
+    }
+    Ok(())
+}
+
+/// Relative paths of all files of a world, without the root `session.lock`.
+fn collect_world_files(world_path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![PathBuf::new()];
+    while let Some(relative_dir) = dirs.pop() {
+        for entry in std::fs::read_dir(world_path.join(&relative_dir))? {
+            let entry = entry?;
+            let relative = relative_dir.join(entry.file_name());
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                dirs.push(relative);
+            } else if file_type.is_file() && relative != Path::new("session.lock") {
+                files.push(relative);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Writes all world files into a new archive. With `root`, entries are placed
+/// below that folder instead of at the archive root.
+fn write_world_archive_blocking(
+    world_path: &Path,
+    archive_path: &Path,
+    format: WorldArchiveFormat,
+    root: Option<&Path>,
+) -> Result<()> {
+    use std::io::Write;
+
+    let files = collect_world_files(world_path)?;
+    let output = std::io::BufWriter::new(std::fs::File::create(archive_path)?);
+
+    match format {
+        WorldArchiveFormat::Zip => {
+            let mut zip = zip::ZipWriter::new(output);
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated);
+            for relative in files {
+                let entry_path = root.map_or_else(|| relative.clone(), |root| root.join(&relative));
+                let name = entry_path.to_string_lossy().replace('\\', "/");
+                zip.start_file(name, options)?;
+                let mut source = std::fs::File::open(world_path.join(&relative))?;
+                std::io::copy(&mut source, &mut zip)?;
+            }
+            zip.finish()?.flush()?;
+        }
+        WorldArchiveFormat::TarZst => {
+            let encoder = zstd::stream::write::Encoder::new(output, 3)?;
+            let mut builder = tar::Builder::new(encoder);
+            for relative in files {
+                let entry_path = root.map_or_else(|| relative.clone(), |root| root.join(&relative));
+                builder.append_path_with_name(world_path.join(&relative), &entry_path)?;
+            }
+            builder.into_inner()?.finish()?.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn extract_world_archive_blocking(
+    archive_path: &Path,
+    target_path: &Path,
+    format: WorldArchiveFormat,
+) -> Result<()> {
+    let input = std::io::BufReader::new(std::fs::File::open(archive_path)?);
+    match format {
+        // Both extractors reject entries that would end up outside of `target_path`
+        WorldArchiveFormat::Zip => zip::ZipArchive::new(input)?.extract(target_path)?,
+        WorldArchiveFormat::TarZst => {
+            tar::Archive::new(zstd::stream::read::Decoder::new(input)?).unpack(target_path)?
+        }
+    }
+    Ok(())
+}
+
+/// Packs a world directory into `archive_path` and returns the archive size.
+/// Entries are relative to the world root. The archive is written next to its
+/// destination first, so an interrupted run never leaves a truncated archive behind.
+pub async fn create_world_archive(
+    world_path: &Path,
+    archive_path: &Path,
+    format: WorldArchiveFormat,
+) -> Result<u64> {
+    write_world_archive(world_path, archive_path, format, None).await
+}
+
+async fn write_world_archive(
+    world_path: &Path,
+    archive_path: &Path,
+    format: WorldArchiveFormat,
+    root: Option<PathBuf>,
+) -> Result<u64> {
+    info!(
+        "Archiving world '{}' to '{}'",
+        world_path.display(),
+        archive_path.display()
+    );
+    if let Some(parent) = archive_path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let mut partial_name = archive_path.as_os_str().to_owned();
+    partial_name.push(".partial");
+    let partial_path = PathBuf::from(partial_name);
+
+    let world = world_path.to_path_buf();
+    let partial = partial_path.clone();
+    let written = tokio::task::spawn_blocking(move || {
+        write_world_archive_blocking(&world, &partial, format, root.as_deref())
+    })
+    .await?;
+    if let Err(e) = written {
+        let _ = fs::remove_file(&partial_path).await;
+        return Err(e);
+    }
+
+    fs::rename(&partial_path, archive_path).await?;
+    Ok(fs::metadata(archive_path).await?.len())
+}
+
+/// Unpacks a world archive into `target_path`, which must not exist yet.
+/// A partially extracted world is removed again if extraction fails.
+pub async fn extract_world_archive(
+    archive_path: &Path,
+    target_path: &Path,
+    format: WorldArchiveFormat,
+) -> Result<()> {
+    info!(
+        "Extracting world archive '{}' to '{}'",
+        archive_path.display(),
+        target_path.display()
+    );
+    if fs::try_exists(target_path).await? {
+        return Err(AppError::InvalidInput(format!(
+            "Target world folder already exists: {}",
+            target_path.display()
+        )));
+    }
+    fs::create_dir_all(target_path).await?;
+
+    let archive = archive_path.to_path_buf();
+    let target = target_path.to_path_buf();
+    let extracted = tokio::task::spawn_blocking(move || {
+        extract_world_archive_blocking(&archive, &target, format)
+    })
+    .await?;
+    if let Err(e) = extracted {
+        warn!(
+            "Extraction into '{}' failed, removing it: {}",
+            target_path.display(),
+            e
+        );
+        let _ = fs::remove_dir_all(target_path).await;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Outcome of importing a world archive into a profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldImportResult {
+    pub folder_name: String,
+    pub level_name: Option<String>,
+    /// `Version.Name` from level.dat, the Minecraft version the world was last played in
+    pub world_version: Option<String>,
+    pub profile_version: String,
+    /// The world was last played in a newer Minecraft version than the profile uses.
+    /// Opening it may fail or lose data.
+    pub newer_than_profile: bool,
+}
+
+/// The level.dat fields that are needed to import a world.
+struct LevelDatSummary {
+    level_name: Option<String>,
+    version_name: Option<String>,
+}
+
+async fn read_level_dat_summary(level_dat_path: &Path) -> Result<LevelDatSummary> {
+    let nbt_value = level_dat_utils::read_level_dat_nbt(level_dat_path).await?;
+
+    let data = match &nbt_value {
+        Value::Compound(root) => match root.get("Data") {
+            Some(Value::Compound(data)) => Some(data),
+            _ => None,
+        },
+        _ => None,
+    };
+    let level_name = data.and_then(|data| match data.get("LevelName") {
+        Some(Value::String(name)) => Some(name.clone()),
+        _ => None,
+    });
+    let version_name = data.and_then(|data| match data.get("Version") {
+        Some(Value::Compound(version)) => match version.get("Name") {
+            Some(Value::String(name)) => Some(name.clone()),
+            _ => None,
+        },
+        _ => None,
+    });
+    Ok(LevelDatSummary {
+        level_name,
+        version_name,
+    })
+}
+
+/// Splits a Minecraft release name into its numbers and a stage, so that
+/// `1.21-pre1` < `1.21-rc1` < `1.21`. Snapshots like `24w14a` have no order.
+fn parse_minecraft_release(name: &str) -> Option<(Vec<u32>, (u8, u32))> {
+    let name = name.trim();
+    let (release, suffix) = match name.find(|c| c == '-' || c == ' ') {
+        Some(index) => (&name[..index], Some(name[index + 1..].trim())),
+        None => (name, None),
+    };
+    let numbers = release
+        .split('.')
+        .map(|segment| segment.parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    let stage = match suffix {
+        None => (2, 0),
+        Some(suffix) => {
+            let suffix = suffix.to_ascii_lowercase();
+            // `1.14 Pre-Release 1` is the old spelling of `1.14-pre1`
+            let (rank, number) = if let Some(number) = suffix.strip_prefix("pre-release") {
+                (0, number)
+            } else if let Some(number) = suffix.strip_prefix("pre") {
+                (0, number)
+            } else if let Some(number) = suffix.strip_prefix("rc") {
+                (1, number)
+            } else {
+                return None;
+            };
+            (rank, number.trim().parse().ok()?)
+        }
+    };
+    Some((numbers, stage))
+}
+
+/// Whether `world_version` is a newer Minecraft release than `profile_version`.
+/// Versions that can't be ordered (snapshots, modded names) never count as newer.
+fn is_newer_minecraft_release(world_version: &str, profile_version: &str) -> bool {
+    let (Some((mut world, world_stage)), Some((mut profile, profile_stage))) = (
+        parse_minecraft_release(world_version),
+        parse_minecraft_release(profile_version),
+    ) else {
+        return false;
+    };
+    // `1.21` and `1.21.0` are the same release
+    let len = world.len().max(profile.len());
+    world.resize(len, 0);
+    profile.resize(len, 0);
+    (world, world_stage) > (profile, profile_stage)
+}
+
+/// Directories at the shallowest depth that contain a `level.dat`. Shared archives
+/// often wrap the world in one or more folders. macOS resource forks are skipped.
+fn find_world_roots(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut current = vec![dir.to_path_buf()];
+    while !current.is_empty() {
+        let roots: Vec<PathBuf> = current
+            .iter()
+            .filter(|candidate| candidate.join("level.dat").is_file())
+            .cloned()
+            .collect();
+        if !roots.is_empty() {
+            return Ok(roots);
+        }
+
+        let mut next = Vec::new();
+        for candidate in &current {
+            for entry in std::fs::read_dir(candidate)? {
+                let entry = entry?;
+                if entry.file_type()?.is_dir() && entry.file_name() != "__MACOSX" {
+                    next.push(entry.path());
+                }
+            }
+        }
+        next.sort();
+        current = next;
+    }
+    Ok(Vec::new())
+}
+
+/// Imports a world from a zip or tar.zst archive into the saves folder of a profile.
+///
+/// The world root is wherever the shallowest `level.dat` is, so both flat archives and
+/// archives with wrapping folders work. The folder name is taken from `world_name`, the
+/// world's `LevelName` or the archive name, in that order, and made unique.
+pub async fn import_world_archive(
+    profile_id: Uuid,
+    archive_path: &Path,
+    world_name: Option<&str>,
+) -> Result<WorldImportResult> {
+    let archive_name = archive_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let format =
+        WorldArchiveFormat::from_file_name(&archive_name.to_lowercase()).ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "Unsupported world archive '{}', expected .zip or .tar.zst",
+                archive_name
+            ))
+        })?;
+    info!(
+        "Importing world archive '{}' into profile {}",
+        archive_path.display(),
+        profile_id
+    );
+
+    let state = State::get().await?;
+    let profile = state.profile_manager.get_profile(profile_id).await?;
+    let instance_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?;
+    let saves_path = instance_path.join("saves");
+    fs::create_dir_all(&saves_path).await?;
+
+    // Extracted next to saves/ on the same filesystem, so moving the world into place is a
+    // rename and an interrupted import never shows up as a world
+    let staging_path = instance_path.join(format!("{}{}", IMPORT_STAGING_PREFIX, Uuid::new_v4()));
+    extract_world_archive(archive_path, &staging_path, format).await?;
+
+    let imported = move_imported_world(
+        &staging_path,
+        &saves_path,
+        &archive_name,
+        format,
+        world_name,
+        &profile.game_version,
+    )
+    .await;
+
+    // Whatever is left around the world root (or everything, on failure)
+    if fs::try_exists(&staging_path).await.unwrap_or(false) {
+        if let Err(e) = fs::remove_dir_all(&staging_path).await {
+            warn!(
+                "Failed to remove import staging folder '{}': {}",
+                staging_path.display(),
+                e
+            );
+        }
+    }
+    imported
+}
+
+/// Removes the staging folders of world imports that were interrupted, e.g. because the
+/// launcher was killed while extracting. Run once on startup, before any import can start.
+pub async fn remove_stale_import_folders() -> Result<()> {
+    let state = State::get().await?;
+    let mut instance_paths = HashSet::new();
+    for profile in state.profile_manager.list_profiles().await? {
+        match state
+            .profile_manager
+            .calculate_instance_path_for_profile(&profile)
+        {
+            Ok(path) => {
+                instance_paths.insert(path);
+            }
+            Err(e) => warn!(
+                "Could not resolve instance folder of profile {}: {}",
+                profile.id, e
+            ),
+        }
+    }
+
+    for instance_path in instance_paths {
+        // Imports used to be staged inside saves/
+        for dir in [instance_path.clone(), instance_path.join("saves")] {
+            let Ok(mut entries) = fs::read_dir(&dir).await else {
+                continue;
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let is_staging = entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(IMPORT_STAGING_PREFIX);
+                if !is_staging || !entry.file_type().await?.is_dir() {
+                    continue;
+                }
+                info!(
+                    "Removing staging folder of an interrupted world import: {}",
+                    entry.path().display()
+                );
+                if let Err(e) = fs::remove_dir_all(entry.path()).await {
+                    warn!(
+                        "Failed to remove import staging folder '{}': {}",
+                        entry.path().display(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Finds the world in an extracted archive and moves it into the saves folder.
+async fn move_imported_world(
+    staging_path: &Path,
+    saves_path: &Path,
+    archive_name: &str,
+    format: WorldArchiveFormat,
+    world_name: Option<&str>,
+    profile_version: &str,
+) -> Result<WorldImportResult> {
+    let staging = staging_path.to_path_buf();
+    let roots = tokio::task::spawn_blocking(move || find_world_roots(&staging)).await??;
+    let world_root = match roots.as_slice() {
+        [root] => root.clone(),
+        [] => {
+            return Err(AppError::InvalidInput(format!(
+                "'{}' does not contain a world (no level.dat found)",
+                archive_name
+            )))
+        }
+        _ => {
+            return Err(AppError::InvalidInput(format!(
+                "'{}' contains {} worlds, only archives with a single world can be imported",
+                archive_name,
+                roots.len()
+            )))
+        }
+    };
+
+    let summary = read_level_dat_summary(&world_root.join("level.dat")).await?;
+    let desired_name = world_name
+        .map(str::to_string)
+        .or_else(|| summary.level_name.clone())
+        .unwrap_or_else(|| {
+            let suffix = format!(".{}", format.extension());
+            archive_name
+                .get(..archive_name.len().saturating_sub(suffix.len()))
+                .unwrap_or(archive_name)
+                .to_string()
+        });
+    let folder_name = find_unique_world_folder_name(saves_path, &desired_name).await?;
+
+    // A lock file from the sharer's machine is meaningless here
+    let _ = fs::remove_file(world_root.join("session.lock")).await;
+    let target_path = saves_path.join(&folder_name);
+    fs::rename(&world_root, &target_path).await?;
+    if let Some(name) = world_name {
+        modify_level_dat_name(&target_path.join("level.dat"), name).await?;
+    }
+
+    let newer_than_profile = summary
+        .version_name
+        .as_deref()
+        .is_some_and(|version| is_newer_minecraft_release(version, profile_version));
+    if newer_than_profile {
+        warn!(
+            "Imported world '{}' was last played in {}, but the profile uses {}",
+            folder_name,
+            summary.version_name.as_deref().unwrap_or_default(),
+            profile_version
+        );
+    }
+
+    Ok(WorldImportResult {
+        folder_name,
+        level_name: world_name.map(str::to_string).or(summary.level_name),
+        world_version: summary.version_name,
+        profile_version: profile_version.to_string(),
+        newer_than_profile,
+    })
+}
+
+/// Exports a world as a zip for sharing. The files are placed in a folder named after
+/// the world and `session.lock` is left out. Fails while the world is open in Minecraft.
+pub async fn export_world_archive(
+    profile_id: Uuid,
+    world_folder: &str,
+    export_path: &Path,
+) -> Result<u64> {
+    validate_world_folder_name(world_folder)?;
+    let state = State::get().await?;
+    let world_path = state
+        .profile_manager
+        .get_profile_instance_path(profile_id)
+        .await?
+        .join("saves")
+        .join(world_folder);
+    if !world_path.is_dir() {
+        return Err(AppError::WorldNotFound {
+            profile_id,
+            world_folder: world_folder.to_string(),
+        });
+    }
+    match check_world_session_lock(&world_path).await {
+        Err(AppError::WorldLocked { .. }) => {
+            return Err(AppError::WorldLocked {
+                profile_id,
+                world_folder: world_folder.to_string(),
+            })
+        }
+        other => other?,
+    }
+
+    write_world_archive(
+        &world_path,
+        export_path,
+        WorldArchiveFormat::Zip,
+        Some(PathBuf::from(world_folder)),
+    )
+    .await
+}
+
 // --- Error Enum Extension (add FsExtra and WorldLocked variants in error.rs) ---
 // Need to add these to the main AppError enum in src-tauri/src/error.rs
 // #[error("World '{world_folder}' in profile {profile_id} is currently locked (in use).")]
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_minecraft_releases() {
+        assert!(is_newer_minecraft_release("1.21", "1.20.4"));
+        assert!(is_newer_minecraft_release("1.21.1", "1.21"));
+        assert!(is_newer_minecraft_release("1.21", "1.21-rc1"));
+        assert!(is_newer_minecraft_release(
+            "1.14 Pre-Release 2",
+            "1.14-pre1"
+        ));
+        assert!(!is_newer_minecraft_release("1.21.0", "1.21"));
+        assert!(!is_newer_minecraft_release("1.20.4", "1.21"));
+        // Snapshots can't be ordered against releases
+        assert!(!is_newer_minecraft_release("24w14a", "1.20.4"));
+    }
+}
@@ -259,6 +259,24 @@ export function GlobalCrashReportModal() {
         <p className="pt-4 text-2xl font-minecraft text-red-400">
           exit code: {crashData.exit_code ?? 'N/A'}
         </p>
+
+        {crashData.crash_analysis && crashData.crash_analysis.diagnoses.length > 0 && (
+          <div className="pt-2 space-y-3 text-left">
+            {crashData.crash_analysis.diagnoses.map((diagnosis, index) => (
+              <div key={`${diagnosis.rule_id}-${index}`} className="p-3 rounded-md bg-black/30 border border-red-400/30">
+                <p className="text-lg font-minecraft text-red-300">{diagnosis.title}</p>
+                <p className="text-sm font-minecraft-ten text-gray-300">{diagnosis.description}</p>
+                {diagnosis.suggestions.length > 0 && (
+                  <ul className="pt-1 list-disc list-inside text-sm font-minecraft-ten text-gray-400">
+                    {diagnosis.suggestions.map((suggestion) => (
+                      <li key={suggestion}>{suggestion}</li>
+                    ))}
+                  </ul>
+                )}
+              </div>
+            ))}
+          </div>
+        )}
       </div>
     </Modal>
   );
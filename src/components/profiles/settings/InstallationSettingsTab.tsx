@@ -664,6 +664,19 @@ export function InstallationSettingsTab({
                 {editedProfile.game_version}
               </Card>
             )}
+
+            <Checkbox
+              checked={editedProfile.settings.skip_mod_validation || false}
+              onChange={(e) => updateProfile({
+                settings: {
+                  ...editedProfile.settings,
+                  skip_mod_validation: e.target.checked
+                }
+              })}
+              label="Skip mod validation"
+              description="Launch even if mods have missing dependencies or conflicts"
+              size="md"
+            />
           </div>
         )}
       </div>
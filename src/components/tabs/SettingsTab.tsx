@@ -404,6 +404,19 @@ export function SettingsTab() {
                 hide_on_process_start: checked,
               }),
           },
+          {
+            id: "server-status-refresh",
+            label: "Refresh Server Status",
+            tooltip: "Ping the servers of all profiles' server lists in the background. The servers see your IP address on every ping.",
+            type: "toggle",
+            value: tempConfig?.server_status_auto_refresh || false,
+            onChange: (checked) =>
+              tempConfig &&
+              setTempConfig({
+                ...tempConfig,
+                server_status_auto_refresh: checked,
+              }),
+          },
         ]}
         disabled={saving}
       />
@@ -438,6 +451,25 @@ export function SettingsTab() {
             minLabel: "1",
             maxLabel: "20",
           },
+          {
+            id: "server-status-interval",
+            label: "Server Status Interval",
+            tooltip: "Minutes between background server status refreshes. Only used when Refresh Server Status is enabled.",
+            type: "range",
+            value: tempConfig?.server_status_refresh_interval_minutes || 5,
+            onChange: (value: number) =>
+              tempConfig &&
+              setTempConfig({
+                ...tempConfig,
+                server_status_refresh_interval_minutes: value,
+              }),
+            min: 1,
+            max: 60,
+            step: 1,
+            icon: "solar:refresh-bold",
+            minLabel: "1m",
+            maxLabel: "60m",
+          },
           {
             id: "border-radius",
             label: "Border Radius",
@@ -728,7 +760,7 @@ export function SettingsTab() {
                 </Button>
               </div>
               <p className="text-sm text-white/60 font-minecraft-ten mb-4">
-                Wrapper command to run Java through (e.g., sandboxing tools). The Java path will be passed as an argument.
+                Wrapper command to run Java through (e.g., gamemoderun). Arguments can be quoted, chain several wrappers with ';' (e.g., gamemoderun; mangohud). The Java path will be passed as an argument.
               </p>
               <input
                 type="text"
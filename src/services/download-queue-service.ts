@@ -0,0 +1,49 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { DownloadJob, DownloadPriority } from "../types/downloadQueue";
+
+/**
+ * Returns all queued, running and recently finished download jobs.
+ */
+export const getDownloadQueue = (): Promise<DownloadJob[]> => {
+  return invoke('get_download_queue');
+};
+
+/**
+ * Calls `callback` with the full queue whenever a job changes or makes progress.
+ */
+export const onDownloadQueueUpdated = (
+  callback: (jobs: DownloadJob[]) => void,
+): Promise<UnlistenFn> => {
+  return listen<DownloadJob[]>('download_queue_updated', (event) => callback(event.payload));
+};
+
+export const pauseDownloadJob = (jobId: string): Promise<void> => {
+  console.debug(`[DownloadQueueService] Pausing job ${jobId}`);
+  return invoke('pause_download_job', { jobId });
+};
+
+export const resumeDownloadJob = (jobId: string): Promise<void> => {
+  console.debug(`[DownloadQueueService] Resuming job ${jobId}`);
+  return invoke('resume_download_job', { jobId });
+};
+
+/**
+ * Cancels a job. Partial files are removed once the job reaches its next checkpoint.
+ */
+export const cancelDownloadJob = (jobId: string): Promise<void> => {
+  console.debug(`[DownloadQueueService] Cancelling job ${jobId}`);
+  return invoke('cancel_download_job', { jobId });
+};
+
+export const setDownloadJobPriority = (
+  jobId: string,
+  priority: DownloadPriority,
+): Promise<void> => {
+  console.debug(`[DownloadQueueService] Setting priority of job ${jobId} to ${priority}`);
+  return invoke('set_download_job_priority', { jobId, priority });
+};
+
+export const clearFinishedDownloadJobs = (): Promise<void> => {
+  return invoke('clear_finished_download_jobs');
+};
@@ -0,0 +1,47 @@
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  JavaRuntimeRemovalResult,
+  JavaRuntimeUpdateInfo,
+  JavaRuntimeUpdateResult,
+  ManagedJavaRuntime,
+} from "../types/java";
+
+/**
+ * Lists the Java runtimes the launcher downloaded, with version, vendor, size and users.
+ */
+export const listJavaRuntimes = (): Promise<ManagedJavaRuntime[]> => {
+  console.debug('[JavaService] Listing managed Java runtimes');
+  return invoke('list_java_runtimes_command');
+};
+
+/**
+ * Pins a profile to a managed runtime. Passing `null` restores automatic selection.
+ */
+export const pinProfileJavaRuntime = (profileId: string, runtimeId: string | null): Promise<void> => {
+  console.debug(`[JavaService] Pinning profile ${profileId} to Java runtime ${runtimeId}`);
+  return invoke('pin_profile_java_runtime_command', { profileId, runtimeId });
+};
+
+/**
+ * Checks every managed runtime for a newer patch release of its distribution.
+ */
+export const checkJavaRuntimeUpdates = (): Promise<JavaRuntimeUpdateInfo[]> => {
+  console.debug('[JavaService] Checking Java runtimes for updates');
+  return invoke('check_java_runtime_updates_command');
+};
+
+/**
+ * Replaces a runtime with the latest release, profile Java paths are fixed up.
+ */
+export const updateJavaRuntime = (runtimeId: string): Promise<JavaRuntimeUpdateResult> => {
+  console.debug(`[JavaService] Updating Java runtime ${runtimeId}`);
+  return invoke('update_java_runtime_command', { runtimeId });
+};
+
+/**
+ * Moves a runtime into the trash and unpins the profiles that used it.
+ */
+export const removeJavaRuntime = (runtimeId: string): Promise<JavaRuntimeRemovalResult> => {
+  console.debug(`[JavaService] Removing Java runtime ${runtimeId}`);
+  return invoke('remove_java_runtime_command', { runtimeId });
+};
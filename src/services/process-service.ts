@@ -1,6 +1,11 @@
 import { invoke } from "@tauri-apps/api/core";
 // Import the actual type with corrected path
-import type { ProcessMetadata, CrashlogDto } from "../types/processState";
+import type {
+  ProcessMetadata,
+  CrashlogDto,
+  LaunchTimeline,
+  ProfilePlayHistory,
+} from "../types/processState";
 import { getLauncherConfig } from "./launcher-config-service";
 import flagsmith from "flagsmith";
 import { toast } from "react-hot-toast";
@@ -76,6 +81,27 @@ export async function getRunningProcesses(): Promise<ProcessMetadata[]> {
   }
 }
 
+/**
+ * Fetches the most recent launch timelines of a profile, newest first.
+ */
+export async function getLaunchTimelines(profileId: string, limit?: number): Promise<LaunchTimeline[]> {
+  return invoke<LaunchTimeline[]>("get_launch_timelines", { profileId, limit });
+}
+
+/**
+ * Fetches the cumulative playtime and the most recent play sessions of a profile.
+ */
+export async function getProfilePlayHistory(profileId: string, limit?: number): Promise<ProfilePlayHistory> {
+  return invoke<ProfilePlayHistory>("get_profile_play_history", { profileId, limit });
+}
+
+/**
+ * Fetches the cumulative playtime in seconds for every played profile, keyed by profile ID.
+ */
+export async function getProfilePlaytimes(): Promise<Record<string, number>> {
+  return invoke<Record<string, number>>("get_profile_playtimes");
+}
+
 /**
  * Stops a specific running process by its ID.
  */
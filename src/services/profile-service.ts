@@ -25,6 +25,10 @@ import type {
 } from "../types/modrinth";
 import { GEGVersionsConfig } from "../types/GEGVersions";
 import { FileNode } from "../types/fileSystem";
+import type {
+  ProfileIntegrityReport,
+  ProfileRepairResult,
+} from "../types/integrity";
 
 export async function listProfiles(): Promise<Profile[]> {
   return invoke<Profile[]>("list_profiles");
@@ -55,8 +59,14 @@ export async function deleteProfile(id: string): Promise<void> {
   return invoke<void>("delete_profile", { id });
 }
 
-export async function repairProfile(id: string): Promise<void> {
-  return invoke<void>("repair_profile", { id });
+export async function repairProfile(id: string): Promise<ProfileRepairResult> {
+  return invoke<ProfileRepairResult>("repair_profile", { id });
+}
+
+export async function verifyProfileIntegrity(
+  id: string,
+): Promise<ProfileIntegrityReport> {
+  return invoke<ProfileIntegrityReport>("verify_profile_integrity", { id });
 }
 
 export async function launchProfile(
@@ -0,0 +1,32 @@
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  ModCacheGcReport,
+  StorageCategory,
+  StorageCleanupResult,
+  StorageReport,
+} from "../types/storage";
+
+/**
+ * Removes mod cache files no profile or GEG pack references anymore.
+ * With `dryRun` nothing is deleted, the report only lists what would be removed.
+ */
+export const gcModCache = (dryRun: boolean): Promise<ModCacheGcReport> => {
+  console.debug(`[StorageService] Running mod cache GC (dry run: ${dryRun})`);
+  return invoke('gc_mod_cache', { dryRun });
+};
+
+/**
+ * Analyzes the disk usage of the meta directory per category and per profile.
+ */
+export const analyzeStorage = (): Promise<StorageReport> => {
+  console.debug('[StorageService] Analyzing storage');
+  return invoke('analyze_storage');
+};
+
+/**
+ * Moves artifacts of the given categories that no profile needs anymore into the trash.
+ */
+export const cleanupStorage = (categories: StorageCategory[]): Promise<StorageCleanupResult> => {
+  console.debug(`[StorageService] Cleaning up storage categories: ${categories.join(', ')}`);
+  return invoke('cleanup_storage', { categories });
+};
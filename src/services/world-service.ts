@@ -3,12 +3,31 @@ import { invoke } from "@tauri-apps/api/core";
 // Import necessary types (adjust paths if needed)
 import type {
     WorldInfo,
+    WorldImportResult,
     ServerInfo,
     ServerPingInfo
 } from '../types/minecraft';
 import type {
     CopyWorldParams // Assuming this is defined in profile types
 } from '../types/profile';
+import type {
+    WorldBackupEntry,
+    WorldBackupPolicy,
+    WorldBackupRunResult
+} from '../types/worldBackup';
+import type {
+    LevelDatSettings,
+    LevelDatUpdate
+} from '../types/levelDat';
+import type {
+    ServerListCopyMode,
+    ServerListEntry,
+    ServerListPushResult
+} from '../types/serverList';
+import type {
+    ServerStatusEntry,
+    ServerStatusSample
+} from '../types/serverStatus';
 
 /**
  * Fetches the list of servers associated with a specific profile.
@@ -18,6 +37,79 @@ export const getServersForProfile = (profileId: string): Promise<ServerInfo[]> =
   return invoke('get_servers_for_profile', { profileId });
 };
 
+/**
+ * Lists all entries of a profile's servers.dat, including hidden "Direct Connect" ones.
+ */
+export const listProfileServers = (profileId: string): Promise<ServerListEntry[]> => {
+  console.debug(`[WorldService] Listing server list entries for profile: ${profileId}`);
+  return invoke('list_profile_servers', { profileId });
+};
+
+/**
+ * Adds a server at the given position, or at the end of the list.
+ * @returns The updated server list.
+ */
+export const addProfileServer = (
+  profileId: string,
+  entry: ServerListEntry,
+  position?: number
+): Promise<ServerListEntry[]> => {
+  console.debug(`[WorldService] Adding server ${entry.address} to profile ${profileId}`);
+  return invoke('add_profile_server', { profileId, entry, position: position ?? null });
+};
+
+/**
+ * Replaces name, address, icon and resource pack policy of the server at `index`.
+ */
+export const updateProfileServer = (profileId: string, index: number, entry: ServerListEntry): Promise<ServerListEntry[]> => {
+  console.debug(`[WorldService] Updating server #${index} in profile ${profileId}`);
+  return invoke('update_profile_server', { profileId, index, entry });
+};
+
+/**
+ * Moves a server to another position in the list.
+ */
+export const moveProfileServer = (profileId: string, from: number, to: number): Promise<ServerListEntry[]> => {
+  console.debug(`[WorldService] Moving server #${from} to #${to} in profile ${profileId}`);
+  return invoke('move_profile_server', { profileId, from, to });
+};
+
+/**
+ * Removes the server at `index` from the list.
+ */
+export const removeProfileServer = (profileId: string, index: number): Promise<ServerListEntry[]> => {
+  console.debug(`[WorldService] Removing server #${index} from profile ${profileId}`);
+  return invoke('remove_profile_server', { profileId, index });
+};
+
+/**
+ * Sets a server's icon from an image file (scaled to 64x64). Without a path the icon is removed.
+ */
+export const setProfileServerIcon = (profileId: string, index: number, imagePath?: string): Promise<ServerListEntry[]> => {
+  console.debug(`[WorldService] Setting icon of server #${index} in profile ${profileId}`);
+  return invoke('set_profile_server_icon', { profileId, index, imagePath: imagePath ?? null });
+};
+
+/**
+ * Copies the server list of one profile into another, replacing it or merging by address.
+ */
+export const copyProfileServerList = (
+  sourceProfileId: string,
+  targetProfileId: string,
+  mode: ServerListCopyMode
+): Promise<ServerListEntry[]> => {
+  console.debug(`[WorldService] Copying server list from ${sourceProfileId} to ${targetProfileId} (${mode})`);
+  return invoke('copy_profile_server_list', { sourceProfileId, targetProfileId, mode });
+};
+
+/**
+ * Merges a team-defined server list into every profile of a group.
+ */
+export const pushServerListToGroup = (group: string, servers: ServerListEntry[]): Promise<ServerListPushResult[]> => {
+  console.debug(`[WorldService] Pushing ${servers.length} servers to group ${group}`);
+  return invoke('push_server_list_to_group', { group, servers });
+};
+
 /**
  * Fetches the list of worlds associated with a specific profile.
  */
@@ -34,6 +126,31 @@ export const pingMinecraftServer = (address: string): Promise<ServerPingInfo> =>
   return invoke('ping_minecraft_server', { address });
 };
 
+/**
+ * Pings every server of a profile's server list (SRV records and pre-1.7 servers included).
+ * Results are also pushed as `server_status_updated` event.
+ */
+export const pingProfileServers = (profileId: string): Promise<ServerStatusEntry[]> => {
+  console.debug(`[WorldService] Pinging all servers of profile: ${profileId}`);
+  return invoke('ping_profile_servers', { profileId });
+};
+
+/**
+ * Returns the last known status of a profile's servers without pinging them.
+ */
+export const getProfileServerStatuses = (profileId: string): Promise<ServerStatusEntry[]> => {
+  console.debug(`[WorldService] Fetching cached server statuses for profile: ${profileId}`);
+  return invoke('get_profile_server_statuses', { profileId });
+};
+
+/**
+ * Fetches the latency and player count history of a server, optionally only after `since` (ISO 8601).
+ */
+export const getServerStatusHistory = (address: string, since?: string): Promise<ServerStatusSample[]> => {
+  console.debug(`[WorldService] Fetching status history for server: ${address}`);
+  return invoke('get_server_status_history', { address, since: since ?? null });
+};
+
 /**
  * Copies a world from one profile to another (or within the same profile).
  */
@@ -59,6 +176,112 @@ export const checkWorldLockStatus = (profileId: string, worldFolder: string): Pr
     return invoke('check_world_lock_status', { profileId, worldFolder });
 };
 
+/**
+ * Imports a world from a .zip or .tar.zst archive into a profile.
+ * Without a name, the world's own name (or the archive name) is used.
+ */
+export const importWorld = (
+  profileId: string,
+  archivePath: string,
+  worldName?: string
+): Promise<WorldImportResult> => {
+  console.debug(`[WorldService] Importing world from ${archivePath} into profile ${profileId}`);
+  return invoke('import_world', { profileId, archivePath, worldName: worldName ?? null });
+};
+
+/**
+ * Exports a world as a zip (without session.lock) for sharing.
+ * @returns The size of the written zip in bytes.
+ */
+export const exportWorld = (profileId: string, worldFolder: string, exportPath: string): Promise<number> => {
+  console.debug(`[WorldService] Exporting world: ${worldFolder} from profile ${profileId} to ${exportPath}`);
+  return invoke('export_world', { profileId, worldFolder, exportPath });
+};
+
+/**
+ * Reads the editable level.dat settings (seed, spawn, game rules, time, weather, ...) of a world.
+ */
+export const getLevelDat = (profileId: string, worldFolder: string): Promise<LevelDatSettings> => {
+  console.debug(`[WorldService] Reading level.dat of world: ${worldFolder} in profile ${profileId}`);
+  return invoke('get_level_dat', { profileId, worldFolder });
+};
+
+/**
+ * Edits a world's level.dat. The world is backed up first; fails while the world is open.
+ * @returns The settings after the edit.
+ */
+export const updateLevelDat = (
+  profileId: string,
+  worldFolder: string,
+  update: LevelDatUpdate
+): Promise<LevelDatSettings> => {
+  console.debug(`[WorldService] Editing level.dat of world: ${worldFolder} in profile ${profileId}`);
+  return invoke('update_level_dat', { profileId, worldFolder, update });
+};
+
+/**
+ * Fetches the world backup policy of a profile (defaults if none was saved).
+ */
+export const getWorldBackupPolicy = (profileId: string): Promise<WorldBackupPolicy> => {
+  console.debug(`[WorldService] Fetching world backup policy for profile: ${profileId}`);
+  return invoke('get_world_backup_policy', { profileId });
+};
+
+/**
+ * Saves the world backup policy of a profile.
+ */
+export const setWorldBackupPolicy = (profileId: string, policy: WorldBackupPolicy): Promise<void> => {
+  console.debug(`[WorldService] Saving world backup policy for profile: ${profileId}`);
+  return invoke('set_world_backup_policy', { profileId, policy });
+};
+
+/**
+ * Creates a manual backup of a world. Fails if the world is open in Minecraft.
+ */
+export const createWorldBackup = (profileId: string, worldFolder: string): Promise<WorldBackupEntry> => {
+  console.debug(`[WorldService] Backing up world: ${worldFolder} in profile ${profileId}`);
+  return invoke('create_world_backup', { profileId, worldFolder });
+};
+
+/**
+ * Backs up every world of a profile, skipping worlds that are in use.
+ */
+export const backupProfileWorlds = (profileId: string): Promise<WorldBackupRunResult> => {
+  console.debug(`[WorldService] Backing up all worlds of profile: ${profileId}`);
+  return invoke('backup_profile_worlds', { profileId });
+};
+
+/**
+ * Lists the backups of a profile, optionally only those of one world. Newest first.
+ */
+export const listWorldBackups = (profileId: string, worldFolder?: string): Promise<WorldBackupEntry[]> => {
+  console.debug(`[WorldService] Listing world backups for profile: ${profileId}`);
+  return invoke('list_world_backups', { profileId, worldFolder: worldFolder ?? null });
+};
+
+/**
+ * Restores a backup. Without a new name the current world is replaced (after a
+ * pre-restore backup), otherwise the backup is extracted next to it.
+ * @returns The folder name of the restored world.
+ */
+export const restoreWorldBackup = (
+  profileId: string,
+  worldFolder: string,
+  fileName: string,
+  newWorldName?: string
+): Promise<string> => {
+  console.debug(`[WorldService] Restoring backup ${fileName} of world ${worldFolder} in profile ${profileId}`);
+  return invoke('restore_world_backup', { profileId, worldFolder, fileName, newWorldName: newWorldName ?? null });
+};
+
+/**
+ * Deletes a single world backup.
+ */
+export const deleteWorldBackup = (profileId: string, worldFolder: string, fileName: string): Promise<void> => {
+  console.debug(`[WorldService] Deleting backup ${fileName} of world ${worldFolder} in profile ${profileId}`);
+  return invoke('delete_world_backup', { profileId, worldFolder, fileName });
+};
+
 // --- Frontend Helper Functions ---
 
 /**
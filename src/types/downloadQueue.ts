@@ -0,0 +1,35 @@
+// Corresponds to DownloadPriority enum in Rust
+export type DownloadPriority = 'background' | 'normal' | 'launch';
+
+// Corresponds to DownloadJobKind enum in Rust
+export type DownloadJobKind = 'launch' | 'install' | 'modpack_import' | 'update_check';
+
+// Corresponds to DownloadJobStatus enum in Rust
+export type DownloadJobStatus =
+  | 'queued'
+  | 'running'
+  | 'paused'
+  | 'completed'
+  | 'failed'
+  | 'cancelled';
+
+/**
+ * A unit of download work in the central download queue.
+ * Mirrors the Rust DownloadJob struct, pushed as `download_queue_updated` event.
+ */
+export interface DownloadJob {
+  id: string;
+  kind: DownloadJobKind;
+  label: string;
+  profile_id: string | null;
+  priority: DownloadPriority;
+  status: DownloadJobStatus;
+  /** Cancellation was requested, the job stops at its next checkpoint */
+  cancel_requested: boolean;
+  downloaded_bytes: number;
+  downloaded_files: number;
+  queued_at: string;
+  started_at: string | null;
+  finished_at: string | null;
+  error: string | null;
+}
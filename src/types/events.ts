@@ -40,6 +40,13 @@ export interface EventPayload {
   message: string;
   progress: number | null;
   error: string | null;
+  throughput?: DownloadThroughput; // Only set on download progress events
+}
+
+export interface DownloadThroughput {
+  bytes_per_second: number;
+  eta_seconds: number | null;
+  limit_bytes_per_second: number | null;
 }
 
 export interface MinecraftProcessExitedPayload {
@@ -49,6 +56,35 @@ export interface MinecraftProcessExitedPayload {
   success: boolean;
   process_metadata: ProcessMetadata | null;
   crash_report_content?: string;
+  crash_analysis?: CrashAnalysis | null;
+}
+
+export type CrashCategory =
+  | 'mixin_failure'
+  | 'missing_dependency'
+  | 'incompatible_mods'
+  | 'wrong_java'
+  | 'out_of_memory'
+  | 'duplicate_mod'
+  | 'graphics_driver'
+  | 'other';
+
+export type CrashSource = 'crash_report' | 'latest_log' | 'jvm_error_log';
+
+export interface CrashDiagnosis {
+  rule_id: string;
+  category: CrashCategory;
+  title: string;
+  description: string;
+  suggestions: string[];
+  source: CrashSource;
+  matched_line: string;
+}
+
+export interface CrashAnalysis {
+  diagnoses: CrashDiagnosis[];
+  analyzed_sources: CrashSource[];
+  crash_report_path?: string | null;
 }
 
 export interface CrashReportContentAvailablePayload {
@@ -0,0 +1,74 @@
+import type { ModLoader } from "./profile";
+
+// Corresponds to IntegrityCategory enum in Rust
+export type IntegrityCategory =
+  | 'client'
+  | 'library'
+  | 'native'
+  | 'loader_installer'
+  | 'loader_library'
+  | 'asset_index'
+  | 'asset'
+  | 'mod'
+  | 'pack_mod';
+
+// Corresponds to IntegrityProblem enum in Rust
+export type IntegrityProblem =
+  | 'missing'
+  | 'size_mismatch'
+  | 'hash_mismatch'
+  | 'corrupt'
+  | 'unreadable';
+
+/**
+ * A missing or corrupt file of a profile.
+ * Mirrors the Rust IntegrityIssue struct.
+ */
+export interface IntegrityIssue {
+  category: IntegrityCategory;
+  name: string;
+  path: string;
+  problem: IntegrityProblem;
+  /** Whether repairing the profile can fix this file */
+  repairable: boolean;
+}
+
+/**
+ * Mirrors the Rust IntegrityCategorySummary struct.
+ */
+export interface IntegrityCategorySummary {
+  category: IntegrityCategory;
+  checked: number;
+  broken: number;
+}
+
+/**
+ * Result of verifying a profile's files.
+ * Mirrors the Rust ProfileIntegrityReport struct.
+ */
+export interface ProfileIntegrityReport {
+  profile_id: string;
+  game_version: string;
+  loader: ModLoader;
+  loader_version: string | null;
+  categories: IntegrityCategorySummary[];
+  issues: IntegrityIssue[];
+  /** Parts that could not be verified, e.g. because metadata was unavailable */
+  notes: string[];
+  checked_at: string;
+}
+
+/**
+ * Result of repairing a profile.
+ * Mirrors the Rust ProfileRepairResult struct.
+ */
+export interface ProfileRepairResult {
+  profile_id: string;
+  /** Everything verification found broken, across all passes */
+  issues: IntegrityIssue[];
+  repaired: number;
+  /** Broken pack mods that are fetched again by the next launch */
+  deferred: number;
+  failed: string[];
+  notes: string[];
+}
@@ -23,3 +23,73 @@ export interface JavaInstallation {
   /** The architecture of the Java installation */
   architecture: JavaArchitecture;
 } 
+
+/** Corresponds to the JavaDistribution enum in Rust */
+export type JavaDistribution = 'temurin' | 'graalvm' | 'zulu' | 'mojang';
+
+/**
+ * A profile that uses a managed Java runtime.
+ * Mirrors the Rust JavaRuntimeUser struct.
+ */
+export interface JavaRuntimeUser {
+  profile_id: string;
+  profile_name: string;
+  /** True if pinned through the custom Java path, false if picked automatically */
+  pinned: boolean;
+}
+
+/**
+ * A Java runtime the launcher downloaded into its meta directory.
+ * Mirrors the Rust ManagedJavaRuntime struct.
+ */
+export interface ManagedJavaRuntime {
+  /** Folder name, e.g. "zulu_17" or "mojang_java-runtime-gamma" */
+  id: string;
+  path: string;
+  java_binary: string | null;
+  distribution: JavaDistribution | null;
+  /** Mojang component, only set for Mojang runtimes */
+  component: string | null;
+  major_version: number | null;
+  version: string | null;
+  vendor: string | null;
+  /** x86_64 runtime on an ARM Mac */
+  x86_64: boolean;
+  size_bytes: number;
+  installed_at: string | null;
+  used_by: JavaRuntimeUser[];
+}
+
+/**
+ * Mirrors the Rust JavaRuntimeUpdateInfo struct.
+ */
+export interface JavaRuntimeUpdateInfo {
+  runtime_id: string;
+  installed_version: string | null;
+  latest_version: string | null;
+  update_available: boolean;
+  /** Why no update information is available, if so */
+  note: string | null;
+}
+
+/**
+ * Mirrors the Rust JavaRuntimeUpdateResult struct.
+ */
+export interface JavaRuntimeUpdateResult {
+  runtime_id: string;
+  previous_version: string | null;
+  version: string | null;
+  /** Profiles whose custom Java path was moved to the new binary */
+  repointed_profiles: string[];
+}
+
+/**
+ * Mirrors the Rust JavaRuntimeRemovalResult struct.
+ */
+export interface JavaRuntimeRemovalResult {
+  runtime_id: string;
+  trash_path: string;
+  freed_bytes: number;
+  /** Profiles that fall back to automatic Java selection now */
+  unpinned_profiles: string[];
+}
@@ -12,6 +12,16 @@ export interface MemorySettings {
   max: number; // u32
 }
 
+export type MirrorMatch = 'host' | 'prefix';
+
+export interface MirrorRule {
+  enabled: boolean;
+  match_type: MirrorMatch;
+  pattern: string; // Host name or URL prefix
+  mirrors: string[]; // Tried in order
+  fallback_to_origin: boolean; // Try the original URL after all mirrors failed
+}
+
 export interface LauncherConfig {
   version: number; // u32
   is_experimental: boolean;
@@ -26,4 +36,10 @@ export interface LauncherConfig {
   hide_on_process_start: boolean;
   global_memory_settings: MemorySettings;
   custom_game_directory: string | null; // Option<PathBuf>
+  offline_mode?: boolean; // Launch with cached credentials, no token refreshes
+  download_mirrors?: MirrorRule[]; // Vec<MirrorRule>, first matching rule wins
+  download_limit_kbps?: number | null; // Option<u32>, null or 0 = unlimited
+  download_limit_while_playing_kbps?: number | null; // Option<u32>, applies while a game is running
+  server_status_auto_refresh?: boolean; // Ping the servers of all server lists in the background
+  server_status_refresh_interval_minutes?: number; // u32, at least 1
 } 
@@ -0,0 +1,62 @@
+// Mirrors the Rust LevelSpawn struct
+export interface LevelSpawn {
+  x: number;
+  y: number;
+  z: number;
+  angle: number;
+}
+
+// Mirrors the Rust LevelWeather struct, times are in ticks
+export interface LevelWeather {
+  raining: boolean;
+  thundering: boolean;
+  rain_time: number;
+  thunder_time: number;
+  clear_weather_time: number;
+}
+
+// Mirrors the Rust LevelDataPacks struct
+export interface LevelDataPacks {
+  enabled: string[];
+  disabled: string[];
+}
+
+/**
+ * The editable parts of a world's level.dat.
+ * Mirrors the Rust LevelDatSettings struct.
+ */
+export interface LevelDatSettings {
+  level_name: string | null;
+  /** Read only, the Minecraft version that saved the world last */
+  version_name: string | null;
+  seed: number | null;
+  spawn: LevelSpawn | null;
+  /** Values as strings ("true", "3"), however the world stores them */
+  game_rules: Record<string, string>;
+  day_time: number | null;
+  /** Read only, ticks the world has been running */
+  game_time: number | null;
+  weather: LevelWeather | null;
+  difficulty: number | null;
+  difficulty_locked: boolean | null;
+  allow_commands: boolean | null;
+  data_packs: LevelDataPacks | null;
+}
+
+/**
+ * Changes to a level.dat, omitted fields are left as they are.
+ * Mirrors the Rust LevelDatUpdate struct.
+ */
+export interface LevelDatUpdate {
+  /** Only affects chunks that have not been generated yet */
+  seed?: number | null;
+  spawn?: LevelSpawn | null;
+  /** Only the given rules are changed */
+  game_rules?: Record<string, string> | null;
+  day_time?: number | null;
+  weather?: LevelWeather | null;
+  difficulty?: number | null;
+  difficulty_locked?: boolean | null;
+  allow_commands?: boolean | null;
+  data_packs?: LevelDataPacks | null;
+}
@@ -6,8 +6,12 @@ export interface MinecraftAccount {
     access_token: string;
     refresh_token?: string;
     expires_at?: string;
+    account_type?: AccountType;
 }
 
+/** "offline" accounts are local-only (name-derived UUID, no Microsoft/GEG tokens) */
+export type AccountType = "microsoft" | "offline";
+
 /**
  * Represents a Minecraft player profile as returned by Mojang's session server
  */
@@ -104,6 +108,20 @@ export interface WorldInfo {
   version_name?: string | null; // Rust Option<String> -> string | null
 }
 
+/**
+ * Outcome of importing a world archive into a profile.
+ * Mirrors the Rust WorldImportResult struct.
+ */
+export interface WorldImportResult {
+  folder_name: string;
+  level_name: string | null;
+  /** The Minecraft version the world was last played in */
+  world_version: string | null;
+  profile_version: string;
+  /** The world was played in a newer version than the profile uses, opening it may fail or lose data */
+  newer_than_profile: boolean;
+}
+
 export interface ServerInfo {
     name: string | null;
     address: string | null; // Matches Rust struct
@@ -41,3 +41,67 @@ export interface CrashlogDto {
   mcLogsUrl: string;
   metadata: ProcessMetadata | null;
 }
+
+/**
+ * One phase of a launch (e.g. downloading libraries).
+ * Corresponds to the Rust struct `LaunchPhase` in `launch_timeline_state.rs`.
+ */
+export interface LaunchPhase {
+  phase: string; // EventType, snake_case
+  start: string; // DateTime<Utc> as ISO string
+  end: string; // DateTime<Utc> as ISO string
+  duration_ms: number;
+  bytes_downloaded: number;
+  files_downloaded: number;
+}
+
+export type LaunchOutcome = 'launched' | 'installed' | 'failed';
+
+/**
+ * Timeline of a single launch attempt.
+ * Corresponds to the Rust struct `LaunchTimeline`.
+ */
+export interface LaunchTimeline {
+  id: string; // Uuid
+  profile_id: string; // Uuid
+  minecraft_version: string;
+  modloader: string;
+  start: string; // DateTime<Utc> as ISO string
+  end: string; // DateTime<Utc> as ISO string
+  duration_ms: number;
+  outcome: LaunchOutcome;
+  error?: string | null;
+  phases: LaunchPhase[];
+}
+
+/**
+ * One run of the game, from process start to exit.
+ * Corresponds to the Rust struct `PlaySession` in `play_session_state.rs`.
+ */
+export interface PlaySession {
+  id: string; // Uuid, same as the process ID
+  profile_id: string; // Uuid
+  start: string; // DateTime<Utc> as ISO string
+  end?: string | null; // null while the game is still running
+  last_seen: string; // DateTime<Utc> as ISO string
+  duration_secs: number;
+  exit_code?: number | null;
+  crashed: boolean;
+  reconciled: boolean; // end reconstructed after a launcher restart
+  launcher_pid?: number | null; // u32, launcher (GUI or CLI) that opened the session
+  account_uuid?: string | null;
+  account_name?: string | null;
+  minecraft_version?: string | null;
+  modloader?: string | null;
+  modloader_version?: string | null;
+}
+
+/**
+ * Cumulative playtime and recent sessions of a profile.
+ * Corresponds to the Rust struct `ProfilePlayHistory`.
+ */
+export interface ProfilePlayHistory {
+  total_playtime_secs: number;
+  session_count: number;
+  sessions: PlaySession[];
+}
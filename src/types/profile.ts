@@ -1,5 +1,6 @@
 import { ContentType } from "./content";
 import { ModPlatform } from "./unified";
+import { Hooks } from "./launcherConfig";
 
 export type ModLoader = "vanilla" | "forge" | "fabric" | "quilt" | "neoforge";
 export type ProfileState =
@@ -82,7 +83,19 @@ export interface ProfileSettings {
   extra_game_args: string[];           // Vec<String> -> string[] (Renamed from extra_args)
   custom_jvm_args: string | null;   // Option<String> -> string | null (New)
   quick_play_path: string | null;   // Option<String> -> string | null (Quick Play path)
-}
+  jvm_preset?: JvmPreset;           // GC preset, defaults to "g1_default"
+  env_vars?: Record<string, string>; // Extra environment variables, values may use $INST_DIR, $PROFILE_ID, $JAVA
+  hooks?: Hooks;                    // Overrides the global hooks, "" disables a global hook
+  skip_mod_validation?: boolean;    // Launch even if mod dependencies or conflicts are detected
+}
+
+export type JvmPreset =
+  | "none"
+  | "g1_default"
+  | "aikar"
+  | "generational_zgc"
+  | "shenandoah"
+  | "custom";
 
 interface ModSourceBase {
   type: "local" | "url" | "maven" | "embedded" | "modrinth" | "curseforge";
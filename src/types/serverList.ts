@@ -0,0 +1,31 @@
+// Corresponds to ServerResourcePackPolicy enum in Rust, stored as `acceptTextures`
+export type ServerResourcePackPolicy = 'prompt' | 'enabled' | 'disabled';
+
+// Corresponds to ServerListCopyMode enum in Rust
+export type ServerListCopyMode = 'replace' | 'merge';
+
+/**
+ * An entry of a profile's multiplayer server list (servers.dat).
+ * Mirrors the Rust ServerListEntry struct.
+ */
+export interface ServerListEntry {
+  name: string;
+  address: string;
+  /** Base64 encoded 64x64 PNG */
+  icon_base64: string | null;
+  resource_pack_policy: ServerResourcePackPolicy;
+  /** Entries written by "Direct Connect", not shown in the server list */
+  hidden: boolean;
+}
+
+/**
+ * Outcome of pushing a server list into one profile of a group.
+ * Mirrors the Rust ServerListPushResult struct.
+ */
+export interface ServerListPushResult {
+  profile_id: string;
+  profile_name: string;
+  added: number;
+  updated: number;
+  error: string | null;
+}
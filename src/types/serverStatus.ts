@@ -0,0 +1,36 @@
+import type { ServerPingInfo } from './minecraft';
+
+/**
+ * One ping result as kept in the local status history.
+ * Mirrors the Rust ServerStatusSample struct.
+ */
+export interface ServerStatusSample {
+  /** ISO 8601 timestamp */
+  timestamp: string;
+  online: boolean;
+  latency_ms: number | null;
+  players_online: number | null;
+  players_max: number | null;
+}
+
+/**
+ * The latest status of a server of a profile's server list.
+ * Mirrors the Rust ServerStatusEntry struct.
+ */
+export interface ServerStatusEntry {
+  name: string;
+  address: string;
+  status: ServerPingInfo;
+  /** ISO 8601 timestamp */
+  checked_at: string;
+}
+
+/**
+ * Payload of the `server_status_updated` event, sent per profile after each batch ping
+ * and background refresh.
+ * Mirrors the Rust ProfileServerStatuses struct.
+ */
+export interface ProfileServerStatuses {
+  profile_id: string;
+  servers: ServerStatusEntry[];
+}
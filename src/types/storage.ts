@@ -0,0 +1,101 @@
+// Corresponds to ModCacheGcItemKind enum in Rust
+export type ModCacheGcItemKind = 'object' | 'legacy' | 'partial';
+
+/**
+ * A file in the mod cache that garbage collection removes (or would remove).
+ * Mirrors the Rust ModCacheGcItem struct.
+ */
+export interface ModCacheGcItem {
+  kind: ModCacheGcItemKind;
+  path: string;
+  /** Content hash for content-addressed objects, null for legacy/partial files */
+  sha1: string | null;
+  /** Filenames the object was stored under */
+  filenames: string[];
+  size_bytes: number;
+}
+
+/**
+ * Result of a mod cache garbage collection run.
+ * Mirrors the Rust ModCacheGcReport struct.
+ */
+export interface ModCacheGcReport {
+  dry_run: boolean;
+  total_objects: number;
+  total_bytes: number;
+  referenced_objects: number;
+  /** Number of profile/pack references keeping objects alive */
+  total_references: number;
+  stale_index_entries: number;
+  items: ModCacheGcItem[];
+  /** Bytes a real run frees (dry run) or freed */
+  reclaimable_bytes: number;
+}
+
+// Corresponds to StorageCategory enum in Rust
+export type StorageCategory =
+  | 'versions'
+  | 'libraries'
+  | 'natives'
+  | 'asset_indexes'
+  | 'asset_objects'
+  | 'geg_assets'
+  | 'java_runtimes'
+  | 'mod_cache'
+  | 'other';
+
+/** A meta artifact no profile needs anymore. */
+export interface StorageOrphan {
+  path: string;
+  size_bytes: number;
+}
+
+/**
+ * Disk usage of one storage category.
+ * Mirrors the Rust StorageCategoryUsage struct.
+ */
+export interface StorageCategoryUsage {
+  category: StorageCategory;
+  total_bytes: number;
+  orphaned_bytes: number;
+  orphan_count: number;
+  /** The largest orphans, capped at 200 entries */
+  orphans: StorageOrphan[];
+  /** False if the needed artifacts could not be determined, cleanup skips the category */
+  cleanable: boolean;
+  note: string | null;
+}
+
+/** Disk usage of a single profile. */
+export interface ProfileStorageUsage {
+  profile_id: string;
+  profile_name: string;
+  /** Size of the instance folder */
+  instance_bytes: number;
+  /** Size of the meta artifacts the profile needs, possibly shared with other profiles */
+  shared_bytes: number;
+  /** True if the instance folder is shared with other profiles */
+  shared_instance: boolean;
+}
+
+/**
+ * Storage analysis of the meta directory.
+ * Mirrors the Rust StorageReport struct.
+ */
+export interface StorageReport {
+  meta_dir: string;
+  total_bytes: number;
+  orphaned_bytes: number;
+  categories: StorageCategoryUsage[];
+  profiles: ProfileStorageUsage[];
+  /** Game versions whose metadata could not be loaded */
+  incomplete_versions: string[];
+  generated_at: string;
+}
+
+/** Result of moving orphaned artifacts to the trash. */
+export interface StorageCleanupResult {
+  moved_items: number;
+  freed_bytes: number;
+  trash_paths: string[];
+}
@@ -0,0 +1,59 @@
+// Corresponds to WorldArchiveFormat enum in Rust
+export type WorldArchiveFormat = 'zip' | 'tar_zst';
+
+// Corresponds to WorldBackupTrigger enum in Rust
+export type WorldBackupTrigger = 'manual' | 'session_end' | 'schedule' | 'pre_restore' | 'pre_edit';
+
+/**
+ * Which automatic backups survive pruning. Manual backups are never pruned.
+ * Mirrors the Rust WorldBackupRetention struct.
+ */
+export interface WorldBackupRetention {
+  /** The newest N backups are always kept (at least one) */
+  keep_last: number;
+  /** Keeps the newest backup of each of the last N days that have backups */
+  keep_daily: number;
+  /** Keeps the newest backup of each of the last N weeks that have backups */
+  keep_weekly: number;
+}
+
+/**
+ * Backup settings of one profile.
+ * Mirrors the Rust WorldBackupPolicy struct.
+ */
+export interface WorldBackupPolicy {
+  format: WorldArchiveFormat;
+  backup_on_session_end: boolean;
+  /** Hours between scheduled backups, null disables the schedule */
+  interval_hours: number | null;
+  /** World folders that are backed up automatically, null means all worlds */
+  worlds: string[] | null;
+  retention: WorldBackupRetention;
+}
+
+/**
+ * A backup archive of a world.
+ * Mirrors the Rust WorldBackupEntry struct.
+ */
+export interface WorldBackupEntry {
+  profile_id: string;
+  world_folder: string;
+  file_name: string;
+  path: string;
+  /** ISO 8601 timestamp */
+  created_at: string;
+  trigger: WorldBackupTrigger;
+  format: WorldArchiveFormat;
+  size_bytes: number;
+}
+
+/**
+ * Outcome of backing up several worlds of a profile at once.
+ * Mirrors the Rust WorldBackupRunResult struct.
+ */
+export interface WorldBackupRunResult {
+  created: WorldBackupEntry[];
+  /** Worlds that were skipped because they are open in Minecraft */
+  locked: string[];
+  failed: string[];
+}